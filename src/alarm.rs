@@ -1,14 +1,138 @@
+use std::io::{IsTerminal, Write};
+use std::process;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Duration;
 
+use serde::{Deserialize, Serialize};
+
+use crate::duck;
+use crate::notify::Severity;
+
+/// tmux user option holding the pair's status, flipped by visual alerts;
+/// reference it in your status line with `#{@mooncap_status}`
+const TMUX_STATUS_OPTION: &str = "@mooncap_status";
+
+/// Whether a visual alert has changed the terminal title / tmux status
+static VISUAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Where the status is shown outside the TUI; unset until the TUI starts, so
+/// tests and daemons leave the terminal alone
+static STATUS_BAR: Mutex<Option<StatusBar>> = Mutex::new(None);
+
+/// The terminal title, the tmux status option and the --status-file
+struct StatusBar {
+    /// Title outside alerts: "mooncap", or "mooncap — <name>"
+    title: String,
+    /// The pair's latest status, shown ahead of the title between alerts
+    status: String,
+    file: Option<String>,
+    /// Whether stdout is a terminal the title can be written to
+    tty: bool,
+}
+
+impl StatusBar {
+    fn current_title(&self) -> String {
+        if self.status.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{} — {}", self.status, self.title)
+        }
+    }
+
+    /// Show `status` everywhere but the title, which alerts write themselves
+    fn publish(&self, status: &str) -> Result<(), String> {
+        set_tmux_status(status);
+        let Some(ref path) = self.file else {
+            return Ok(());
+        };
+        let line = if status.is_empty() { String::new() } else { format!("{}\n", status) };
+        std::fs::write(path, line)
+            .map_err(|e| format!("Failed to write status file {}: {}", path, e))
+    }
+
+    /// Show the pair's status again after an alert
+    fn restore(&self) -> Result<(), String> {
+        if self.tty {
+            write_title(&self.current_title());
+        }
+        self.publish(&self.status)
+    }
+}
+
+fn status_bar() -> MutexGuard<'static, Option<StatusBar>> {
+    STATUS_BAR.lock().unwrap_or_else(|e| e.into_inner())
+}
+
 #[cfg(feature = "audio")]
 use std::io::BufReader;
 
+/// Alarm sound and length for each alert severity (`alarm_sounds` in the config)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlarmSounds {
+    pub info: AlarmSound,
+    pub warning: AlarmSound,
+    pub critical: AlarmSound,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlarmSound {
+    /// Audio file played instead of --alarm's
+    pub file: Option<String>,
+    /// Seconds to sound instead of the severity's default
+    pub duration: Option<u64>,
+}
+
+impl AlarmSounds {
+    /// Audio file and seconds for an alarm of `severity`. Unless overridden,
+    /// every severity plays `file`: critical for `duration`, warning for half
+    /// of it and info just once.
+    pub fn resolve(
+        &self,
+        severity: Severity,
+        file: Option<String>,
+        duration: u64,
+    ) -> (Option<String>, u64) {
+        let (sound, default) = match severity {
+            Severity::Info => (&self.info, duration.min(1)),
+            Severity::Warning => (&self.warning, duration - duration / 2),
+            Severity::Critical => (&self.critical, duration),
+        };
+        (sound.file.clone().or(file), sound.duration.unwrap_or(default))
+    }
+}
+
+/// Ring the terminal bell the way `severity` sounds: one bell for info and
+/// warning, a double bell for critical. Returns the pause before the next
+/// ring, shorter for critical alarms.
+pub fn bell(severity: Severity) -> Duration {
+    let rings = if severity == Severity::Critical { 2 } else { 1 };
+    for ring in 0..rings {
+        if ring > 0 {
+            std::thread::sleep(Duration::from_millis(150));
+        }
+        print!("\x07");
+        let _ = std::io::stdout().flush();
+    }
+    match severity {
+        Severity::Critical => Duration::from_secs(1),
+        _ => Duration::from_secs(2),
+    }
+}
+
 /// Plays alarm sound. If an alarm file is provided and the `audio` feature is enabled,
-/// uses rodio to play it on loop. Otherwise, emits terminal bell characters.
-/// Returns a stop handle that can be used to stop the alarm.
-pub fn start_alarm(alarm_file: Option<&str>, duration_secs: u64) -> Arc<AtomicBool> {
+/// uses rodio to play it on loop. Otherwise, emits terminal bell characters in the
+/// pattern of `severity`. With `duck`, other applications are turned down to that
+/// percent of their volume until the alarm ends. Returns a stop handle that can be
+/// used to stop the alarm.
+pub fn start_alarm(
+    alarm_file: Option<&str>,
+    duration_secs: u64,
+    duck: Option<u8>,
+    severity: Severity,
+) -> Arc<AtomicBool> {
     let stop_flag = Arc::new(AtomicBool::new(false));
     let flag_clone = stop_flag.clone();
 
@@ -16,7 +140,8 @@ pub fn start_alarm(alarm_file: Option<&str>, duration_secs: u64) -> Arc<AtomicBo
     if let Some(file_path) = alarm_file {
         let path = file_path.to_string();
         std::thread::spawn(move || {
-            play_audio_alarm(&path, duration_secs, &flag_clone);
+            let _ducked = duck.and_then(|percent| duck::duck(percent).ok());
+            play_audio_alarm(&path, duration_secs, severity, &flag_clone);
         });
         return stop_flag;
     }
@@ -31,7 +156,8 @@ pub fn start_alarm(alarm_file: Option<&str>, duration_secs: u64) -> Arc<AtomicBo
     }
 
     std::thread::spawn(move || {
-        play_bell_alarm(duration_secs, &flag_clone);
+        let _ducked = duck.and_then(|percent| duck::duck(percent).ok());
+        play_bell_alarm(duration_secs, severity, &flag_clone);
     });
 
     stop_flag
@@ -40,13 +166,111 @@ pub fn start_alarm(alarm_file: Option<&str>, duration_secs: u64) -> Arc<AtomicBo
 /// Stop the alarm by setting the stop flag
 pub fn stop_alarm(stop_flag: &Arc<AtomicBool>) {
     stop_flag.store(true, Ordering::Relaxed);
+    clear_visual_alert();
+}
+
+/// Sound-free alert: sets the terminal title, fires an OSC desktop notification
+/// (OSC 9 and OSC 777, whichever the terminal understands) and flips the tmux
+/// status option. Returns a stop handle like `start_alarm`.
+pub fn start_visual_alert(summary: &str) -> Arc<AtomicBool> {
+    let title = format!("🔥 MoonCap ALERT — {}", summary);
+    let mut out = std::io::stdout();
+    let _ = write!(out, "\x1b]0;{}\x07", title);
+    let _ = write!(out, "\x1b]9;{}\x07", title);
+    let _ = write!(out, "\x1b]777;notify;MoonCap;{}\x07", summary);
+    let _ = out.flush();
+
+    if let Some(ref bar) = *status_bar() {
+        let _ = bar.publish(&format!("🔥 {}", summary));
+    }
+    VISUAL_ACTIVE.store(true, Ordering::Relaxed);
+
+    Arc::new(AtomicBool::new(false))
+}
+
+/// Restore the terminal title and tmux status after a visual alert
+fn clear_visual_alert() {
+    if !VISUAL_ACTIVE.swap(false, Ordering::Relaxed) {
+        return;
+    }
+    if let Some(ref bar) = *status_bar() {
+        let _ = bar.restore();
+    }
+}
+
+/// Called as the TUI starts: title the terminal "mooncap", or "mooncap — <name>"
+/// for a named instance, and start showing the status, in `status_file` too if
+/// one is given
+pub fn set_title(instance: Option<&str>, status_file: Option<&str>) {
+    let title = match instance {
+        Some(name) => format!("mooncap — {}", name),
+        None => "mooncap".to_string(),
+    };
+    let tty = std::io::stdout().is_terminal();
+    if tty {
+        write_title(&title);
+    }
+    *status_bar() = Some(StatusBar {
+        title,
+        status: String::new(),
+        file: status_file.map(str::to_string),
+        tty,
+    });
+}
+
+/// Show the pair's status (`PEPE $512.3K (64% to target)`) in the terminal
+/// title, the tmux status option and the status file. A visual alert keeps
+/// them until it is stopped. Does nothing outside the TUI.
+pub fn set_status(status: &str) -> Result<(), String> {
+    let mut bar = status_bar();
+    let Some(ref mut bar) = *bar else {
+        return Ok(());
+    };
+    if bar.status == status {
+        return Ok(());
+    }
+    bar.status = status.to_string();
+    if VISUAL_ACTIVE.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    bar.restore()
+}
+
+/// Empty the tmux status option and the status file as the TUI exits, since a
+/// stale status would read as live
+pub fn clear_status() {
+    if let Some(bar) = status_bar().take() {
+        let _ = bar.publish("");
+    }
+}
+
+fn write_title(title: &str) {
+    let mut out = std::io::stdout();
+    let _ = write!(out, "\x1b]0;{}\x07", title);
+    let _ = out.flush();
+}
+
+fn set_tmux_status(value: &str) {
+    if std::env::var_os("TMUX").is_none() {
+        return;
+    }
+    let _ = process::Command::new("tmux")
+        .args(["set-option", "-gq", TMUX_STATUS_OPTION, value])
+        .stdout(process::Stdio::null())
+        .stderr(process::Stdio::null())
+        .status();
 }
 
 #[cfg(feature = "audio")]
-fn play_audio_alarm(file_path: &str, duration_secs: u64, stop_flag: &AtomicBool) {
+fn play_audio_alarm(
+    file_path: &str,
+    duration_secs: u64,
+    severity: Severity,
+    stop_flag: &AtomicBool,
+) {
     let Ok((_stream, stream_handle)) = rodio::OutputStream::try_default() else {
         eprintln!("Failed to open audio output, falling back to bell");
-        play_bell_alarm(duration_secs, stop_flag);
+        play_bell_alarm(duration_secs, severity, stop_flag);
         return;
     };
 
@@ -54,7 +278,7 @@ fn play_audio_alarm(file_path: &str, duration_secs: u64, stop_flag: &AtomicBool)
         Ok(f) => f,
         Err(e) => {
             eprintln!("Failed to open alarm file '{}': {}", file_path, e);
-            play_bell_alarm(duration_secs, stop_flag);
+            play_bell_alarm(duration_secs, severity, stop_flag);
             return;
         }
     };
@@ -63,7 +287,7 @@ fn play_audio_alarm(file_path: &str, duration_secs: u64, stop_flag: &AtomicBool)
         Ok(s) => s,
         Err(e) => {
             eprintln!("Failed to decode audio: {}", e);
-            play_bell_alarm(duration_secs, stop_flag);
+            play_bell_alarm(duration_secs, severity, stop_flag);
             return;
         }
     };
@@ -72,7 +296,7 @@ fn play_audio_alarm(file_path: &str, duration_secs: u64, stop_flag: &AtomicBool)
         Ok(s) => s,
         Err(e) => {
             eprintln!("Failed to create audio sink: {}", e);
-            play_bell_alarm(duration_secs, stop_flag);
+            play_bell_alarm(duration_secs, severity, stop_flag);
             return;
         }
     };
@@ -91,14 +315,12 @@ fn play_audio_alarm(file_path: &str, duration_secs: u64, stop_flag: &AtomicBool)
     sink.stop();
 }
 
-fn play_bell_alarm(duration_secs: u64, stop_flag: &AtomicBool) {
+fn play_bell_alarm(duration_secs: u64, severity: Severity, stop_flag: &AtomicBool) {
     let start = std::time::Instant::now();
     while start.elapsed() < Duration::from_secs(duration_secs) {
         if stop_flag.load(Ordering::Relaxed) {
             break;
         }
-        // Terminal bell
-        print!("\x07");
-        std::thread::sleep(Duration::from_secs(2));
+        std::thread::sleep(bell(severity));
     }
 }
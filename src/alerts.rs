@@ -0,0 +1,531 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::PriceChange;
+use crate::notify::Severity;
+
+/// A rule evaluated across every tracked token (the monitored pair plus the
+/// active portfolio) rather than against a single pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WatchlistRule {
+    /// Combined market cap of all tracked tokens fell `percent` below its peak
+    /// within the last `window_mins`
+    TotalValueDrop { percent: f64, window_mins: u64 },
+    /// Any single tracked token's liquidity fell `percent` below its peak
+    /// within the last `window_mins`
+    LiquidityDrop { percent: f64, window_mins: u64 },
+}
+
+impl WatchlistRule {
+    /// A liquidity pull looks like a rug, so it is always critical
+    pub fn severity(&self) -> Severity {
+        match self {
+            WatchlistRule::TotalValueDrop { .. } => Severity::Warning,
+            WatchlistRule::LiquidityDrop { .. } => Severity::Critical,
+        }
+    }
+
+    fn window(&self) -> Duration {
+        match self {
+            WatchlistRule::TotalValueDrop { window_mins, .. }
+            | WatchlistRule::LiquidityDrop { window_mins, .. } => {
+                Duration::from_secs(window_mins * 60)
+            }
+        }
+    }
+}
+
+/// Windows DexScreener reports price changes over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeWindow {
+    M5,
+    H1,
+    H6,
+    H24,
+}
+
+impl ChangeWindow {
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "5m" | "m5" => Some(ChangeWindow::M5),
+            "1h" | "h1" | "60m" => Some(ChangeWindow::H1),
+            "6h" | "h6" => Some(ChangeWindow::H6),
+            "24h" | "h24" | "1d" => Some(ChangeWindow::H24),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChangeWindow::M5 => "5m",
+            ChangeWindow::H1 => "1h",
+            ChangeWindow::H6 => "6h",
+            ChangeWindow::H24 => "24h",
+        }
+    }
+
+    pub fn of(&self, change: &PriceChange) -> Option<f64> {
+        match self {
+            ChangeWindow::M5 => change.m5,
+            ChangeWindow::H1 => change.h1,
+            ChangeWindow::H6 => change.h6,
+            ChangeWindow::H24 => change.h24,
+        }
+    }
+}
+
+/// What a target is compared against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetKind {
+    #[default]
+    MarketCap,
+    Price,
+}
+
+impl TargetKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TargetKind::MarketCap => "market cap",
+            TargetKind::Price => "price",
+        }
+    }
+
+    /// A USD value of this kind for log lines and notifications
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            TargetKind::MarketCap => format!("${:.0}", value),
+            TargetKind::Price => {
+                let text = format!("{:.8}", value);
+                format!("${}", text.trim_end_matches('0').trim_end_matches('.'))
+            }
+        }
+    }
+}
+
+/// Prefix marking a target as a token price rather than a market cap
+pub const PRICE_PREFIX: &str = "p:";
+
+/// An alarm target: a market cap (`150000`) or a token price (`p:0.0005`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Target {
+    pub kind: TargetKind,
+    pub value: f64,
+}
+
+impl std::str::FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        let (kind, amount) = match s.strip_prefix(PRICE_PREFIX) {
+            Some(price) => (TargetKind::Price, price),
+            None => (TargetKind::MarketCap, s),
+        };
+        let value: f64 = amount
+            .trim()
+            .trim_start_matches('$')
+            .parse()
+            .map_err(|_| {
+                format!("'{}': expected a market cap like 150000 or a price like p:0.0005", s)
+            })?;
+        if value <= 0.0 || !value.is_finite() {
+            return Err(format!("'{}': the target must be positive", s));
+        }
+        Ok(Self { kind, value })
+    }
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.kind {
+            TargetKind::MarketCap => write!(f, "{}", self.value as u64),
+            TargetKind::Price => write!(f, "{}{}", PRICE_PREFIX, self.value),
+        }
+    }
+}
+
+/// One or more targets of the same kind, comma-separated: `100000`,
+/// `50000,100000,250000` or `p:0.0005,p:0.001`. The highest is the target;
+/// the others are milestones alerted on along the way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Targets {
+    pub kind: TargetKind,
+    /// Ascending without duplicates, never empty
+    pub levels: Vec<f64>,
+}
+
+impl Targets {
+    /// Sorts and dedups `levels`, which must hold at least one positive value
+    pub fn new(kind: TargetKind, mut levels: Vec<f64>) -> Self {
+        levels.retain(|level| *level > 0.0);
+        levels.sort_by(f64::total_cmp);
+        levels.dedup();
+        Self { kind, levels }
+    }
+
+    /// The highest level, which ends the run
+    pub fn top(&self) -> Target {
+        Target {
+            kind: self.kind,
+            value: self.levels.last().copied().unwrap_or(0.0),
+        }
+    }
+
+    /// The levels below the top one
+    pub fn milestones(&self) -> &[f64] {
+        &self.levels[..self.levels.len().saturating_sub(1)]
+    }
+}
+
+impl std::str::FromStr for Targets {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let mut targets = s
+            .split(',')
+            .filter(|part| !part.trim().is_empty())
+            .map(str::parse::<Target>);
+        let first = targets
+            .next()
+            .unwrap_or_else(|| Err("expected a target like 150000 or p:0.0005".to_string()))?;
+        let mut levels = vec![first.value];
+        for target in targets {
+            let target = target?;
+            if target.kind != first.kind {
+                return Err(format!(
+                    "'{}': targets can't mix market caps and prices",
+                    s.trim()
+                ));
+            }
+            levels.push(target.value);
+        }
+        Ok(Self::new(first.kind, levels))
+    }
+}
+
+impl std::fmt::Display for Targets {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let levels: Vec<String> = self
+            .levels
+            .iter()
+            .map(|value| {
+                Target {
+                    kind: self.kind,
+                    value: *value,
+                }
+                .to_string()
+            })
+            .collect();
+        f.write_str(&levels.join(","))
+    }
+}
+
+/// Alert on a relative price move over one of the reported windows, e.g.
+/// `+30%/1h` (up at least 30%) or `-20%/5m` (down at least 20%)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoveRule {
+    /// Signed threshold in percent
+    pub percent: f64,
+    pub window: ChangeWindow,
+}
+
+impl MoveRule {
+    /// Parse `+30%/1h`, `-20% in 5m` or `30/6h` (no sign means up)
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let spec = spec.trim();
+        let (amount, window) = spec
+            .split_once('/')
+            .or_else(|| spec.split_once(" in "))
+            .ok_or_else(|| format!("'{}' needs a window, e.g. +30%/1h", spec))?;
+        let window = ChangeWindow::parse(window)
+            .ok_or_else(|| format!("'{}': window must be 5m, 1h, 6h or 24h", spec))?;
+        let percent: f64 = amount
+            .trim()
+            .trim_end_matches('%')
+            .trim_start_matches('+')
+            .trim()
+            .parse()
+            .map_err(|_| format!("'{}': expected a percentage like +30% or -20%", spec))?;
+        if percent == 0.0 || !percent.is_finite() {
+            return Err(format!("'{}': the move must be non-zero", spec));
+        }
+        Ok(Self { percent, window })
+    }
+
+    /// Parse a comma-separated list, as typed in the config modal
+    pub fn parse_list(specs: &str) -> Result<Vec<Self>, String> {
+        specs
+            .split(',')
+            .filter(|s| !s.trim().is_empty())
+            .map(Self::parse)
+            .collect()
+    }
+
+    /// The reported change, if it crosses the threshold
+    pub fn triggered(&self, change: &PriceChange) -> Option<f64> {
+        let value = self.window.of(change)?;
+        let hit = if self.percent > 0.0 {
+            value >= self.percent
+        } else {
+            value <= self.percent
+        };
+        hit.then_some(value)
+    }
+}
+
+impl std::fmt::Display for MoveRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:+}%/{}", self.percent, self.window.name())
+    }
+}
+
+/// Market cap rate of change over a sliding window, to catch vertical candles
+/// between polls (--velocity)
+#[derive(Debug, Clone)]
+pub struct Velocity {
+    /// Alert threshold in dollars per minute, either direction
+    pub threshold: f64,
+    pub window: Duration,
+    history: VecDeque<(Instant, f64)>,
+    firing: bool,
+}
+
+impl Velocity {
+    pub fn new(threshold: f64, window_mins: u64) -> Self {
+        Self {
+            threshold,
+            window: Duration::from_secs(window_mins.max(1) * 60),
+            history: VecDeque::new(),
+            firing: false,
+        }
+    }
+
+    /// Record a market cap sample. Returns the velocity when it has just
+    /// reached the threshold; it fires again only after dropping back below.
+    pub fn record(&mut self, now: Instant, market_cap: f64) -> Option<f64> {
+        if market_cap <= 0.0 {
+            return None;
+        }
+        push_sample(&mut self.history, now, market_cap, self.window);
+        let velocity = self.current()?;
+        if velocity.abs() < self.threshold {
+            self.firing = false;
+            return None;
+        }
+        (!std::mem::replace(&mut self.firing, true)).then_some(velocity)
+    }
+
+    /// Change in dollars per minute across the window. Moves within the first
+    /// minute count in full, so one tick can't extrapolate to a huge rate.
+    pub fn current(&self) -> Option<f64> {
+        if self.history.len() < 2 {
+            return None;
+        }
+        let (first_at, first) = self.history.front()?;
+        let (last_at, last) = self.history.back()?;
+        let minutes = last_at.duration_since(*first_at).as_secs_f64() / 60.0;
+        Some((last - first) / minutes.max(1.0))
+    }
+
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.firing = false;
+    }
+}
+
+/// Fewest transactions between two fetches that can count as a burst, so a
+/// quiet pool going from one trade to five doesn't alert
+const WHALE_MIN_TXNS: u64 = 20;
+
+/// Transactions that landed between two fetches
+#[derive(Debug, Clone, Copy)]
+pub struct Burst {
+    pub buys: u64,
+    pub sells: u64,
+    pub elapsed: Duration,
+    /// Transactions the day's average rate predicts for `elapsed`
+    pub expected: f64,
+}
+
+impl Burst {
+    pub fn total(&self) -> u64 {
+        self.buys + self.sells
+    }
+
+    /// How many times the usual rate this burst is
+    pub fn ratio(&self) -> f64 {
+        self.total() as f64 / self.expected.max(1.0)
+    }
+}
+
+/// Bursts of buys and sells from one fetch to the next, a proxy for whale or
+/// bot activity (--whale-alert). Counts come from the rolling 24h totals, which
+/// also shed trades from a day ago, so a delta slightly undercounts.
+#[derive(Debug, Clone)]
+pub struct WhaleWatch {
+    /// How many times the usual rate counts as a burst
+    pub multiple: f64,
+    last: Option<(Instant, u64, u64)>,
+}
+
+impl WhaleWatch {
+    pub fn new(multiple: f64) -> Self {
+        Self {
+            multiple,
+            last: None,
+        }
+    }
+
+    /// Record the 24h buy and sell counts. Returns the transactions since the
+    /// previous record when they reach `multiple` times the day's average rate.
+    pub fn record(&mut self, now: Instant, buys: u64, sells: u64) -> Option<Burst> {
+        let (at, previous_buys, previous_sells) = self.last.replace((now, buys, sells))?;
+        let elapsed = now.duration_since(at);
+        let burst = Burst {
+            buys: buys.saturating_sub(previous_buys),
+            sells: sells.saturating_sub(previous_sells),
+            elapsed,
+            expected: (previous_buys + previous_sells) as f64 * elapsed.as_secs_f64()
+                / (24.0 * 3600.0),
+        };
+        (burst.total() >= WHALE_MIN_TXNS && burst.ratio() >= self.multiple).then_some(burst)
+    }
+
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+/// Latest values for one tracked token, fed into the engine each cycle
+pub struct TrackedToken {
+    pub key: String,
+    pub symbol: String,
+    pub market_cap: f64,
+    pub liquidity: f64,
+}
+
+/// Rolling-window state for the watchlist-wide rules
+pub struct WatchlistAlerts {
+    rules: Vec<WatchlistRule>,
+    total_history: VecDeque<(Instant, f64)>,
+    liquidity_history: HashMap<String, VecDeque<(Instant, f64)>>,
+    /// (rule index, token key) pairs currently firing; re-armed once the condition clears
+    firing: HashSet<(usize, String)>,
+}
+
+impl WatchlistAlerts {
+    pub fn new(rules: Vec<WatchlistRule>) -> Self {
+        Self {
+            rules,
+            total_history: VecDeque::new(),
+            liquidity_history: HashMap::new(),
+            firing: HashSet::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Record a new cycle of data and return the severity and message of each
+    /// rule that just fired.
+    /// `complete` is false when some tracked token failed to fetch this cycle, in
+    /// which case the combined total is not sampled (it would look like a drop).
+    pub fn evaluate(&mut self, tokens: &[TrackedToken], complete: bool) -> Vec<(Severity, String)> {
+        let now = Instant::now();
+        let keep = self
+            .rules
+            .iter()
+            .map(|r| r.window())
+            .max()
+            .unwrap_or_default();
+
+        if complete && !tokens.is_empty() {
+            let total = tokens.iter().map(|t| t.market_cap).sum();
+            push_sample(&mut self.total_history, now, total, keep);
+        }
+        for token in tokens {
+            let history = self.liquidity_history.entry(token.key.clone()).or_default();
+            push_sample(history, now, token.liquidity, keep);
+        }
+
+        let mut fired = Vec::new();
+        for (i, rule) in self.rules.iter().enumerate() {
+            match rule {
+                WatchlistRule::TotalValueDrop { percent, .. } => {
+                    let drop = drop_from_peak(&self.total_history, now, rule.window());
+                    let key = (i, String::new());
+                    if drop.is_some_and(|d| d >= *percent) {
+                        if self.firing.insert(key) {
+                            fired.push((
+                                rule.severity(),
+                                format!(
+                                    "Total watchlist value down {:.1}% in {}m (threshold {:.0}%)",
+                                    drop.unwrap_or(0.0),
+                                    rule.window().as_secs() / 60,
+                                    percent
+                                ),
+                            ));
+                        }
+                    } else {
+                        self.firing.remove(&key);
+                    }
+                }
+                WatchlistRule::LiquidityDrop { percent, .. } => {
+                    for token in tokens {
+                        let Some(history) = self.liquidity_history.get(&token.key) else {
+                            continue;
+                        };
+                        let drop = drop_from_peak(history, now, rule.window());
+                        let key = (i, token.key.clone());
+                        if drop.is_some_and(|d| d >= *percent) {
+                            if self.firing.insert(key) {
+                                fired.push((
+                                    rule.severity(),
+                                    format!(
+                                        "{} liquidity down {:.1}% in {}m (threshold {:.0}%)",
+                                        token.symbol,
+                                        drop.unwrap_or(0.0),
+                                        rule.window().as_secs() / 60,
+                                        percent
+                                    ),
+                                ));
+                            }
+                        } else {
+                            self.firing.remove(&key);
+                        }
+                    }
+                }
+            }
+        }
+        fired
+    }
+}
+
+fn push_sample(history: &mut VecDeque<(Instant, f64)>, now: Instant, value: f64, keep: Duration) {
+    history.push_back((now, value));
+    while history
+        .front()
+        .is_some_and(|(at, _)| now.duration_since(*at) > keep)
+    {
+        history.pop_front();
+    }
+}
+
+/// Percent drop of the latest sample from the peak within `window`
+fn drop_from_peak(history: &VecDeque<(Instant, f64)>, now: Instant, window: Duration) -> Option<f64> {
+    let (_, latest) = *history.back()?;
+    let peak = history
+        .iter()
+        .filter(|(at, _)| now.duration_since(*at) <= window)
+        .map(|(_, v)| *v)
+        .fold(f64::MIN, f64::max);
+    if peak <= 0.0 {
+        return None;
+    }
+    Some((peak - latest) / peak * 100.0)
+}
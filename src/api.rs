@@ -1,7 +1,41 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Local, TimeZone};
 use serde::Deserialize;
 
-#[allow(dead_code)]
-const BASE_URL: &str = "https://api.dexscreener.com/latest/dex";
+use crate::chaos;
+use crate::coingecko;
+use crate::endpoints::{self, Api};
+use crate::health::ProviderHealth;
+use crate::jupiter;
+use crate::profile::Link;
+use crate::pumpfun;
+
+const DEXSCREENER_ROOT: &str = "https://api.dexscreener.com";
+
+/// Maximum characters of an error response body to include in log messages
+const MAX_ERROR_BODY: usize = 160;
+
+/// Most search results worth listing
+const SEARCH_RESULTS: usize = 10;
+
+/// Chains DexScreener is known to index; used only to word error hints
+const KNOWN_CHAINS: &[&str] = &[
+    "solana", "ethereum", "bsc", "base", "arbitrum", "polygon", "avalanche", "optimism",
+    "sui", "ton", "tron", "blast", "fantom", "cronos", "pulsechain", "linea", "zksync",
+    "mantle", "sonic", "abstract", "hyperliquid", "berachain", "apechain",
+];
+
+/// Well-known endpoint used to tell "network down" apart from "API erroring"
+const CONNECTIVITY_PROBE_URL: &str = "https://www.gstatic.com/generate_204";
+
+/// Maximum addresses DexScreener accepts in one comma-separated request
+const MAX_BATCH: usize = 30;
+
+/// Error message used when a response parses but contains no pairs
+const NO_PAIRS: &str = "No pair data found in response";
 
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -9,6 +43,15 @@ pub struct DexResponse {
     pub pairs: Option<Vec<PairData>>,
 }
 
+/// The `latest/dex` routes wrap pairs in `{"pairs": [...]}`; the chain-scoped
+/// v1 routes return a bare array
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PairsBody {
+    Wrapped(DexResponse),
+    List(Vec<PairData>),
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -26,6 +69,62 @@ pub struct PairData {
     pub volume: Option<Volume>,
     pub price_change: Option<PriceChange>,
     pub liquidity: Option<Liquidity>,
+    /// When the pair was created, in milliseconds since the epoch
+    pub pair_created_at: Option<i64>,
+    /// Token image, websites and socials (DexScreener only)
+    pub info: Option<PairInfo>,
+    /// pump.fun bonding curve state, for tokens not yet migrated to a DEX
+    #[serde(skip)]
+    pub bonding_curve: Option<BondingCurve>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BondingCurve {
+    /// Share of the curve's tokens sold, in percent
+    pub progress: f64,
+    /// The curve is full and liquidity has migrated to a DEX
+    pub complete: bool,
+}
+
+impl PairData {
+    pub fn created_at(&self) -> Option<DateTime<Local>> {
+        self.pair_created_at
+            .and_then(|ms| Local.timestamp_millis_opt(ms).single())
+    }
+
+    /// Market cap in USD, falling back to FDV when the market cap is missing
+    pub fn market_cap_usd(&self) -> f64 {
+        self.market_cap.unwrap_or(self.fdv.unwrap_or(0.0))
+    }
+
+    pub fn volume_24h_usd(&self) -> f64 {
+        self.volume.as_ref().and_then(|v| v.h24).unwrap_or(0.0)
+    }
+
+    pub fn liquidity_usd(&self) -> f64 {
+        self.liquidity.as_ref().and_then(|l| l.usd).unwrap_or(0.0)
+    }
+
+    pub fn price_usd_f64(&self) -> f64 {
+        self.price_usd
+            .as_deref()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(0.0)
+    }
+
+    pub fn quote_symbol(&self) -> &str {
+        self.quote_token
+            .as_ref()
+            .and_then(|t| t.symbol.as_deref())
+            .unwrap_or("???")
+    }
+
+    pub fn base_symbol(&self) -> &str {
+        self.base_token
+            .as_ref()
+            .and_then(|t| t.symbol.as_deref())
+            .unwrap_or("???")
+    }
 }
 
 #[allow(dead_code)]
@@ -63,6 +162,7 @@ pub struct Volume {
 #[allow(dead_code)]
 #[derive(Debug, Deserialize, Clone)]
 pub struct PriceChange {
+    pub m5: Option<f64>,
     pub h1: Option<f64>,
     pub h6: Option<f64>,
     pub h24: Option<f64>,
@@ -76,40 +176,590 @@ pub struct Liquidity {
     pub quote: Option<f64>,
 }
 
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PairInfo {
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub websites: Vec<Link>,
+    #[serde(default)]
+    pub socials: Vec<Link>,
+}
+
+impl PairInfo {
+    pub fn is_empty(&self) -> bool {
+        self.websites.is_empty() && self.socials.is_empty()
+    }
+}
+
+/// What to fetch: an address on a chain, plus the token's mint/contract once a
+/// previous fetch has revealed it (sources keyed by token use it over a pool address)
+pub struct Target<'a> {
+    pub chain: &'a str,
+    pub address: &'a str,
+    pub token: Option<&'a str>,
+}
+
+/// A market data source. Each provider maps its own API into `PairData` so
+/// the rest of the app never needs to know which one answered.
+#[async_trait]
+pub trait DataSource: Send + Sync {
+    fn provider(&self) -> Provider;
+
+    async fn fetch(&self, target: &Target<'_>) -> Result<PairData, String>;
+}
+
+/// DexScreener pairs/tokens API
+pub struct DexScreener {
+    client: reqwest::Client,
+}
+
+#[async_trait]
+impl DataSource for DexScreener {
+    fn provider(&self) -> Provider {
+        Provider::DexScreener
+    }
+
+    async fn fetch(&self, target: &Target<'_>) -> Result<PairData, String> {
+        fetch_pair_data(&self.client, target.chain, target.address).await
+    }
+}
+
+/// A market data provider that can be selected per chain or via `--provider`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    DexScreener,
+    CoinGecko,
+    Jupiter,
+    PumpFun,
+}
+
+impl Provider {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Provider::DexScreener => "dexscreener",
+            Provider::CoinGecko => "coingecko",
+            Provider::Jupiter => "jupiter",
+            Provider::PumpFun => "pumpfun",
+        }
+    }
+
+    /// The data source implementing this provider
+    pub fn source(&self, client: &reqwest::Client) -> Box<dyn DataSource> {
+        let client = client.clone();
+        let source: Box<dyn DataSource> = match self {
+            Provider::DexScreener => Box::new(DexScreener { client }),
+            Provider::CoinGecko => Box::new(coingecko::CoinGecko::new(client)),
+            Provider::Jupiter => Box::new(jupiter::Jupiter::new(client)),
+            Provider::PumpFun => Box::new(pumpfun::PumpFun::new(client)),
+        };
+        chaos::wrap(source)
+    }
+
+    /// Parse a provider name (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "dexscreener" | "dex" => Some(Provider::DexScreener),
+            "coingecko" | "cg" => Some(Provider::CoinGecko),
+            "jupiter" | "jup" => Some(Provider::Jupiter),
+            "pumpfun" | "pump.fun" | "pump" => Some(Provider::PumpFun),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a list of provider names, returning the known providers and the unknown names
+pub fn parse_providers<S: AsRef<str>>(names: &[S]) -> (Vec<Provider>, Vec<String>) {
+    let mut known = Vec::new();
+    let mut unknown = Vec::new();
+    for name in names {
+        let name = name.as_ref();
+        match Provider::parse(name) {
+            Some(p) if !known.contains(&p) => known.push(p),
+            Some(_) => {}
+            None => unknown.push(name.trim().to_string()),
+        }
+    }
+    (known, unknown)
+}
+
+/// Fetch pair data from each provider in order, returning the first success.
+/// Falls back to DexScreener when no providers are given.
+pub async fn fetch_from_providers(
+    client: &reqwest::Client,
+    providers: &[Provider],
+    chain: &str,
+    address: &str,
+) -> Result<PairData, String> {
+    fetch_with_source(client, providers, chain, address, None, None)
+        .await
+        .map(|(data, _)| data)
+}
+
+/// Sources deviating from the median price by more than this are outliers
+const OUTLIER_PERCENT: f64 = 5.0;
+
+/// Result of querying every provider and taking the median
+pub struct Aggregate {
+    /// The preferred provider's data with price and market cap replaced by the medians
+    pub data: PairData,
+    /// Providers that answered
+    pub sources: Vec<Provider>,
+    /// Providers whose price strayed from the median, with their deviation in percent
+    pub outliers: Vec<(Provider, f64)>,
+}
+
+/// Query every provider for the same token and combine them: median price and
+/// market cap, with sources far from the median flagged as outliers. A single
+/// glitching API then can't move the numbers (with three or more sources).
+pub async fn fetch_aggregated(
+    client: &reqwest::Client,
+    providers: &[Provider],
+    chain: &str,
+    address: &str,
+    token: Option<&str>,
+    health: &mut ProviderHealth,
+) -> Result<Aggregate, String> {
+    let target = Target {
+        chain,
+        address,
+        token,
+    };
+    let mut answers = Vec::new();
+    let mut last_err = String::from("No providers configured");
+    for provider in providers {
+        let started = Instant::now();
+        let result = provider.source(client).fetch(&target).await;
+        health.record(*provider, started.elapsed(), result.as_ref().err().map(|e| e.as_str()));
+        match result {
+            Ok(data) if data.price_usd_f64() > 0.0 => answers.push((*provider, data)),
+            Ok(_) => last_err = format!("{}: no price in response", provider.name()),
+            Err(e) => last_err = format!("{}: {}", provider.name(), e),
+        }
+    }
+    if answers.is_empty() {
+        return Err(last_err);
+    }
+
+    let price = median(answers.iter().map(|(_, d)| d.price_usd_f64()).collect());
+    let market_cap = median(
+        answers
+            .iter()
+            .map(|(_, d)| d.market_cap_usd())
+            .filter(|m| *m > 0.0)
+            .collect(),
+    );
+    let outliers = answers
+        .iter()
+        .map(|(p, d)| (*p, (d.price_usd_f64() - price) / price * 100.0))
+        .filter(|(_, deviation)| deviation.abs() > OUTLIER_PERCENT)
+        .collect();
+    let sources = answers.iter().map(|(p, _)| *p).collect();
+
+    let (_, mut data) = answers.swap_remove(0);
+    let ratio = price / data.price_usd_f64();
+    data.price_usd = Some(price.to_string());
+    if market_cap > 0.0 {
+        data.market_cap = Some(market_cap);
+    }
+    data.fdv = data.fdv.map(|f| f * ratio);
+
+    Ok(Aggregate {
+        data,
+        sources,
+        outliers,
+    })
+}
+
+/// Median of the values (mean of the middle two for an even count); 0 if empty
+fn median(mut values: Vec<f64>) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if mid * 2 == values.len() {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Like `fetch_from_providers`, but also reports which provider answered.
+/// When every provider fails on Solana, pump.fun (for pump.fun mints still on
+/// their bonding curve) and then Jupiter are tried as a last resort.
+/// `token` is the token's mint/contract if a previous fetch revealed it, and
+/// `health` notes how long each provider took to answer.
+pub async fn fetch_with_source(
+    client: &reqwest::Client,
+    providers: &[Provider],
+    chain: &str,
+    address: &str,
+    token: Option<&str>,
+    mut health: Option<&mut ProviderHealth>,
+) -> Result<(PairData, Provider), String> {
+    let mut sources: Vec<Box<dyn DataSource>> = providers.iter().map(|p| p.source(client)).collect();
+    if sources.is_empty() {
+        sources.push(Provider::DexScreener.source(client));
+    }
+    let configured = sources.len();
+    if chain.eq_ignore_ascii_case("solana") {
+        let mint = token.unwrap_or(address);
+        if pumpfun::is_pump_mint(mint) && !providers.contains(&Provider::PumpFun) {
+            sources.push(Provider::PumpFun.source(client));
+        }
+        if !providers.contains(&Provider::Jupiter) {
+            sources.push(Provider::Jupiter.source(client));
+        }
+    }
+
+    let target = Target {
+        chain,
+        address,
+        token,
+    };
+    let mut last_err = String::new();
+    for (i, source) in sources.iter().enumerate() {
+        let started = Instant::now();
+        let result = source.fetch(&target).await;
+        if let Some(ref mut health) = health {
+            let error = result.as_ref().err().map(|e| e.as_str());
+            health.record(source.provider(), started.elapsed(), error);
+        }
+        match result {
+            Ok(data) => return Ok((data, source.provider())),
+            // Report the configured providers' errors, not the fallback's
+            Err(e) if i < configured => {
+                last_err = format!("{}: {}", source.provider().name(), e)
+            }
+            Err(_) => {}
+        }
+    }
+    Err(last_err)
+}
+
 pub async fn fetch_pair_data(
     client: &reqwest::Client,
     chain: &str,
     address: &str,
 ) -> Result<PairData, String> {
-    // Try the /tokens/ endpoint first (works with contract addresses)
-    let token_url = format!("{}/tokens/{}", BASE_URL, address);
-    if let Ok(result) = try_fetch(client, &token_url).await {
-        return Ok(result);
+    // Try the chain-scoped token-pairs endpoint first (works with contract
+    // addresses) and take the most liquid pool rather than whatever
+    // DexScreener lists first
+    if let Some(best) = fetch_chain_pools(client, chain, address)
+        .await
+        .ok()
+        .and_then(|pools| pools.into_iter().next())
+    {
+        return Ok(best);
     }
 
     // Fall back to /pairs/{chain}/{address} (works with pair addresses)
-    let pair_url = format!("{}/pairs/{}/{}", BASE_URL, chain, address);
-    try_fetch(client, &pair_url).await
+    let pair_url = format!("{}/pairs/{}/{}", base_url(), chain, address);
+    let error = match try_fetch(client, &pair_url).await {
+        Ok(data) => return Ok(data),
+        Err(e) => e,
+    };
+
+    // Nothing on this chain. The chain-agnostic /tokens/ endpoint tells a
+    // wrong --chain apart from a wrong address, but is never shown.
+    let elsewhere = listed_chains(client, address)
+        .await
+        .into_iter()
+        .find(|c| !c.eq_ignore_ascii_case(chain));
+    if let Some(other) = elsewhere {
+        return Err(format!(
+            "{} is listed on {}, not {} — check the chain",
+            address, other, chain
+        ));
+    }
+    if error != NO_PAIRS {
+        return Err(error);
+    }
+    if KNOWN_CHAINS.contains(&chain.to_lowercase().as_str()) {
+        Err(format!(
+            "No pairs found for {} on {} — check the address and chain",
+            address, chain
+        ))
+    } else {
+        Err(format!(
+            "No pairs found for {} — chain '{}' may not be supported by DexScreener",
+            address, chain
+        ))
+    }
+}
+
+/// Chains DexScreener lists `address` on (as a token or pair), the chain of its
+/// first-listed pool first. Empty if it's listed nowhere or the lookup fails.
+pub async fn listed_chains(client: &reqwest::Client, address: &str) -> Vec<String> {
+    let token_url = format!("{}/tokens/{}", base_url(), address);
+    let mut chains: Vec<String> = Vec::new();
+    for chain in try_fetch_all(client, &token_url)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|p| p.chain_id)
+    {
+        if !chains.contains(&chain) {
+            chains.push(chain);
+        }
+    }
+    chains
+}
+
+/// Pairs matching a symbol, name or address on any chain, most liquid first,
+/// from DexScreener's search endpoint
+pub async fn search_pairs(client: &reqwest::Client, query: &str) -> Result<Vec<PairData>, String> {
+    let url = reqwest::Url::parse_with_params(&format!("{}/search", base_url()), [("q", query)])
+        .map_err(|e| format!("Invalid search URL: {}", e))?;
+    let mut pairs = try_fetch_all(client, url.as_str()).await?;
+    if pairs.is_empty() {
+        return Err(format!("Nothing on DexScreener matches '{}'", query));
+    }
+    pairs.sort_by(|a, b| b.liquidity_usd().total_cmp(&a.liquidity_usd()));
+    pairs.truncate(SEARCH_RESULTS);
+    Ok(pairs)
+}
+
+/// Pools of a token on `chain` only, most liquid first, from the chain-scoped
+/// token-pairs endpoint
+async fn fetch_chain_pools(
+    client: &reqwest::Client,
+    chain: &str,
+    token_address: &str,
+) -> Result<Vec<PairData>, String> {
+    let url = format!(
+        "{}/token-pairs/v1/{}/{}",
+        dexscreener_root(),
+        chain.to_lowercase(),
+        token_address
+    );
+    let mut pools = try_fetch_all(client, &url).await?;
+    if pools.is_empty() {
+        return Err(NO_PAIRS.to_string());
+    }
+    pools.sort_by(|a, b| b.liquidity_usd().total_cmp(&a.liquidity_usd()));
+    Ok(pools)
+}
+
+/// All pools DexScreener lists for a token address, ordered with pools on
+/// `chain` first and then by USD liquidity, highest first. Pools on other
+/// chains only appear when the chain-scoped lookup finds none.
+pub async fn fetch_token_pools(
+    client: &reqwest::Client,
+    chain: &str,
+    token_address: &str,
+) -> Result<Vec<PairData>, String> {
+    if let Ok(pools) = fetch_chain_pools(client, chain, token_address).await {
+        return Ok(pools);
+    }
+
+    let token_url = format!("{}/tokens/{}", base_url(), token_address);
+    let mut pools = try_fetch_all(client, &token_url).await?;
+    if pools.is_empty() {
+        return Err(NO_PAIRS.to_string());
+    }
+
+    let off_chain = |p: &PairData| {
+        !p.chain_id
+            .as_deref()
+            .is_some_and(|c| c.eq_ignore_ascii_case(chain))
+    };
+    pools.sort_by(|a, b| {
+        off_chain(a)
+            .cmp(&off_chain(b))
+            .then(b.liquidity_usd().total_cmp(&a.liquidity_usd()))
+    });
+    Ok(pools)
+}
+
+/// Fetch many addresses (token or pair) on one chain using DexScreener's
+/// comma-separated endpoints — one or two HTTP calls per 30 addresses instead of
+/// one per address. Returns the pairs found keyed by the requested address;
+/// addresses with no match are simply absent.
+pub async fn fetch_pairs_batch(
+    client: &reqwest::Client,
+    chain: &str,
+    addresses: &[String],
+) -> Result<HashMap<String, PairData>, String> {
+    let mut found = HashMap::new();
+    let mut last_err = None;
+
+    for chunk in addresses.chunks(MAX_BATCH) {
+        // Token addresses first
+        let token_url = format!("{}/tokens/{}", base_url(), chunk.join(","));
+        match try_fetch_all(client, &token_url).await {
+            Ok(pairs) => match_batch(chunk, chain, &pairs, &mut found),
+            Err(e) => last_err = Some(e),
+        }
+
+        // Whatever didn't match is probably a pair address
+        let remaining: Vec<String> = chunk
+            .iter()
+            .filter(|a| !found.contains_key(*a))
+            .cloned()
+            .collect();
+        if remaining.is_empty() {
+            continue;
+        }
+        let pair_url = format!("{}/pairs/{}/{}", base_url(), chain, remaining.join(","));
+        match try_fetch_all(client, &pair_url).await {
+            Ok(pairs) => match_batch(&remaining, chain, &pairs, &mut found),
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    match last_err {
+        Some(e) if found.is_empty() => Err(e),
+        _ => Ok(found),
+    }
+}
+
+/// Assign each requested address the most liquid returned pair on `chain`
+/// whose pair address or base token address matches it
+fn match_batch(
+    addresses: &[String],
+    chain: &str,
+    pairs: &[PairData],
+    found: &mut HashMap<String, PairData>,
+) {
+    for address in addresses {
+        if found.contains_key(address) {
+            continue;
+        }
+        let hit = pairs
+            .iter()
+            .filter(|p| {
+                let on_chain = match p.chain_id.as_deref() {
+                    Some(c) => c.eq_ignore_ascii_case(chain),
+                    None => true,
+                };
+                let pair_match = p
+                    .pair_address
+                    .as_deref()
+                    .is_some_and(|a| a.eq_ignore_ascii_case(address));
+                let token_match = p
+                    .base_token
+                    .as_ref()
+                    .and_then(|t| t.address.as_deref())
+                    .is_some_and(|a| a.eq_ignore_ascii_case(address));
+                on_chain && (pair_match || token_match)
+            })
+            .max_by(|a, b| a.liquidity_usd().total_cmp(&b.liquidity_usd()));
+        if let Some(pair) = hit {
+            found.insert(address.clone(), pair.clone());
+        }
+    }
 }
 
 async fn try_fetch(client: &reqwest::Client, url: &str) -> Result<PairData, String> {
-    let response = client
-        .get(url)
+    try_fetch_all(client, url)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| NO_PAIRS.to_string())
+}
+
+async fn try_fetch_all(client: &reqwest::Client, url: &str) -> Result<Vec<PairData>, String> {
+    let response = endpoints::get(client, Api::DexScreener, url)
         .timeout(std::time::Duration::from_secs(10))
         .send()
         .await
         .map_err(|e| format!("HTTP request failed: {}", e))?;
 
-    if !response.status().is_success() {
-        return Err(format!("API returned status: {}", response.status()));
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(describe_http_error(status, &body));
     }
 
-    let data: DexResponse = response
+    let data: PairsBody = response
         .json()
         .await
         .map_err(|e| format!("JSON parse error: {}", e))?;
 
-    data.pairs
-        .and_then(|pairs| pairs.into_iter().next())
-        .ok_or_else(|| "No pair data found in response".to_string())
+    Ok(match data {
+        PairsBody::Wrapped(response) => response.pairs.unwrap_or_default(),
+        PairsBody::List(pairs) => pairs,
+    })
+}
+
+/// Point every DexScreener request (pairs, tokens, profiles) at another root
+/// URL, as the `apis` config section does. Only the first call takes effect.
+#[cfg(test)]
+pub fn set_dexscreener_root(url: &str) {
+    let api = crate::config::ApiConfig {
+        base_url: Some(url.to_string()),
+        ..Default::default()
+    };
+    endpoints::configure(&HashMap::from([(Api::DexScreener.key().to_string(), api)]));
+}
+
+/// DexScreener API root (configured or public), without a trailing slash
+pub fn dexscreener_root() -> &'static str {
+    endpoints::base_url(Api::DexScreener, DEXSCREENER_ROOT)
+}
+
+fn base_url() -> String {
+    format!("{}/latest/dex", dexscreener_root())
+}
+
+/// Probe a well-known endpoint. Any HTTP response at all means the network is up.
+pub async fn network_reachable(client: &reqwest::Client) -> bool {
+    client
+        .head(CONNECTIVITY_PROBE_URL)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await
+        .is_ok()
+}
+
+/// Turn a non-2xx response into an actionable message, including a truncated
+/// excerpt of the error body returned by the API
+pub fn describe_http_error(status: reqwest::StatusCode, body: &str) -> String {
+    let hint = match status.as_u16() {
+        400 => "Bad request — the chain or address format may be invalid",
+        401 | 403 => "Access denied by the API — check API keys or proxy settings",
+        404 => "Not found — check the token/pair address and chain",
+        429 => "Rate limited — try a longer --interval",
+        500..=599 => "API server error — will retry next cycle",
+        _ => "Unexpected API response",
+    };
+
+    let detail = error_body_message(body);
+    if detail.is_empty() {
+        format!("{} (HTTP {})", hint, status.as_u16())
+    } else {
+        format!("{} (HTTP {}: {})", hint, status.as_u16(), detail)
+    }
+}
+
+/// Extract a readable message from an error body: the `message`/`error` field if
+/// it's JSON, otherwise the raw text with whitespace collapsed. Truncated.
+fn error_body_message(body: &str) -> String {
+    // HTML error pages (proxies, CDNs) are noise in a one-line log entry
+    if body.trim_start().starts_with('<') {
+        return String::new();
+    }
+    let from_json = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| {
+            ["message", "error", "detail"]
+                .iter()
+                .find_map(|k| v.get(k).and_then(|m| m.as_str()).map(String::from))
+        });
+    let text = from_json
+        .unwrap_or_else(|| body.split_whitespace().collect::<Vec<_>>().join(" "));
+    truncate(&text, MAX_ERROR_BODY)
+}
+
+/// Truncate to at most `max` characters, appending an ellipsis when cut
+pub fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(max.saturating_sub(1)).collect();
+    out.push('…');
+    out
 }
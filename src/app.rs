@@ -1,29 +1,285 @@
-use chrono::Local;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
 
-use crate::api::PairData;
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::alerts::{
+    MoveRule, Target, TargetKind, Targets, TrackedToken, Velocity, WatchlistAlerts, WhaleWatch,
+};
+use crate::birdeye::{self, BirdeyeView};
+use crate::bus::BusEvent;
+use crate::cex::CexCheck;
+use crate::api::{self, BondingCurve, PairData, PairInfo, PriceChange, Provider};
+use crate::coingecko::TickerQuote;
+use crate::compare::CompareState;
+use crate::condition::{Condition, Variable};
+use crate::ath::{self, AllTimeHigh, HighStore};
+use crate::config::{Config, RecentPair, SavedRule, PINNED_PORTFOLIO};
+use crate::display::Display;
+use crate::eventlog::{Event, EventLog};
+use crate::fx::Currency;
+use crate::geckoterminal::Candle;
+use crate::health::ProviderHealth;
+use crate::hook::Hooks;
+use crate::jitter;
+use crate::macros;
+use crate::metadata::{self, MetadataCache, TokenMetadata};
+use crate::notify::{self, AlertContext, Channel, Outbox, Severity};
+use crate::ohlc::OhlcTable;
+use crate::onboarding::Onboarding;
+use crate::pacing::FramePacer;
+use crate::perps::{self, PerpMarket};
+use crate::portfolio::GroupView;
+use crate::profile::TokenProfile;
+use crate::quotes::{self, PoolTotals, QuoteGap};
+use crate::rpc::{self, Fee};
+use crate::safety::{self, SecurityReport};
+use crate::schedule::{self, Schedule};
+use crate::screencast::Script;
+use crate::session::SessionStats;
+use crate::social::{self, Audience, Network};
+use crate::speech;
+use crate::ui;
+use crate::unlocks::{self, Unlock};
+use crate::wallet::{self, WalletToken};
 
 /// Maximum number of history points to keep for the sparkline
-const MAX_HISTORY: usize = 60;
+pub const MAX_HISTORY: usize = 60;
 
 /// Maximum number of log messages to keep
 const MAX_LOG: usize = 100;
 
-/// Field labels for the config modal
-pub const MODAL_FIELD_LABELS: [&str; 4] = ["Token / Pair Address", "Chain", "Target MCap ($)", "Interval (s)"];
+/// Consecutive fetch failures before probing whether the network itself is down
+pub const OFFLINE_PROBE_AFTER: u32 = 3;
+
+/// Retry interval while the network is unreachable
+const OFFLINE_RETRY_SECS: u64 = 15;
+
+/// How often the on-chain supply is re-read in --onchain-supply mode
+const SUPPLY_REFRESH: Duration = Duration::from_secs(600);
+
+/// How often the DexScreener profile/boost feeds are re-checked
+const PROFILE_REFRESH: Duration = Duration::from_secs(300);
+
+/// How often the gas / priority fee indicator is refreshed
+const FEE_REFRESH: Duration = Duration::from_secs(60);
+
+/// How often a --wallet is re-scanned for tokens bought or sold
+const WALLET_REFRESH: Duration = Duration::from_secs(300);
+
+/// How often the --wallet balance of the monitored token is re-read
+const BALANCE_REFRESH: Duration = Duration::from_secs(30);
+
+/// How often the --currency exchange rate is refreshed
+const FX_REFRESH: Duration = Duration::from_secs(3600);
+
+/// How often CoinGecko is asked for new exchange listings (--listings)
+const LISTINGS_REFRESH: Duration = Duration::from_secs(600);
+
+/// How often the providers not leading the failover chain are asked too, so
+/// their health stays current
+const HEALTH_PROBE: Duration = Duration::from_secs(300);
+
+/// How often perp funding and open interest are refreshed (--perps)
+const PERPS_REFRESH: Duration = Duration::from_secs(300);
+
+/// How often Telegram member and Twitter follower counts are re-read (--social)
+const SOCIAL_REFRESH: Duration = Duration::from_secs(900);
+
+/// How often the header ticker strip is refreshed
+const TICKER_REFRESH: Duration = Duration::from_secs(60);
+
+/// How long an observer has to press q again to quit
+const QUIT_CONFIRM: Duration = Duration::from_secs(3);
+
+/// Minimum time between two alerts from the same rule, unless configured
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(300);
+
+/// Snooze lengths 'z' steps through, in minutes, before switching it off
+const SNOOZE_STEPS: [i64; 3] = [15, 60, 240];
+
+/// Redraw/input tick in normal mode
+const TICK_MS: u64 = 200;
+
+/// Pool liquidity (USD) below which the low liquidity warning is shown
+pub const LOW_LIQUIDITY_USD: f64 = 10_000.0;
+
+/// One line of the activity log
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    /// `HH:MM:SS` the line was logged at
+    pub time: String,
+    /// Day it was logged, from the same clock reading as `time`
+    pub date: NaiveDate,
+    /// The message, without its time stamp
+    pub text: String,
+    /// Severity, when the line reports an alert
+    pub level: Option<Severity>,
+}
+
+impl LogLine {
+    /// A line logged `at`. Messages come stamped `[HH:MM:SS] ...`; the stamp is
+    /// replaced by `at`'s, so time and date never straddle midnight.
+    fn new(msg: String, at: DateTime<Local>) -> Self {
+        let stamped = msg.len() > 10 && msg.starts_with('[') && msg.as_bytes()[9] == b']';
+        let text = match msg.get(10..).filter(|_| stamped) {
+            Some(rest) => rest.strip_prefix(' ').unwrap_or(rest).to_string(),
+            None => msg,
+        };
+        Self {
+            time: at.format("%H:%M:%S").to_string(),
+            date: at.date_naive(),
+            text,
+            level: None,
+        }
+    }
+}
+
+impl std::fmt::Display for LogLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.time, self.text)
+    }
+}
+
+/// A standing warning shown in a banner over the body until dismissed with 'w'
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenWarning {
+    /// The safety check found the token dangerous
+    Security,
+    LowLiquidity,
+    /// An alarm file is set, but this build can't play audio
+    NoAudio,
+}
+
+impl TokenWarning {
+    /// Name the dismissal is stored under in the config file
+    pub fn name(&self) -> &'static str {
+        match self {
+            TokenWarning::Security => "security",
+            TokenWarning::LowLiquidity => "low_liquidity",
+            TokenWarning::NoAudio => "no_audio",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            TokenWarning::Security => "security",
+            TokenWarning::LowLiquidity => "low liquidity",
+            TokenWarning::NoAudio => "missing audio",
+        }
+    }
+}
+
+/// What picking an entry in the recent pairs popup does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwitcherMode {
+    /// Switch monitoring to the chosen pair
+    Switch,
+    /// Show the chosen pair side-by-side with the monitored one
+    Compare,
+}
+
+/// A field of the config modal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModalField {
+    Pair,
+    Chain,
+    Target,
+    Interval,
+    MoveAlerts,
+    AlarmDuration,
+    AlarmFile,
+}
+
+impl ModalField {
+    /// Every field, in display order
+    pub const ALL: [ModalField; 7] = [
+        ModalField::Pair,
+        ModalField::Chain,
+        ModalField::Target,
+        ModalField::Interval,
+        ModalField::MoveAlerts,
+        ModalField::AlarmDuration,
+        ModalField::AlarmFile,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ModalField::Pair => "Token / Pair Address",
+            ModalField::Chain => "Chain",
+            ModalField::Target => "Target MCap ($) or price (p:0.0005), comma-separated",
+            ModalField::Interval => "Interval (s)",
+            ModalField::MoveAlerts => "Move alerts (e.g. +30%/1h, -20%/5m)",
+            ModalField::AlarmDuration => "Alarm duration (s)",
+            ModalField::AlarmFile => "Alarm sound file (empty for the terminal bell)",
+        }
+    }
+
+    fn position(&self) -> usize {
+        Self::ALL.iter().position(|f| f == self).unwrap_or(0)
+    }
+
+    /// The field below this one, wrapping around
+    pub fn next(&self) -> Self {
+        Self::ALL[(self.position() + 1) % Self::ALL.len()]
+    }
+
+    /// The field above this one, wrapping around
+    pub fn prev(&self) -> Self {
+        Self::ALL[(self.position() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// Text typed into each config modal field
+#[derive(Debug, Clone)]
+pub struct ModalFields(Vec<String>);
+
+impl Default for ModalFields {
+    fn default() -> Self {
+        Self(vec![String::new(); ModalField::ALL.len()])
+    }
+}
+
+impl std::ops::Index<ModalField> for ModalFields {
+    type Output = String;
+
+    fn index(&self, field: ModalField) -> &String {
+        &self.0[field.position()]
+    }
+}
+
+impl std::ops::IndexMut<ModalField> for ModalFields {
+    fn index_mut(&mut self, field: ModalField) -> &mut String {
+        &mut self.0[field.position()]
+    }
+}
 
 #[allow(dead_code)]
 pub struct App {
     // Config
     pub pair_address: String,
     pub chain: String,
+    /// The target, a market cap or a token price depending on `target_kind`
     pub target_market_cap: f64,
+    pub target_kind: TargetKind,
+    /// Levels below the target given with it, from --target or the config modal
+    pub milestones: Vec<f64>,
+    /// Values alerted on, ascending: the target plus configured milestones
+    pub targets: Vec<f64>,
     pub check_interval: u64,
     pub alarm_file: Option<String>,
     pub alarm_duration: u64,
+    pub providers: Vec<Provider>,
+    pub rpc_url: Option<String>,
+    pub config: Config,
 
     // Live data
     pub token_name: String,
     pub token_symbol: String,
+    pub token_address: Option<String>,
+    /// Token decimals, once read on-chain or from the metadata cache
+    pub token_decimals: Option<u32>,
     pub current_price: f64,
     pub market_cap: f64,
     pub fdv: f64,
@@ -33,25 +289,278 @@ pub struct App {
     pub liquidity_usd: f64,
     pub buys_24h: u64,
     pub sells_24h: u64,
+    /// Websites and socials from DexScreener's pair info
+    pub info: Option<PairInfo>,
+    pub pair_created_at: Option<DateTime<Local>>,
+    /// pump.fun bonding curve, while the token hasn't migrated to a DEX
+    pub bonding: Option<BondingCurve>,
 
     // UI state
     pub market_cap_history: Vec<u64>,
-    pub log_messages: Vec<String>,
+    /// Hourly/daily OHLC summary of the market cap (--summary)
+    pub ohlc: Option<OhlcTable>,
+    /// Averages, time around the target and streaks since the pair was configured
+    pub session: SessionStats,
+    /// Session panel toggled with 'i'
+    pub show_session: bool,
+    /// Values at the progress gauge's ticks, toggled with 't'
+    pub show_gauge_values: bool,
+    /// Colors and characters the terminal can show; frames are brought down to it
+    pub display: Display,
+    pub log: Vec<LogLine>,
     pub last_fetch: Option<String>,
+    /// When the last scheduled fetch started, and the random delay added to the next
+    pub fetch_started: Option<DateTime<Local>>,
+    pub fetch_jitter: Duration,
+    /// Fetch on wall-clock multiples of the interval (--align)
+    pub align_fetches: bool,
+    /// How many of `targets` have been reached (always the lowest ones)
+    pub targets_hit: usize,
+    /// Whether every target has been reached
     pub target_hit: bool,
+    /// Percentages of the way to the highest target that raise an info alert,
+    /// ascending (--progress-alert)
+    pub progress_alerts: Vec<f64>,
+    /// How many of `progress_alerts` have been passed
+    pub progress_hit: usize,
+    /// Re-arm a reached target once the value falls this percent below it
+    /// (--rearm); unset, targets stay reached
+    pub rearm_percent: Option<f64>,
+    /// Separate fully diluted valuation target with its own gauge (--fdv-target)
+    pub fdv_target: Option<f64>,
+    pub fdv_target_hit: bool,
+    /// Alert when the market cap retraces this percent from the session peak
+    /// (--trailing-stop)
+    pub trailing_stop: Option<f64>,
+    /// Session peak the trailing stop last fired at; a new high re-arms it
+    pub trailing_fired_at: Option<f64>,
+    /// Market cap $/minute alert over a sliding window (--velocity)
+    pub velocity: Option<Velocity>,
+    /// Transaction burst alert between fetches (--whale-alert)
+    pub whale_watch: Option<WhaleWatch>,
+    /// Alert when the market cap beats the pair's recorded all-time high (--ath-alert)
+    pub ath_alert: bool,
+    /// The pair's all-time high from earlier sessions, read at the first fetch
+    pub ath_previous: Option<f64>,
+    pub ath_loaded: bool,
+    /// The pair's all-time high, this session's included
+    pub ath: Option<AllTimeHigh>,
+    /// Where highs are kept across sessions
+    pub highs: HighStore,
+    /// When `ath` was last written, and whether it has risen since
+    ath_saved_at: Option<Instant>,
+    ath_unsaved: bool,
+    /// Intervals without a successful fetch before the data is stale (--stale-after)
+    pub stale_after: Option<u32>,
+    /// Raise the stale-data alert as critical, so it sets off the alarm (--stale-alarm)
+    pub stale_alarm: bool,
+    /// Desktop notifications for target hits and failing fetches, whatever
+    /// the alert routing says (--desktop-notify)
+    pub desktop_notify: bool,
+    /// File the pair's status line is written to after each fetch, for a tmux
+    /// status bar (--status-file)
+    pub status_file: Option<String>,
+    /// Last successful fetch, or when polling last (re)started
+    pub fresh_at: Instant,
+    /// Whether the stale-data alert has fired since the last good fetch
+    pub stale_fired: bool,
     pub alarm_active: bool,
+    /// Most severe alert since the alarm went off; picks its sound and colors
+    pub alarm_severity: Severity,
+    /// Audio file of the saved rule that set off the alarm, if it has one
+    pub alarm_sound_file: Option<String>,
+    /// Short description of the most recent alert, for banners and notifications
+    pub last_alert: Option<String>,
+    /// Cooldown for rules without one in `alert_cooldowns` (--cooldown)
+    pub cooldown_override: Option<Duration>,
+    /// When each rule (e.g. `move:+30%/1h`) last alerted
+    pub rule_alerted: HashMap<String, Instant>,
+    /// Alarm and notifications are held back until then ('z')
+    pub snoozed_until: Option<DateTime<Local>>,
+    /// Visual-only alerts: banner, title, OSC notification and tmux status, no audio
+    pub silent_alerts: bool,
     pub running: bool,
     pub fetch_count: u64,
     pub error_count: u64,
+    pub consecutive_failures: u32,
+    pub offline: bool,
+    /// Outside the pair's monitoring schedule: the pair isn't polled
+    pub schedule_paused: bool,
+    /// Polling paused from the keyboard (space)
+    pub manual_paused: bool,
+    /// When the current pause began, and once it's over, the start of the gap
+    /// the next fetch reports on
+    pub paused_since: Option<DateTime<Local>>,
+    pub catch_up_since: Option<DateTime<Local>>,
+    pub low_power: bool,
+    /// Follow the battery state automatically (cleared by a manual toggle)
+    pub low_power_auto: bool,
 
     // Modal state
     pub modal_open: bool,
-    pub modal_fields: [String; 4], // [pair, chain, target, interval]
-    pub modal_active_field: usize,
+    pub modal_fields: ModalFields,
+    pub modal_active_field: ModalField,
     pub configured: bool,
 
+    // Recent pairs quick-switch popup
+    pub switcher_open: bool,
+    pub switcher_query: String,
+    pub switcher_selected: usize,
+    pub switcher_mode: SwitcherMode,
+
+    // Diagnostics shown when a pair's very first fetch fails
+    pub onboarding: Option<Onboarding>,
+    /// Whether the diagnostics were already shown for the current pair
+    pub onboarding_offered: bool,
+
+    // Keystroke macros
+    /// Keys recorded so far while recording a macro ('m')
+    pub macro_recording: Option<Vec<String>>,
+    /// A finished recording waiting for the digit key to bind it to
+    pub macro_unbound: Option<Vec<String>>,
+    /// Keys of a macro being played back, dispatched one per loop pass
+    pub macro_queue: VecDeque<(KeyCode, KeyModifiers)>,
+
+    // Compare mode
+    pub compare: Option<CompareState>,
+
+    /// Scripted demo run that replaces fetching (--screencast)
+    pub screencast: Option<Script>,
+
+    // Pool selector (other pools for the same token)
+    pub pools: Vec<PairData>,
+    /// Pair address of the pool the last fetch returned
+    pub pool_address: Option<String>,
+    pub pool_selector_open: bool,
+    pub pool_selected: usize,
+    /// Price difference between the token's quote pools (e.g. SOL vs USDC)
+    pub quote_gap: Option<QuoteGap>,
+    /// Show liquidity/volume summed across all pools (--all-pools)
+    pub all_pools: bool,
+    pub pool_totals: Option<PoolTotals>,
+    /// Alert when the quote gap reaches this percent
+    pub gap_alert: Option<f64>,
+    pub gap_alert_firing: bool,
+    /// Alert when liquidity falls by this percent between two fetches (--rug-alert)
+    pub rug_alert: Option<f64>,
+
+    /// Compute market cap from on-chain supply x price (--onchain-supply)
+    pub onchain_mcap: bool,
+    pub onchain_supply: Option<f64>,
+    pub supply_checked: Option<Instant>,
+
+    /// Median across all providers instead of first-success (--aggregate)
+    pub aggregate: bool,
+    pub aggregate_sources: Vec<Provider>,
+    pub outliers: Vec<Provider>,
+
+    /// Provider that answered the last successful fetch
+    pub active_source: Option<Provider>,
+    /// Latency and error rate per provider, which orders the failover chain
+    pub provider_health: ProviderHealth,
+    pub health_checked: Option<Instant>,
+    /// Provider put first by hand, overriding the health ranking
+    pub pinned_provider: Option<Provider>,
+    /// Whether the provider diagnostics panel is shown
+    pub show_health: bool,
+    /// Redraw cap and render times, also shown in the diagnostics panel
+    pub frames: FramePacer,
+
+    /// DEX vs CEX price cross-check (--cex)
+    pub cex: Option<CexCheck>,
+    /// Watch for new centralized exchange listings (--listings)
+    pub watch_listings: bool,
+    /// Exchanges the token is known to be listed on; None until the first check
+    pub listed_on: Option<BTreeSet<String>>,
+    pub listings_checked: Option<Instant>,
+    /// Show funding and open interest of the token's perp market (--perps)
+    pub watch_perps: bool,
+    pub perp: Option<PerpMarket>,
+    pub perp_error: Option<String>,
+    pub perps_checked: Option<Instant>,
+    /// Track the token's Telegram and Twitter audience (--social)
+    pub watch_social: bool,
+    pub social: Vec<Audience>,
+    pub social_checked: Option<Instant>,
+    /// Growth in percent between two checks that raises an alert (--social-alert)
+    pub social_alert: Option<f64>,
+
+    /// Holder data from Birdeye (Solana tokens, when an API key is set)
+    pub birdeye: Option<BirdeyeView>,
+    /// Gas price or priority fee on the pair's chain
+    pub fee: Option<Fee>,
+    pub fee_checked: Option<Instant>,
+    /// Majors shown in the header ticker strip
+    pub ticker: Vec<TickerQuote>,
+    pub ticker_checked: Option<Instant>,
+    /// Currency values are displayed in (--currency)
+    pub currency: Currency,
+    pub fx_checked: Option<Instant>,
+    /// DexScreener profile and boost status
+    pub profile: Option<TokenProfile>,
+    pub profile_checked: Option<Instant>,
+    /// Resolved token metadata, kept on disk across sessions
+    pub metadata: MetadataCache,
+    /// Rug/honeypot check, run once per configured pair
+    pub security: Option<SecurityReport>,
+    /// Live prices from Birdeye's websocket between fetches (--stream)
+    pub streaming: bool,
+    pub stream_live: bool,
+
+    // Portfolio panel
+    pub portfolio: Option<GroupView>,
+    /// Wallet whose tokens are watched in the portfolio panel (--wallet)
+    pub wallet: Option<String>,
+    /// ENS/SNS name the wallet was given as, if any
+    pub wallet_name: Option<String>,
+    /// Holdings worth less than this are left out
+    pub wallet_min_usd: f64,
+    /// Mints watched from the wallet, largest holding first
+    pub wallet_tokens: Vec<String>,
+    pub wallet_checked: Option<Instant>,
+    /// How much of the monitored token the wallet holds
+    pub wallet_balance: Option<f64>,
+    /// Size of the last balance change, shown next to the balance
+    pub balance_change: Option<f64>,
+    pub balance_checked: Option<Instant>,
+    pub watchlist_alerts: WatchlistAlerts,
+    /// (unlock date, hours-before) alerts already raised this session
+    pub unlock_alerts_fired: HashSet<(String, u64)>,
+    /// Alerts waiting for (re)delivery on notification channels
+    pub outbox: Outbox,
+    /// `on_alert` commands still running
+    pub hooks: Hooks,
+    /// Alerts waiting to be published to the Redis event bus
+    pub bus_queue: Vec<BusEvent>,
+    /// Relative price move alerts (--move-alert or the config modal)
+    pub move_alerts: Vec<MoveRule>,
+    /// Indexes of move alerts that fired and haven't re-armed yet
+    pub moves_firing: HashSet<usize>,
+    /// Expression alerts over the pair data (--when)
+    pub conditions: Vec<Condition>,
+    /// Indexes of conditions that hold and have already alerted
+    pub conditions_firing: HashSet<usize>,
+    /// Saved rules to attach by name besides those the config attaches (--rule)
+    pub rule_names: Vec<String>,
+    /// Saved rules attached to the pair, with their parsed conditions
+    pub saved_rules: Vec<(SavedRule, Condition)>,
+    /// Names of saved rules that hold and have already alerted
+    pub saved_rules_firing: HashSet<String>,
+    /// Durable record of what happened, if `event_log` is configured
+    pub event_log: Option<EventLog>,
+
     // Daemon / idle
     pub go_idle: bool,
+    /// Alert flags given that the daemon doesn't check, which keep the TUI from
+    /// going idle
+    pub tui_only_alerts: Vec<&'static str>,
+
+    /// Name of this instance (--name), shown in the header, terminal title and notifications
+    pub instance: Option<String>,
+    /// Read-only mode for shared screens (--observer)
+    pub observer: bool,
+    /// When an observer first pressed q
+    pub quit_armed: Option<Instant>,
 }
 
 impl App {
@@ -61,12 +570,20 @@ impl App {
             pair_address: String::new(),
             chain: String::from("solana"),
             target_market_cap: 100000.0,
+            target_kind: TargetKind::MarketCap,
+            milestones: Vec::new(),
+            targets: Vec::new(),
             check_interval: 180,
             alarm_file,
             alarm_duration,
+            providers: vec![Provider::DexScreener],
+            rpc_url: None,
+            config: Config::load(),
 
             token_name: String::from("MoonCap Demo"),
             token_symbol: String::from("MOON"),
+            token_address: None,
+            token_decimals: None,
             current_price: 0.00004200,
             market_cap: 42000.0,
             fdv: 42000.0,
@@ -76,28 +593,164 @@ impl App {
             liquidity_usd: 8500.0,
             buys_24h: 420,
             sells_24h: 69,
+            info: None,
+            pair_created_at: None,
+            bonding: None,
 
             market_cap_history: vec![35000, 36500, 38000, 37200, 39000, 40500, 41000, 42000],
-            log_messages: Vec::new(),
+            ohlc: None,
+            session: SessionStats::new(Local::now()),
+            show_session: false,
+            show_gauge_values: false,
+            display: Display::default(),
+            log: Vec::new(),
             last_fetch: None,
+            fetch_started: None,
+            fetch_jitter: Duration::ZERO,
+            align_fetches: false,
+            targets_hit: 0,
             target_hit: false,
+            rearm_percent: None,
+            progress_alerts: Vec::new(),
+            progress_hit: 0,
+            fdv_target: None,
+            fdv_target_hit: false,
+            trailing_stop: None,
+            ath_alert: false,
+            ath_previous: None,
+            ath_loaded: false,
+            ath: None,
+            highs: HighStore::open(),
+            ath_saved_at: None,
+            ath_unsaved: false,
+            stale_after: None,
+            stale_alarm: false,
+            desktop_notify: false,
+            status_file: None,
+            fresh_at: Instant::now(),
+            stale_fired: false,
+            trailing_fired_at: None,
+            velocity: None,
+            whale_watch: None,
             alarm_active: false,
+            alarm_severity: Severity::Warning,
+            alarm_sound_file: None,
+            last_alert: None,
+            cooldown_override: None,
+            rule_alerted: HashMap::new(),
+            snoozed_until: None,
+            silent_alerts: false,
             running: true,
             fetch_count: 0,
             error_count: 0,
+            consecutive_failures: 0,
+            offline: false,
+            schedule_paused: false,
+            manual_paused: false,
+            paused_since: None,
+            catch_up_since: None,
+            low_power: false,
+            low_power_auto: false,
 
             modal_open: true,
-            modal_fields: [
-                String::new(),                // pair
-                String::from("solana"),        // chain
-                String::from("100000"),        // target
-                String::from("180"),           // interval
-            ],
-            modal_active_field: 0,
+            modal_fields: ModalFields::default(),
+            modal_active_field: ModalField::Pair,
             configured: false,
 
+            switcher_open: false,
+            macro_recording: None,
+            macro_unbound: None,
+            macro_queue: VecDeque::new(),
+            switcher_query: String::new(),
+            switcher_selected: 0,
+            switcher_mode: SwitcherMode::Switch,
+            onboarding: None,
+            onboarding_offered: false,
+
+            compare: None,
+            screencast: None,
+
+            pools: Vec::new(),
+            pool_address: None,
+            pool_selector_open: false,
+            pool_selected: 0,
+            quote_gap: None,
+            all_pools: false,
+            pool_totals: None,
+            gap_alert: None,
+            gap_alert_firing: false,
+            rug_alert: None,
+
+            onchain_mcap: false,
+            onchain_supply: None,
+            supply_checked: None,
+            aggregate: false,
+            aggregate_sources: Vec::new(),
+            outliers: Vec::new(),
+            active_source: None,
+            provider_health: ProviderHealth::default(),
+            health_checked: None,
+            pinned_provider: None,
+            show_health: false,
+            frames: FramePacer::default(),
+            cex: None,
+            watch_listings: false,
+            listed_on: None,
+            listings_checked: None,
+            watch_perps: false,
+            perp: None,
+            perp_error: None,
+            perps_checked: None,
+            watch_social: false,
+            social: Vec::new(),
+            social_checked: None,
+            social_alert: None,
+            birdeye: None,
+            fee: None,
+            fee_checked: None,
+            ticker: Vec::new(),
+            ticker_checked: None,
+            currency: Currency::usd(),
+            fx_checked: None,
+            profile: None,
+            profile_checked: None,
+            metadata: MetadataCache::load(),
+            security: None,
+            streaming: false,
+            stream_live: false,
+
+            portfolio: None,
+            wallet: None,
+            wallet_name: None,
+            wallet_min_usd: 0.0,
+            wallet_tokens: Vec::new(),
+            wallet_checked: None,
+            wallet_balance: None,
+            balance_change: None,
+            balance_checked: None,
+            watchlist_alerts: WatchlistAlerts::new(Vec::new()),
+            unlock_alerts_fired: HashSet::new(),
+            outbox: Outbox::default(),
+            hooks: Hooks::default(),
+            bus_queue: Vec::new(),
+            move_alerts: Vec::new(),
+            moves_firing: HashSet::new(),
+            conditions: Vec::new(),
+            conditions_firing: HashSet::new(),
+            rule_names: Vec::new(),
+            saved_rules: Vec::new(),
+            saved_rules_firing: HashSet::new(),
+            event_log: None,
+
             go_idle: false,
+            tui_only_alerts: Vec::new(),
+            instance: None,
+            observer: false,
+            quit_armed: None,
         };
+        app.modal_fields = app.current_fields();
+        app.watchlist_alerts = WatchlistAlerts::new(app.config.watchlist_alerts.clone());
+        app.low_power_auto = app.config.low_power.auto_on_battery;
 
         let now = Local::now().format("%H:%M:%S").to_string();
         app.add_log(format!(
@@ -121,12 +774,20 @@ impl App {
             pair_address: pair_address.clone(),
             chain: chain.clone(),
             target_market_cap,
+            target_kind: TargetKind::MarketCap,
+            milestones: Vec::new(),
+            targets: Vec::new(),
             check_interval,
             alarm_file,
             alarm_duration,
+            providers: vec![Provider::DexScreener],
+            rpc_url: None,
+            config: Config::load(),
 
             token_name: String::from("Loading..."),
             token_symbol: String::from("???"),
+            token_address: None,
+            token_decimals: None,
             current_price: 0.0,
             market_cap: 0.0,
             fdv: 0.0,
@@ -136,34 +797,172 @@ impl App {
             liquidity_usd: 0.0,
             buys_24h: 0,
             sells_24h: 0,
+            info: None,
+            pair_created_at: None,
+            bonding: None,
 
             market_cap_history: Vec::new(),
-            log_messages: Vec::new(),
+            ohlc: None,
+            session: SessionStats::new(Local::now()),
+            show_session: false,
+            show_gauge_values: false,
+            display: Display::default(),
+            log: Vec::new(),
             last_fetch: None,
+            fetch_started: None,
+            fetch_jitter: Duration::ZERO,
+            align_fetches: false,
+            targets_hit: 0,
             target_hit: false,
+            rearm_percent: None,
+            progress_alerts: Vec::new(),
+            progress_hit: 0,
+            fdv_target: None,
+            fdv_target_hit: false,
+            trailing_stop: None,
+            ath_alert: false,
+            ath_previous: None,
+            ath_loaded: false,
+            ath: None,
+            highs: HighStore::open(),
+            ath_saved_at: None,
+            ath_unsaved: false,
+            stale_after: None,
+            stale_alarm: false,
+            desktop_notify: false,
+            status_file: None,
+            fresh_at: Instant::now(),
+            stale_fired: false,
+            trailing_fired_at: None,
+            velocity: None,
+            whale_watch: None,
             alarm_active: false,
+            alarm_severity: Severity::Warning,
+            alarm_sound_file: None,
+            last_alert: None,
+            cooldown_override: None,
+            rule_alerted: HashMap::new(),
+            snoozed_until: None,
+            silent_alerts: false,
             running: true,
             fetch_count: 0,
             error_count: 0,
+            consecutive_failures: 0,
+            offline: false,
+            schedule_paused: false,
+            manual_paused: false,
+            paused_since: None,
+            catch_up_since: None,
+            low_power: false,
+            low_power_auto: false,
 
             modal_open: false,
-            modal_fields: [
-                pair_address,
-                chain.clone(),
-                format!("{}", target_market_cap as u64),
-                format!("{}", check_interval),
-            ],
-            modal_active_field: 0,
+            modal_fields: ModalFields::default(),
+            modal_active_field: ModalField::Pair,
             configured: true,
 
+            switcher_open: false,
+            macro_recording: None,
+            macro_unbound: None,
+            macro_queue: VecDeque::new(),
+            switcher_query: String::new(),
+            switcher_selected: 0,
+            switcher_mode: SwitcherMode::Switch,
+            onboarding: None,
+            onboarding_offered: false,
+
+            compare: None,
+            screencast: None,
+
+            pools: Vec::new(),
+            pool_address: None,
+            pool_selector_open: false,
+            pool_selected: 0,
+            quote_gap: None,
+            all_pools: false,
+            pool_totals: None,
+            gap_alert: None,
+            gap_alert_firing: false,
+            rug_alert: None,
+
+            onchain_mcap: false,
+            onchain_supply: None,
+            supply_checked: None,
+            aggregate: false,
+            aggregate_sources: Vec::new(),
+            outliers: Vec::new(),
+            active_source: None,
+            provider_health: ProviderHealth::default(),
+            health_checked: None,
+            pinned_provider: None,
+            show_health: false,
+            frames: FramePacer::default(),
+            cex: None,
+            watch_listings: false,
+            listed_on: None,
+            listings_checked: None,
+            watch_perps: false,
+            perp: None,
+            perp_error: None,
+            perps_checked: None,
+            watch_social: false,
+            social: Vec::new(),
+            social_checked: None,
+            social_alert: None,
+            birdeye: None,
+            fee: None,
+            fee_checked: None,
+            ticker: Vec::new(),
+            ticker_checked: None,
+            currency: Currency::usd(),
+            fx_checked: None,
+            profile: None,
+            profile_checked: None,
+            metadata: MetadataCache::load(),
+            security: None,
+            streaming: false,
+            stream_live: false,
+
+            portfolio: None,
+            wallet: None,
+            wallet_name: None,
+            wallet_min_usd: 0.0,
+            wallet_tokens: Vec::new(),
+            wallet_checked: None,
+            wallet_balance: None,
+            balance_change: None,
+            balance_checked: None,
+            watchlist_alerts: WatchlistAlerts::new(Vec::new()),
+            unlock_alerts_fired: HashSet::new(),
+            outbox: Outbox::default(),
+            hooks: Hooks::default(),
+            bus_queue: Vec::new(),
+            move_alerts: Vec::new(),
+            moves_firing: HashSet::new(),
+            conditions: Vec::new(),
+            conditions_firing: HashSet::new(),
+            rule_names: Vec::new(),
+            saved_rules: Vec::new(),
+            saved_rules_firing: HashSet::new(),
+            event_log: None,
+
             go_idle: false,
+            tui_only_alerts: Vec::new(),
+            instance: None,
+            observer: false,
+            quit_armed: None,
         };
+        app.modal_fields = app.current_fields();
+        app.watchlist_alerts = WatchlistAlerts::new(app.config.watchlist_alerts.clone());
+        app.low_power_auto = app.config.low_power.auto_on_battery;
+        app.targets = app.config.target_ladder(&app.pair_address, target_market_cap, &[]);
 
         let now = Local::now().format("%H:%M:%S").to_string();
         app.add_log(format!(
             "[{}] 🚀 MoonCap started | Chain: {} | Target: ${:.0}",
             now, chain, target_market_cap
         ));
+        app.log_milestones();
         app.add_log(format!(
             "[{}] 📡 Monitoring pair: {}",
             now, app.pair_address
@@ -172,187 +971,3246 @@ impl App {
             "[{}] ⏱  Check interval: {}s",
             now, check_interval
         ));
+        app.apply_chain_defaults();
 
         app
     }
 
-    /// Apply the modal field values to the app config
-    pub fn apply_modal_config(&mut self) {
-        self.pair_address = self.modal_fields[0].trim().to_string();
-        self.chain = if self.modal_fields[1].trim().is_empty() {
-            String::from("solana")
-        } else {
-            self.modal_fields[1].trim().to_string()
+    /// Apply the provider/RPC defaults configured for the current chain
+    pub fn apply_chain_defaults(&mut self) {
+        let defaults = self.config.chain_defaults(&self.chain).cloned();
+        let Some(defaults) = defaults else {
+            self.providers = vec![Provider::DexScreener];
+            self.rpc_url = None;
+            self.aggregate = false;
+            return;
         };
-        self.target_market_cap = self.modal_fields[2]
-            .trim()
-            .parse::<f64>()
-            .unwrap_or(100000.0);
-        self.check_interval = self.modal_fields[3]
-            .trim()
-            .parse::<u64>()
-            .unwrap_or(180);
-
-        self.configured = true;
-        self.modal_open = false;
 
-        // Reset live data for the new pair
-        self.token_name = String::from("Loading...");
-        self.token_symbol = String::from("???");
-        self.current_price = 0.0;
-        self.market_cap = 0.0;
-        self.fdv = 0.0;
-        self.volume_24h = 0.0;
-        self.price_change_1h = 0.0;
-        self.price_change_24h = 0.0;
-        self.liquidity_usd = 0.0;
-        self.buys_24h = 0;
-        self.sells_24h = 0;
-        self.market_cap_history.clear();
-        self.target_hit = false;
-        self.alarm_active = false;
-        self.fetch_count = 0;
-        self.error_count = 0;
+        let (providers, unknown) = api::parse_providers(&defaults.providers);
+        self.set_providers(providers, &unknown);
+        self.rpc_url = defaults.rpc_url;
+        self.aggregate = defaults.aggregate;
 
         let now = Local::now().format("%H:%M:%S").to_string();
-        self.log_messages.clear();
-        self.add_log(format!(
-            "[{}] 🚀 Configured | Chain: {} | Target: ${:.0}",
-            now, self.chain, self.target_market_cap
-        ));
-        self.add_log(format!(
-            "[{}] 📡 Monitoring pair: {}",
-            now, self.pair_address
-        ));
         self.add_log(format!(
-            "[{}] ⏱  Check interval: {}s",
-            now, self.check_interval
+            "[{}] ⚙  {} defaults: providers {}{}",
+            now,
+            self.chain,
+            self.provider_names(),
+            if self.rpc_url.is_some() { " | custom RPC" } else { "" }
         ));
     }
 
-    /// Open the modal with current config values pre-filled
-    pub fn open_modal(&mut self) {
-        self.modal_fields = [
-            self.pair_address.clone(),
-            self.chain.clone(),
-            format!("{}", self.target_market_cap as u64),
-            format!("{}", self.check_interval),
-        ];
-        self.modal_active_field = 0;
-        self.modal_open = true;
-    }
-
-    /// Navigate to next modal field
-    pub fn modal_next_field(&mut self) {
-        self.modal_active_field = (self.modal_active_field + 1) % 4;
+    /// Record which sources fed the median, logging when the outlier set changes
+    pub fn set_aggregate_sources(
+        &mut self,
+        sources: Vec<Provider>,
+        outliers: Vec<(Provider, f64)>,
+    ) {
+        let names: Vec<Provider> = outliers.iter().map(|(p, _)| *p).collect();
+        if names != self.outliers {
+            let now = Local::now().format("%H:%M:%S").to_string();
+            if outliers.is_empty() {
+                self.add_log(format!("[{}] ⚖  Sources agree again", now));
+            } else {
+                let detail: Vec<String> = outliers
+                    .iter()
+                    .map(|(p, d)| format!("{} {:+.1}%", p.name(), d))
+                    .collect();
+                self.add_log(format!(
+                    "[{}] ⚖  Outlier vs median price: {}",
+                    now,
+                    detail.join(", ")
+                ));
+            }
+        }
+        self.outliers = names;
+        self.aggregate_sources = sources;
     }
 
-    /// Navigate to previous modal field
-    pub fn modal_prev_field(&mut self) {
-        self.modal_active_field = if self.modal_active_field == 0 {
-            3
+    /// Replace the provider list, logging any names that weren't recognised
+    pub fn set_providers(&mut self, providers: Vec<Provider>, unknown: &[String]) {
+        self.providers = if providers.is_empty() {
+            vec![Provider::DexScreener]
         } else {
-            self.modal_active_field - 1
+            providers
         };
+        if !unknown.is_empty() {
+            let now = Local::now().format("%H:%M:%S").to_string();
+            self.add_log(format!(
+                "[{}] ⚠ Unknown provider(s) ignored: {}",
+                now,
+                unknown.join(", ")
+            ));
+        }
     }
 
-    /// Type a character into the active modal field
-    pub fn modal_type_char(&mut self, c: char) {
-        self.modal_fields[self.modal_active_field].push(c);
+    /// Birdeye API key, if the holder panel applies to the monitored token
+    pub fn birdeye_key(&self) -> Option<String> {
+        if !birdeye::supports_chain(&self.chain) {
+            return None;
+        }
+        birdeye::api_key(self.config.birdeye_api_key.as_deref())
     }
 
-    /// Delete last character from the active modal field
-    pub fn modal_backspace(&mut self) {
-        self.modal_fields[self.modal_active_field].pop();
+    /// Whether the on-chain supply should be (re)read before applying a fetch
+    pub fn needs_supply_refresh(&self) -> bool {
+        if !self.onchain_mcap || self.rpc_url.is_none() {
+            return false;
+        }
+        match self.supply_checked {
+            Some(at) => at.elapsed() >= SUPPLY_REFRESH,
+            None => true,
+        }
     }
 
-    pub fn update_from_pair_data(&mut self, data: &PairData) {
-        if let Some(ref base) = data.base_token {
-            if let Some(ref name) = base.name {
-                self.token_name = name.clone();
+    pub fn set_onchain_supply(&mut self, result: Result<(f64, u32), String>) {
+        self.supply_checked = Some(Instant::now());
+        let now = Local::now().format("%H:%M:%S").to_string();
+        if let Ok((_, decimals)) = result {
+            if self.token_decimals != Some(decimals) {
+                self.token_decimals = Some(decimals);
+                self.remember_metadata();
             }
-            if let Some(ref symbol) = base.symbol {
-                self.token_symbol = symbol.clone();
+        }
+        match result {
+            Ok((supply, _)) if supply > 0.0 => {
+                if self.onchain_supply.is_none() {
+                    self.add_log(format!(
+                        "[{}] ⛓  On-chain supply {:.0} — market cap computed locally",
+                        now, supply
+                    ));
+                }
+                self.onchain_supply = Some(supply);
             }
+            Ok(_) => self.add_log(format!("[{}] ⚠ On-chain supply reported as zero", now)),
+            Err(e) => self.add_log(format!(
+                "[{}] ⚠ On-chain supply unavailable: {}",
+                now,
+                api::truncate(&e, 80)
+            )),
         }
+    }
 
-        if let Some(ref price_str) = data.price_usd {
-            self.current_price = price_str.parse().unwrap_or(0.0);
+    /// RPC for read-only queries (fee indicator, wallet scans): the configured
+    /// one, else a public endpoint
+    pub fn read_rpc(&self) -> Option<String> {
+        self.rpc_url
+            .clone()
+            .or_else(|| rpc::public_rpc(&self.chain).map(str::to_string))
+    }
+
+    pub fn needs_fee_refresh(&self) -> bool {
+        match self.fee_checked {
+            Some(at) => at.elapsed() >= FEE_REFRESH,
+            None => true,
         }
+    }
 
-        self.market_cap = data.market_cap.unwrap_or(data.fdv.unwrap_or(0.0));
-        self.fdv = data.fdv.unwrap_or(0.0);
+    pub fn needs_ticker_refresh(&self) -> bool {
+        if self.config.ticker.as_ref().is_some_and(|t| t.is_empty()) {
+            return false;
+        }
+        match self.ticker_checked {
+            Some(at) => at.elapsed() >= TICKER_REFRESH,
+            None => true,
+        }
+    }
 
-        if let Some(ref vol) = data.volume {
-            self.volume_24h = vol.h24.unwrap_or(0.0);
+    pub fn needs_fx_refresh(&self) -> bool {
+        if self.currency.is_usd() {
+            return false;
         }
+        match self.fx_checked {
+            Some(at) => at.elapsed() >= FX_REFRESH,
+            None => true,
+        }
+    }
 
-        if let Some(ref pc) = data.price_change {
-            self.price_change_1h = pc.h1.unwrap_or(0.0);
-            self.price_change_24h = pc.h24.unwrap_or(0.0);
+    /// Store the USD exchange rate for --currency. Until the first one
+    /// arrives, and whenever it can't be fetched, the last known rate (or
+    /// USD) is kept.
+    pub fn set_fx_rate(&mut self, result: Result<f64, String>) {
+        self.fx_checked = Some(Instant::now());
+        let now = Local::now().format("%H:%M:%S").to_string();
+        match result {
+            Ok(rate) => {
+                if self.currency.rate.is_none() {
+                    self.add_log(format!(
+                        "[{}] 💱 Showing values in {} (1 USD = {:.4} {})",
+                        now, self.currency.code, rate, self.currency.code
+                    ));
+                }
+                self.currency.rate = Some(rate);
+            }
+            Err(e) => self.add_log(format!(
+                "[{}] ⚠ {} exchange rate unavailable: {}",
+                now,
+                self.currency.code,
+                api::truncate(&e, 80)
+            )),
         }
+    }
 
-        if let Some(ref liq) = data.liquidity {
-            self.liquidity_usd = liq.usd.unwrap_or(0.0);
+    pub fn needs_listings_refresh(&self) -> bool {
+        if !self.watch_listings {
+            return false;
         }
+        match self.listings_checked {
+            Some(at) => at.elapsed() >= LISTINGS_REFRESH,
+            None => self.token_address.is_some(),
+        }
+    }
 
-        if let Some(ref txns) = data.txns {
-            if let Some(ref h24) = txns.h24 {
-                self.buys_24h = h24.buys.unwrap_or(0);
-                self.sells_24h = h24.sells.unwrap_or(0);
+    /// Compare the latest exchange listings with the known ones. The first
+    /// check sets the baseline; later ones alert on each new exchange.
+    pub fn set_listings(&mut self, result: Result<BTreeSet<String>, String>) {
+        self.listings_checked = Some(Instant::now());
+        let now = Local::now().format("%H:%M:%S").to_string();
+        let exchanges = match result {
+            Ok(exchanges) => exchanges,
+            Err(e) => {
+                self.add_log(format!(
+                    "[{}] ⚠ Listing check failed: {}",
+                    now,
+                    api::truncate(&e, 80)
+                ));
+                return;
+            }
+        };
+
+        let Some(known) = self.listed_on.take() else {
+            if exchanges.is_empty() {
+                self.add_log(format!(
+                    "[{}] 🏦 No major exchange listings yet — watching for new ones",
+                    now
+                ));
+            } else {
+                let names: Vec<&str> = exchanges.iter().map(String::as_str).collect();
+                self.add_log(format!("[{}] 🏦 Listed on {}", now, names.join(", ")));
             }
+            self.listed_on = Some(exchanges);
+            return;
+        };
+
+        // Exchanges missing from one response are kept, so a stale ticker
+        // dropping out and back in doesn't alert twice
+        for exchange in exchanges.difference(&known) {
+            let message = format!("{} listed on {}", self.token_symbol, exchange);
+            self.raise_alert(
+                Severity::Warning,
+                "listing",
+                format!("[{}] 🚨 🏦 {}", now, message),
+                message,
+            );
         }
+        self.listed_on = Some(known.union(&exchanges).cloned().collect());
+    }
 
-        // Track history for sparkline
-        let mcap_u64 = self.market_cap as u64;
-        self.market_cap_history.push(mcap_u64);
-        if self.market_cap_history.len() > MAX_HISTORY {
-            self.market_cap_history.remove(0);
+    pub fn needs_perps_refresh(&self) -> bool {
+        if !self.watch_perps || self.fetch_count == 0 {
+            return false;
         }
+        match self.perps_checked {
+            Some(at) => at.elapsed() >= PERPS_REFRESH,
+            None => true,
+        }
+    }
 
-        self.fetch_count += 1;
+    /// Store the latest perp market data, keeping the last good numbers on failure
+    pub fn set_perp(&mut self, result: Result<Option<PerpMarket>, String>) {
+        let first = self.perps_checked.is_none();
+        self.perps_checked = Some(Instant::now());
         let now = Local::now().format("%H:%M:%S").to_string();
-        self.last_fetch = Some(now.clone());
+        match result {
+            Ok(Some(market)) => {
+                if first {
+                    self.add_log(format!(
+                        "[{}] 📊 Perp market {}: funding {:+.4}%",
+                        now, market.symbol, market.funding_rate
+                    ));
+                }
+                self.perp = Some(market);
+                self.perp_error = None;
+            }
+            Ok(None) => {
+                if first {
+                    self.add_log(format!(
+                        "[{}] 📊 No Binance perp market for {}",
+                        now, self.token_symbol
+                    ));
+                }
+                self.perp = None;
+                self.perp_error = None;
+            }
+            Err(e) => {
+                if self.perp_error.is_none() {
+                    self.add_log(format!(
+                        "[{}] ⚠ Perp data failed: {}",
+                        now,
+                        api::truncate(&e, 80)
+                    ));
+                }
+                self.perp_error = Some(e);
+            }
+        }
+    }
 
-        let change_str = if self.price_change_1h >= 0.0 {
-            format!("+{:.2}%", self.price_change_1h)
-        } else {
-            format!("{:.2}%", self.price_change_1h)
-        };
+    /// A warning to go with a target hit when perp funding is extreme, since
+    /// crowded longs tend to unwind
+    fn funding_note(&self) -> Option<String> {
+        let market = self.perp.as_ref()?;
+        (market.funding_rate.abs() >= perps::FUNDING_EXTREME).then(|| {
+            let side = if market.funding_rate > 0.0 { "longs" } else { "shorts" };
+            format!(
+                "funding {:+.4}% on {}: crowded {}",
+                market.funding_rate, market.symbol, side
+            )
+        })
+    }
 
-        self.add_log(format!(
-            "[{}] MCap: ${:.0} | Price: ${:.8} | 1h: {}",
-            now, self.market_cap, self.current_price, change_str
-        ));
+    pub fn needs_social_refresh(&self) -> bool {
+        if !self.watch_social {
+            return false;
+        }
+        match self.social_checked {
+            Some(at) => at.elapsed() >= SOCIAL_REFRESH,
+            None => self.info.is_some() || self.profile.is_some(),
+        }
+    }
 
-        // Check target
-        if self.market_cap >= self.target_market_cap && !self.target_hit {
-            self.target_hit = true;
-            self.alarm_active = true;
+    /// Telegram and Twitter accounts from the pair info and DexScreener profile
+    /// links. Twitter needs a bearer token, so it's left out without one.
+    pub fn social_accounts(&self) -> Vec<(Network, String)> {
+        let info = self.info.iter().flat_map(|i| i.socials.iter());
+        let profile = self.profile.iter().flat_map(|p| p.links.iter());
+        let twitter = social::twitter_enabled();
+        social::accounts(info.chain(profile))
+            .into_iter()
+            .filter(|(network, _)| twitter || *network != Network::Twitter)
+            .collect()
+    }
+
+    /// Record the latest audience counts, alerting when one jumps by at
+    /// least --social-alert percent since the previous check
+    pub fn set_social(&mut self, results: Vec<(Network, String, Result<u64, String>)>) {
+        let first = self.social_checked.is_none();
+        self.social_checked = Some(Instant::now());
+        let at = Local::now();
+        let now = at.format("%H:%M:%S").to_string();
+        if first && results.is_empty() {
             self.add_log(format!(
-                "[{}] 🔥 TARGET HIT! Market cap reached ${:.0} 🔥",
-                now, self.market_cap
+                "[{}] 👥 No Telegram or Twitter links for {} — social panel stays empty",
+                now, self.token_symbol
             ));
+            return;
+        }
+
+        for (network, handle, result) in results {
+            let index = match self
+                .social
+                .iter()
+                .position(|a| a.network == network && a.handle == handle)
+            {
+                Some(index) => index,
+                None => {
+                    self.social.push(Audience::new(network, handle));
+                    self.social.len() - 1
+                }
+            };
+            let audience = &mut self.social[index];
+            let count = match result {
+                Ok(count) => count,
+                Err(e) => {
+                    if audience.error.is_none() {
+                        let message = format!(
+                            "[{}] ⚠ {} @{} check failed: {}",
+                            now,
+                            network.name(),
+                            audience.handle,
+                            api::truncate(&e, 80)
+                        );
+                        audience.error = Some(e);
+                        self.add_log(message);
+                    }
+                    continue;
+                }
+            };
+            let previous = audience.samples.back().copied();
+            let growth = audience.record(at, count);
+            let (Some(threshold), Some(growth), Some((then, before))) =
+                (self.social_alert, growth, previous)
+            else {
+                continue;
+            };
+            if growth < threshold {
+                continue;
+            }
+            let message = format!(
+                "{} {} {} +{:.1}% in {}m ({} → {})",
+                self.token_symbol,
+                network.name(),
+                network.unit(),
+                growth,
+                (at - then).num_minutes().max(1),
+                before,
+                count
+            );
+            let rule = format!("social:{}", self.social[index].handle);
+            if self.in_cooldown(&rule, &message) {
+                continue;
+            }
+            self.raise_alert(
+                Severity::Warning,
+                &rule,
+                format!("[{}] 🚨 👥 {}", now, message),
+                message,
+            );
         }
     }
 
-    pub fn add_log(&mut self, msg: String) {
-        self.log_messages.push(msg);
-        if self.log_messages.len() > MAX_LOG {
-            self.log_messages.remove(0);
+    pub fn needs_profile_refresh(&self) -> bool {
+        match self.profile_checked {
+            Some(at) => at.elapsed() >= PROFILE_REFRESH,
+            None => self.token_address.is_some(),
         }
     }
 
-    pub fn add_error(&mut self, err: String) {
-        self.error_count += 1;
+    /// Store the DexScreener profile, logging when a boost starts or ends
+    pub fn set_profile(&mut self, result: Result<TokenProfile, String>) {
+        self.profile_checked = Some(Instant::now());
+        let was_boosted = self.profile.as_ref().and_then(|p| p.boost).is_some();
+        let profile = match result {
+            Ok(profile) => profile,
+            Err(e) => {
+                let mut profile = self.profile.take().unwrap_or_default();
+                profile.error = Some(e);
+                self.profile = Some(profile);
+                return;
+            }
+        };
+
         let now = Local::now().format("%H:%M:%S").to_string();
-        self.add_log(format!("[{}] ❌ Error: {}", now, err));
+        match profile.boost {
+            Some(amount) if !was_boosted => self.add_log(format!(
+                "[{}] 📣 {} has an active DexScreener boost ({:.0})",
+                now, self.token_symbol, amount
+            )),
+            None if was_boosted => self.add_log(format!(
+                "[{}] 📣 {} DexScreener boost ended",
+                now, self.token_symbol
+            )),
+            _ => {}
+        }
+        self.profile = Some(profile);
+        self.remember_metadata();
     }
 
-    pub fn progress(&self) -> f64 {
-        if self.target_market_cap <= 0.0 {
-            return 0.0;
+    pub fn needs_security_check(&self) -> bool {
+        self.security.is_none()
+            && self.token_address.is_some()
+            && safety::supports_chain(&self.chain)
+    }
+
+    /// Store the safety check, alerting when the token is flagged as dangerous
+    pub fn set_security(&mut self, result: Result<SecurityReport, String>) {
+        let now = Local::now().format("%H:%M:%S").to_string();
+        let report = match result {
+            Ok(report) => report,
+            Err(e) => {
+                self.add_log(format!(
+                    "[{}] ⚠ Security check unavailable: {}",
+                    now,
+                    api::truncate(&e, 80)
+                ));
+                self.security = Some(SecurityReport {
+                    error: Some(e),
+                    ..SecurityReport::default()
+                });
+                return;
+            }
+        };
+
+        if report.is_dangerous() {
+            let summary = format!(
+                "{} flagged dangerous by {}: {}",
+                self.token_symbol,
+                report.source,
+                report.dangers().join(", ")
+            );
+            self.raise_alert(
+                Severity::Critical,
+                "security",
+                format!("[{}] 🚨 {}", now, summary),
+                summary,
+            );
+        } else {
+            let found = match report.flags.len() {
+                0 => "no issues found".to_string(),
+                1 => "1 warning".to_string(),
+                n => format!("{} warnings", n),
+            };
+            self.add_log(format!(
+                "[{}] 🛡 Security check ({}): {}",
+                now, report.source, found
+            ));
+        }
+        self.security = Some(report);
+    }
+
+    /// Note which provider answered, logging whenever it isn't the preferred
+    /// one or it changes (e.g. falling back to Jupiter while DexScreener is down)
+    pub fn set_active_source(&mut self, source: Provider) {
+        if self.active_source == Some(source) {
+            return;
+        }
+        let preferred = self.preferred_provider() == Some(source);
+        if self.active_source.is_some() || !preferred {
+            let now = Local::now().format("%H:%M:%S").to_string();
+            self.add_log(format!("[{}] 🔀 Data source: {}", now, source.name()));
+        }
+        self.active_source = Some(source);
+    }
+
+    /// The provider asked first: the pinned one, else the healthiest
+    pub fn preferred_provider(&self) -> Option<Provider> {
+        self.pinned_provider
+            .filter(|p| self.providers.contains(p))
+            .or(self.provider_health.leader())
+            .or(self.providers.first().copied())
+    }
+
+    /// The failover chain for the next fetch, healthiest provider first unless
+    /// one is pinned. Logs when the health ranking picks a new leader.
+    pub fn provider_order(&mut self) -> Vec<Provider> {
+        let leader = self.provider_health.leader();
+        let mut order = self.provider_health.rank(&self.providers);
+        let new_leader = self.provider_health.leader();
+        if leader.is_some() && new_leader != leader && self.pinned_provider.is_none() {
+            if let Some(summary) = new_leader.and_then(|p| self.provider_health.summary(p)) {
+                self.add_log(format!(
+                    "[{}] 🩺 Preferring {} ({}ms, {:.0}% errors)",
+                    Local::now().format("%H:%M:%S"),
+                    summary.provider.name(),
+                    summary.latency.as_millis(),
+                    summary.error_rate * 100.0
+                ));
+            }
         }
-        (self.market_cap / self.target_market_cap * 100.0).min(100.0)
+        if let Some(pinned) = self.pinned_provider.filter(|p| self.providers.contains(p)) {
+            order.retain(|p| *p != pinned);
+            order.insert(0, pinned);
+        }
+        order
+    }
+
+    /// Pin the next configured provider to the front of the failover chain,
+    /// going back to health-based ordering after the last one
+    pub fn cycle_provider_pin(&mut self) {
+        let now = Local::now().format("%H:%M:%S").to_string();
+        if self.providers.len() < 2 {
+            self.add_log(format!("[{}] 🩺 Only one provider configured", now));
+            return;
+        }
+        let next = match self.pinned_provider {
+            None => Some(0),
+            Some(pinned) => self
+                .providers
+                .iter()
+                .position(|p| *p == pinned)
+                .map(|i| i + 1)
+                .filter(|i| *i < self.providers.len()),
+        };
+        self.pinned_provider = next.map(|i| self.providers[i]);
+        let msg = match self.pinned_provider {
+            Some(provider) => format!("[{}] 📌 Provider pinned: {}", now, provider.name()),
+            None => format!("[{}] 🩺 Provider order: healthiest first", now),
+        };
+        self.add_log(msg);
+    }
+
+    /// Whether the providers behind the leader should be asked, to keep their
+    /// latency and error rate current. Aggregate mode asks all of them anyway.
+    pub fn needs_health_probe(&self) -> bool {
+        if self.providers.len() < 2 || self.aggregate || self.fetch_count == 0 {
+            return false;
+        }
+        match self.health_checked {
+            Some(at) => at.elapsed() >= HEALTH_PROBE,
+            None => true,
+        }
+    }
+
+    /// Providers to use for a chain other than the monitored one (portfolio members)
+    pub fn providers_for_chain(&self, chain: &str) -> Vec<Provider> {
+        if chain.eq_ignore_ascii_case(&self.chain) {
+            return self.providers.clone();
+        }
+        let (providers, _) = self
+            .config
+            .chain_defaults(chain)
+            .map(|d| api::parse_providers(&d.providers))
+            .unwrap_or_default();
+        if providers.is_empty() {
+            vec![Provider::DexScreener]
+        } else {
+            providers
+        }
+    }
+
+    /// Show the named portfolio in the portfolio panel. Returns false if it doesn't exist.
+    pub fn set_portfolio(&mut self, name: &str) -> bool {
+        let mut group = if self.wallet_group_name().as_deref() == Some(name) {
+            GroupView::from_addresses(name, &self.chain, &self.wallet_tokens)
+        } else {
+            let Some(group) = self.config.portfolio(name).as_ref().map(GroupView::from_config)
+            else {
+                return false;
+            };
+            group
+        };
+        self.label_members(&mut group);
+        let now = Local::now().format("%H:%M:%S").to_string();
+        self.add_log(format!(
+            "[{}] 💼 Portfolio: {} ({} pairs)",
+            now,
+            group.name,
+            group.members.len()
+        ));
+        self.portfolio = Some(group);
+        true
+    }
+
+    /// Cycle the portfolio panel through the configured portfolios, then off
+    pub fn cycle_portfolio(&mut self) {
+        let mut names = self.config.portfolio_names();
+        names.extend(self.wallet_group_name());
+        let next = match self.portfolio {
+            None => names.first().cloned(),
+            Some(ref current) => names
+                .iter()
+                .position(|n| *n == current.name)
+                .and_then(|i| names.get(i + 1).cloned()),
+        };
+        match next {
+            Some(name) => {
+                self.set_portfolio(&name);
+            }
+            None => self.portfolio = None,
+        }
+    }
+
+    /// Portfolio panel name of the --wallet group
+    pub fn wallet_group_name(&self) -> Option<String> {
+        if !wallet::supports_chain(&self.chain) {
+            return None;
+        }
+        self.wallet_label().map(|label| format!("Wallet {}", label))
+    }
+
+    /// The wallet's ENS/SNS name, else its shortened address
+    pub fn wallet_label(&self) -> Option<String> {
+        self.wallet_name
+            .clone()
+            .or_else(|| self.wallet.as_deref().map(wallet::short_address))
+    }
+
+    pub fn needs_wallet_refresh(&self) -> bool {
+        if self.wallet.is_none() || !wallet::supports_chain(&self.chain) {
+            return false;
+        }
+        match self.wallet_checked {
+            Some(at) => at.elapsed() >= WALLET_REFRESH,
+            None => true,
+        }
+    }
+
+    pub fn needs_balance_refresh(&self) -> bool {
+        if self.wallet.is_none() || self.token_address.is_none() {
+            return false;
+        }
+        match self.balance_checked {
+            Some(at) => at.elapsed() >= BALANCE_REFRESH,
+            None => true,
+        }
+    }
+
+    /// Store the wallet's balance of the monitored token, alerting when it
+    /// changes (an airdrop or a fill arriving, tokens moved out)
+    pub fn set_wallet_balance(&mut self, result: Result<f64, String>) {
+        self.balance_checked = Some(Instant::now());
+        let now = Local::now().format("%H:%M:%S").to_string();
+        let balance = match result {
+            Ok(balance) => balance,
+            Err(e) => {
+                self.add_log(format!(
+                    "[{}] ⚠ Wallet balance unavailable: {}",
+                    now,
+                    api::truncate(&e, 80)
+                ));
+                return;
+            }
+        };
+        let Some(previous) = self.wallet_balance.replace(balance) else {
+            self.add_log(format!(
+                "[{}] 👛 Holding {} {}",
+                now,
+                ui::format_amount(balance),
+                self.token_symbol
+            ));
+            return;
+        };
+        let change = balance - previous;
+        // uiAmount is a float; ignore rounding noise
+        if change.abs() <= previous.abs().max(1.0) * 1e-9 {
+            return;
+        }
+        self.balance_change = Some(change);
+
+        let value = if self.current_price > 0.0 {
+            format!(" (≈${:.2})", change.abs() * self.current_price)
+        } else {
+            String::new()
+        };
+        let summary = format!(
+            "{} balance {}{}{}, now {}",
+            self.token_symbol,
+            if change > 0.0 { "+" } else { "-" },
+            ui::format_amount(change.abs()),
+            value,
+            ui::format_amount(balance)
+        );
+        self.raise_alert(Severity::Info, "balance", format!("[{}] 🚨 {}", now, summary), summary);
+    }
+
+    /// Apply a wallet scan: log tokens bought or sold since the last scan and
+    /// refresh the wallet group if it's on screen (or on the first scan)
+    pub fn set_wallet_tokens(&mut self, result: Result<Vec<WalletToken>, String>) {
+        let first_scan = self.wallet_checked.is_none();
+        self.wallet_checked = Some(Instant::now());
+        let now = Local::now().format("%H:%M:%S").to_string();
+        let mut tokens = match result {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                self.add_log(format!(
+                    "[{}] ⚠ Wallet scan failed: {}",
+                    now,
+                    api::truncate(&e, 80)
+                ));
+                return;
+            }
+        };
+        let held = tokens.len();
+        tokens.truncate(wallet::MAX_TOKENS);
+
+        if first_scan {
+            let shown = if held > tokens.len() {
+                format!(" (largest {} of {})", tokens.len(), held)
+            } else {
+                String::new()
+            };
+            self.add_log(format!(
+                "[{}] 👛 Wallet: {} tokens worth ${:.0}+{}",
+                now, held, self.wallet_min_usd, shown
+            ));
+        } else {
+            let mut changes: Vec<String> = tokens
+                .iter()
+                .filter(|t| !self.wallet_tokens.contains(&t.mint))
+                .map(|t| format!("+{} (${:.0})", t.symbol(), t.value_usd))
+                .collect();
+            let member_symbol = |mint: &String| {
+                self.portfolio
+                    .as_ref()
+                    .and_then(|g| g.members.iter().find(|m| m.pair == *mint))
+                    .map(|m| m.symbol())
+                    .unwrap_or_else(|| wallet::short_address(mint))
+            };
+            changes.extend(
+                self.wallet_tokens
+                    .iter()
+                    .filter(|mint| !tokens.iter().any(|t| t.mint == **mint))
+                    .map(|mint| format!("-{}", member_symbol(mint))),
+            );
+            if !changes.is_empty() {
+                self.add_log(format!("[{}] 👛 Wallet changed: {}", now, changes.join(" ")));
+            }
+        }
+        self.wallet_tokens = tokens.iter().map(|t| t.mint.clone()).collect();
+
+        let Some(name) = self.wallet_group_name() else {
+            return;
+        };
+        let showing = match self.portfolio {
+            Some(ref group) => group.name == name,
+            None => first_scan,
+        };
+        if showing {
+            let mut group = GroupView::from_addresses(&name, &self.chain, &self.wallet_tokens);
+            for (member, token) in group.members.iter_mut().zip(tokens) {
+                member.data = Some(token.data);
+            }
+            self.portfolio = Some(group);
+        }
+    }
+
+    /// Run the watchlist-wide alert rules over the monitored pair and the
+    /// active portfolio. Any rule firing raises the alarm.
+    pub fn evaluate_watchlist_alerts(&mut self) {
+        if self.watchlist_alerts.is_empty() {
+            return;
+        }
+
+        let mut tokens = Vec::new();
+        let mut complete = true;
+        if self.fetch_count > 0 {
+            tokens.push(TrackedToken {
+                key: format!("{}:{}", self.chain, self.pair_address),
+                symbol: self.token_symbol.clone(),
+                market_cap: self.market_cap,
+                liquidity: self.liquidity_usd,
+            });
+        }
+        if let Some(ref group) = self.portfolio {
+            for member in &group.members {
+                // Skip the monitored pair if it is also a portfolio member
+                if member.pair == self.pair_address && member.chain == self.chain {
+                    continue;
+                }
+                match (&member.data, &member.error) {
+                    (Some(data), None) => tokens.push(TrackedToken {
+                        key: format!("{}:{}", member.chain, member.pair),
+                        symbol: member.symbol(),
+                        market_cap: data.market_cap_usd(),
+                        liquidity: data.liquidity_usd(),
+                    }),
+                    _ => complete = false,
+                }
+            }
+        }
+
+        let fired = self.watchlist_alerts.evaluate(&tokens, complete);
+        let now = Local::now().format("%H:%M:%S").to_string();
+        for (severity, msg) in fired {
+            self.raise_alert(severity, "watchlist", format!("[{}] 🚨 {}", now, msg), msg);
+        }
+    }
+
+    /// The next known unlock for the monitored token, matched by the configured
+    /// address, the token address or the pool address
+    pub fn next_unlock(
+        &self,
+        now: chrono::DateTime<Local>,
+    ) -> Option<(&Unlock, chrono::DateTime<Local>)> {
+        let addresses = [
+            self.pair_address.as_str(),
+            self.token_address.as_deref().unwrap_or(""),
+            self.pool_address.as_deref().unwrap_or(""),
+        ];
+        unlocks::next_unlock(&self.config.unlocks, &addresses, now)
+    }
+
+    /// Raise pre-unlock alerts once each time an unlock enters an alert window
+    pub fn check_unlocks(&mut self) {
+        let checked_at = Local::now();
+        let Some((unlock, at)) = self.next_unlock(checked_at) else {
+            return;
+        };
+        let hours_left = (at - checked_at).num_minutes() as f64 / 60.0;
+        let due: Vec<u64> = unlock
+            .alert_hours_before
+            .iter()
+            .copied()
+            .filter(|h| hours_left <= *h as f64)
+            .filter(|h| !self.unlock_alerts_fired.contains(&(unlock.date.clone(), *h)))
+            .collect();
+        let Some(&hours) = due.iter().min() else {
+            return;
+        };
+
+        let (date, detail) = (unlock.date.clone(), unlock.describe());
+        // Mark every window we've passed so only the tightest one alerts
+        for h in due {
+            self.unlock_alerts_fired.insert((date.clone(), h));
+        }
+        let now = Local::now().format("%H:%M:%S").to_string();
+        let msg = format!(
+            "{} unlock in {} {}",
+            self.token_symbol,
+            unlocks::format_countdown(at, checked_at),
+            detail
+        );
+        self.raise_alert(
+            Severity::Warning,
+            "unlock",
+            format!("[{}] 🔓 {} (≤{}h alert)", now, msg, hours),
+            msg,
+        );
+    }
+
+    /// Pin or unpin the monitored pair. Returns true if the pinned group changed
+    /// while it's on screen (so it needs a refetch).
+    pub fn toggle_pin(&mut self) -> bool {
+        if !self.configured || self.pair_address.is_empty() {
+            return false;
+        }
+        // Pinned targets are market caps, so a price target isn't carried over
+        let target = (self.target_kind == TargetKind::MarketCap).then_some(self.target_market_cap);
+        let pinned = self.config.toggle_pin(&self.pair_address, &self.chain, target);
+        if let Err(e) = self.config.save() {
+            self.add_error(e);
+        }
+
+        let now = Local::now().format("%H:%M:%S").to_string();
+        self.add_log(format!(
+            "[{}] 📌 {} {}",
+            now,
+            if pinned { "Pinned" } else { "Unpinned" },
+            self.token_symbol
+        ));
+
+        // Refresh the pinned group if it's the one being shown
+        let showing_pinned = self
+            .portfolio
+            .as_ref()
+            .is_some_and(|g| g.name == PINNED_PORTFOLIO);
+        if showing_pinned {
+            self.portfolio = self.config.portfolio(PINNED_PORTFOLIO).map(|p| {
+                let mut group = GroupView::from_config(&p);
+                self.label_members(&mut group);
+                group
+            });
+            return self.portfolio.is_some();
+        }
+        false
+    }
+
+    pub fn is_pinned(&self) -> bool {
+        self.config.is_pinned(&self.pair_address, &self.chain)
+    }
+
+    /// Comma-separated provider names for display
+    pub fn provider_names(&self) -> String {
+        self.providers
+            .iter()
+            .map(|p| p.name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Apply the modal field values to the app config
+    pub fn apply_modal_config(&mut self) {
+        let fields = &self.modal_fields;
+        self.pair_address = fields[ModalField::Pair].trim().to_string();
+        self.chain = if fields[ModalField::Chain].trim().is_empty() {
+            String::from("solana")
+        } else {
+            fields[ModalField::Chain].trim().to_string()
+        };
+        let targets: Targets = fields[ModalField::Target]
+            .parse()
+            .unwrap_or_else(|_| Targets::new(TargetKind::MarketCap, vec![100000.0]));
+        self.target_market_cap = targets.top().value;
+        self.target_kind = targets.kind;
+        self.milestones = targets.milestones().to_vec();
+        self.check_interval = fields[ModalField::Interval]
+            .trim()
+            .parse::<u64>()
+            .unwrap_or(180);
+        let moves = MoveRule::parse_list(&fields[ModalField::MoveAlerts]);
+        let alarm_duration = fields[ModalField::AlarmDuration].trim().parse::<u64>();
+        let alarm_file = Some(fields[ModalField::AlarmFile].trim().to_string())
+            .filter(|f| !f.is_empty());
+        self.move_alerts = moves.clone().unwrap_or_default();
+        self.moves_firing.clear();
+        self.conditions_firing.clear();
+        // A duration that isn't a number keeps the current one
+        let alarm_duration = alarm_duration.unwrap_or(self.alarm_duration);
+        let alarm_changed = alarm_duration != self.alarm_duration || alarm_file != self.alarm_file;
+        self.alarm_duration = alarm_duration;
+        self.alarm_file = alarm_file;
+
+        self.configured = true;
+        self.modal_open = false;
+
+        // A pause carries over, but the gap before it belonged to the old pair
+        self.paused_since = self.polling_paused().then(Local::now);
+        self.catch_up_since = None;
+
+        // Reset live data for the new pair
+        self.token_name = String::from("Loading...");
+        self.token_symbol = String::from("???");
+        self.token_address = None;
+        self.token_decimals = None;
+        self.current_price = 0.0;
+        self.market_cap = 0.0;
+        self.fdv = 0.0;
+        self.volume_24h = 0.0;
+        self.price_change_1h = 0.0;
+        self.price_change_24h = 0.0;
+        self.liquidity_usd = 0.0;
+        self.buys_24h = 0;
+        self.sells_24h = 0;
+        self.info = None;
+        self.pair_created_at = None;
+        self.bonding = None;
+        self.market_cap_history.clear();
+        if let Some(ref mut table) = self.ohlc {
+            *table = OhlcTable::new(table.period);
+        }
+        self.session = SessionStats::new(Local::now());
+        self.fresh_at = Instant::now();
+        self.stale_fired = false;
+        self.save_ath();
+        self.ath = None;
+        self.ath_previous = None;
+        self.ath_loaded = false;
+        self.trailing_fired_at = None;
+        if let Some(ref mut velocity) = self.velocity {
+            velocity.reset();
+        }
+        if let Some(ref mut whale_watch) = self.whale_watch {
+            whale_watch.reset();
+        }
+        self.pools.clear();
+        self.pool_address = None;
+        self.quote_gap = None;
+        self.pool_totals = None;
+        self.gap_alert_firing = false;
+        self.birdeye = None;
+        self.profile = None;
+        self.profile_checked = None;
+        self.security = None;
+        self.wallet_balance = None;
+        self.balance_change = None;
+        self.balance_checked = None;
+        self.fee = None;
+        self.fee_checked = None;
+        if let Some(ref mut cex) = self.cex {
+            cex.reset();
+        }
+        self.listed_on = None;
+        self.listings_checked = None;
+        self.perp = None;
+        self.perp_error = None;
+        self.perps_checked = None;
+        self.social.clear();
+        self.social_checked = None;
+        self.active_source = None;
+        self.aggregate_sources.clear();
+        self.outliers.clear();
+        self.onchain_supply = None;
+        self.supply_checked = None;
+        self.targets = self.target_ladder();
+        self.targets_hit = 0;
+        self.target_hit = false;
+        self.progress_hit = 0;
+        self.fdv_target_hit = false;
+        self.alarm_active = false;
+        self.last_alert = None;
+        self.fetch_count = 0;
+        self.error_count = 0;
+        self.consecutive_failures = 0;
+        self.onboarding = None;
+        self.onboarding_offered = false;
+
+        let now = Local::now().format("%H:%M:%S").to_string();
+        self.log.clear();
+        self.add_log(format!(
+            "[{}] 🚀 Configured | Chain: {} | Target: {}",
+            now,
+            self.chain,
+            self.target_kind.format(self.target_market_cap)
+        ));
+        self.add_log(format!(
+            "[{}] 📡 Monitoring pair: {}",
+            now, self.pair_address
+        ));
+        self.add_log(format!(
+            "[{}] ⏱  Check interval: {}s",
+            now, self.check_interval
+        ));
+        if alarm_changed {
+            self.add_log(format!(
+                "[{}] 🔔 Alarm: {} for {}s",
+                now,
+                self.alarm_file.as_deref().unwrap_or("terminal bell"),
+                self.alarm_duration
+            ));
+            if let Some(file) = self.alarm_file.clone() {
+                if !std::path::Path::new(&file).is_file() {
+                    self.add_log(format!(
+                        "[{}] ⚠ Alarm file {} not found — the terminal bell rings instead",
+                        now, file
+                    ));
+                }
+            }
+        }
+        self.log_milestones();
+        self.attach_saved_rules();
+        match moves {
+            Ok(rules) if !rules.is_empty() => self.log_move_alerts(),
+            Ok(_) => {}
+            Err(e) => self.add_log(format!("[{}] ⚠ Move alerts ignored: {}", now, e)),
+        }
+        self.record_event(Event::config(
+            &self.chain,
+            &self.pair_address,
+            self.target(),
+            self.check_interval,
+        ));
+        self.apply_chain_defaults();
+        self.load_cached_metadata();
+    }
+
+    /// Modal fields filled with the current config values
+    pub fn current_fields(&self) -> ModalFields {
+        let mut fields = ModalFields::default();
+        fields[ModalField::Pair] = self.pair_address.clone();
+        fields[ModalField::Chain] = self.chain.clone();
+        fields[ModalField::Target] = self.target_text();
+        fields[ModalField::Interval] = format!("{}", self.check_interval);
+        fields[ModalField::MoveAlerts] = self.move_alerts_text();
+        fields[ModalField::AlarmDuration] = format!("{}", self.alarm_duration);
+        fields[ModalField::AlarmFile] = self.alarm_file.clone().unwrap_or_default();
+        fields
+    }
+
+    /// Open the modal with current config values pre-filled
+    pub fn open_modal(&mut self) {
+        self.modal_fields = self.current_fields();
+        self.modal_active_field = ModalField::Pair;
+        self.modal_open = true;
+    }
+
+    /// Navigate to next modal field
+    pub fn modal_next_field(&mut self) {
+        self.modal_active_field = self.modal_active_field.next();
+    }
+
+    /// Navigate to previous modal field
+    pub fn modal_prev_field(&mut self) {
+        self.modal_active_field = self.modal_active_field.prev();
+    }
+
+    /// Type a character into the active modal field
+    pub fn modal_type_char(&mut self, c: char) {
+        self.modal_fields[self.modal_active_field].push(c);
+    }
+
+    /// Delete last character from the active modal field
+    pub fn modal_backspace(&mut self) {
+        self.modal_fields[self.modal_active_field].pop();
+    }
+
+    /// Open the recent pairs quick-switch popup
+    pub fn open_switcher(&mut self, mode: SwitcherMode) {
+        self.switcher_mode = mode;
+        self.switcher_open = true;
+        self.switcher_query.clear();
+        self.switcher_selected = 0;
+    }
+
+    pub fn close_switcher(&mut self) {
+        self.switcher_open = false;
+    }
+
+    /// Recent pairs matching the switcher query (symbol, name, chain or address)
+    pub fn switcher_matches(&self) -> Vec<&RecentPair> {
+        let query = self.switcher_query.to_lowercase();
+        self.config
+            .recent_pairs
+            .iter()
+            .filter(|r| {
+                query.is_empty()
+                    || r.symbol.to_lowercase().contains(&query)
+                    || r.name.to_lowercase().contains(&query)
+                    || r.chain.to_lowercase().contains(&query)
+                    || r.pair.to_lowercase().contains(&query)
+            })
+            .collect()
+    }
+
+    /// Move the switcher selection up (-1) or down (+1), wrapping around
+    pub fn switcher_move(&mut self, delta: i32) {
+        let len = self.switcher_matches().len();
+        if len == 0 {
+            self.switcher_selected = 0;
+            return;
+        }
+        let current = self.switcher_selected.min(len - 1) as i32;
+        self.switcher_selected = (current + delta).rem_euclid(len as i32) as usize;
+    }
+
+    pub fn switcher_type_char(&mut self, c: char) {
+        self.switcher_query.push(c);
+        self.switcher_selected = 0;
+    }
+
+    pub fn switcher_backspace(&mut self) {
+        self.switcher_query.pop();
+        self.switcher_selected = 0;
+    }
+
+    /// Act on the selected recent pair: switch monitoring to it, or compare
+    /// against it. Returns true if a fetch is needed.
+    pub fn switch_to_selected(&mut self) -> bool {
+        let Some(entry) = self
+            .switcher_matches()
+            .get(self.switcher_selected)
+            .map(|r| (*r).clone())
+        else {
+            return false;
+        };
+
+        if self.switcher_mode == SwitcherMode::Compare {
+            self.set_compare(entry.pair, entry.chain);
+            self.switcher_open = false;
+            return true;
+        }
+
+        self.monitor(entry.pair, entry.chain);
+        self.switcher_open = false;
+        true
+    }
+
+    /// Explain a failed first fetch, once per pair. `other_chains` are the
+    /// chains the address is listed on instead.
+    pub fn open_onboarding(&mut self, error: &str, other_chains: Vec<String>) {
+        if self.onboarding_offered || self.observer || self.modal_open {
+            return;
+        }
+        self.onboarding_offered = true;
+        self.onboarding = Some(Onboarding::new(error.to_string(), other_chains));
+    }
+
+    pub fn close_onboarding(&mut self) {
+        self.onboarding = None;
+    }
+
+    /// Leave the diagnostics for the config modal
+    pub fn onboarding_edit_config(&mut self, field: ModalField) {
+        self.onboarding = None;
+        self.open_modal();
+        self.modal_active_field = field;
+    }
+
+    /// Monitor the address on the chain it's listed on, or edit the chain if
+    /// there's no suggestion. Returns true if a fetch is needed.
+    pub fn onboarding_switch_chain(&mut self) -> bool {
+        let Some(chain) = self
+            .onboarding
+            .as_ref()
+            .and_then(|o| o.suggested_chain())
+            .map(String::from)
+        else {
+            self.onboarding_edit_config(ModalField::Chain);
+            return false;
+        };
+        self.monitor(self.pair_address.clone(), chain);
+        true
+    }
+
+    /// Monitor the selected search result. Returns true if a fetch is needed.
+    pub fn onboarding_select(&mut self) -> bool {
+        let Some(pair) = self
+            .onboarding
+            .as_ref()
+            .and_then(|o| o.matches().get(o.selected).cloned())
+        else {
+            return false;
+        };
+        let Some(address) = pair.pair_address.clone() else {
+            return false;
+        };
+        let chain = pair.chain_id.clone().unwrap_or_else(|| self.chain.clone());
+        self.monitor(address, chain);
+        true
+    }
+
+    /// The symbol search the diagnostics are waiting on, if any
+    pub fn pending_onboarding_search(&mut self) -> Option<String> {
+        let onboarding = self.onboarding.as_mut().filter(|o| o.search_pending)?;
+        Some(onboarding.query.trim().to_string())
+    }
+
+    /// Switch monitoring to another pair, keeping the target and settings
+    fn monitor(&mut self, pair: String, chain: String) {
+        self.modal_fields = self.current_fields();
+        self.modal_fields[ModalField::Pair] = pair;
+        self.modal_fields[ModalField::Chain] = chain;
+        self.apply_modal_config();
+    }
+
+    /// Show another pair side-by-side with the monitored one
+    pub fn set_compare(&mut self, pair: String, chain: String) {
+        let now = Local::now().format("%H:%M:%S").to_string();
+        self.add_log(format!("[{}] ⚖  Comparing with {} ({})", now, pair, chain));
+        self.compare = Some(CompareState::new(pair, chain));
+    }
+
+    pub fn clear_compare(&mut self) {
+        self.compare = None;
+    }
+
+    /// Store the pools found for the monitored token
+    pub fn set_pools(&mut self, pools: Vec<PairData>) {
+        if pools.len() > 1 {
+            let now = Local::now().format("%H:%M:%S").to_string();
+            let best = &pools[0];
+            self.add_log(format!(
+                "[{}] 🏊 {} pools found — best: {} {}/{} (liq {:.0}) — press 'o' to choose",
+                now,
+                pools.len(),
+                best.dex_id.as_deref().unwrap_or("?"),
+                best.base_symbol(),
+                best.quote_symbol(),
+                best.liquidity_usd()
+            ));
+        }
+        self.refresh_pools(pools);
+    }
+
+    /// Replace the pool list with fresh data (no log) and re-check the quote gap
+    pub fn refresh_pools(&mut self, pools: Vec<PairData>) {
+        self.pools = pools;
+        self.quote_gap = quotes::quote_gap(&self.pools, &self.chain);
+        self.pool_totals = quotes::pool_totals(&self.pools, &self.chain);
+
+        let Some(threshold) = self.gap_alert else {
+            return;
+        };
+        let Some(gap) = self.quote_gap.clone() else {
+            self.gap_alert_firing = false;
+            return;
+        };
+        if gap.percent < threshold {
+            self.gap_alert_firing = false;
+            return;
+        }
+        if !self.gap_alert_firing {
+            self.gap_alert_firing = true;
+            let msg = format!(
+                "{} quote gap {:.2}%: {} {} vs {} {} (threshold {:.2}%)",
+                self.token_symbol,
+                gap.percent,
+                gap.cheap.quote,
+                ui::format_price(gap.cheap.price, &self.config.price_format, &self.currency),
+                gap.rich.quote,
+                ui::format_price(gap.rich.price, &self.config.price_format, &self.currency),
+                threshold
+            );
+            if self.in_cooldown("gap", &msg) {
+                return;
+            }
+            let now = Local::now().format("%H:%M:%S").to_string();
+            self.raise_alert(Severity::Warning, "gap", format!("[{}] 🚨 {}", now, msg), msg);
+        }
+    }
+
+    /// Record the latest CEX price and alert when the DEX price diverges
+    pub fn set_cex_price(&mut self, result: Result<f64, String>) {
+        let Some(mut cex) = self.cex.take() else {
+            return;
+        };
+        match result {
+            Ok(price) => {
+                cex.price = Some(price);
+                cex.error = None;
+            }
+            Err(e) => cex.error = Some(e),
+        }
+
+        // Fires once, re-arming when the spread is back under the threshold
+        if let (Some(spread), Some(threshold)) = (cex.spread(self.current_price), cex.alert_percent) {
+            if spread.abs() < threshold {
+                cex.alert_firing = false;
+            } else if !cex.alert_firing {
+                cex.alert_firing = true;
+                let msg = format!(
+                    "{} DEX/CEX spread {:+.2}%: DEX {} vs {} {} (threshold {:.2}%)",
+                    self.token_symbol,
+                    spread,
+                    ui::format_price(self.current_price, &self.config.price_format, &self.currency),
+                    cex.exchange.name(),
+                    ui::format_price(cex.price.unwrap_or(0.0), &self.config.price_format, &self.currency),
+                    threshold
+                );
+                if !self.in_cooldown("cex", &msg) {
+                    let now = Local::now().format("%H:%M:%S").to_string();
+                    self.raise_alert(Severity::Warning, "cex", format!("[{}] 🚨 {}", now, msg), msg);
+                }
+            }
+        }
+        self.cex = Some(cex);
+    }
+
+    /// Open the pool selector with the monitored pool highlighted
+    pub fn open_pool_selector(&mut self) {
+        if self.pools.len() < 2 {
+            return;
+        }
+        self.pool_selected = self
+            .pools
+            .iter()
+            .position(|p| self.is_current_pool(p))
+            .unwrap_or(0);
+        self.pool_selector_open = true;
+    }
+
+    pub fn is_current_pool(&self, pool: &PairData) -> bool {
+        pool.pair_address.is_some() && pool.pair_address == self.pool_address
+    }
+
+    pub fn pool_move(&mut self, delta: i32) {
+        if self.pools.is_empty() {
+            return;
+        }
+        let len = self.pools.len() as i32;
+        self.pool_selected = (self.pool_selected as i32 + delta).rem_euclid(len) as usize;
+    }
+
+    /// Switch monitoring to the selected pool's pair address. Returns true if a
+    /// fetch is needed.
+    pub fn select_pool(&mut self) -> bool {
+        self.pool_selector_open = false;
+        let Some(pool) = self.pools.get(self.pool_selected).cloned() else {
+            return false;
+        };
+        let Some(pair) = pool.pair_address.clone() else {
+            return false;
+        };
+        let chain = pool.chain_id.clone().unwrap_or_else(|| self.chain.clone());
+        let pools = std::mem::take(&mut self.pools);
+
+        self.remember_chosen_pool(&pair, &chain);
+        self.monitor(pair, chain);
+        self.pools = pools;
+        true
+    }
+
+    /// Seed the sparkline and summary table with historical candles. Prices
+    /// are converted to market cap using the supply implied by the latest
+    /// fetch (mcap / price).
+    pub fn backfill_history(&mut self, candles: &[Candle], source: &str) {
+        if self.current_price <= 0.0 || candles.is_empty() {
+            return;
+        }
+        let supply = self.market_cap / self.current_price;
+        let live = std::mem::take(&mut self.market_cap_history);
+
+        let keep = MAX_HISTORY.saturating_sub(live.len());
+        let start = candles.len().saturating_sub(keep);
+        self.market_cap_history = candles[start..]
+            .iter()
+            .map(|c| (c.close * supply) as u64)
+            .chain(live)
+            .collect();
+
+        if let Some(ref mut table) = self.ohlc {
+            table.backfill(candles.iter().filter_map(|c| {
+                let at = Local.timestamp_opt(c.timestamp, 0).single()?;
+                Some((at, [c.open, c.high, c.low, c.close].map(|p| p * supply)))
+            }));
+        }
+
+        let since = Local
+            .timestamp_opt(candles[0].timestamp, 0)
+            .single()
+            .map(|t| format!(" since {}", t.format("%m-%d %H:%M")))
+            .unwrap_or_default();
+        let now = Local::now().format("%H:%M:%S").to_string();
+        self.add_log(format!(
+            "[{}] 📈 Backfilled {} candles{} from {}",
+            now,
+            candles.len(),
+            since,
+            source
+        ));
+    }
+
+    /// Record the current pair at the front of the persistent MRU list
+    fn remember_current_pair(&mut self) {
+        self.config.remember_pair(RecentPair {
+            pair: self.pair_address.clone(),
+            chain: self.chain.clone(),
+            name: self.token_name.clone(),
+            symbol: self.token_symbol.clone(),
+            last_used: Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        });
+        if let Err(e) = self.config.save() {
+            self.add_error(e);
+        }
+    }
+
+    /// Hours cached token metadata is trusted; 0 when the cache is off
+    fn metadata_ttl(&self) -> u64 {
+        self.config
+            .metadata_ttl_hours
+            .unwrap_or(metadata::DEFAULT_TTL_HOURS)
+    }
+
+    /// Fill in the token from the metadata cache ahead of the first fetch.
+    /// Returns the pool picked for it with `o` in an earlier session, if any.
+    fn load_cached_metadata(&mut self) -> Option<String> {
+        let entry = self
+            .metadata
+            .get(&self.chain, &self.pair_address, self.metadata_ttl())?
+            .clone();
+        self.token_name = entry.name.clone();
+        self.token_symbol = entry.symbol.clone();
+        self.token_address = entry.token_address.clone();
+        self.token_decimals = entry.decimals;
+        if entry.description.is_some() || !entry.links.is_empty() {
+            self.profile = Some(TokenProfile {
+                description: entry.description.clone(),
+                links: entry.links.clone(),
+                ..Default::default()
+            });
+            self.profile_checked = Some(Instant::now());
+        }
+        self.add_log(format!(
+            "[{}] 🗃  {} ({}) from the metadata cache, resolved {} ago — u to refresh",
+            Local::now().format("%H:%M:%S"),
+            entry.name,
+            entry.symbol,
+            format_wait(Duration::from_secs(entry.age_secs()))
+        ));
+        entry.chosen_pair.filter(|p| *p != self.pair_address)
+    }
+
+    /// Fill in the token from the metadata cache on startup, moving on to the
+    /// pool picked for it with `o` in an earlier session
+    pub fn restore_metadata(&mut self) {
+        let Some(pool) = self.load_cached_metadata() else {
+            return;
+        };
+        let previous = self.pair_address.clone();
+        let chain = self.chain.clone();
+        self.monitor(pool, chain);
+        self.add_log(format!(
+            "[{}] 📌 Pool picked with o for {} last time — o to choose again",
+            Local::now().format("%H:%M:%S"),
+            previous
+        ));
+    }
+
+    /// Save what's been resolved about the token, unless the cache already
+    /// holds the same and it hasn't expired
+    fn remember_metadata(&mut self) {
+        let ttl = self.metadata_ttl();
+        if ttl == 0 || self.token_address.is_none() {
+            return;
+        }
+        let cached = self.metadata.find(&self.chain, &self.pair_address).cloned();
+        let fresh = self
+            .metadata
+            .get(&self.chain, &self.pair_address, ttl)
+            .is_some();
+        let profile = self.profile.as_ref();
+        let mut entry = TokenMetadata {
+            chain: self.chain.clone(),
+            pair: self.pair_address.clone(),
+            name: self.token_name.clone(),
+            symbol: self.token_symbol.clone(),
+            token_address: self.token_address.clone(),
+            decimals: self.token_decimals,
+            description: profile.and_then(|p| p.description.clone()),
+            links: profile.map(|p| p.links.clone()).unwrap_or_default(),
+            chosen_pair: cached.as_ref().and_then(|c| c.chosen_pair.clone()),
+            resolved_at: Local::now().timestamp(),
+        };
+        if let Some(cached) = cached.filter(|_| fresh) {
+            entry.resolved_at = cached.resolved_at;
+            if entry == cached {
+                return;
+            }
+        }
+        self.metadata.put(entry);
+        self.save_metadata();
+    }
+
+    /// Remember the pool picked with `o`, so the next start monitors it
+    fn remember_chosen_pool(&mut self, pool: &str, chain: &str) {
+        if self.metadata_ttl() == 0 {
+            return;
+        }
+        let Some(mut entry) = self.metadata.find(&self.chain, &self.pair_address).cloned() else {
+            return;
+        };
+        entry.chosen_pair = Some(pool.to_string());
+        self.metadata.put(entry);
+        // The picked pool is where the choice ends, not a step to another one
+        if let Some(mut picked) = self.metadata.find(chain, pool).cloned() {
+            picked.chosen_pair = None;
+            self.metadata.put(picked);
+        }
+        self.save_metadata();
+    }
+
+    /// Cache the tokens of portfolio members fetched for the first time
+    pub fn remember_members(&mut self, group: &GroupView) {
+        let ttl = self.metadata_ttl();
+        if ttl == 0 {
+            return;
+        }
+        let mut changed = false;
+        for member in &group.members {
+            let Some(ref data) = member.data else {
+                continue;
+            };
+            if self.metadata.get(&member.chain, &member.pair, ttl).is_some() {
+                continue;
+            }
+            let mut entry = TokenMetadata::from_pair(&member.chain, &member.pair, data);
+            entry.chosen_pair = self
+                .metadata
+                .find(&member.chain, &member.pair)
+                .and_then(|e| e.chosen_pair.clone());
+            self.metadata.put(entry);
+            changed = true;
+        }
+        if changed {
+            self.save_metadata();
+        }
+    }
+
+    /// Label portfolio members with their cached symbols until their first fetch
+    fn label_members(&self, group: &mut GroupView) {
+        let ttl = self.metadata_ttl();
+        for member in group.members.iter_mut() {
+            member.cached_symbol = self
+                .metadata
+                .get(&member.chain, &member.pair, ttl)
+                .map(|e| e.symbol.clone());
+        }
+    }
+
+    /// Forget the cached metadata of the pair and the shown portfolio, so it's
+    /// all resolved again on the next fetch
+    pub fn refresh_metadata(&mut self) {
+        let mut pairs = vec![(self.chain.clone(), self.pair_address.clone())];
+        if let Some(ref group) = self.portfolio {
+            pairs.extend(group.members.iter().map(|m| (m.chain.clone(), m.pair.clone())));
+        }
+        let removed = pairs
+            .iter()
+            .filter(|(chain, pair)| self.metadata.remove(chain, pair))
+            .count();
+        self.token_decimals = None;
+        self.profile_checked = None;
+        self.supply_checked = None;
+        if removed > 0 {
+            self.save_metadata();
+        }
+        self.add_log(format!(
+            "[{}] 🗃  Resolving token metadata again ({} cached entries dropped)",
+            Local::now().format("%H:%M:%S"),
+            removed
+        ));
+    }
+
+    fn save_metadata(&mut self) {
+        if let Err(e) = self.metadata.save() {
+            let now = Local::now().format("%H:%M:%S").to_string();
+            self.add_log(format!("[{}] ⚠ {}", now, e));
+        }
+    }
+
+    /// Track pump.fun curve progress, and move to DexScreener once the token
+    /// graduates (its curve completes or a DEX pair answers instead)
+    fn update_bonding_curve(&mut self, data: &PairData) {
+        let was_on_curve = self.bonding.is_some_and(|c| !c.complete);
+        self.bonding = data.bonding_curve;
+        // DexScreener lists some pairs still on the curve under the pumpfun dex
+        let graduated = match data.bonding_curve {
+            Some(curve) => curve.complete,
+            None => data.dex_id.as_deref() != Some("pumpfun"),
+        };
+        if !was_on_curve || !graduated {
+            return;
+        }
+
+        let now = Local::now().format("%H:%M:%S").to_string();
+        let msg = format!("{} graduated from the pump.fun bonding curve", self.token_symbol);
+        self.raise_alert(Severity::Info, "graduation", format!("[{}] 🎓 {}", now, msg), msg);
+        if self.providers.contains(&Provider::PumpFun) {
+            self.providers.retain(|p| *p != Provider::PumpFun);
+            if !self.providers.contains(&Provider::DexScreener) {
+                self.providers.insert(0, Provider::DexScreener);
+            }
+            self.add_log(format!(
+                "[{}] 🔀 Switching from pump.fun to {}",
+                now,
+                self.providers[0].name()
+            ));
+        }
+    }
+
+    pub fn update_from_pair_data(&mut self, data: &PairData) {
+        let previous_liquidity = self.liquidity_usd;
+        self.pool_address = data.pair_address.clone();
+        if let Some(ref base) = data.base_token {
+            if let Some(ref name) = base.name {
+                self.token_name = name.clone();
+            }
+            if let Some(ref symbol) = base.symbol {
+                self.token_symbol = symbol.clone();
+            }
+            self.token_address = base.address.clone();
+        }
+        self.remember_metadata();
+
+        if let Some(ref price_str) = data.price_usd {
+            self.current_price = price_str.parse().unwrap_or(0.0);
+        }
+
+        self.market_cap = match self.onchain_supply {
+            Some(supply) if self.current_price > 0.0 => supply * self.current_price,
+            _ => data.market_cap.unwrap_or(data.fdv.unwrap_or(0.0)),
+        };
+        self.fdv = data.fdv.unwrap_or(0.0);
+
+        if let Some(ref vol) = data.volume {
+            self.volume_24h = vol.h24.unwrap_or(0.0);
+        }
+
+        if let Some(ref pc) = data.price_change {
+            self.price_change_1h = pc.h1.unwrap_or(0.0);
+            self.price_change_24h = pc.h24.unwrap_or(0.0);
+        }
+
+        if let Some(ref liq) = data.liquidity {
+            self.liquidity_usd = liq.usd.unwrap_or(0.0);
+        }
+
+        // Only DexScreener has these; keep them while a fallback provider answers
+        if let Some(ref info) = data.info {
+            self.info = Some(info.clone());
+        }
+        if let Some(created) = data.created_at() {
+            self.pair_created_at = Some(created);
+        }
+        self.update_bonding_curve(data);
+
+        let mut counted_txns = false;
+        if let Some(ref txns) = data.txns {
+            if let Some(ref h24) = txns.h24 {
+                self.buys_24h = h24.buys.unwrap_or(0);
+                self.sells_24h = h24.sells.unwrap_or(0);
+                counted_txns = true;
+            }
+        }
+
+        // Track history for sparkline
+        let mcap_u64 = self.market_cap as u64;
+        self.market_cap_history.push(mcap_u64);
+        if self.market_cap_history.len() > MAX_HISTORY {
+            self.market_cap_history.remove(0);
+        }
+        let at_target = self.tracked_value() >= self.target_market_cap;
+        self.session.record(Local::now(), self.market_cap, at_target);
+        let closed = self
+            .ohlc
+            .as_mut()
+            .and_then(|t| t.record(Local::now(), self.market_cap));
+        if let Some(row) = closed {
+            self.add_log(format!(
+                "[{}] 📅 {} O ${:.0} H ${:.0} L ${:.0} C ${:.0} ({:+.2}%)",
+                Local::now().format("%H:%M:%S"),
+                row.period,
+                row.open,
+                row.high,
+                row.low,
+                row.close,
+                row.change()
+            ));
+        }
+
+        self.mark_online();
+        self.fetch_count += 1;
+        if self.fetch_count == 1 {
+            self.remember_current_pair();
+        }
+        let now = Local::now().format("%H:%M:%S").to_string();
+        self.last_fetch = Some(now.clone());
+
+        let change_str = if self.price_change_1h >= 0.0 {
+            format!("+{:.2}%", self.price_change_1h)
+        } else {
+            format!("{:.2}%", self.price_change_1h)
+        };
+
+        self.add_log(format!(
+            "[{}] MCap: ${:.0} | Price: ${:.8} | 1h: {}",
+            now, self.market_cap, self.current_price, change_str
+        ));
+
+        // Heads-ups on the way come before the target they lead up to
+        self.check_progress();
+        self.check_target();
+        self.check_fdv_target();
+        self.check_trailing_stop();
+        self.check_ath();
+        self.check_velocity();
+        if counted_txns {
+            self.check_whales();
+        }
+        if let Some(ref change) = data.price_change {
+            self.check_moves(change);
+        }
+        if let Some(liquidity) = data.liquidity.as_ref().and_then(|l| l.usd) {
+            self.check_liquidity_drop(previous_liquidity, liquidity);
+        }
+        self.check_conditions(data);
+        self.check_saved_rules(data);
+    }
+
+    /// Apply a live price from the stream. Market cap and FDV move with the
+    /// price, using the supply implied by the last fetch.
+    #[cfg(feature = "stream")]
+    pub fn apply_stream_price(&mut self, price: f64) {
+        if self.current_price <= 0.0 {
+            return;
+        }
+        let ratio = price / self.current_price;
+        self.current_price = price;
+        self.market_cap *= ratio;
+        self.fdv *= ratio;
+        if let Some(last) = self.market_cap_history.last_mut() {
+            *last = self.market_cap as u64;
+        }
+        self.check_progress();
+        self.check_target();
+        self.check_fdv_target();
+        self.check_trailing_stop();
+        self.check_velocity();
+    }
+
+    /// Alert on each target the market cap has reached, lowest first. The
+    /// last one is the target proper; those below it are milestones.
+    fn check_target(&mut self) {
+        let kind = self.target_kind;
+        let value = self.tracked_value();
+        self.rearm_targets(value);
+        while let Some(&level) = self.targets.get(self.targets_hit) {
+            if value < level {
+                break;
+            }
+            self.targets_hit += 1;
+            let now = Local::now().format("%H:%M:%S").to_string();
+            if self.targets_hit < self.targets.len() {
+                let line = format!(
+                    "[{}] 🎯 Milestone {}/{}: {} passed {}",
+                    now,
+                    self.targets_hit,
+                    self.targets.len(),
+                    kind.name(),
+                    kind.format(level)
+                );
+                let message = format!(
+                    "{} passed {} (milestone {}/{})",
+                    self.token_symbol,
+                    kind.format(level),
+                    self.targets_hit,
+                    self.targets.len()
+                );
+                self.raise_alert(Severity::Critical, "milestone", line, message.clone());
+                self.notify_target(&message, "milestone");
+                continue;
+            }
+            self.target_hit = true;
+            let line = format!(
+                "[{}] 🔥 TARGET HIT! {} reached {} 🔥",
+                now,
+                if kind == TargetKind::Price { "Price" } else { "Market cap" },
+                kind.format(value)
+            );
+            let message = format!(
+                "{} hit {} (target {})",
+                self.token_symbol,
+                kind.format(value),
+                kind.format(level)
+            );
+            self.raise_alert(Severity::Critical, "target", line, message.clone());
+            self.notify_target(&message, "target");
+            if let Some(note) = self.funding_note() {
+                self.add_log(format!("[{}] ⚠ Perp {}", now, note));
+            }
+        }
+    }
+
+    /// Replace the progress alert percentages, dropping any outside 0-100
+    pub fn set_progress_alerts(&mut self, mut percents: Vec<f64>) {
+        percents.retain(|p| *p > 0.0 && *p < 100.0);
+        percents.sort_by(f64::total_cmp);
+        percents.dedup();
+        self.progress_alerts = percents;
+        self.progress_hit = 0;
+        if self.progress_alerts.is_empty() {
+            return;
+        }
+        let levels: Vec<String> =
+            self.progress_alerts.iter().map(|p| format!("{}%", p)).collect();
+        self.add_log(format!(
+            "[{}] 📶 Progress alerts at {}",
+            Local::now().format("%H:%M:%S"),
+            levels.join(", ")
+        ));
+    }
+
+    /// Heads-up info alert as the value passes each --progress-alert percentage
+    /// of the highest target; a jump past several alerts once, for the highest.
+    /// With --rearm, a percentage is given back once the value falls that far
+    /// below it.
+    fn check_progress(&mut self) {
+        let top = self.top_target();
+        let value = self.tracked_value();
+        if top <= 0.0 || value <= 0.0 {
+            return;
+        }
+        if let Some(rearm) = self.rearm_percent {
+            while self.progress_hit > 0 {
+                let level = top * self.progress_alerts[self.progress_hit - 1] / 100.0;
+                if value >= level * (1.0 - rearm / 100.0) {
+                    break;
+                }
+                self.progress_hit -= 1;
+            }
+        }
+        let passed = self
+            .progress_alerts
+            .iter()
+            .take_while(|p| value >= top * **p / 100.0)
+            .count();
+        if passed <= self.progress_hit {
+            return;
+        }
+        self.progress_hit = passed;
+        let percent = self.progress_alerts[passed - 1];
+        let message = format!(
+            "{} passed {}% of the way to {} ({})",
+            self.token_symbol,
+            percent,
+            self.target_kind.format(top),
+            self.target_kind.format(value)
+        );
+        self.raise_alert(
+            Severity::Info,
+            "progress",
+            format!(
+                "[{}] {} {}",
+                Local::now().format("%H:%M:%S"),
+                progress_emoji(percent),
+                message
+            ),
+            message,
+        );
+    }
+
+    /// Alert once the FDV reaches --fdv-target. With --rearm it is given back
+    /// the same way as the main target.
+    fn check_fdv_target(&mut self) {
+        let Some(target) = self.fdv_target else {
+            return;
+        };
+        let now = Local::now().format("%H:%M:%S").to_string();
+        if self.fdv_target_hit {
+            let Some(percent) = self.rearm_percent else {
+                return;
+            };
+            if self.fdv < target * (1.0 - percent / 100.0) {
+                self.fdv_target_hit = false;
+                self.add_log(format!(
+                    "[{}] 🔁 Re-armed FDV target ${:.0}: FDV fell to ${:.0}, over {}% below it",
+                    now, target, self.fdv, percent
+                ));
+            }
+            return;
+        }
+        if self.fdv <= 0.0 || self.fdv < target {
+            return;
+        }
+        self.fdv_target_hit = true;
+        self.raise_alert(
+            Severity::Critical,
+            "fdv_target",
+            format!("[{}] 🔥 FDV TARGET HIT! FDV reached ${:.0} 🔥", now, self.fdv),
+            format!(
+                "{} FDV hit ${:.0} (FDV target ${:.0})",
+                self.token_symbol, self.fdv, target
+            ),
+        );
+    }
+
+    /// The market cap at which the trailing stop fires, from the session peak
+    pub fn trailing_stop_level(&self) -> Option<f64> {
+        let percent = self.trailing_stop?;
+        (self.session.fetches > 0).then(|| self.session.high * (1.0 - percent / 100.0))
+    }
+
+    /// Alert when the market cap has fallen --trailing-stop percent from the
+    /// session peak. Fires once per peak; a new high re-arms it.
+    fn check_trailing_stop(&mut self) {
+        let (Some(percent), Some(level)) = (self.trailing_stop, self.trailing_stop_level()) else {
+            return;
+        };
+        let peak = self.session.high;
+        if self.trailing_fired_at.is_some_and(|at| peak <= at) {
+            return;
+        }
+        self.trailing_fired_at = None;
+        if self.market_cap <= 0.0 || self.market_cap > level {
+            return;
+        }
+        self.trailing_fired_at = Some(peak);
+        let message = format!(
+            "{} down {:.1}% from the session peak ${:.0} to ${:.0} (trailing stop {}%)",
+            self.token_symbol,
+            (peak - self.market_cap) / peak * 100.0,
+            peak,
+            self.market_cap,
+            percent
+        );
+        if self.in_cooldown("trailing_stop", &message) {
+            return;
+        }
+        self.raise_alert(
+            Severity::Warning,
+            "trailing_stop",
+            format!("[{}] 🚨 📉 {}", Local::now().format("%H:%M:%S"), message),
+            message,
+        );
+    }
+
+    /// Keep the pair's all-time high, alerting with --ath-alert when the market
+    /// cap beats the high from earlier sessions. A pair's first session only
+    /// records it. While it keeps climbing, it is written at most once every
+    /// `ath::SAVE_INTERVAL`, and on exit.
+    fn check_ath(&mut self) {
+        if self.market_cap <= 0.0 {
+            return;
+        }
+        if !self.ath_loaded {
+            self.ath_loaded = true;
+            self.ath = self.highs.load(&self.chain, &self.pair_address);
+            self.ath_previous = self.ath.as_ref().map(|h| h.market_cap);
+        }
+        if self.ath.as_ref().is_some_and(|h| self.market_cap <= h.market_cap) {
+            return;
+        }
+        self.ath = Some(AllTimeHigh {
+            pair: self.pair_address.clone(),
+            chain: self.chain.clone(),
+            market_cap: self.market_cap,
+            at: Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        });
+        self.ath_unsaved = true;
+        let due = match self.ath_saved_at {
+            Some(at) => at.elapsed() >= ath::SAVE_INTERVAL,
+            None => true,
+        };
+        if due {
+            self.save_ath();
+        }
+        let Some(previous) = self.ath_previous.filter(|_| self.ath_alert) else {
+            return;
+        };
+        let message = format!(
+            "{} new all-time high ${:.0}, {:+.1}% over the previous ${:.0}",
+            self.token_symbol,
+            self.market_cap,
+            (self.market_cap - previous) / previous * 100.0,
+            previous
+        );
+        if self.in_cooldown("ath", &message) {
+            return;
+        }
+        self.raise_alert(
+            Severity::Warning,
+            "ath",
+            format!("[{}] 🚨 🏔 {}", Local::now().format("%H:%M:%S"), message),
+            message,
+        );
+    }
+
+    /// Write the all-time high if it has risen since it was last written
+    pub fn save_ath(&mut self) {
+        let Some(high) = self.ath.as_ref().filter(|_| self.ath_unsaved) else {
+            return;
+        };
+        let saved = self.highs.save(high);
+        self.ath_unsaved = false;
+        self.ath_saved_at = Some(Instant::now());
+        if let Err(e) = saved {
+            self.add_error(e);
+        }
+    }
+
+    /// Alert when the market cap moves faster than --velocity dollars a minute,
+    /// fed by fetches and, with --stream, by every live price
+    fn check_velocity(&mut self) {
+        let Some(ref mut velocity) = self.velocity else {
+            return;
+        };
+        let Some(rate) = velocity.record(Instant::now(), self.market_cap) else {
+            return;
+        };
+        let message = format!(
+            "{} market cap moving {}${:.0}/min over {}m (threshold ${:.0}/min)",
+            self.token_symbol,
+            if rate >= 0.0 { "+" } else { "-" },
+            rate.abs(),
+            velocity.window.as_secs() / 60,
+            velocity.threshold
+        );
+        if self.in_cooldown("velocity", &message) {
+            return;
+        }
+        self.raise_alert(
+            Severity::Warning,
+            "velocity",
+            format!("[{}] 🚨 ⚡ {}", Local::now().format("%H:%M:%S"), message),
+            message,
+        );
+    }
+
+    /// Alert when more transactions landed since the last fetch than
+    /// --whale-alert times the day's average rate predicts
+    fn check_whales(&mut self) {
+        let Some(ref mut whale_watch) = self.whale_watch else {
+            return;
+        };
+        let Some(burst) = whale_watch.record(Instant::now(), self.buys_24h, self.sells_24h)
+        else {
+            return;
+        };
+        let message = format!(
+            "{}: {} transactions in {} ({} buys / {} sells), {:.1}x the usual rate — \
+             whale or bot activity",
+            self.token_symbol,
+            burst.total(),
+            format_wait(burst.elapsed),
+            burst.buys,
+            burst.sells,
+            burst.ratio()
+        );
+        if self.in_cooldown("whale", &message) {
+            return;
+        }
+        self.raise_alert(
+            Severity::Warning,
+            "whale",
+            format!("[{}] 🚨 🐋 {}", Local::now().format("%H:%M:%S"), message),
+            message,
+        );
+    }
+
+    /// With --rearm, give back reached targets the value has fallen well below,
+    /// highest first, so the next breakout alerts (and sounds the alarm) again
+    fn rearm_targets(&mut self, value: f64) {
+        let Some(percent) = self.rearm_percent else {
+            return;
+        };
+        while self.targets_hit > 0 {
+            let level = self.targets[self.targets_hit - 1];
+            if value >= level * (1.0 - percent / 100.0) {
+                break;
+            }
+            self.targets_hit -= 1;
+            self.target_hit = false;
+            self.alarm_active = false;
+            self.add_log(format!(
+                "[{}] 🔁 Re-armed {}: {} fell to {}, over {}% below it",
+                Local::now().format("%H:%M:%S"),
+                self.target_kind.format(level),
+                self.target_kind.name(),
+                self.target_kind.format(value),
+                percent
+            ));
+        }
+    }
+
+    /// Alert on each move rule the reported price changes trigger. A rule fires
+    /// once, then re-arms when the move is back under its threshold.
+    fn check_moves(&mut self, change: &PriceChange) {
+        for (i, rule) in self.move_alerts.clone().into_iter().enumerate() {
+            let Some(value) = rule.triggered(change) else {
+                self.moves_firing.remove(&i);
+                continue;
+            };
+            if !self.moves_firing.insert(i) {
+                continue;
+            }
+            let message = format!(
+                "{} {:+.1}% in {} (alert {})",
+                self.token_symbol,
+                value,
+                rule.window.name(),
+                rule
+            );
+            let key = format!("move:{}", rule);
+            if self.in_cooldown(&key, &message) {
+                continue;
+            }
+            self.raise_alert(
+                Severity::Warning,
+                &key,
+                format!("[{}] 🚨 {}", Local::now().format("%H:%M:%S"), message),
+                message,
+            );
+        }
+    }
+
+    /// The current value of a --when variable; None when the provider that
+    /// answered doesn't report it
+    fn condition_value(&self, variable: Variable, data: &PairData) -> Option<f64> {
+        match variable {
+            Variable::MarketCap => Some(self.market_cap),
+            Variable::Price => Some(self.current_price),
+            Variable::Progress => {
+                let top = self.top_target();
+                (top > 0.0).then(|| self.tracked_value() / top * 100.0)
+            }
+            _ => variable.reported(data),
+        }
+    }
+
+    /// Attach the saved rules the config file gives the pair, plus those named
+    /// with --rule, logging any that can't be used
+    pub fn attach_saved_rules(&mut self) {
+        let now = Local::now().format("%H:%M:%S").to_string();
+        self.saved_rules.clear();
+        self.saved_rules_firing.clear();
+        for name in &self.rule_names.clone() {
+            if !self.config.rules.iter().any(|r| r.name.eq_ignore_ascii_case(name)) {
+                self.add_log(format!("[{}] ⚠ No saved rule named '{}'", now, name));
+            }
+        }
+        let rules: Vec<SavedRule> = self
+            .config
+            .rules
+            .iter()
+            .filter(|r| {
+                r.attached_to(&self.pair_address)
+                    || self.rule_names.iter().any(|n| n.eq_ignore_ascii_case(&r.name))
+            })
+            .cloned()
+            .collect();
+        for rule in rules {
+            match rule.when.parse::<Condition>() {
+                Ok(condition) => self.saved_rules.push((rule, condition)),
+                Err(e) => self.add_log(format!(
+                    "[{}] ⚠ Saved rule '{}' ignored: {}",
+                    now, rule.name, e
+                )),
+            }
+        }
+        if self.saved_rules.is_empty() {
+            return;
+        }
+        let names: Vec<&str> = self.saved_rules.iter().map(|(r, _)| r.name.as_str()).collect();
+        let msg = format!("[{}] 📏 Saved rules: {}", now, names.join(", "));
+        self.add_log(msg);
+    }
+
+    /// Alert on each attached saved rule that starts to hold, at its own
+    /// severity and with its own sound. Fires once, re-arms when it stops holding.
+    fn check_saved_rules(&mut self, data: &PairData) {
+        for (rule, condition) in self.saved_rules.clone() {
+            if !condition.holds(|v| self.condition_value(v, data)) {
+                self.saved_rules_firing.remove(&rule.name);
+                continue;
+            }
+            if !self.saved_rules_firing.insert(rule.name.clone()) {
+                continue;
+            }
+            let values: Vec<String> = condition
+                .variables()
+                .into_iter()
+                .filter_map(|v| {
+                    let value = self.condition_value(v, data)?;
+                    Some(format!("{} {}", v.name(), v.format(value)))
+                })
+                .collect();
+            let message = format!(
+                "{}: {} — {} ({})",
+                self.token_symbol,
+                rule.name,
+                condition,
+                values.join(", ")
+            );
+            let key = format!("rule:{}", rule.name);
+            if self.in_cooldown(&key, &message) {
+                continue;
+            }
+            self.raise_alert_with_sound(
+                rule.severity,
+                &key,
+                format!("[{}] 🚨 📏 {}", Local::now().format("%H:%M:%S"), message),
+                message,
+                rule.sound.clone(),
+            );
+        }
+    }
+
+    /// Alert on each --when condition that starts to hold. Like move alerts,
+    /// a condition fires once and re-arms when it stops holding.
+    fn check_conditions(&mut self, data: &PairData) {
+        for (i, condition) in self.conditions.clone().into_iter().enumerate() {
+            if !condition.holds(|v| self.condition_value(v, data)) {
+                self.conditions_firing.remove(&i);
+                continue;
+            }
+            if !self.conditions_firing.insert(i) {
+                continue;
+            }
+            let values: Vec<String> = condition
+                .variables()
+                .into_iter()
+                .filter_map(|v| {
+                    let value = self.condition_value(v, data)?;
+                    Some(format!("{} {}", v.name(), v.format(value)))
+                })
+                .collect();
+            let message =
+                format!("{}: {} ({})", self.token_symbol, condition, values.join(", "));
+            let key = format!("when:{}", condition);
+            if self.in_cooldown(&key, &message) {
+                continue;
+            }
+            self.raise_alert(
+                Severity::Warning,
+                &key,
+                format!("[{}] 🚨 {}", Local::now().format("%H:%M:%S"), message),
+                message,
+            );
+        }
+    }
+
+    /// Alert when liquidity fell by at least the --rug-alert percent since the
+    /// previous fetch
+    fn check_liquidity_drop(&mut self, previous: f64, liquidity: f64) {
+        let Some(threshold) = self.rug_alert else {
+            return;
+        };
+        if previous <= 0.0 {
+            return;
+        }
+        let drop = (previous - liquidity) / previous * 100.0;
+        if drop < threshold {
+            return;
+        }
+        let message = format!(
+            "{} liquidity fell {:.1}% in one fetch: ${:.0} → ${:.0} (possible rug)",
+            self.token_symbol, drop, previous, liquidity
+        );
+        if self.in_cooldown("rug", &message) {
+            return;
+        }
+        self.raise_alert(
+            Severity::Critical,
+            "rug",
+            format!("[{}] 🚨 {}", Local::now().format("%H:%M:%S"), message),
+            message,
+        );
+    }
+
+    /// Move alerts as typed in the config modal
+    pub fn move_alerts_text(&self) -> String {
+        self.move_alerts
+            .iter()
+            .map(|r| r.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    pub fn log_move_alerts(&mut self) {
+        let rules = self.move_alerts_text();
+        self.add_log(format!("[{}] 📐 Move alerts: {}", Local::now().format("%H:%M:%S"), rules));
+    }
+
+    /// The lowest target not yet reached, or the highest once all are
+    pub fn next_target(&self) -> f64 {
+        self.targets
+            .get(self.targets_hit)
+            .or(self.targets.last())
+            .copied()
+            .unwrap_or(self.target_market_cap)
+    }
+
+    /// One-line state for the terminal title and status file, e.g.
+    /// `PEPE $512.3K (64% to target)`
+    pub fn status_line(&self) -> String {
+        let mut line = format!("{} ${}", self.token_symbol, ui::format_amount(self.market_cap));
+        if self.target_hit {
+            line.push_str(" (target hit)");
+        } else if self.next_target() > 0.0 {
+            line.push_str(&format!(" ({:.0}% to target)", self.progress()));
+        }
+        line
+    }
+
+    /// The highest target, the one that ends the run
+    pub fn top_target(&self) -> f64 {
+        self.targets.last().copied().unwrap_or(self.target_market_cap)
+    }
+
+    /// The value the target is compared against: the market cap or the price
+    pub fn tracked_value(&self) -> f64 {
+        match self.target_kind {
+            TargetKind::MarketCap => self.market_cap,
+            TargetKind::Price => self.current_price,
+        }
+    }
+
+    pub fn target(&self) -> Target {
+        Target {
+            kind: self.target_kind,
+            value: self.target_market_cap,
+        }
+    }
+
+    /// The target with its milestones, as typed in the config modal
+    pub fn target_text(&self) -> String {
+        let mut levels = self.milestones.clone();
+        levels.push(self.target_market_cap);
+        Targets::new(self.target_kind, levels).to_string()
+    }
+
+    /// Replace the target, e.g. with a price target from --target p:0.0005
+    /// or a ladder from --target 50000,100000
+    pub fn set_target(&mut self, targets: Targets) {
+        let target = targets.top();
+        self.target_market_cap = target.value;
+        self.target_kind = target.kind;
+        self.milestones = targets.milestones().to_vec();
+        self.targets = self.target_ladder();
+        self.targets_hit = 0;
+        self.target_hit = false;
+        self.progress_hit = 0;
+        self.modal_fields[ModalField::Target] = self.target_text();
+        if target.kind == TargetKind::Price {
+            self.add_log(format!(
+                "[{}] 🎯 Price target: {}",
+                Local::now().format("%H:%M:%S"),
+                target.kind.format(target.value)
+            ));
+        }
+        if !self.milestones.is_empty() {
+            self.log_milestones();
+        }
+    }
+
+    /// Milestones in the config file are market caps, so a price target only
+    /// has those given with it
+    fn target_ladder(&self) -> Vec<f64> {
+        match self.target_kind {
+            TargetKind::MarketCap => self.config.target_ladder(
+                &self.pair_address,
+                self.target_market_cap,
+                &self.milestones,
+            ),
+            TargetKind::Price => {
+                let mut levels = self.milestones.clone();
+                levels.push(self.target_market_cap);
+                Targets::new(TargetKind::Price, levels).levels
+            }
+        }
+    }
+
+    /// Log the milestones below the target, if any are configured
+    fn log_milestones(&mut self) {
+        if self.targets.len() < 2 {
+            return;
+        }
+        let levels: Vec<String> =
+            self.targets.iter().map(|t| self.target_kind.format(*t)).collect();
+        self.add_log(format!(
+            "[{}] 🎯 Targets: {}",
+            Local::now().format("%H:%M:%S"),
+            levels.join(" → ")
+        ));
+    }
+
+    /// How long `kind` of rule stays quiet after alerting: its entry in
+    /// `alert_cooldowns`, else --cooldown, else the "default" entry
+    fn cooldown(&self, kind: &str) -> Duration {
+        let configured = |key: &str| self.config.alert_cooldowns.get(key).copied();
+        configured(kind)
+            .map(Duration::from_secs)
+            .or(self.cooldown_override)
+            .or_else(|| configured("default").map(Duration::from_secs))
+            .unwrap_or(DEFAULT_COOLDOWN)
+    }
+
+    /// Whether `rule` alerted too recently to alert again. Otherwise its
+    /// cooldown starts now. Rules are named "kind" or "kind:detail".
+    fn in_cooldown(&mut self, rule: &str, message: &str) -> bool {
+        let kind = rule.split(':').next().unwrap_or_default();
+        let cooldown = self.cooldown(kind);
+        if let Some(at) = self.rule_alerted.get(rule) {
+            let left = cooldown.saturating_sub(at.elapsed());
+            if !left.is_zero() {
+                self.add_log(format!(
+                    "[{}] 🔕 {} (cooldown, {}s left)",
+                    Local::now().format("%H:%M:%S"),
+                    message,
+                    left.as_secs()
+                ));
+                return true;
+            }
+        }
+        self.rule_alerted.insert(rule.to_string(), Instant::now());
+        false
+    }
+
+    pub fn snoozed(&self) -> bool {
+        self.snoozed_until.is_some_and(|until| Local::now() < until)
+    }
+
+    /// Snooze for the next length in `SNOOZE_STEPS`, counted from now, or end
+    /// the snooze after the longest
+    pub fn cycle_snooze(&mut self) {
+        let now = Local::now();
+        let current = self
+            .snoozed_until
+            .filter(|_| self.snoozed())
+            .map(|until| (until - now).num_minutes() + 1);
+        let next = match current {
+            None => SNOOZE_STEPS.first().copied(),
+            Some(left) => SNOOZE_STEPS.iter().copied().find(|m| *m > left),
+        };
+        self.snoozed_until = next.map(|mins| now + chrono::Duration::minutes(mins));
+        match self.snoozed_until {
+            Some(until) => self.add_log(format!(
+                "[{}] 😴 Alerts snoozed until {} — alarm and notifications held back",
+                now.format("%H:%M:%S"),
+                until.format("%H:%M")
+            )),
+            None => self.add_log(format!("[{}] 🔔 Snooze off", now.format("%H:%M:%S"))),
+        }
+    }
+
+    /// Log `line`, marked with the alert's severity, and route the alert
+    /// `summary` raised by `rule` ("kind" or "kind:detail", as in
+    /// `alert_routing.types`) to the channels configured for it. The alarm
+    /// channel makes the main loop start the alarm (or visual alert). While
+    /// snoozed the alert is only logged and recorded.
+    pub fn raise_alert(&mut self, severity: Severity, rule: &str, line: String, summary: String) {
+        self.raise_alert_with_sound(severity, rule, line, summary, None);
+    }
+
+    /// `raise_alert`, with the alarm playing `sound` instead of the severity's
+    /// audio file if this alert starts it
+    pub fn raise_alert_with_sound(
+        &mut self,
+        severity: Severity,
+        rule: &str,
+        line: String,
+        summary: String,
+        sound: Option<String>,
+    ) {
+        let mut line = LogLine::new(line, Local::now());
+        line.level = Some(severity);
+        self.push_log(line);
+        let (channels, unknown) = notify::route(&self.config, &self.pair_address, severity, rule);
+        if !unknown.is_empty() {
+            let now = Local::now().format("%H:%M:%S").to_string();
+            self.add_log(format!(
+                "[{}] ⚠ Unknown {} alert channel(s) skipped: {}",
+                now,
+                severity.name(),
+                unknown.join(", ")
+            ));
+        }
+        self.record_event(Event::alert(
+            &self.chain,
+            &self.pair_address,
+            &self.token_symbol,
+            severity,
+            &summary,
+        ));
+        if self.config.redis.url.is_some() || self.config.mqtt.host().is_some() {
+            self.bus_queue.push(BusEvent::alert(
+                &self.chain,
+                &self.pair_address,
+                &self.token_symbol,
+                severity,
+                &summary,
+            ));
+        }
+        // An automation, so it runs even while notifications are snoozed
+        if let Some(command) = self.config.on_alert.clone().filter(|c| !c.trim().is_empty()) {
+            let context = self.alert_context();
+            if let Err(e) = self.hooks.run(&command, severity, &summary, rule, &context) {
+                self.add_log(format!("[{}] ⚠ {}", Local::now().format("%H:%M:%S"), e));
+            }
+        }
+        if self.snoozed() {
+            self.last_alert = Some(summary);
+            return;
+        }
+        for channel in channels {
+            match channel {
+                Channel::Alarm | Channel::Speech if self.quiet_hours(severity) => {
+                    self.add_log(format!(
+                        "[{}] 🌙 Quiet hours: {} silenced",
+                        Local::now().format("%H:%M:%S"),
+                        channel.name()
+                    ));
+                }
+                Channel::Alarm => {
+                    if !self.alarm_active || severity > self.alarm_severity {
+                        self.alarm_severity = severity;
+                        self.alarm_sound_file = sound.clone();
+                    }
+                    self.alarm_active = true;
+                }
+                Channel::Desktop | Channel::Telegram | Channel::Twilio => {
+                    let message = self.notification_message(&summary);
+                    self.outbox.queue(channel, severity, message, self.alert_context())
+                }
+                // These carry the token and market cap in fields of their own
+                Channel::Discord | Channel::Slack | Channel::Webhook => {
+                    let message = self.labelled(&summary);
+                    self.outbox.queue(channel, severity, message, self.alert_context())
+                }
+                Channel::Speech => {
+                    let message = self.spoken(&summary, rule);
+                    self.outbox.queue(channel, severity, message, self.alert_context())
+                }
+            }
+        }
+        self.last_alert = Some(summary);
+    }
+
+    /// The most serious warning to show for the pair, unless it was dismissed
+    pub fn active_warning(&self) -> Option<TokenWarning> {
+        let security = self.security.as_ref().is_some_and(|s| s.is_dangerous());
+        let low_liquidity = self.fetch_count > 0
+            && self.liquidity_usd > 0.0
+            && self.liquidity_usd < LOW_LIQUIDITY_USD;
+        let no_audio = !cfg!(feature = "audio")
+            && [Severity::Info, Severity::Warning, Severity::Critical]
+                .iter()
+                .any(|severity| {
+                    let (file, _) = self.config.alarm_sounds.resolve(
+                        *severity,
+                        self.alarm_file.clone(),
+                        self.alarm_duration,
+                    );
+                    file.is_some()
+                });
+        [
+            (TokenWarning::Security, security),
+            (TokenWarning::LowLiquidity, low_liquidity),
+            (TokenWarning::NoAudio, no_audio),
+        ]
+        .into_iter()
+        .filter(|(_, shown)| *shown)
+        .map(|(warning, _)| warning)
+        .find(|warning| {
+            !self
+                .config
+                .warning_dismissed(&self.chain, &self.pair_address, warning.name())
+        })
+    }
+
+    /// Hide the warning banner for this pair, in this session and later ones
+    pub fn dismiss_warning(&mut self) {
+        let Some(warning) = self.active_warning() else {
+            return;
+        };
+        self.config.dismiss_warning(&self.chain, &self.pair_address, warning.name());
+        if let Err(e) = self.config.save() {
+            self.add_error(e);
+        }
+        self.add_log(format!(
+            "[{}] 🙈 Dismissed the {} warning for {}; it stays hidden for this pair",
+            Local::now().format("%H:%M:%S"),
+            warning.description(),
+            self.token_symbol
+        ));
+    }
+
+    /// Whether the configured quiet hours keep an alert of `severity` silent now
+    pub fn quiet_hours(&self, severity: Severity) -> bool {
+        self.config
+            .quiet_hours
+            .as_ref()
+            .is_some_and(|quiet| quiet.silences(severity, Local::now()))
+    }
+
+    /// Audio file and seconds the alarm plays for, by its severity. A saved
+    /// rule's own sound replaces the file.
+    pub fn alarm_sound(&self) -> (Option<String>, u64) {
+        let (file, duration) = self.config.alarm_sounds.resolve(
+            self.alarm_severity,
+            self.alarm_file.clone(),
+            self.alarm_duration,
+        );
+        (self.alarm_sound_file.clone().or(file), duration)
+    }
+
+    /// Body of a desktop or Telegram notification: the alert, then the token,
+    /// its market cap and how far along it is to the target
+    fn notification_message(&self, summary: &str) -> String {
+        let top = self.top_target();
+        let progress = if top > 0.0 { self.tracked_value() / top * 100.0 } else { 0.0 };
+        format!(
+            "{}\n{} ({}) · MCap ${} · {:.0}% of {}",
+            self.labelled(summary),
+            self.token_name,
+            self.token_symbol,
+            ui::format_amount(self.market_cap),
+            progress,
+            self.target_kind.format(top)
+        )
+    }
+
+    /// An alert as the speech channel reads it. Target and milestone hits name
+    /// the level reached rather than the exact value.
+    fn spoken(&self, summary: &str, rule: &str) -> String {
+        let level = self.targets_hit.checked_sub(1).and_then(|i| self.targets.get(i));
+        match level {
+            Some(&level) if rule == "target" || rule == "milestone" => {
+                speech::target_phrase(&self.token_symbol, self.target_kind, level)
+            }
+            _ => speech::phrase(summary),
+        }
+    }
+
+    /// The pair alerts are about, for channels that show more than their text
+    fn alert_context(&self) -> AlertContext {
+        let top = self.top_target();
+        let progress = (top > 0.0).then(|| {
+            format!(
+                "{:.0}% of {}",
+                self.tracked_value() / top * 100.0,
+                self.target_kind.format(top)
+            )
+        });
+        AlertContext {
+            chain: self.chain.clone(),
+            pair: self.pair_address.clone(),
+            name: self.token_name.clone(),
+            symbol: self.token_symbol.clone(),
+            market_cap: self.market_cap,
+            price: self.current_price,
+            target: top,
+            progress,
+            history: self.market_cap_history.clone(),
+        }
+    }
+
+    /// Queue a desktop notification for --desktop-notify, unless alerts are
+    /// snoozed
+    pub fn notify_desktop(&mut self, severity: Severity, summary: &str) {
+        if !self.desktop_notify || self.snoozed() {
+            return;
+        }
+        let message = self.notification_message(summary);
+        self.outbox.queue(Channel::Desktop, severity, message, self.alert_context());
+    }
+
+    /// Tell --desktop-notify and the channels taking errors (Slack) that
+    /// fetches keep failing, unless alerts are snoozed
+    pub fn notify_failure(&mut self, summary: &str) {
+        self.notify_desktop(Severity::Warning, summary);
+        if self.snoozed() {
+            return;
+        }
+        for channel in notify::error_channels(&self.config) {
+            let message = self.labelled(summary);
+            self.outbox.queue(channel, Severity::Warning, message, self.alert_context());
+        }
+    }
+
+    /// Desktop notification for a target or milestone hit, unless the alert
+    /// routing already sent one
+    fn notify_target(&mut self, summary: &str, rule: &str) {
+        let (channels, _) =
+            notify::route(&self.config, &self.pair_address, Severity::Critical, rule);
+        if !channels.contains(&Channel::Desktop) {
+            self.notify_desktop(Severity::Critical, summary);
+        }
+    }
+
+    /// `text` prefixed with the instance name, for messages leaving the TUI
+    pub fn labelled(&self, text: &str) -> String {
+        notify::label(self.instance.as_deref(), text)
+    }
+
+    /// Append to the event log. A log that can't be written is closed, once
+    /// its failure is logged.
+    pub fn record_event(&mut self, event: Event) {
+        let Some(ref mut log) = self.event_log else {
+            return;
+        };
+        if let Err(e) = log.append(&event) {
+            self.event_log = None;
+            self.add_log(format!(
+                "[{}] ⚠ Event log disabled: {}",
+                Local::now().format("%H:%M:%S"),
+                e
+            ));
+        }
+    }
+
+    /// Start queued notifications that are due, and log the outcome of the
+    /// ones that have finished
+    pub fn deliver_notifications(&mut self, client: &reqwest::Client) {
+        self.outbox.dispatch(client, &self.config);
+        for (delivery, result) in self.outbox.finished() {
+            let now = Local::now().format("%H:%M:%S");
+            match self.outbox.record(delivery, result) {
+                Ok(line) => self.add_log(format!("[{}] 📨 {}", now, line)),
+                Err(line) => self.add_log(format!("[{}] ❌ {}", now, line)),
+            }
+        }
+    }
+
+    /// Log `on_alert` commands that exited with an error
+    pub fn reap_hooks(&mut self) {
+        for failure in self.hooks.reap() {
+            self.add_log(format!("[{}] ⚠ {}", Local::now().format("%H:%M:%S"), failure));
+        }
+    }
+
+    pub fn add_log(&mut self, msg: String) {
+        self.push_log(LogLine::new(msg, Local::now()));
+    }
+
+    fn push_log(&mut self, line: LogLine) {
+        self.log.push(line);
+        if self.log.len() > MAX_LOG {
+            self.log.remove(0);
+        }
+    }
+
+    pub fn add_error(&mut self, err: String) {
+        self.error_count += 1;
+        let now = Local::now().format("%H:%M:%S").to_string();
+        self.add_log(format!("[{}] ❌ Error: {}", now, err));
+    }
+
+    /// Seconds until the next scheduled fetch should happen
+    pub fn effective_interval(&self) -> u64 {
+        let base = if self.low_power {
+            let lp = &self.config.low_power;
+            let stretched = (self.check_interval as f64 * lp.interval_multiplier.max(1.0)) as u64;
+            stretched.min(lp.max_interval).max(self.check_interval)
+        } else {
+            self.check_interval
+        };
+        if self.offline {
+            base.min(OFFLINE_RETRY_SECS)
+        } else {
+            base
+        }
+    }
+
+    /// Note the start of a fetch and pick the jitter for the next one. Aligned
+    /// fetches get none, as it would pull them off the boundary.
+    pub fn start_fetch(&mut self) {
+        self.fetch_started = Some(Local::now());
+        self.fetch_jitter = match self.align_fetches {
+            true => Duration::ZERO,
+            false => jitter::delay(self.config.fetch_jitter.unwrap_or(0)),
+        };
+    }
+
+    /// Time from one fetch to the next: the interval plus this round's jitter,
+    /// or with --align up to the next wall-clock boundary
+    pub fn fetch_delay(&self) -> Duration {
+        let interval = self.effective_interval();
+        match self.fetch_started {
+            Some(started) if self.align_fetches => {
+                jitter::until_boundary(interval, started.into())
+            }
+            _ => Duration::from_secs(interval) + self.fetch_jitter,
+        }
+    }
+
+    /// When the next scheduled fetch is due, for the status bar
+    pub fn next_fetch(&self) -> Option<DateTime<Local>> {
+        let started = self.fetch_started?;
+        Some(started + chrono::Duration::from_std(self.fetch_delay()).ok()?)
+    }
+
+    /// Redraw/input tick, lengthened in low-power mode
+    pub fn tick_rate(&self) -> Duration {
+        if self.low_power {
+            Duration::from_millis(self.config.low_power.tick_ms.max(TICK_MS))
+        } else {
+            Duration::from_millis(TICK_MS)
+        }
+    }
+
+    /// Turn low-power mode on or off, logging the change
+    pub fn set_low_power(&mut self, on: bool, reason: &str) {
+        if self.low_power == on {
+            return;
+        }
+        self.low_power = on;
+        let now = Local::now().format("%H:%M:%S").to_string();
+        if on {
+            self.add_log(format!(
+                "[{}] 🔋 Low-power mode on ({}) — fetching every {}s",
+                now,
+                reason,
+                self.effective_interval()
+            ));
+        } else {
+            self.add_log(format!("[{}] 🔌 Low-power mode off ({})", now, reason));
+        }
+    }
+
+    /// Whether an observer's quit is confirmed: true on the second press of q
+    /// within `QUIT_CONFIRM`, otherwise arms the confirmation
+    pub fn confirm_quit(&mut self) -> bool {
+        if self.quit_pending() {
+            return true;
+        }
+        self.quit_armed = Some(Instant::now());
+        false
+    }
+
+    /// Whether an observer's first q is waiting for the second
+    pub fn quit_pending(&self) -> bool {
+        self.quit_armed.is_some_and(|at| at.elapsed() < QUIT_CONFIRM)
+    }
+
+    /// Manual toggle; disables battery auto-detection for the rest of the session
+    pub fn toggle_low_power(&mut self) {
+        self.low_power_auto = false;
+        self.set_low_power(!self.low_power, "manual");
+    }
+
+    /// The connectivity probe failed: stop counting errors until we're back
+    pub fn mark_offline(&mut self, err: &str) {
+        if self.offline {
+            return;
+        }
+        self.offline = true;
+        let now = Local::now().format("%H:%M:%S").to_string();
+        self.add_log(format!(
+            "[{}] 📡 Network unreachable ({}) — pausing error count, retrying every {}s",
+            now,
+            api::truncate(err, 60),
+            self.effective_interval()
+        ));
+    }
+
+    /// The monitoring schedule configured for the current pair or token
+    pub fn schedule(&self) -> Option<&Schedule> {
+        schedule::find(&self.config.schedules, &self.pair_address).or_else(|| {
+            self.token_address
+                .as_deref()
+                .and_then(|t| schedule::find(&self.config.schedules, t))
+        })
+    }
+
+    /// Re-check the pair's schedule, logging when polling pauses or resumes.
+    /// Returns true when polling just resumed, so the caller fetches right away.
+    pub fn update_schedule(&mut self) -> bool {
+        let was_paused = self.polling_paused();
+        let Some(schedule) = self.schedule() else {
+            self.schedule_paused = false;
+            return self.pause_changed(was_paused);
+        };
+        let paused = !schedule.active_at(Local::now());
+        if paused == self.schedule_paused {
+            return false;
+        }
+        let window = schedule.describe();
+        self.schedule_paused = paused;
+        let now = Local::now().format("%H:%M:%S").to_string();
+        if paused {
+            self.add_log(format!(
+                "[{}] 💤 Outside monitoring schedule ({}) — polling paused",
+                now, window
+            ));
+        } else {
+            self.add_log(format!("[{}] ⏰ Monitoring schedule active — polling resumed", now));
+        }
+        self.pause_changed(was_paused)
+    }
+
+    /// Whether the monitored pair is skipped by the schedule or a manual pause
+    pub fn polling_paused(&self) -> bool {
+        self.schedule_paused || self.manual_paused
+    }
+
+    /// Start recording a macro, or stop and wait for the key to bind it to
+    pub fn toggle_macro_recording(&mut self) {
+        let now = Local::now().format("%H:%M:%S").to_string();
+        match self.macro_recording.take() {
+            None => {
+                self.macro_recording = Some(Vec::new());
+                self.add_log(format!(
+                    "[{}] ⏺ Recording macro — press m again to stop",
+                    now
+                ));
+            }
+            Some(keys) if keys.is_empty() => {
+                self.add_log(format!("[{}] ⏺ Macro discarded (no keys recorded)", now));
+            }
+            Some(keys) => {
+                self.add_log(format!(
+                    "[{}] ⏺ Recorded {} keys — press 0-9 to bind them, Esc to discard",
+                    now,
+                    keys.len()
+                ));
+                self.macro_unbound = Some(keys);
+            }
+        }
+    }
+
+    /// Add a key press to the macro being recorded
+    pub fn record_macro_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        let Some(ref mut keys) = self.macro_recording else {
+            return;
+        };
+        if keys.len() >= macros::MAX_KEYS {
+            return;
+        }
+        if let Some(name) = macros::key_name(code, modifiers) {
+            keys.push(name);
+        }
+    }
+
+    /// Bind the finished recording to a digit key and save it to the config;
+    /// Esc discards it
+    pub fn bind_macro(&mut self, code: KeyCode) {
+        let now = Local::now().format("%H:%M:%S").to_string();
+        let slot = match code {
+            KeyCode::Char(c) if c.is_ascii_digit() => c,
+            KeyCode::Esc => {
+                self.macro_unbound = None;
+                self.add_log(format!("[{}] ⏺ Macro discarded", now));
+                return;
+            }
+            _ => return,
+        };
+        let Some(keys) = self.macro_unbound.take() else {
+            return;
+        };
+        self.add_log(format!("[{}] ⏺ Macro {}: {}", now, slot, keys.join(" ")));
+        self.config.macros.insert(slot.to_string(), keys);
+        if let Err(e) = self.config.save() {
+            self.add_error(e);
+        }
+    }
+
+    /// Queue the keys of the macro bound to `slot` for playback
+    pub fn play_macro(&mut self, slot: char) {
+        let now = Local::now().format("%H:%M:%S").to_string();
+        let Some(keys) = self.config.macros.get(&slot.to_string()).cloned() else {
+            self.add_log(format!(
+                "[{}] ⏺ No macro on {} — press m to record one",
+                now, slot
+            ));
+            return;
+        };
+        let mut queue = VecDeque::new();
+        for name in &keys {
+            match macros::parse_key(name) {
+                Some(key) => queue.push_back(key),
+                None => {
+                    self.add_log(format!("[{}] ⚠ Macro {}: unknown key '{}'", now, slot, name));
+                    return;
+                }
+            }
+        }
+        self.macro_queue = queue;
+    }
+
+    /// Pause or resume polling. Returns true on resume, so the caller fetches
+    /// right away.
+    pub fn toggle_pause(&mut self) -> bool {
+        if !self.configured {
+            return false;
+        }
+        let was_paused = self.polling_paused();
+        self.manual_paused = !self.manual_paused;
+        let now = Local::now().format("%H:%M:%S").to_string();
+        if self.manual_paused {
+            self.add_log(format!("[{}] ⏸  Monitoring paused", now));
+        } else {
+            self.add_log(format!("[{}] ▶  Monitoring resumed", now));
+        }
+        self.pause_changed(was_paused)
+    }
+
+    /// Track when a pause starts, and hand its start to the catch-up report
+    /// when it ends. Returns true when polling just resumed.
+    fn pause_changed(&mut self, was_paused: bool) -> bool {
+        match (was_paused, self.polling_paused()) {
+            (false, true) => {
+                self.paused_since = Some(Local::now());
+                false
+            }
+            (true, false) => {
+                self.catch_up_since = self.paused_since.take();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Report what the pair did while polling was paused, from candles
+    /// `candle_secs` long. Targets crossed in the gap but not held until now
+    /// are alerted on, since the live check can't see them.
+    pub fn report_catch_up(
+        &mut self,
+        since: DateTime<Local>,
+        candles: &[Candle],
+        candle_secs: i64,
+    ) {
+        let now = Local::now().format("%H:%M:%S").to_string();
+        let from = since.format("%H:%M");
+        let gap: Vec<&Candle> = candles
+            .iter()
+            .filter(|c| c.timestamp + candle_secs > since.timestamp())
+            .collect();
+        if gap.is_empty() || self.current_price <= 0.0 {
+            self.add_log(format!(
+                "[{}] ⏪ While paused (since {}): no history available",
+                now, from
+            ));
+            return;
+        }
+
+        // Candles are prices; scale them to market caps with the implied supply
+        let kind = self.target_kind;
+        let scale = match kind {
+            TargetKind::MarketCap => self.market_cap / self.current_price,
+            TargetKind::Price => 1.0,
+        };
+        let high = gap.iter().map(|c| c.high).fold(f64::MIN, f64::max) * scale;
+        let low = gap.iter().map(|c| c.low).fold(f64::MAX, f64::min) * scale;
+
+        let missed: Vec<f64> = self.targets[self.targets_hit.min(self.targets.len())..]
+            .iter()
+            .copied()
+            .filter(|level| *level <= high)
+            .collect();
+        let crossed = match missed.last() {
+            Some(level) if Some(level) == self.targets.last() => " (target crossed)",
+            Some(_) => " (milestone crossed)",
+            None => "",
+        };
+        self.add_log(format!(
+            "[{}] ⏪ While paused (since {}): high {}, low {}{}",
+            now,
+            from,
+            kind.format(high),
+            kind.format(low),
+            crossed
+        ));
+        if let Some(level) = missed.last() {
+            let message = format!(
+                "{} crossed {} while paused (high {}, now {})",
+                self.token_symbol,
+                kind.format(*level),
+                kind.format(high),
+                kind.format(self.tracked_value())
+            );
+            self.raise_alert(
+                Severity::Warning,
+                "catch_up",
+                format!("[{}] 🚨 {}", now, message),
+                message,
+            );
+        }
+    }
+
+    /// Alert once when no fetch has succeeded for --stale-after intervals, so a
+    /// silent API outage can't hide a pump. Paused polling doesn't count.
+    pub fn check_stale(&mut self) {
+        let Some(intervals) = self.stale_after else {
+            return;
+        };
+        if self.polling_paused() || self.modal_open {
+            self.fresh_at = Instant::now();
+            return;
+        }
+        let limit = Duration::from_secs(self.effective_interval() * intervals as u64);
+        if self.stale_fired || self.fresh_at.elapsed() < limit {
+            return;
+        }
+        self.stale_fired = true;
+        let message = format!(
+            "{} data stale: no successful fetch for {} ({} intervals)",
+            self.token_symbol,
+            format_wait(self.fresh_at.elapsed()),
+            intervals
+        );
+        let now = Local::now().format("%H:%M:%S").to_string();
+        let severity = if self.stale_alarm { Severity::Critical } else { Severity::Warning };
+        self.raise_alert(severity, "stale", format!("[{}] 🚨 {}", now, message), message);
+    }
+
+    /// A fetch succeeded: reset the failure streak and leave offline mode
+    pub fn mark_online(&mut self) {
+        self.consecutive_failures = 0;
+        if self.stale_fired {
+            self.stale_fired = false;
+            self.add_log(format!(
+                "[{}] 📡 Data fresh again after {}",
+                Local::now().format("%H:%M:%S"),
+                format_wait(self.fresh_at.elapsed())
+            ));
+        }
+        self.fresh_at = Instant::now();
+        if self.offline {
+            self.offline = false;
+            let now = Local::now().format("%H:%M:%S").to_string();
+            self.add_log(format!("[{}] 📡 Network restored", now));
+        }
+    }
+
+    /// Progress toward the next target, in percent
+    pub fn progress(&self) -> f64 {
+        let target = self.next_target();
+        if target <= 0.0 {
+            return 0.0;
+        }
+        (self.tracked_value() / target * 100.0).min(100.0)
+    }
+
+    /// Percentage of the way to --fdv-target, capped at 100
+    pub fn fdv_progress(&self) -> Option<f64> {
+        let target = self.fdv_target.filter(|t| *t > 0.0)?;
+        Some((self.fdv / target * 100.0).min(100.0))
+    }
+}
+
+/// Moon phase for a progress alert, waxing toward the full moon at the target
+fn progress_emoji(percent: f64) -> &'static str {
+    if percent < 25.0 {
+        "🌑"
+    } else if percent < 50.0 {
+        "🌒"
+    } else if percent < 75.0 {
+        "🌓"
+    } else if percent < 90.0 {
+        "🌔"
+    } else {
+        "🌕"
+    }
+}
+
+/// A wait as "45s", "12m" or "2h 05m"
+fn format_wait(wait: Duration) -> String {
+    let secs = wait.as_secs();
+    if secs < 120 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h {:02}m", secs / 3600, secs % 3600 / 60)
     }
 }
@@ -0,0 +1,108 @@
+//! All-time highs per pair, each in a small file of its own next to the config
+//! (`<config dir>/ath/<chain>-<pair>.json`), so recording one never rewrites
+//! config.json and instances watching other pairs can't overwrite it.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+
+/// Least time between writes of a pair's high while it keeps climbing
+pub const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The highest market cap mooncap has fetched for a pair
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AllTimeHigh {
+    pub pair: String,
+    pub chain: String,
+    pub market_cap: f64,
+    /// Local time it was seen
+    pub at: String,
+}
+
+/// Where the highs are kept; the default keeps them in memory only
+#[derive(Debug, Default, Clone)]
+pub struct HighStore {
+    dir: Option<PathBuf>,
+}
+
+impl HighStore {
+    /// The store beside the config file
+    pub fn open() -> Self {
+        let dir = config::config_path().parent().map(|d| d.join("ath"));
+        Self { dir }
+    }
+
+    fn path(&self, chain: &str, pair: &str) -> Option<PathBuf> {
+        let safe: String = pair
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+            .collect();
+        let name = format!("{}-{}.json", chain.to_lowercase(), safe);
+        self.dir.as_ref().map(|dir| dir.join(name))
+    }
+
+    /// The recorded high of a pair, if any
+    pub fn load(&self, chain: &str, pair: &str) -> Option<AllTimeHigh> {
+        let json = fs::read_to_string(self.path(chain, pair)?).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Record `high`, unless the file already holds a higher one (another
+    /// instance on the same pair got there first). Written beside the old file
+    /// and renamed over it, like the config.
+    pub fn save(&self, high: &AllTimeHigh) -> Result<(), String> {
+        let Some(path) = self.path(&high.chain, &high.pair) else {
+            return Ok(());
+        };
+        if self
+            .load(&high.chain, &high.pair)
+            .is_some_and(|recorded| recorded.market_cap >= high.market_cap)
+        {
+            return Ok(());
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Failed to create ATH dir: {}", e))?;
+        }
+        let json = serde_json::to_string_pretty(high)
+            .map_err(|e| format!("Failed to serialize ATH: {}", e))?;
+        let temp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        fs::write(&temp, json).map_err(|e| format!("Failed to write ATH: {}", e))?;
+        fs::rename(&temp, &path).map_err(|e| {
+            let _ = fs::remove_file(&temp);
+            format!("Failed to replace ATH: {}", e)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn high(market_cap: f64) -> AllTimeHigh {
+        AllTimeHigh {
+            pair: "ath-pair".to_string(),
+            chain: "mockchain".to_string(),
+            market_cap,
+            at: "2026-01-15 12:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn a_lower_high_never_replaces_a_higher_one() {
+        let dir = std::env::temp_dir().join(format!("mooncap-ath-{}", std::process::id()));
+        let store = HighStore {
+            dir: Some(dir.clone()),
+        };
+        store.save(&high(90_000.0)).unwrap();
+        // Another instance that saw less of the run-up saves later
+        store.save(&high(70_000.0)).unwrap();
+        let recorded = store.load("mockchain", "ath-pair").map(|h| h.market_cap);
+        let _ = fs::remove_dir_all(dir);
+        assert_eq!(recorded, Some(90_000.0));
+    }
+}
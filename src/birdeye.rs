@@ -0,0 +1,141 @@
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::api;
+use crate::endpoints::{self, Api};
+use crate::secrets;
+
+const BASE_URL: &str = "https://public-api.birdeye.so";
+
+/// Environment variable holding the Birdeye API key (overrides the config file)
+pub const API_KEY_ENV: &str = "BIRDEYE_API_KEY";
+
+/// Number of largest holders summed for the concentration figure
+pub const TOP_HOLDERS: usize = 10;
+
+#[derive(Debug, Deserialize)]
+struct Envelope<T> {
+    data: Option<T>,
+    #[serde(default)]
+    success: bool,
+    message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenOverview {
+    price: Option<f64>,
+    holder: Option<u64>,
+    supply: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HolderPage {
+    #[serde(default)]
+    items: Vec<Holder>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Holder {
+    ui_amount: Option<f64>,
+}
+
+/// Holder data for the Birdeye panel
+#[derive(Debug, Clone, Default)]
+pub struct BirdeyeView {
+    pub price: f64,
+    pub holders: Option<u64>,
+    /// Share of supply held by the largest wallets, in percent
+    pub top_percent: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// Keyring entry holding the Birdeye API key (`mooncap secrets set birdeye-api-key`)
+pub const API_KEY_SECRET: &str = "birdeye-api-key";
+
+/// Resolve the API key: `BIRDEYE_API_KEY` first, then the keyring, then the config file
+pub fn api_key(configured: Option<&str>) -> Option<String> {
+    std::env::var(API_KEY_ENV)
+        .ok()
+        .filter(|k| !k.trim().is_empty())
+        .or_else(|| secrets::get(API_KEY_SECRET))
+        .or_else(|| configured.and_then(secrets::resolve))
+        .filter(|k| !k.trim().is_empty())
+}
+
+/// Birdeye's richer data is only used for Solana tokens
+pub fn supports_chain(chain: &str) -> bool {
+    chain.eq_ignore_ascii_case("solana")
+}
+
+async fn get<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    api_key: &str,
+    path: &str,
+) -> Result<T, String> {
+    let url = format!("{}{}", endpoints::base_url(Api::Birdeye, BASE_URL), path);
+    let response = endpoints::get(client, Api::Birdeye, &url)
+        .header("X-API-KEY", api_key)
+        .header("x-chain", "solana")
+        .header("Accept", "application/json")
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(api::describe_http_error(status, &body));
+    }
+
+    let envelope: Envelope<T> = response
+        .json()
+        .await
+        .map_err(|e| format!("JSON parse error: {}", e))?;
+    match envelope.data {
+        Some(data) if envelope.success => Ok(data),
+        _ => Err(envelope
+            .message
+            .unwrap_or_else(|| "Birdeye returned no data".to_string())),
+    }
+}
+
+/// Fetch live price, holder count and top-holder concentration for a token
+pub async fn fetch_view(
+    client: &reqwest::Client,
+    api_key: &str,
+    token_address: &str,
+) -> Result<BirdeyeView, String> {
+    let overview: TokenOverview = get(
+        client,
+        api_key,
+        &format!("/defi/token_overview?address={}", token_address),
+    )
+    .await?;
+
+    // Concentration is a nice-to-have; keep the overview if it fails
+    let holders: Result<HolderPage, String> = get(
+        client,
+        api_key,
+        &format!(
+            "/defi/v3/token/holder?address={}&offset=0&limit={}",
+            token_address, TOP_HOLDERS
+        ),
+    )
+    .await;
+
+    let top_percent = match (&holders, overview.supply) {
+        (Ok(page), Some(supply)) if supply > 0.0 => {
+            let held: f64 = page.items.iter().filter_map(|h| h.ui_amount).sum();
+            Some(held / supply * 100.0)
+        }
+        _ => None,
+    };
+
+    Ok(BirdeyeView {
+        price: overview.price.unwrap_or(0.0),
+        holders: overview.holder,
+        top_percent,
+        error: holders.err(),
+    })
+}
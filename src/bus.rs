@@ -0,0 +1,180 @@
+#![cfg_attr(not(feature = "redis"), allow(dead_code))]
+
+use chrono::Local;
+use serde::Serialize;
+
+use crate::api::PairData;
+use crate::config::RedisConfig;
+use crate::mqtt::{MqttBus, MqttConfig};
+use crate::notify::Severity;
+
+/// Something worth telling other systems about
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BusEvent {
+    Update {
+        at: String,
+        chain: String,
+        pair: String,
+        symbol: String,
+        price_usd: f64,
+        market_cap: f64,
+        liquidity_usd: f64,
+        volume_24h: f64,
+    },
+    Alert {
+        at: String,
+        chain: String,
+        pair: String,
+        symbol: String,
+        severity: Severity,
+        message: String,
+    },
+}
+
+impl BusEvent {
+    pub fn update(chain: &str, pair: &str, data: &PairData) -> Self {
+        BusEvent::Update {
+            at: Local::now().to_rfc3339(),
+            chain: chain.to_string(),
+            pair: pair.to_string(),
+            symbol: data.base_symbol().to_string(),
+            price_usd: data.price_usd_f64(),
+            market_cap: data.market_cap_usd(),
+            liquidity_usd: data.liquidity_usd(),
+            volume_24h: data.volume_24h_usd(),
+        }
+    }
+
+    pub fn alert(chain: &str, pair: &str, symbol: &str, severity: Severity, message: &str) -> Self {
+        BusEvent::Alert {
+            at: Local::now().to_rfc3339(),
+            chain: chain.to_string(),
+            pair: pair.to_string(),
+            symbol: symbol.to_string(),
+            severity,
+            message: message.to_string(),
+        }
+    }
+
+    /// Channel (or stream key) suffix
+    fn topic(&self) -> &'static str {
+        match self {
+            BusEvent::Update { .. } => "updates",
+            BusEvent::Alert { .. } => "alerts",
+        }
+    }
+}
+
+/// Publishes events to Redis and to an MQTT broker, whichever are configured
+pub struct EventBus {
+    redis: Option<RedisBus>,
+    mqtt: Option<MqttBus>,
+}
+
+impl EventBus {
+    /// Connect to the configured Redis and MQTT broker. Returns None when
+    /// neither is configured or reachable, and why each that is configured
+    /// couldn't be used.
+    pub async fn connect(redis: &RedisConfig, mqtt: &MqttConfig) -> (Option<Self>, Vec<String>) {
+        let mut errors = Vec::new();
+        let redis = RedisBus::connect(redis).await.unwrap_or_else(|e| {
+            errors.push(format!("Redis event bus disabled: {}", e));
+            None
+        });
+        let mqtt = MqttBus::connect(mqtt).await.unwrap_or_else(|e| {
+            errors.push(format!("MQTT publishing disabled: {}", e));
+            None
+        });
+        let bus = (redis.is_some() || mqtt.is_some()).then_some(Self { redis, mqtt });
+        (bus, errors)
+    }
+
+    /// Publish to every connected backend; failures are joined into one error
+    pub async fn publish(&self, event: &BusEvent) -> Result<(), String> {
+        let mut errors = Vec::new();
+        if let Some(ref redis) = self.redis {
+            if let Err(e) = redis.publish(event).await {
+                errors.push(e);
+            }
+        }
+        if let Some(ref mqtt) = self.mqtt {
+            if let Err(e) = mqtt.publish(event).await {
+                errors.push(e);
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+/// Publishes events to Redis as `<prefix>:updates` / `<prefix>:alerts`, either
+/// with PUBLISH or, with `streams`, XADD (field `event` holds the JSON)
+struct RedisBus {
+    #[cfg(feature = "redis")]
+    conn: redis::aio::ConnectionManager,
+    prefix: String,
+    streams: bool,
+}
+
+impl RedisBus {
+    /// Connect to the configured Redis, if any
+    async fn connect(config: &RedisConfig) -> Result<Option<Self>, String> {
+        let Some(ref url) = config.url else {
+            return Ok(None);
+        };
+
+        #[cfg(feature = "redis")]
+        {
+            let client = redis::Client::open(url.as_str())
+                .map_err(|e| format!("Invalid Redis URL: {}", e))?;
+            let conn = redis::aio::ConnectionManager::new(client)
+                .await
+                .map_err(|e| format!("Redis connect failed: {}", e))?;
+            Ok(Some(Self {
+                conn,
+                prefix: config.prefix.clone(),
+                streams: config.streams,
+            }))
+        }
+
+        #[cfg(not(feature = "redis"))]
+        {
+            let _ = url;
+            Err("redis.url is set but mooncap was built without the 'redis' feature".to_string())
+        }
+    }
+
+    async fn publish(&self, event: &BusEvent) -> Result<(), String> {
+        let key = format!("{}:{}", self.prefix, event.topic());
+        let payload = serde_json::to_string(event)
+            .map_err(|e| format!("Failed to serialize event: {}", e))?;
+
+        #[cfg(feature = "redis")]
+        {
+            let mut conn = self.conn.clone();
+            let cmd = if self.streams {
+                let mut cmd = redis::cmd("XADD");
+                cmd.arg(&key).arg("*").arg("event").arg(&payload);
+                cmd
+            } else {
+                let mut cmd = redis::cmd("PUBLISH");
+                cmd.arg(&key).arg(&payload);
+                cmd
+            };
+            cmd.query_async::<redis::Value>(&mut conn)
+                .await
+                .map(|_| ())
+                .map_err(|e| format!("Redis publish to {} failed: {}", key, e))
+        }
+
+        #[cfg(not(feature = "redis"))]
+        {
+            let _ = (key, payload);
+            Ok(())
+        }
+    }
+}
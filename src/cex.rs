@@ -0,0 +1,120 @@
+use serde::Deserialize;
+
+use crate::api;
+
+/// A centralized exchange whose spot ticker is compared with the DEX price
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exchange {
+    Binance,
+    Coinbase,
+}
+
+impl Exchange {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Exchange::Binance => "Binance",
+            Exchange::Coinbase => "Coinbase",
+        }
+    }
+
+    /// Parse an exchange name (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "binance" => Some(Exchange::Binance),
+            "coinbase" => Some(Exchange::Coinbase),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Ticker {
+    price: String,
+}
+
+/// Cross-check state: which market to compare against and the latest result
+#[derive(Debug, Clone)]
+pub struct CexCheck {
+    pub exchange: Exchange,
+    /// Base asset symbol override; defaults to the token's symbol
+    pub symbol: Option<String>,
+    pub price: Option<f64>,
+    pub error: Option<String>,
+    /// Alert when |DEX - CEX| / CEX reaches this percent
+    pub alert_percent: Option<f64>,
+    pub alert_firing: bool,
+}
+
+impl CexCheck {
+    pub fn new(exchange: Exchange, symbol: Option<String>, alert_percent: Option<f64>) -> Self {
+        Self {
+            exchange,
+            symbol,
+            price: None,
+            error: None,
+            alert_percent,
+            alert_firing: false,
+        }
+    }
+
+    /// DEX price premium over the CEX price, in percent
+    pub fn spread(&self, dex_price: f64) -> Option<f64> {
+        match self.price {
+            Some(cex) if cex > 0.0 && dex_price > 0.0 => Some((dex_price - cex) / cex * 100.0),
+            _ => None,
+        }
+    }
+
+    /// Forget the previous token's result (the check itself stays configured)
+    pub fn reset(&mut self) {
+        self.price = None;
+        self.error = None;
+        self.alert_firing = false;
+    }
+}
+
+/// Fetch the USD(T) spot price of `symbol` on `exchange`
+pub async fn fetch_price(
+    client: &reqwest::Client,
+    exchange: Exchange,
+    symbol: &str,
+) -> Result<f64, String> {
+    let symbol = symbol.trim().to_uppercase();
+    let url = match exchange {
+        Exchange::Binance => format!(
+            "https://api.binance.com/api/v3/ticker/price?symbol={}USDT",
+            symbol
+        ),
+        Exchange::Coinbase => format!(
+            "https://api.exchange.coinbase.com/products/{}-USD/ticker",
+            symbol
+        ),
+    };
+
+    let response = client
+        .get(&url)
+        .header("Accept", "application/json")
+        .header("User-Agent", "mooncap")
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        if status.as_u16() == 400 || status.as_u16() == 404 {
+            return Err(format!("{} is not listed on {}", symbol, exchange.name()));
+        }
+        return Err(api::describe_http_error(status, &body));
+    }
+
+    let ticker: Ticker = response
+        .json()
+        .await
+        .map_err(|e| format!("JSON parse error: {}", e))?;
+    ticker
+        .price
+        .parse()
+        .map_err(|_| format!("{} returned an invalid price", exchange.name()))
+}
@@ -0,0 +1,153 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::api::{DataSource, PairData, Provider, Target};
+
+/// Fault injection settings for `--chaos` (developer mode)
+#[derive(Debug, Clone)]
+pub struct ChaosConfig {
+    /// Extra latency range added to every fetch, in milliseconds
+    pub latency_ms: (u64, u64),
+    /// Probability a fetch fails outright
+    pub fail: f64,
+    /// Probability a fetch returns malformed data
+    pub malformed: f64,
+    pub seed: u64,
+}
+
+struct Chaos {
+    config: ChaosConfig,
+    /// xorshift state, shared by every source so runs replay with the same seed
+    state: AtomicU64,
+}
+
+static CHAOS: OnceLock<Chaos> = OnceLock::new();
+
+/// Parse a spec like `latency=200-2000,fail=0.2,malformed=0.1,seed=42`
+pub fn parse_spec(spec: &str) -> Result<ChaosConfig, String> {
+    let mut config = ChaosConfig {
+        latency_ms: (0, 0),
+        fail: 0.0,
+        malformed: 0.0,
+        seed: 1,
+    };
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, value) = part
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid chaos option '{}' (expected key=value)", part))?;
+        let bad = || format!("Invalid value for chaos option '{}': {}", key, value);
+        match key {
+            "latency" => {
+                config.latency_ms = match value.split_once('-') {
+                    Some((min, max)) => (
+                        min.parse().map_err(|_| bad())?,
+                        max.parse().map_err(|_| bad())?,
+                    ),
+                    None => {
+                        let ms = value.parse().map_err(|_| bad())?;
+                        (ms, ms)
+                    }
+                };
+                if config.latency_ms.0 > config.latency_ms.1 {
+                    return Err(bad());
+                }
+            }
+            "fail" | "malformed" => {
+                let p: f64 = value.parse().map_err(|_| bad())?;
+                if !(0.0..=1.0).contains(&p) {
+                    return Err(bad());
+                }
+                if key == "fail" {
+                    config.fail = p;
+                } else {
+                    config.malformed = p;
+                }
+            }
+            "seed" => config.seed = value.parse().map_err(|_| bad())?,
+            _ => return Err(format!("Unknown chaos option '{}'", key)),
+        }
+    }
+    Ok(config)
+}
+
+/// Turn on fault injection for every data source in this process
+pub fn enable(config: ChaosConfig) {
+    let seed = config.seed.max(1);
+    let _ = CHAOS.set(Chaos {
+        config,
+        state: AtomicU64::new(seed),
+    });
+}
+
+pub fn enabled() -> bool {
+    CHAOS.get().is_some()
+}
+
+impl Chaos {
+    /// Next pseudo-random number in [0, 1)
+    fn next(&self) -> f64 {
+        let mut x = self.state.load(Ordering::Relaxed);
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state.store(x, Ordering::Relaxed);
+        (x >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Wraps a real source and injects latency, failures and malformed data
+pub struct ChaosSource {
+    inner: Box<dyn DataSource>,
+}
+
+/// Wrap `source` when chaos mode is on
+pub fn wrap(source: Box<dyn DataSource>) -> Box<dyn DataSource> {
+    if enabled() {
+        Box::new(ChaosSource { inner: source })
+    } else {
+        source
+    }
+}
+
+#[async_trait]
+impl DataSource for ChaosSource {
+    fn provider(&self) -> Provider {
+        self.inner.provider()
+    }
+
+    async fn fetch(&self, target: &Target<'_>) -> Result<PairData, String> {
+        let Some(chaos) = CHAOS.get() else {
+            return self.inner.fetch(target).await;
+        };
+        let config = &chaos.config;
+
+        let (min, max) = config.latency_ms;
+        let delay = min + ((max - min) as f64 * chaos.next()) as u64;
+        if delay > 0 {
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+        }
+
+        if chaos.next() < config.fail {
+            return Err("HTTP 503 Service Unavailable (chaos: injected failure)".to_string());
+        }
+
+        let roll = chaos.next();
+        let mut data = self.inner.fetch(target).await?;
+        if roll < config.malformed {
+            // Alternate between a parse failure and structurally broken data
+            if chaos.next() < 0.5 {
+                return Err(
+                    "JSON parse error: expected value at line 1 column 1 (chaos: injected)"
+                        .to_string(),
+                );
+            }
+            data.price_usd = Some("NaN".to_string());
+            data.market_cap = None;
+            data.fdv = None;
+        }
+        Ok(data)
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::api::{self, DataSource, PairData, PriceChange, Provider, Target, Token, Volume};
+use crate::endpoints::{self, Api};
+
+const BASE_URL: &str = "https://api.coingecko.com/api/v3";
+
+#[derive(Debug, Deserialize)]
+struct CoinMarket {
+    id: String,
+    symbol: String,
+    name: String,
+    current_price: Option<f64>,
+    market_cap: Option<f64>,
+    fully_diluted_valuation: Option<f64>,
+    total_volume: Option<f64>,
+    price_change_percentage_1h_in_currency: Option<f64>,
+    price_change_percentage_24h_in_currency: Option<f64>,
+}
+
+/// Centralized exchanges the listing watcher looks for, by CoinGecko market id
+const LISTING_EXCHANGES: &[&str] = &[
+    "binance",
+    "gdax",
+    "kraken",
+    "okex",
+    "bybit_spot",
+    "kucoin",
+    "gate",
+    "mxc",
+    "bitget",
+    "huobi",
+    "upbit",
+    "crypto_com",
+    "bitfinex",
+    "bitstamp",
+];
+
+#[derive(Debug, Deserialize)]
+struct CoinTickers {
+    #[serde(default)]
+    tickers: Vec<CoinTicker>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoinTicker {
+    market: TickerMarket,
+}
+
+#[derive(Debug, Deserialize)]
+struct TickerMarket {
+    name: String,
+    identifier: String,
+}
+
+/// One coin in the header ticker strip
+#[derive(Debug, Clone)]
+pub struct TickerQuote {
+    pub symbol: String,
+    pub price: f64,
+    pub change_24h: Option<f64>,
+}
+
+/// CoinGecko markets API; the target address is a coin id
+pub struct CoinGecko {
+    client: reqwest::Client,
+}
+
+impl CoinGecko {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl DataSource for CoinGecko {
+    fn provider(&self) -> Provider {
+        Provider::CoinGecko
+    }
+
+    async fn fetch(&self, target: &Target<'_>) -> Result<PairData, String> {
+        fetch_coin(&self.client, target.address).await
+    }
+}
+
+/// `/coins/markets` for one or more comma-separated coin ids
+async fn fetch_markets(
+    client: &reqwest::Client,
+    coin_ids: &str,
+) -> Result<Vec<CoinMarket>, String> {
+    let url = format!(
+        "{}/coins/markets?vs_currency=usd&ids={}&price_change_percentage=1h,24h",
+        endpoints::base_url(Api::CoinGecko, BASE_URL),
+        coin_ids.trim().to_lowercase()
+    );
+
+    let response = endpoints::get(client, Api::CoinGecko, &url)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(api::describe_http_error(status, &body));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("JSON parse error: {}", e))
+}
+
+/// Prices of the majors (or any coin ids) for the ticker strip, in the given order
+pub async fn fetch_ticker(
+    client: &reqwest::Client,
+    coin_ids: &[String],
+) -> Result<Vec<TickerQuote>, String> {
+    let coins = fetch_markets(client, &coin_ids.join(",")).await?;
+    Ok(coin_ids
+        .iter()
+        .filter_map(|id| coins.iter().find(|c| c.id.eq_ignore_ascii_case(id.trim())))
+        .filter_map(|c| {
+            Some(TickerQuote {
+                symbol: c.symbol.to_uppercase(),
+                price: c.current_price?,
+                change_24h: c.price_change_percentage_24h_in_currency,
+            })
+        })
+        .collect())
+}
+
+/// Fetch a CoinGecko-listed coin by id (e.g. `bitcoin`, `ethereum`, `solana`)
+/// and map it into `PairData` so the rest of the app treats it like a pair.
+/// CoinGecko has no pool, so liquidity and transaction counts are absent.
+async fn fetch_coin(client: &reqwest::Client, coin_id: &str) -> Result<PairData, String> {
+    let coins = fetch_markets(client, coin_id).await?;
+
+    let coin = coins.into_iter().next().ok_or_else(|| {
+        format!(
+            "No CoinGecko coin with id '{}' — use the id from the coin's URL (e.g. 'bitcoin')",
+            coin_id
+        )
+    })?;
+
+    Ok(PairData {
+        chain_id: Some("coingecko".to_string()),
+        dex_id: None,
+        pair_address: Some(coin.id.clone()),
+        base_token: Some(Token {
+            address: Some(coin.id),
+            name: Some(coin.name),
+            symbol: Some(coin.symbol.to_uppercase()),
+        }),
+        quote_token: None,
+        price_native: None,
+        price_usd: coin.current_price.map(|p| p.to_string()),
+        fdv: coin.fully_diluted_valuation,
+        market_cap: coin.market_cap,
+        txns: None,
+        volume: Some(Volume {
+            h24: coin.total_volume,
+            h6: None,
+            h1: None,
+            m5: None,
+        }),
+        price_change: Some(PriceChange {
+            m5: None,
+            h1: coin.price_change_percentage_1h_in_currency,
+            h6: None,
+            h24: coin.price_change_percentage_24h_in_currency,
+        }),
+        liquidity: None,
+        pair_created_at: None,
+        info: None,
+        bonding_curve: None,
+    })
+}
+
+/// CoinGecko's asset platform for a DexScreener chain id
+fn asset_platform(chain: &str) -> Option<&'static str> {
+    match chain.to_lowercase().as_str() {
+        "solana" => Some("solana"),
+        "ethereum" => Some("ethereum"),
+        "bsc" => Some("binance-smart-chain"),
+        "base" => Some("base"),
+        "arbitrum" => Some("arbitrum-one"),
+        "polygon" => Some("polygon-pos"),
+        "avalanche" => Some("avalanche"),
+        "optimism" => Some("optimistic-ethereum"),
+        "sui" => Some("sui"),
+        "ton" => Some("the-open-network"),
+        _ => None,
+    }
+}
+
+/// Major centralized exchanges trading the token, by display name. A token
+/// CoinGecko doesn't know yet has no listings rather than an error.
+pub async fn fetch_listings(
+    client: &reqwest::Client,
+    chain: &str,
+    token: &str,
+) -> Result<BTreeSet<String>, String> {
+    let base = endpoints::base_url(Api::CoinGecko, BASE_URL);
+    let url = if chain == "coingecko" {
+        format!(
+            "{}/coins/{}?localization=false&market_data=false&community_data=false",
+            base,
+            token.trim().to_lowercase()
+        )
+    } else {
+        let platform = asset_platform(chain)
+            .ok_or_else(|| format!("CoinGecko has no platform for chain '{}'", chain))?;
+        format!("{}/coins/{}/contract/{}", base, platform, token.trim())
+    };
+
+    let response = endpoints::get(client, Api::CoinGecko, &url)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if status.as_u16() == 404 {
+        return Ok(BTreeSet::new());
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(api::describe_http_error(status, &body));
+    }
+
+    let coin: CoinTickers = response
+        .json()
+        .await
+        .map_err(|e| format!("JSON parse error: {}", e))?;
+    Ok(coin
+        .tickers
+        .into_iter()
+        .filter(|t| LISTING_EXCHANGES.contains(&t.market.identifier.as_str()))
+        .map(|t| t.market.name)
+        .collect())
+}
@@ -0,0 +1,88 @@
+use crate::api::PairData;
+use crate::app::MAX_HISTORY;
+
+/// A second pair rendered next to the monitored one in compare mode
+#[derive(Debug, Clone)]
+pub struct CompareState {
+    pub pair: String,
+    pub chain: String,
+    pub data: Option<PairData>,
+    pub history: Vec<u64>,
+    pub error: Option<String>,
+}
+
+impl CompareState {
+    pub fn new(pair: String, chain: String) -> Self {
+        Self {
+            pair,
+            chain,
+            data: None,
+            history: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn update(&mut self, data: PairData) {
+        self.history.push(data.market_cap_usd() as u64);
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+        self.data = Some(data);
+        self.error = None;
+    }
+
+    pub fn symbol(&self) -> String {
+        match self.data {
+            Some(ref d) => d.base_symbol().to_string(),
+            None => self.pair.chars().take(8).collect(),
+        }
+    }
+}
+
+/// Convert two market cap series into sparkline data on a shared percentage
+/// scale. Each series is expressed as % change from its own first point (over
+/// the most recent points both series have), then shifted so the lowest value
+/// across both is zero. Returns (left, right, shared max).
+pub fn normalize_pair(left: &[u64], right: &[u64]) -> (Vec<u64>, Vec<u64>, u64) {
+    let len = left.len().min(right.len());
+    let pct = |series: &[u64]| -> Vec<f64> {
+        let tail = &series[series.len() - len..];
+        let base = tail.first().copied().unwrap_or(0) as f64;
+        tail.iter()
+            .map(|v| {
+                if base > 0.0 {
+                    (*v as f64 / base - 1.0) * 100.0
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    };
+    let (l, r) = (pct(left), pct(right));
+
+    let min = l.iter().chain(r.iter()).copied().fold(f64::MAX, f64::min);
+    let max = l.iter().chain(r.iter()).copied().fold(f64::MIN, f64::max);
+    if len == 0 {
+        return (Vec::new(), Vec::new(), 1);
+    }
+
+    // Hundredths of a percent keep small moves visible
+    let scale = |series: Vec<f64>| -> Vec<u64> {
+        series.iter().map(|p| ((p - min) * 100.0) as u64 + 1).collect()
+    };
+    let shared_max = ((max - min) * 100.0) as u64 + 1;
+    (scale(l), scale(r), shared_max)
+}
+
+/// Percent change across the last `len` points of a series
+pub fn window_change(series: &[u64], len: usize) -> Option<f64> {
+    if len < 2 || series.len() < len {
+        return None;
+    }
+    let first = series[series.len() - len] as f64;
+    let last = *series.last()? as f64;
+    if first <= 0.0 {
+        return None;
+    }
+    Some((last / first - 1.0) * 100.0)
+}
@@ -0,0 +1,519 @@
+//! Alert conditions written as small expressions over the live pair data,
+//! e.g. `mcap > 1_000_000 && liquidity < 50_000` or `change_1h > 25`.
+//!
+//! ```text
+//! condition  = and ("||" and)*
+//! and        = term ("&&" term)*
+//! term       = "(" condition ")" | operand op operand
+//! op         = ">" | ">=" | "<" | "<=" | "==" | "!="
+//! operand    = variable | number
+//! ```
+//!
+//! `and` / `or` work as well as `&&` / `||`. Numbers may be negative
+//! (`change_1h < -10`) and may use `_` separators, a `$` prefix and a `K`, `M`
+//! or `B` suffix; a trailing `%` is ignored.
+
+use crate::alerts::ChangeWindow;
+use crate::api::PairData;
+
+/// A value a condition can refer to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variable {
+    MarketCap,
+    Fdv,
+    Price,
+    Liquidity,
+    Volume,
+    Change(ChangeWindow),
+    Buys,
+    Sells,
+    /// Percent of the way to the highest target, e.g. 200 at twice the target
+    Progress,
+}
+
+impl Variable {
+    /// Names accepted in conditions, canonical one first for each variable
+    const NAMES: [(&'static str, Variable); 17] = [
+        ("mcap", Variable::MarketCap),
+        ("market_cap", Variable::MarketCap),
+        ("fdv", Variable::Fdv),
+        ("price", Variable::Price),
+        ("liquidity", Variable::Liquidity),
+        ("liq", Variable::Liquidity),
+        ("volume", Variable::Volume),
+        ("volume_24h", Variable::Volume),
+        ("change_5m", Variable::Change(ChangeWindow::M5)),
+        ("change_1h", Variable::Change(ChangeWindow::H1)),
+        ("change_6h", Variable::Change(ChangeWindow::H6)),
+        ("change_24h", Variable::Change(ChangeWindow::H24)),
+        ("buys", Variable::Buys),
+        ("sells", Variable::Sells),
+        ("buys_24h", Variable::Buys),
+        ("sells_24h", Variable::Sells),
+        ("progress", Variable::Progress),
+    ];
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::NAMES.iter().find(|(n, _)| *n == name).map(|(_, v)| *v)
+    }
+
+    pub fn name(&self) -> &'static str {
+        Self::NAMES
+            .iter()
+            .find(|(_, v)| v == self)
+            .map(|(n, _)| *n)
+            .unwrap_or("?")
+    }
+
+    /// The value as the pair data reports it; None when the provider that
+    /// answered doesn't, and always for progress, which depends on the targets
+    pub fn reported(&self, data: &PairData) -> Option<f64> {
+        match self {
+            Variable::MarketCap => Some(data.market_cap_usd()),
+            Variable::Fdv => data.fdv,
+            Variable::Price => Some(data.price_usd_f64()),
+            Variable::Liquidity => data.liquidity.as_ref().and_then(|l| l.usd),
+            Variable::Volume => data.volume.as_ref().and_then(|v| v.h24),
+            Variable::Change(window) => data.price_change.as_ref().and_then(|c| window.of(c)),
+            Variable::Buys => data.txns.as_ref().and_then(|t| Some(t.h24.as_ref()?.buys? as f64)),
+            Variable::Sells => data.txns.as_ref().and_then(|t| Some(t.h24.as_ref()?.sells? as f64)),
+            Variable::Progress => None,
+        }
+    }
+
+    /// The value as shown in an alert: dollars, a price, a percentage or a count
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            Variable::MarketCap | Variable::Fdv | Variable::Liquidity | Variable::Volume => {
+                format!("${:.0}", value)
+            }
+            Variable::Price => {
+                let text = format!("{:.8}", value);
+                format!("${}", text.trim_end_matches('0').trim_end_matches('.'))
+            }
+            Variable::Change(_) => format!("{:+.1}%", value),
+            Variable::Buys | Variable::Sells => format!("{:.0}", value),
+            Variable::Progress => format!("{:.0}%", value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl Op {
+    fn apply(&self, left: f64, right: f64) -> bool {
+        match self {
+            Op::Gt => left > right,
+            Op::Ge => left >= right,
+            Op::Lt => left < right,
+            Op::Le => left <= right,
+            Op::Eq => left == right,
+            Op::Ne => left != right,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operand {
+    Number(f64),
+    Var(Variable),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Compare(Operand, Op, Operand),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Comparisons with a value the current provider didn't report are false
+    fn holds(&self, value: &dyn Fn(Variable) -> Option<f64>) -> bool {
+        let operand = |o: &Operand| match o {
+            Operand::Number(n) => Some(*n),
+            Operand::Var(v) => value(*v),
+        };
+        match self {
+            Expr::Compare(left, op, right) => match (operand(left), operand(right)) {
+                (Some(l), Some(r)) => op.apply(l, r),
+                _ => false,
+            },
+            Expr::And(a, b) => a.holds(value) && b.holds(value),
+            Expr::Or(a, b) => a.holds(value) || b.holds(value),
+        }
+    }
+
+    fn collect_variables(&self, out: &mut Vec<Variable>) {
+        match self {
+            Expr::Compare(left, _, right) => {
+                for operand in [left, right] {
+                    if let Operand::Var(v) = operand {
+                        if !out.contains(v) {
+                            out.push(*v);
+                        }
+                    }
+                }
+            }
+            Expr::And(a, b) | Expr::Or(a, b) => {
+                a.collect_variables(out);
+                b.collect_variables(out);
+            }
+        }
+    }
+}
+
+/// A parsed alert condition, displayed as it was written
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    text: String,
+    expr: Expr,
+}
+
+impl Condition {
+    /// Whether the condition holds for the given variable values
+    pub fn holds(&self, value: impl Fn(Variable) -> Option<f64>) -> bool {
+        self.expr.holds(&value)
+    }
+
+    /// Variables the condition refers to, in order of appearance
+    pub fn variables(&self) -> Vec<Variable> {
+        let mut out = Vec::new();
+        self.expr.collect_variables(&mut out);
+        out
+    }
+}
+
+impl std::str::FromStr for Condition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            return Err("empty condition".to_string());
+        }
+        let mut parser = Parser {
+            tokens: tokenize(&text)?,
+            pos: 0,
+        };
+        let expr = parser.condition()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("unexpected {}", token.describe()));
+        }
+        Ok(Self { text, expr })
+    }
+}
+
+impl std::fmt::Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Op(Op),
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Number(n) => format!("number {}", n),
+            Token::Name(name) => format!("'{}'", name),
+            Token::Op(_) => "comparison".to_string(),
+            Token::And => "'&&'".to_string(),
+            Token::Or => "'||'".to_string(),
+            Token::Open => "'('".to_string(),
+            Token::Close => "')'".to_string(),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let token = match c {
+            ' ' => {
+                i += 1;
+                continue;
+            }
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '&' if next == Some('&') => Token::And,
+            '|' if next == Some('|') => Token::Or,
+            '>' | '<' | '=' | '!' => {
+                let (op, len) = match (c, next) {
+                    ('>', Some('=')) => (Op::Ge, 2),
+                    ('<', Some('=')) => (Op::Le, 2),
+                    ('=', Some('=')) => (Op::Eq, 2),
+                    ('!', Some('=')) => (Op::Ne, 2),
+                    ('>', _) => (Op::Gt, 1),
+                    ('<', _) => (Op::Lt, 1),
+                    _ => return Err(format!("'{}' isn't an operator (use ==, !=, <, >)", c)),
+                };
+                tokens.push(Token::Op(op));
+                i += len;
+                continue;
+            }
+            // Only literals can be negative, not values (`-mcap`)
+            c if starts_number(c)
+                || (c == '-' && !next.is_some_and(|n| n.is_ascii_alphabetic() || n == '_')) =>
+            {
+                let negative = c == '-';
+                if negative {
+                    i += 1;
+                    while chars.get(i) == Some(&' ') {
+                        i += 1;
+                    }
+                }
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || matches!(chars[i], '.' | '_' | '$'))
+                {
+                    i += 1;
+                }
+                let literal: String = chars[start..i].iter().collect();
+                if literal.is_empty() {
+                    return Err("expected a number after '-'".to_string());
+                }
+                let number = parse_number(&literal)?;
+                tokens.push(Token::Number(if negative { -number } else { number }));
+                if chars.get(i) == Some(&'%') {
+                    i += 1;
+                }
+                continue;
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect::<String>().to_lowercase();
+                tokens.push(match word.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    _ => Token::Name(word),
+                });
+                continue;
+            }
+            _ => return Err(format!("unexpected '{}'", c)),
+        };
+        i += if matches!(token, Token::And | Token::Or) { 2 } else { 1 };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn starts_number(c: char) -> bool {
+    c.is_ascii_digit() || c == '.' || c == '$'
+}
+
+/// `1_000_000`, `$50K`, `2.5m` or `0.0004`
+fn parse_number(literal: &str) -> Result<f64, String> {
+    let digits = literal.trim_start_matches('$').replace('_', "");
+    let (digits, scale) = match digits.chars().last().map(|c| c.to_ascii_lowercase()) {
+        Some('k') => (&digits[..digits.len() - 1], 1e3),
+        Some('m') => (&digits[..digits.len() - 1], 1e6),
+        Some('b') => (&digits[..digits.len() - 1], 1e9),
+        _ => (digits.as_str(), 1.0),
+    };
+    digits
+        .parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite())
+        .map(|n| n * scale)
+        .ok_or_else(|| format!("'{}' isn't a number", literal))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn condition(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.term()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.term()?));
+        }
+        Ok(expr)
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(&Token::Open) {
+            self.pos += 1;
+            let expr = self.condition()?;
+            return match self.next() {
+                Some(Token::Close) => Ok(expr),
+                _ => Err("missing ')'".to_string()),
+            };
+        }
+        let left = self.operand()?;
+        let op = match self.next() {
+            Some(Token::Op(op)) => op,
+            Some(token) => return Err(format!("expected a comparison before {}", token.describe())),
+            None => return Err("expected a comparison like mcap > 1M".to_string()),
+        };
+        let right = self.operand()?;
+        Ok(Expr::Compare(left, op, right))
+    }
+
+    fn operand(&mut self) -> Result<Operand, String> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Operand::Number(n)),
+            Some(Token::Name(name)) => Variable::parse(&name).map(Operand::Var).ok_or_else(|| {
+                let known: Vec<&str> = Variable::NAMES
+                    .iter()
+                    .filter(|(n, v)| v.name() == *n)
+                    .map(|(n, _)| *n)
+                    .collect();
+                format!("unknown value '{}' (known: {})", name, known.join(", "))
+            }),
+            Some(token) => Err(format!("expected a value, got {}", token.describe())),
+            None => Err("expected a value at the end".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Condition {
+        text.parse().unwrap_or_else(|e| panic!("{:?} didn't parse: {}", text, e))
+    }
+
+    fn error(text: &str) -> String {
+        text.parse::<Condition>().expect_err(text)
+    }
+
+    /// Values for a pair at $60K market cap, $5K liquidity, down 12% in the hour
+    fn sample(variable: Variable) -> Option<f64> {
+        match variable {
+            Variable::MarketCap => Some(60_000.0),
+            Variable::Liquidity => Some(5_000.0),
+            Variable::Change(ChangeWindow::H1) => Some(-12.0),
+            _ => None,
+        }
+    }
+
+    fn holds(text: &str) -> bool {
+        parse(text).holds(sample)
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_number("1_000_000"), Ok(1_000_000.0));
+        assert_eq!(parse_number("$50K"), Ok(50_000.0));
+        assert_eq!(parse_number("2.5m"), Ok(2_500_000.0));
+        assert_eq!(parse_number("0.0004"), Ok(0.0004));
+        assert!(holds("mcap == 60K") && holds("mcap >= $0.06M") && holds("liq < 5_001"));
+    }
+
+    #[test]
+    fn negative_literals() {
+        assert!(holds("change_1h < -10"));
+        assert!(holds("change_1h<-10%"));
+        assert!(holds("change_1h > - 12.5"));
+        assert!(!holds("change_1h < -15"));
+        assert!(holds("-$70K < mcap"));
+        assert_eq!(
+            parse("change_1h < -10").expr,
+            Expr::Compare(
+                Operand::Var(Variable::Change(ChangeWindow::H1)),
+                Op::Lt,
+                Operand::Number(-10.0)
+            )
+        );
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        // Read as `mcap > 1M || (liq < 10K && change_1h < -10)`
+        assert!(holds("mcap > 1M || liq < 10K && change_1h < -10"));
+        assert!(holds("mcap > 1M or liq < 10K and change_1h < -10"));
+        // Read as `(mcap > 1M && liq < 10K) || change_1h > 0`
+        assert!(!holds("mcap > 1M && liq < 10K || change_1h > 0"));
+    }
+
+    #[test]
+    fn parentheses_group() {
+        assert!(!holds("(mcap > 1M || liq < 10K) && change_1h > 0"));
+        assert!(holds("(mcap > 1M || liq < 10K) && (change_1h < 0)"));
+        assert!(holds("((mcap == 60K))"));
+    }
+
+    #[test]
+    fn missing_values_are_false() {
+        assert!(!holds("fdv > 0"));
+        assert!(!holds("fdv <= 0"));
+        assert!(holds("fdv > 0 || mcap > 0"));
+    }
+
+    #[test]
+    fn text_and_variables() {
+        let condition = parse("  liq < 10K   and mcap > 50K  && liquidity > 1K");
+        assert_eq!(condition.to_string(), "liq < 10K and mcap > 50K && liquidity > 1K");
+        assert_eq!(condition.variables(), vec![Variable::Liquidity, Variable::MarketCap]);
+    }
+
+    #[test]
+    fn unknown_values() {
+        let message = error("holders > 100");
+        assert!(message.starts_with("unknown value 'holders' (known: mcap, fdv, price,"));
+        assert!(!message.contains("market_cap"), "aliases aren't listed: {}", message);
+    }
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(error(""), "empty condition");
+        assert_eq!(error("mcap"), "expected a comparison like mcap > 1M");
+        assert_eq!(error("mcap 5"), "expected a comparison before number 5");
+        assert_eq!(error("mcap >"), "expected a value at the end");
+        assert_eq!(error("mcap > &&"), "expected a value, got '&&'");
+        assert_eq!(error("mcap = 5"), "'=' isn't an operator (use ==, !=, <, >)");
+        assert_eq!(error("(mcap > 5"), "missing ')'");
+        assert_eq!(error("mcap > 5)"), "unexpected ')'");
+        assert_eq!(error("mcap > 5x"), "'5x' isn't a number");
+        assert_eq!(error("mcap > -"), "expected a number after '-'");
+        assert_eq!(error("-mcap < 5"), "unexpected '-'");
+        assert_eq!(error("mcap # 5"), "unexpected '#'");
+    }
+}
@@ -1,12 +1,30 @@
+use std::collections::HashSet;
 use std::fs;
 use std::path::PathBuf;
 use std::process;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use chrono::Local;
 use serde::{Deserialize, Serialize};
 
+use crate::alarm;
+use crate::alerts::{Target, TargetKind, Targets};
 use crate::api;
+use crate::bus::{BusEvent, EventBus};
+use crate::condition::{Condition, Variable};
+use crate::config::Config;
+use crate::discord;
+use crate::duck;
+use crate::eventlog::{Event, EventLog};
+use crate::health::ProviderHealth;
+use crate::hook::Hooks;
+use crate::jitter;
+use crate::notify::{self, AlertContext, Channel, Outbox, Severity};
+use crate::ohlc::{self, OhlcRow, OhlcTable, Period};
+use crate::output::{OutputFormat, Record};
+use crate::schedule;
+use crate::speech;
+use crate::storage::{self, Sample};
 
 /// Daemon config saved alongside the PID file so the TUI can resume
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -14,9 +32,54 @@ pub struct DaemonConfig {
     pub pair: String,
     pub chain: String,
     pub target: f64,
+    /// Whether `target` is a market cap or a token price
+    #[serde(default)]
+    pub target_kind: TargetKind,
+    /// Levels below the target given with it (`--target 50000,100000`)
+    #[serde(default)]
+    pub milestones: Vec<f64>,
     pub interval: u64,
     pub alarm: Option<String>,
     pub alarm_duration: u64,
+    /// Provider override; empty means use the chain defaults from the config file
+    #[serde(default)]
+    pub providers: Vec<String>,
+    /// RPC endpoint override; `None` means use the chain default
+    #[serde(default)]
+    pub rpc_url: Option<String>,
+    /// Visual-only alerts: desktop notification, no audio
+    #[serde(default)]
+    pub silent: bool,
+    /// OHLC summary period ("hourly" / "daily"), appended to the summary CSV
+    #[serde(default)]
+    pub summary: Option<String>,
+    /// Instance name prefixed to notifications (--name)
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Fetch on wall-clock multiples of the interval (--align)
+    #[serde(default)]
+    pub align: bool,
+    /// Line format of the log and stdout (--output-format)
+    #[serde(default)]
+    pub output_format: OutputFormat,
+    /// Intervals without a successful fetch before alerting (--stale-after)
+    #[serde(default)]
+    pub stale_after: Option<u32>,
+    /// Ring the alarm on the stale-data alert (--stale-alarm)
+    #[serde(default)]
+    pub stale_alarm: bool,
+    /// Alert conditions as written (--when)
+    #[serde(default)]
+    pub conditions: Vec<String>,
+}
+
+impl DaemonConfig {
+    /// The target with its milestones, as given to --target
+    pub fn target_spec(&self) -> Targets {
+        let mut levels = self.milestones.clone();
+        levels.push(self.target);
+        Targets::new(self.target_kind, levels)
+    }
 }
 
 /// Returns the pidfile path for a given pair address
@@ -31,6 +94,25 @@ pub fn log_file(pair: &str) -> PathBuf {
     PathBuf::from(format!("/tmp/mooncap-{}.log", safe))
 }
 
+/// Returns the OHLC summary CSV path for a given pair address
+pub fn summary_file(pair: &str) -> PathBuf {
+    let safe = pair.chars().take(12).collect::<String>();
+    PathBuf::from(format!("/tmp/mooncap-{}-ohlc.csv", safe))
+}
+
+/// Append a summary row, writing the header first if the file is new
+fn append_summary(pair: &str, row: &OhlcRow) {
+    use std::io::Write;
+    let path = summary_file(pair);
+    let new = !path.exists();
+    if let Ok(mut f) = fs::OpenOptions::new().create(true).append(true).open(&path) {
+        if new {
+            let _ = writeln!(f, "{}", ohlc::CSV_HEADER);
+        }
+        let _ = writeln!(f, "{}", row.csv_line());
+    }
+}
+
 /// Returns the config file path for a given pair address
 fn config_file(pair: &str) -> PathBuf {
     let safe = pair.chars().take(12).collect::<String>();
@@ -114,14 +196,8 @@ pub fn kill_daemon_quiet(pair: &str) {
 
 /// Spawn a background daemon worker. Relaunches the binary with --daemon-worker.
 /// Returns the PID of the spawned process.
-pub fn spawn_daemon(
-    pair: &str,
-    chain: &str,
-    target: f64,
-    interval: u64,
-    alarm: Option<&str>,
-    alarm_duration: u64,
-) -> Result<u32, String> {
+pub fn spawn_daemon(config: &DaemonConfig) -> Result<u32, String> {
+    let pair = config.pair.as_str();
     let exe = std::env::current_exe()
         .map_err(|e| format!("Failed to get executable path: {}", e))?;
 
@@ -141,15 +217,7 @@ pub fn spawn_daemon(
     }
 
     // Save config so the TUI can resume later
-    let config = DaemonConfig {
-        pair: pair.to_string(),
-        chain: chain.to_string(),
-        target,
-        interval,
-        alarm: alarm.map(|s| s.to_string()),
-        alarm_duration,
-    };
-    save_config(pair, &config);
+    save_config(pair, config);
 
     let log = fs::OpenOptions::new()
         .create(true)
@@ -162,14 +230,44 @@ pub fn spawn_daemon(
     let mut cmd = process::Command::new(&exe);
     cmd.arg("--daemon-worker")
         .arg("--pair").arg(pair)
-        .arg("--chain").arg(chain)
-        .arg("--target").arg(target.to_string())
-        .arg("--interval").arg(interval.to_string())
-        .arg("--alarm-duration").arg(alarm_duration.to_string());
+        .arg("--chain").arg(&config.chain)
+        .arg("--target").arg(config.target_spec().to_string())
+        .arg("--interval").arg(config.interval.to_string())
+        .arg("--alarm-duration").arg(config.alarm_duration.to_string());
 
-    if let Some(a) = alarm {
+    if let Some(ref a) = config.alarm {
         cmd.arg("--alarm").arg(a);
     }
+    if !config.providers.is_empty() {
+        cmd.arg("--provider").arg(config.providers.join(","));
+    }
+    if let Some(ref rpc) = config.rpc_url {
+        cmd.arg("--rpc-url").arg(rpc);
+    }
+    if config.silent {
+        cmd.arg("--silent-alerts");
+    }
+    if let Some(ref summary) = config.summary {
+        cmd.arg("--summary").arg(summary);
+    }
+    if let Some(ref name) = config.name {
+        cmd.arg("--name").arg(name);
+    }
+    if config.align {
+        cmd.arg("--align");
+    }
+    if config.output_format != OutputFormat::Human {
+        cmd.arg("--output-format").arg(config.output_format.name());
+    }
+    if let Some(intervals) = config.stale_after {
+        cmd.arg("--stale-after").arg(intervals.to_string());
+    }
+    if config.stale_alarm {
+        cmd.arg("--stale-alarm");
+    }
+    for condition in &config.conditions {
+        cmd.arg("--when").arg(condition);
+    }
 
     cmd.stdout(log)
         .stderr(log_err)
@@ -201,90 +299,543 @@ fn process_is_alive(pid: u32) -> bool {
 }
 
 /// The headless background worker loop
-pub async fn run_daemon_worker(
-    pair: String,
-    chain: String,
-    target: f64,
-    interval: u64,
-    alarm_file: Option<String>,
-    alarm_duration: u64,
-) {
+pub async fn run_daemon_worker(config: DaemonConfig) {
     let pid = process::id();
-    let pid_path = pid_file(&pair);
-    let log_path = log_file(&pair);
+    let pid_path = pid_file(&config.pair);
+    let log_path = log_file(&config.pair);
 
     let _ = fs::write(&pid_path, pid.to_string());
 
     // Also write config in case it wasn't written by the parent
-    let config = DaemonConfig {
-        pair: pair.clone(),
-        chain: chain.clone(),
+    save_config(&config.pair, &config);
+
+    let DaemonConfig {
+        pair,
+        chain,
         target,
+        target_kind,
+        milestones,
         interval,
-        alarm: alarm_file.clone(),
+        alarm: alarm_file,
         alarm_duration,
+        providers,
+        rpc_url: _,
+        silent,
+        summary,
+        name: instance,
+        align,
+        output_format,
+        stale_after,
+        stale_alarm,
+        conditions,
+    } = config;
+
+    let user_config = Config::load();
+
+    // Milestones below the target alert without ending the run. Those in the
+    // config file are market caps, so a price target only has its own.
+    let ladder = match target_kind {
+        TargetKind::MarketCap => user_config.target_ladder(&pair, target, &milestones),
+        TargetKind::Price => {
+            let levels = milestones.into_iter().chain(std::iter::once(target)).collect();
+            Targets::new(target_kind, levels).levels
+        }
+    };
+    let (milestones, target) = match ladder.split_last() {
+        Some((&top, below)) => (below.to_vec(), top),
+        None => (Vec::new(), target),
     };
-    save_config(&pair, &config);
+    let mut milestones_hit = 0;
+
+    // CLI providers win; otherwise use the chain defaults from the config file
+    let provider_names = if providers.is_empty() {
+        user_config
+            .chain_defaults(&chain)
+            .map(|d| d.providers.clone())
+            .unwrap_or_default()
+    } else {
+        providers
+    };
+    let (providers, unknown) = api::parse_providers(&provider_names);
 
-    let log = |msg: &str| {
-        let now = Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
-        let line = format!("[{}] {}\n", now, msg);
+    let formatter = output_format.formatter();
+    let write_line = |line: &str| {
         let _ = fs::OpenOptions::new()
             .create(true)
             .append(true)
             .open(&log_path)
             .map(|mut f| {
                 use std::io::Write;
-                let _ = f.write_all(line.as_bytes());
+                let _ = writeln!(f, "{}", line);
             });
-        print!("{}", line);
+        println!("{}", line);
+    };
+    // A CSV header goes at the top of the log, not before every run appended to it
+    if let Some(header) = formatter.header() {
+        if fs::metadata(&log_path).map(|m| m.len() == 0).unwrap_or(true) {
+            write_line(&header);
+        } else {
+            println!("{}", header);
+        }
+    }
+    let emit = |record: Record| write_line(&formatter.format(Local::now(), &record));
+    let log = |msg: &str| emit(Record::Info(msg));
+    let warn = |msg: &str| emit(Record::Warning(msg));
+    let report = |outcome: Result<String, String>| match outcome {
+        Ok(line) => log(&format!("📨 {}", line)),
+        Err(line) => warn(&line),
     };
 
     log(&format!(
-        "🚀 MoonCap daemon started | PID: {} | Chain: {} | Target: ${:.0} | Interval: {}s",
-        pid, chain, target, interval
+        "🚀 MoonCap daemon started | PID: {} | Chain: {} | Target: {} | Interval: {}s",
+        pid,
+        chain,
+        target_kind.format(target),
+        interval
     ));
     log(&format!("📡 Monitoring: {}", pair));
+    if !milestones.is_empty() {
+        let levels: Vec<String> = milestones.iter().map(|&t| target_kind.format(t)).collect();
+        log(&format!("🎯 Milestones: {}", levels.join(" → ")));
+    }
+    if !unknown.is_empty() {
+        warn(&format!("Unknown provider(s) ignored: {}", unknown.join(", ")));
+    }
+    let mut parsed = Vec::new();
+    for text in &conditions {
+        match text.parse::<Condition>() {
+            Ok(condition) => parsed.push(condition),
+            Err(e) => warn(&format!("Condition '{}' ignored: {}", text, e)),
+        }
+    }
+    let conditions = parsed;
+    if !conditions.is_empty() {
+        let rules: Vec<String> = conditions.iter().map(|c| c.to_string()).collect();
+        log(&format!("📐 Conditions: {}", rules.join("; ")));
+    }
+    // Like the TUI, a condition fires once and re-arms when it stops holding
+    let mut conditions_firing = HashSet::new();
+
+    let mut summary = match summary.as_deref().map(|s| (s, Period::parse(s))) {
+        Some((_, Some(period))) => {
+            log(&format!(
+                "📅 Writing {} OHLC summary to {}",
+                period.name(),
+                summary_file(&pair).display()
+            ));
+            Some(OhlcTable::new(period))
+        }
+        Some((name, None)) => {
+            warn(&format!("Unknown summary period '{}' (use hourly or daily)", name));
+            None
+        }
+        None => None,
+    };
+
+    let store = match storage::open(&user_config.storage).await {
+        Ok(store) => store,
+        Err(e) => {
+            warn(&format!("History storage disabled: {}", e));
+            None
+        }
+    };
+    if let Some(ref store) = store {
+        log(&format!("🗄  Recording history to {}", store.name()));
+    }
+
+    let (bus, bus_errors) = EventBus::connect(&user_config.redis, &user_config.mqtt).await;
+    for e in bus_errors {
+        warn(&e);
+    }
+
+    let mut events = match user_config.event_log.as_deref().map(EventLog::open) {
+        Some(Ok(events)) => {
+            log(&format!("📝 Recording events to {}", events.path()));
+            Some(events)
+        }
+        Some(Err(e)) => {
+            warn(&format!("Event log disabled: {}", e));
+            None
+        }
+        None => None,
+    };
+    let mut record = |event: Event| {
+        if let Some(ref mut events) = events {
+            if let Err(e) = events.append(&event) {
+                warn(&e);
+            }
+        }
+    };
+    record(Event::started("daemon"));
+    let spec = Target {
+        kind: target_kind,
+        value: target,
+    };
+    record(Event::config(&chain, &pair, spec, interval));
+
+    let schedule = schedule::find(&user_config.schedules, &pair).cloned();
+    if let Some(ref schedule) = schedule {
+        match schedule.validate() {
+            Ok(()) => log(&format!("⏰ Monitoring schedule: {}", schedule.describe())),
+            Err(e) => warn(&format!("Schedule ignored: {}", e)),
+        }
+    }
+    let mut paused = false;
+
+    let quiet_hours = user_config.quiet_hours.clone();
+    if let Some(ref quiet) = quiet_hours {
+        match quiet.validate() {
+            Ok(()) => log(&format!("🌙 Quiet hours: {}", quiet.describe())),
+            Err(e) => warn(&format!("Quiet hours ignored: {}", e)),
+        }
+    }
+    let quiet = |severity: Severity| {
+        quiet_hours
+            .as_ref()
+            .is_some_and(|q| q.silences(severity, Local::now()))
+    };
+    // The alarm rings for alerts routed to it, unless --silent-alerts or quiet
+    // hours hold it back
+    let sound = |severity: Severity, channels: &[Channel]| {
+        if silent || !channels.contains(&Channel::Alarm) {
+            return None;
+        }
+        if quiet(severity) {
+            log("🌙 Quiet hours: alarm silenced");
+            return None;
+        }
+        Some(user_config.alarm_sounds.resolve(severity, alarm_file.clone(), alarm_duration))
+    };
+
+    let align = align || user_config.align_fetches;
+    let max_jitter = if align { 0 } else { user_config.fetch_jitter.unwrap_or(0) };
+    if align {
+        log(&format!("🕐 Fetches aligned to every {}s on the clock", interval));
+    } else if max_jitter > 0 {
+        log(&format!("🎲 Fetch jitter: up to {}s", max_jitter));
+    }
+    let mut jitter = Duration::ZERO;
 
     let client = reqwest::Client::new();
+    let mut health = ProviderHealth::default();
     let mut last_fetch = Instant::now() - Duration::from_secs(interval + 1);
+    // Last successful fetch (or unpause), and whether the stale alert has fired since
+    let mut fresh_at = Instant::now();
+    let mut stale_fired = false;
+    // Token symbol for the stale alert, the pair address until a fetch succeeds
+    let mut label = pair.clone();
+    // What remote alerts say about the pair, filled in by each fetch
+    let mut context = AlertContext {
+        chain: chain.clone(),
+        pair: pair.clone(),
+        ..Default::default()
+    };
+    let on_alert = user_config.on_alert.clone().filter(|c| !c.trim().is_empty());
+    let mut outlets = Outlets {
+        client: &client,
+        config: &user_config,
+        bus: bus.as_ref(),
+        instance: instance.as_deref(),
+        on_alert: on_alert.as_deref(),
+        hooks: Hooks::default(),
+        outbox: Outbox::default(),
+    };
 
     loop {
-        if last_fetch.elapsed() >= Duration::from_secs(interval) {
+        for failure in outlets.hooks.reap() {
+            warn(&failure);
+        }
+        for outcome in outlets.deliver() {
+            report(outcome);
+        }
+        if let Some(ref schedule) = schedule {
+            let now_paused = !schedule.active_at(Local::now());
+            if now_paused != paused {
+                paused = now_paused;
+                log(if paused {
+                    "💤 Outside monitoring schedule — polling paused"
+                } else {
+                    "⏰ Monitoring schedule active — polling resumed"
+                });
+            }
+        }
+
+        if paused {
+            fresh_at = Instant::now();
+        } else if let Some(intervals) = stale_after {
+            let limit = Duration::from_secs(interval * intervals as u64);
+            if !stale_fired && fresh_at.elapsed() >= limit {
+                stale_fired = true;
+                let message = format!(
+                    "{} data stale: no successful fetch for {}m ({} intervals)",
+                    label,
+                    fresh_at.elapsed().as_secs() / 60,
+                    intervals
+                );
+                let severity = if stale_alarm { Severity::Critical } else { Severity::Warning };
+                emit(Record::Alert {
+                    severity,
+                    symbol: &label,
+                    message: &message,
+                });
+                record(Event::alert(&chain, &pair, &label, severity, &message));
+                let alert = Alert {
+                    severity,
+                    rule: "stale",
+                    symbol: &label,
+                    title: "⏳ MoonCap — data stale".to_string(),
+                    body: message.clone(),
+                    message: &message,
+                    spoken: speech::phrase(&message),
+                };
+                let quiet = quiet(severity);
+                let (channels, failures) = outlets.send(&alert, &context, quiet).await;
+                // Channels taking errors hear about it even when it isn't routed to them
+                let text = notify::label(instance.as_deref(), &message);
+                for channel in notify::error_channels(&user_config) {
+                    if !channels.contains(&channel) {
+                        outlets.outbox.queue(channel, severity, text.clone(), context.clone());
+                    }
+                }
+                for e in failures {
+                    warn(&e);
+                }
+                if let Some((file, secs)) = sound(severity, &channels) {
+                    std::thread::spawn(move || ring(file.as_deref(), secs));
+                }
+            }
+        }
+
+        if !paused && last_fetch.elapsed() >= Duration::from_secs(interval) + jitter {
             last_fetch = Instant::now();
+            jitter = jitter::delay(max_jitter);
+            if align {
+                // Due at the next boundary: `interval` after last_fetch, minus the overshoot
+                let wait = jitter::until_boundary(interval, SystemTime::now());
+                last_fetch -= Duration::from_secs(interval).saturating_sub(wait);
+            }
 
-            match api::fetch_pair_data(&client, &chain, &pair).await {
+            let order = health.rank(&providers);
+            let result =
+                api::fetch_with_source(&client, &order, &chain, &pair, None, Some(&mut health))
+                    .await;
+            match result.map(|(data, _)| data) {
                 Ok(data) => {
-                    let market_cap = data.market_cap.unwrap_or(data.fdv.unwrap_or(0.0));
-                    let price = data
-                        .price_usd
-                        .as_deref()
-                        .unwrap_or("0")
-                        .parse::<f64>()
-                        .unwrap_or(0.0);
+                    if stale_fired {
+                        stale_fired = false;
+                        let wait = fresh_at.elapsed().as_secs() / 60;
+                        log(&format!("📡 Data fresh again after {}m", wait));
+                    }
+                    fresh_at = Instant::now();
+                    let market_cap = data.market_cap_usd();
+                    let price = data.price_usd_f64();
                     let name = data
                         .base_token
                         .as_ref()
                         .and_then(|t| t.name.as_deref())
                         .unwrap_or("Token");
-                    let symbol = data
-                        .base_token
-                        .as_ref()
-                        .and_then(|t| t.symbol.as_deref())
-                        .unwrap_or("???");
-
-                    log(&format!(
-                        "✓ {} ({}) | MCap: ${:.0} | Price: ${:.8} | Target: ${:.0}",
-                        name, symbol, market_cap, price, target
-                    ));
+                    let symbol = data.base_symbol();
+                    label = symbol.to_string();
+                    let value = match target_kind {
+                        TargetKind::MarketCap => market_cap,
+                        TargetKind::Price => price,
+                    };
+                    context.name = name.to_string();
+                    context.symbol = symbol.to_string();
+                    context.market_cap = market_cap;
+                    context.price = price;
+                    context.target = target;
+                    context.progress = (target > 0.0).then(|| {
+                        format!("{:.0}% of {}", value / target * 100.0, target_kind.format(target))
+                    });
+                    context.history.push(market_cap.max(0.0) as u64);
+                    if context.history.len() > discord::SPARKLINE_POINTS {
+                        context.history.remove(0);
+                    }
 
-                    if market_cap >= target {
+                    emit(Record::Fetch {
+                        name,
+                        symbol,
+                        market_cap,
+                        price,
+                        target: spec,
+                    });
+                    record(Event::fetch(&chain, &pair, &data));
+
+                    if let Some(row) = summary
+                        .as_mut()
+                        .and_then(|t| t.record(Local::now(), market_cap))
+                    {
                         log(&format!(
-                            "🔥 TARGET HIT! {} reached ${:.0}",
-                            name, market_cap
+                            "📅 {} O ${:.0} H ${:.0} L ${:.0} C ${:.0} ({:+.2}%)",
+                            row.period,
+                            row.open,
+                            row.high,
+                            row.low,
+                            row.close,
+                            row.change()
                         ));
+                        append_summary(&pair, &row);
+                    }
 
-                        fire_alarm(name, symbol, market_cap, target, alarm_file.as_deref(), alarm_duration);
+                    if let Some(ref store) = store {
+                        if let Err(e) = store.record(&Sample::new(&chain, &pair, &data)).await {
+                            warn(&format!("{} write failed: {}", store.name(), e));
+                        }
+                    }
+                    if let Some(ref bus) = bus {
+                        if let Err(e) = bus.publish(&BusEvent::update(&chain, &pair, &data)).await {
+                            warn(&e);
+                        }
+                    }
+
+                    while let Some(&level) = milestones.get(milestones_hit) {
+                        if value < level {
+                            break;
+                        }
+                        milestones_hit += 1;
+                        let message = format!(
+                            "{} passed {} (milestone {}/{})",
+                            symbol,
+                            target_kind.format(level),
+                            milestones_hit,
+                            milestones.len() + 1
+                        );
+                        emit(Record::Alert {
+                            severity: Severity::Critical,
+                            symbol,
+                            message: &message,
+                        });
+                        record(Event::alert(&chain, &pair, symbol, Severity::Critical, &message));
+                        let alert = Alert {
+                            severity: Severity::Critical,
+                            rule: "milestone",
+                            symbol,
+                            title: format!(
+                                "🎯 MoonCap — {} passed {}",
+                                symbol,
+                                target_kind.format(level)
+                            ),
+                            body: message.clone(),
+                            message: &message,
+                            spoken: speech::target_phrase(symbol, target_kind, level),
+                        };
+                        let quiet = quiet(Severity::Critical);
+                        let (channels, failures) = outlets.send(&alert, &context, quiet).await;
+                        for e in failures {
+                            warn(&e);
+                        }
+                        if let Some((file, secs)) = sound(Severity::Critical, &channels) {
+                            std::thread::spawn(move || ring(file.as_deref(), secs));
+                        }
+                    }
+
+                    for (i, condition) in conditions.iter().enumerate() {
+                        let current = |v: Variable| match v {
+                            Variable::Progress => (target > 0.0).then(|| value / target * 100.0),
+                            _ => v.reported(&data),
+                        };
+                        if !condition.holds(current) {
+                            conditions_firing.remove(&i);
+                            continue;
+                        }
+                        if !conditions_firing.insert(i) {
+                            continue;
+                        }
+                        let values: Vec<String> = condition
+                            .variables()
+                            .into_iter()
+                            .filter_map(|v| Some(format!("{} {}", v.name(), v.format(current(v)?))))
+                            .collect();
+                        let message = format!("{}: {} ({})", symbol, condition, values.join(", "));
+                        let rule = format!("when:{}", condition);
+                        emit(Record::Alert {
+                            severity: Severity::Warning,
+                            symbol,
+                            message: &message,
+                        });
+                        record(Event::alert(&chain, &pair, symbol, Severity::Warning, &message));
+                        let alert = Alert {
+                            severity: Severity::Warning,
+                            rule: &rule,
+                            symbol,
+                            title: "📐 MoonCap — condition met".to_string(),
+                            body: message.clone(),
+                            message: &message,
+                            spoken: speech::phrase(&message),
+                        };
+                        let quiet = quiet(Severity::Warning);
+                        let (channels, failures) = outlets.send(&alert, &context, quiet).await;
+                        for e in failures {
+                            warn(&e);
+                        }
+                        if let Some((file, secs)) = sound(Severity::Warning, &channels) {
+                            std::thread::spawn(move || ring(file.as_deref(), secs));
+                        }
+                    }
+
+                    if value >= target {
+                        let message = format!(
+                            "{} hit {} (target {})",
+                            symbol,
+                            target_kind.format(value),
+                            target_kind.format(target)
+                        );
+                        emit(Record::Alert {
+                            severity: Severity::Critical,
+                            symbol,
+                            message: &message,
+                        });
+                        record(Event::alert(&chain, &pair, symbol, Severity::Critical, &message));
+                        let alert = Alert {
+                            severity: Severity::Critical,
+                            rule: "target",
+                            symbol,
+                            title: format!("🚀 MoonCap — {} hit target!", symbol),
+                            body: format!(
+                                "{} ({}) {} reached {}\nTarget was {}",
+                                name,
+                                symbol,
+                                target_kind.name(),
+                                target_kind.format(value),
+                                target_kind.format(target)
+                            ),
+                            message: &message,
+                            spoken: speech::target_phrase(symbol, target_kind, target),
+                        };
+                        let quiet = quiet(Severity::Critical);
+                        let (channels, failures) = outlets.send(&alert, &context, quiet).await;
+                        for e in failures {
+                            warn(&e);
+                        }
+
+                        // The run ends with the alarm, so it rings before exiting
+                        let (file, duration) =
+                            sound(Severity::Critical, &channels).unwrap_or_default();
+                        // Other audio stays turned down until the alarm ends
+                        let _ducked = match user_config.duck_volume.filter(|_| duration > 0) {
+                            Some(percent) => duck::duck(percent)
+                                .map_err(|e| warn(&format!("Audio ducking failed: {}", e)))
+                                .ok(),
+                            None => None,
+                        };
+                        ring(file.as_deref(), duration);
+
+                        // The period in progress is the last row of the run
+                        if let Some(row) = summary.as_ref().and_then(|t| t.rows.back()) {
+                            append_summary(&pair, row);
+                        }
+
+                        // Deliveries still going, retries included, finish first
+                        if !outlets.outbox.is_empty() {
+                            log("📨 Waiting for alert deliveries before exiting");
+                        }
+                        while !outlets.outbox.is_empty() {
+                            for outcome in outlets.deliver() {
+                                report(outcome);
+                            }
+                            tokio::time::sleep(Duration::from_secs(1)).await;
+                        }
 
                         let _ = fs::remove_file(&pid_path);
                         let _ = fs::remove_file(config_file(&pair));
@@ -293,7 +844,8 @@ pub async fn run_daemon_worker(
                     }
                 }
                 Err(e) => {
-                    log(&format!("❌ Fetch error: {}", e));
+                    emit(Record::FetchFailed(&e));
+                    record(Event::fetch_failed(&chain, &pair, &e));
                 }
             }
         }
@@ -302,25 +854,97 @@ pub async fn run_daemon_worker(
     }
 }
 
-/// Trigger desktop notification + audio alarm
-fn fire_alarm(
-    name: &str,
-    symbol: &str,
-    market_cap: f64,
-    target: f64,
-    alarm_file: Option<&str>,
-    alarm_duration: u64,
-) {
-    let summary = format!("🚀 MoonCap — {} hit target!", symbol);
-    let body = format!(
-        "{} ({}) market cap reached ${:.0}\nTarget was ${:.0}",
-        name, symbol, market_cap, target
-    );
-
-    let _ = process::Command::new("notify-send")
-        .args(["--urgency=critical", "--expire-time=0", &summary, &body])
-        .spawn();
+/// A daemon alert, as each channel shows it
+struct Alert<'a> {
+    severity: Severity,
+    /// Rule that raised it, for `alert_routing.types` and the `on_alert` hook
+    rule: &'a str,
+    symbol: &'a str,
+    /// Desktop notification
+    title: String,
+    body: String,
+    /// What the log, the event bus and the remote channels say
+    message: &'a str,
+    /// What the speech channel reads out
+    spoken: String,
+}
+
+/// Where a daemon's alerts go besides its log
+struct Outlets<'a> {
+    client: &'a reqwest::Client,
+    config: &'a Config,
+    bus: Option<&'a EventBus>,
+    instance: Option<&'a str>,
+    on_alert: Option<&'a str>,
+    hooks: Hooks,
+    /// Remote deliveries, retried with backoff like the TUI's
+    outbox: Outbox,
+}
+
+impl Outlets<'_> {
+    /// Publish `alert` and send it to the channels `alert_routing` gives it,
+    /// except the alarm, which is left to the caller. A daemon has no screen to
+    /// flash, so the alarm channel brings a desktop notification too. Remote
+    /// channels are queued in the outbox. Returns the channels and the failures.
+    async fn send(
+        &mut self,
+        alert: &Alert<'_>,
+        context: &AlertContext,
+        quiet: bool,
+    ) -> (Vec<Channel>, Vec<String>) {
+        let severity = alert.severity;
+        let (channels, unknown) = notify::route(self.config, &context.pair, severity, alert.rule);
+        let mut failures = Vec::new();
+        if !unknown.is_empty() {
+            failures.push(format!(
+                "Unknown {} alert channel(s) skipped: {}",
+                severity.name(),
+                unknown.join(", ")
+            ));
+        }
+        if let Some(bus) = self.bus {
+            let (chain, pair) = (&context.chain, &context.pair);
+            let event = BusEvent::alert(chain, pair, alert.symbol, severity, alert.message);
+            if let Err(e) = bus.publish(&event).await {
+                failures.push(e);
+            }
+        }
+        if channels.contains(&Channel::Alarm) || channels.contains(&Channel::Desktop) {
+            let title = notify::label(self.instance, &alert.title);
+            if let Err(e) = notify::send_desktop(title, alert.body.clone()).await {
+                failures.push(format!("Desktop notification failed: {}", e));
+            }
+        }
+        if channels.contains(&Channel::Speech) && !quiet {
+            if let Err(e) = speech::speak(&self.config.speech, &alert.spoken) {
+                failures.push(format!("Speech failed: {}", e));
+            }
+        }
+        let text = notify::label(self.instance, alert.message);
+        self.outbox.queue_remote(&channels, severity, &text, context);
+        self.outbox.dispatch(self.client, self.config);
+        if let Some(command) = self.on_alert {
+            if let Err(e) = self.hooks.run(command, severity, alert.message, alert.rule, context) {
+                failures.push(e);
+            }
+        }
+        (channels, failures)
+    }
+
+    /// Start the deliveries that are due, and return the outcomes of the ones
+    /// that have finished
+    fn deliver(&mut self) -> Vec<Result<String, String>> {
+        self.outbox.dispatch(self.client, self.config);
+        let finished = self.outbox.finished();
+        finished
+            .into_iter()
+            .map(|(delivery, result)| self.outbox.record(delivery, result))
+            .collect()
+    }
+}
 
+/// Play the alarm file (or the terminal bell) for `alarm_duration` seconds
+fn ring(alarm_file: Option<&str>, alarm_duration: u64) {
     let end = Instant::now() + Duration::from_secs(alarm_duration);
 
     if let Some(file) = alarm_file {
@@ -341,8 +965,7 @@ fn fire_alarm(
         }
     } else {
         while Instant::now() < end {
-            print!("\x07");
-            std::thread::sleep(Duration::from_secs(2));
+            std::thread::sleep(alarm::bell(Severity::Critical));
         }
     }
 }
@@ -0,0 +1,136 @@
+//! Alerts posted to Discord channels through webhooks, as embeds carrying the
+//! token, its chain and market cap and a text sparkline of the recent history.
+
+use std::collections::HashMap;
+
+use chrono::{SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::notify::{AlertContext, Severity};
+use crate::secrets;
+use crate::ui;
+
+/// Market caps drawn in an embed's sparkline, newest last
+pub const SPARKLINE_POINTS: usize = 30;
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Webhooks alerts are posted to; a value of `keyring:<name>` is read from the
+/// OS keyring instead
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DiscordConfig {
+    /// Webhook every pair posts to unless it has its own
+    pub webhook_url: Option<String>,
+    /// Webhooks by pair address, used instead of `webhook_url` for that pair
+    pub pairs: HashMap<String, String>,
+}
+
+impl DiscordConfig {
+    /// The webhook alerts about `pair` go to, if any
+    pub fn webhook(&self, pair: &str) -> Option<String> {
+        self.pairs
+            .iter()
+            .find(|(address, _)| address.trim().eq_ignore_ascii_case(pair))
+            .map(|(_, url)| url.as_str())
+            .or(self.webhook_url.as_deref())
+            .and_then(secrets::resolve)
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+    }
+}
+
+/// `values` as a line of block characters, scaled between their low and high
+pub fn sparkline(values: &[u64]) -> String {
+    let recent = &values[values.len().saturating_sub(SPARKLINE_POINTS)..];
+    let (Some(&low), Some(&high)) = (recent.iter().min(), recent.iter().max()) else {
+        return String::new();
+    };
+    let range = (high - low).max(1) as f64;
+    recent
+        .iter()
+        .map(|v| {
+            let level = ((v - low) as f64 / range * (SPARK_LEVELS.len() - 1) as f64).round();
+            SPARK_LEVELS[level as usize]
+        })
+        .collect()
+}
+
+fn color(severity: Severity) -> u32 {
+    match severity {
+        Severity::Info => 0x3498db,
+        Severity::Warning => 0xf1c40f,
+        Severity::Critical => 0xe74c3c,
+    }
+}
+
+fn embed(severity: Severity, message: &str, context: &AlertContext) -> serde_json::Value {
+    let field = |name: &str, value: String| {
+        serde_json::json!({ "name": name, "value": value, "inline": true })
+    };
+    let mut fields = Vec::new();
+    if !context.symbol.is_empty() {
+        let token = if context.name.is_empty() || context.name == context.symbol {
+            context.symbol.clone()
+        } else {
+            format!("{} ({})", context.name, context.symbol)
+        };
+        fields.push(field("Token", token));
+    }
+    fields.push(field("Chain", context.chain.clone()));
+    if context.market_cap > 0.0 {
+        fields.push(field("Market cap", format!("${}", ui::format_amount(context.market_cap))));
+    }
+    if let Some(ref progress) = context.progress {
+        fields.push(field("Progress", progress.clone()));
+    }
+    let spark = sparkline(&context.history);
+    if !spark.is_empty() {
+        fields.push(serde_json::json!({ "name": "History", "value": format!("`{}`", spark) }));
+    }
+    serde_json::json!({
+        "title": format!("MoonCap {} alert", severity.name()),
+        "description": message,
+        "color": color(severity),
+        "fields": fields,
+        "footer": { "text": context.pair },
+        "timestamp": Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorReply {
+    message: Option<String>,
+}
+
+/// Post an alert embed to a webhook
+pub async fn send(
+    client: &reqwest::Client,
+    webhook_url: &str,
+    severity: Severity,
+    message: &str,
+    context: &AlertContext,
+) -> Result<(), String> {
+    let body = serde_json::json!({
+        "username": "MoonCap",
+        "embeds": [embed(severity, message, context)],
+    });
+    // Request errors carry the URL, and with it the webhook token
+    let response = client
+        .post(webhook_url)
+        .json(&body)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e.without_url()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let reply: Option<ErrorReply> = response.json().await.ok();
+    match reply.and_then(|r| r.message) {
+        Some(message) => Err(format!("Discord refused the message: {}", message)),
+        None => Err(format!("Discord answered HTTP {}", status.as_u16())),
+    }
+}
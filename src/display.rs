@@ -0,0 +1,276 @@
+//! What the terminal can show, detected at startup, and a pass that brings
+//! each drawn frame down to it: RGB and 256-color styles mapped onto the 16
+//! basic colors (or dropped for bold/reverse on monochrome terminals), and box
+//! drawing, block elements and emoji swapped for ASCII, so basic consoles and
+//! serial terminals don't fill up with escape noise.
+
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+
+/// How many colors the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorLevel {
+    Mono,
+    /// The 16 ANSI colors
+    Basic,
+    /// 256 colors or true color
+    Full,
+}
+
+impl ColorLevel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorLevel::Mono => "monochrome",
+            ColorLevel::Basic => "16 colors",
+            ColorLevel::Full => "full color",
+        }
+    }
+}
+
+/// `--color`: auto-detected or forced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    Auto,
+    Forced(ColorLevel),
+}
+
+impl std::str::FromStr for ColorMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Ok(ColorMode::Auto),
+            "full" | "truecolor" | "256" => Ok(ColorMode::Forced(ColorLevel::Full)),
+            "16" | "basic" => Ok(ColorMode::Forced(ColorLevel::Basic)),
+            "mono" | "none" | "off" => Ok(ColorMode::Forced(ColorLevel::Mono)),
+            _ => Err("use auto, full, 16 or mono".to_string()),
+        }
+    }
+}
+
+/// `--glyphs`: auto-detected or forced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphMode {
+    Auto,
+    Unicode,
+    Ascii,
+}
+
+impl std::str::FromStr for GlyphMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Ok(GlyphMode::Auto),
+            "unicode" | "utf8" | "utf-8" => Ok(GlyphMode::Unicode),
+            "ascii" => Ok(GlyphMode::Ascii),
+            _ => Err("use auto, unicode or ascii".to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Display {
+    pub colors: ColorLevel,
+    /// Box drawing, blocks and emoji; ASCII only when false
+    pub unicode: bool,
+}
+
+impl Default for Display {
+    fn default() -> Self {
+        Self {
+            colors: ColorLevel::Full,
+            unicode: true,
+        }
+    }
+}
+
+fn env(name: &str) -> String {
+    std::env::var(name).unwrap_or_default().trim().to_lowercase()
+}
+
+/// Consoles that draw neither colors nor anything beyond ASCII reliably
+fn basic_console(term: &str) -> bool {
+    term == "dumb" || term.starts_with("vt1") || term.starts_with("vt2")
+}
+
+impl Display {
+    /// What the terminal supports, from `NO_COLOR`, `TERM`, `COLORTERM` and the
+    /// locale, unless `--color` / `--glyphs` say otherwise
+    pub fn detect(colors: ColorMode, glyphs: GlyphMode) -> Self {
+        let term = env("TERM");
+        let colors = match colors {
+            ColorMode::Forced(level) => level,
+            ColorMode::Auto if !env("NO_COLOR").is_empty() || basic_console(&term) => {
+                ColorLevel::Mono
+            }
+            ColorMode::Auto
+                if term.is_empty()
+                    || term.contains("256color")
+                    || term.contains("direct")
+                    || matches!(env("COLORTERM").as_str(), "truecolor" | "24bit") =>
+            {
+                ColorLevel::Full
+            }
+            ColorMode::Auto => ColorLevel::Basic,
+        };
+        let unicode = match glyphs {
+            GlyphMode::Unicode => true,
+            GlyphMode::Ascii => false,
+            GlyphMode::Auto => {
+                // The first locale variable set decides; with none set, assume UTF-8
+                let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+                    .iter()
+                    .map(|name| env(name))
+                    .find(|value| !value.is_empty());
+                let utf8 = match locale {
+                    Some(l) => l.contains("utf-8") || l.contains("utf8"),
+                    None => true,
+                };
+                utf8 && !basic_console(&term) && term != "linux"
+            }
+        };
+        Self { colors, unicode }
+    }
+
+    /// Whether frames need bringing down to the terminal at all
+    pub fn is_degraded(&self) -> bool {
+        self.colors < ColorLevel::Full || !self.unicode
+    }
+
+    /// e.g. "16 colors, ASCII"
+    pub fn describe(&self) -> String {
+        let glyphs = if self.unicode { "Unicode" } else { "ASCII" };
+        format!("{}, {}", self.colors.name(), glyphs)
+    }
+
+    /// Bring a drawn frame down to what the terminal can show
+    pub fn apply(&self, buffer: &mut Buffer) {
+        if !self.is_degraded() {
+            return;
+        }
+        for cell in buffer.content.iter_mut() {
+            if !self.unicode && !cell.symbol().is_ascii() {
+                let symbol = ascii_symbol(cell.symbol());
+                cell.set_symbol(symbol);
+            }
+            match self.colors {
+                ColorLevel::Full => {}
+                ColorLevel::Basic => {
+                    cell.fg = basic_color(cell.fg);
+                    cell.bg = basic_color(cell.bg);
+                }
+                ColorLevel::Mono => {
+                    // Banners and highlights keep standing out, reversed
+                    if !matches!(cell.bg, Color::Reset | Color::Black) {
+                        cell.modifier.insert(Modifier::REVERSED);
+                    }
+                    cell.fg = Color::Reset;
+                    cell.bg = Color::Reset;
+                }
+            }
+        }
+    }
+}
+
+/// The 16 ANSI colors with their usual xterm RGB values
+const BASIC_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// The nearest of the 16 ANSI colors to an RGB or 256-palette color
+fn basic_color(color: Color) -> Color {
+    let (r, g, b) = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(i) if i < 16 => return BASIC_COLORS[i as usize].0,
+        Color::Indexed(i) if i < 232 => {
+            // 6x6x6 color cube
+            let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+            let i = i - 16;
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        Color::Indexed(i) => {
+            let gray = 8 + (i - 232) * 10;
+            (gray, gray, gray)
+        }
+        other => return other,
+    };
+    let distance = |(cr, cg, cb): (u8, u8, u8)| {
+        let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+        d(r, cr) + d(g, cg) + d(b, cb)
+    };
+    BASIC_COLORS
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .map(|(c, _)| *c)
+        .unwrap_or(Color::Reset)
+}
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+/// An ASCII stand-in for a cell's symbol
+fn ascii_symbol(symbol: &str) -> &'static str {
+    let Some(c) = symbol.chars().next() else {
+        return " ";
+    };
+    match c {
+        '─' | '━' | '═' | '╌' | '╍' | '┄' | '┅' | '┈' | '┉' | '—' | '–' | '‒' | '−' => "-",
+        '│' | '┃' | '║' | '╎' | '╏' | '┆' | '┇' | '┊' | '┋' => "|",
+        // Corners, tees and crossings
+        '\u{2500}'..='\u{257F}' => "+",
+        // Sparkline bars, lowest to highest, and gauge blocks
+        '▁' => "_",
+        '▂' | '▃' => ".",
+        '▄' | '▅' => "-",
+        '▆' | '▇' => "=",
+        '▏' | '▎' | '▍' => "|",
+        '░' => ":",
+        '▒' => "%",
+        '\u{2580}'..='\u{259F}' => "#",
+        // Braille chart dots
+        '\u{2800}' => " ",
+        '\u{2801}'..='\u{28FF}' => ".",
+        '▲' | '△' | '▴' | '↑' | '⬆' => "^",
+        '▼' | '▽' | '▾' | '↓' | '⬇' => "v",
+        '→' | '▶' | '►' | '▸' | '➜' => ">",
+        '←' | '◀' | '◄' | '◂' => "<",
+        '↗' => "/",
+        '↘' => "\\",
+        '•' | '●' | '◆' | '■' | '★' | '✦' | '◉' => "*",
+        '○' | '◯' | '◇' | '□' | '☆' => "o",
+        '·' | '…' | '⋯' => ".",
+        '×' | '✗' | '✘' | '✖' | '❌' => "x",
+        '✓' | '✔' | '✅' => "+",
+        '≈' | '∼' => "~",
+        '≥' => ">",
+        '≤' => "<",
+        '±' => "+",
+        '°' => "o",
+        '€' => "E",
+        '£' => "L",
+        '¥' => "Y",
+        '₿' => "B",
+        // Subscript zero counts in prices, e.g. $0.0₄412
+        '₀'..='₉' => DIGITS[(c as u32 - '₀' as u32) as usize],
+        '⚠' | '❗' | '‼' | '🚨' => "!",
+        'ℹ' => "i",
+        // Other symbols and emoji
+        '\u{2190}'..='\u{2BFF}' | '\u{1F000}'..='\u{1FAFF}' => "*",
+        _ => "?",
+    }
+}
@@ -0,0 +1,167 @@
+//! Lowering other applications' audio while the alarm sounds. There's no
+//! portable mixer API, so each platform drives its own tool: `pactl`
+//! (PulseAudio / PipeWire) on Linux, AppleScript for Music and Spotify on macOS.
+
+/// Other applications' volumes, lowered until this is dropped
+pub struct Ducked {
+    #[cfg_attr(not(any(target_os = "linux", target_os = "macos")), allow(dead_code))]
+    restore: Vec<platform::Saved>,
+}
+
+/// Lower every other application's volume to `percent` of its current level
+pub fn duck(percent: u8) -> Result<Ducked, String> {
+    let percent = percent.min(100) as u64;
+    Ok(Ducked {
+        restore: platform::duck(percent)?,
+    })
+}
+
+impl Drop for Ducked {
+    fn drop(&mut self) {
+        platform::restore(&self.restore);
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::process::Command;
+
+    /// A PulseAudio sink input and its per-channel raw volumes
+    pub struct Saved {
+        index: u32,
+        volumes: Vec<u64>,
+    }
+
+    pub fn duck(percent: u64) -> Result<Vec<Saved>, String> {
+        let output = Command::new("pactl")
+            .env("LC_ALL", "C")
+            .args(["list", "sink-inputs"])
+            .output()
+            .map_err(|e| format!("pactl not available: {}", e))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+
+        let own_pid = std::process::id().to_string();
+        let saved: Vec<Saved> = parse_sink_inputs(&String::from_utf8_lossy(&output.stdout))
+            .into_iter()
+            .filter(|(_, pid)| pid.as_deref() != Some(own_pid.as_str()))
+            .map(|(saved, _)| saved)
+            .collect();
+        for input in &saved {
+            let lowered: Vec<u64> = input.volumes.iter().map(|v| v * percent / 100).collect();
+            set_volume(input.index, &lowered);
+        }
+        Ok(saved)
+    }
+
+    pub fn restore(saved: &[Saved]) {
+        for input in saved {
+            set_volume(input.index, &input.volumes);
+        }
+    }
+
+    fn set_volume(index: u32, volumes: &[u64]) {
+        let mut command = Command::new("pactl");
+        command.args(["set-sink-input-volume", &index.to_string()]);
+        command.args(volumes.iter().map(|v| v.to_string()));
+        let _ = command.output();
+    }
+
+    /// Sink inputs and their owning process ids from `pactl list sink-inputs`:
+    ///
+    /// ```text
+    /// Sink Input #42
+    ///     Volume: front-left: 65536 / 100% / 0.00 dB,   front-right: 65536 / 100% / 0.00 dB
+    ///         application.process.id = "1234"
+    /// ```
+    fn parse_sink_inputs(text: &str) -> Vec<(Saved, Option<String>)> {
+        let mut inputs: Vec<(Saved, Option<String>)> = Vec::new();
+        for line in text.lines().map(str::trim) {
+            if let Some(index) = line.strip_prefix("Sink Input #") {
+                if let Ok(index) = index.trim().parse() {
+                    inputs.push((
+                        Saved {
+                            index,
+                            volumes: Vec::new(),
+                        },
+                        None,
+                    ));
+                }
+                continue;
+            }
+            let Some((input, pid)) = inputs.last_mut() else {
+                continue;
+            };
+            if let Some(channels) = line.strip_prefix("Volume:") {
+                input.volumes = channels
+                    .split(',')
+                    .filter_map(|c| c.split(':').nth(1)?.split('/').next()?.trim().parse().ok())
+                    .collect();
+            } else if let Some(value) = line.strip_prefix("application.process.id =") {
+                *pid = Some(value.trim().trim_matches('"').to_string());
+            }
+        }
+        inputs.retain(|(input, _)| !input.volumes.is_empty());
+        inputs
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::process::Command;
+
+    /// macOS has no per-app mixer, so only players with their own volume are ducked
+    const PLAYERS: [&str; 2] = ["Music", "Spotify"];
+
+    /// A player and its volume (0-100)
+    pub struct Saved {
+        player: &'static str,
+        volume: u64,
+    }
+
+    pub fn duck(percent: u64) -> Result<Vec<Saved>, String> {
+        let mut saved = Vec::new();
+        for player in PLAYERS {
+            let script = format!(
+                "if application \"{0}\" is running then tell application \"{0}\" to get sound volume",
+                player
+            );
+            let Some(volume) = osascript(&script).and_then(|v| v.trim().parse::<u64>().ok()) else {
+                continue;
+            };
+            set_volume(player, volume * percent / 100);
+            saved.push(Saved { player, volume });
+        }
+        Ok(saved)
+    }
+
+    pub fn restore(saved: &[Saved]) {
+        for entry in saved {
+            set_volume(entry.player, entry.volume);
+        }
+    }
+
+    fn set_volume(player: &str, volume: u64) {
+        osascript(&format!("tell application \"{}\" to set sound volume to {}", player, volume));
+    }
+
+    fn osascript(script: &str) -> Option<String> {
+        let output = Command::new("osascript").args(["-e", script]).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+mod platform {
+    pub struct Saved;
+
+    pub fn duck(_: u64) -> Result<Vec<Saved>, String> {
+        Err("audio ducking isn't supported on this platform".to_string())
+    }
+
+    pub fn restore(_: &[Saved]) {}
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::config::ApiConfig;
+use crate::secrets;
+
+/// Data APIs whose base URL and headers can be overridden in the config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Api {
+    DexScreener,
+    GeckoTerminal,
+    CoinGecko,
+    Jupiter,
+    PumpFun,
+    Birdeye,
+    GoPlus,
+    RugCheck,
+    Ens,
+    Sns,
+    Fx,
+    Telegram,
+    Twitter,
+}
+
+impl Api {
+    pub const ALL: [Api; 13] = [
+        Api::DexScreener,
+        Api::GeckoTerminal,
+        Api::CoinGecko,
+        Api::Jupiter,
+        Api::PumpFun,
+        Api::Birdeye,
+        Api::GoPlus,
+        Api::RugCheck,
+        Api::Ens,
+        Api::Sns,
+        Api::Fx,
+        Api::Telegram,
+        Api::Twitter,
+    ];
+
+    /// Key under `apis` in the config file
+    pub fn key(&self) -> &'static str {
+        match self {
+            Api::DexScreener => "dexscreener",
+            Api::GeckoTerminal => "geckoterminal",
+            Api::CoinGecko => "coingecko",
+            Api::Jupiter => "jupiter",
+            Api::PumpFun => "pumpfun",
+            Api::Birdeye => "birdeye",
+            Api::GoPlus => "goplus",
+            Api::RugCheck => "rugcheck",
+            Api::Ens => "ens",
+            Api::Sns => "sns",
+            Api::Fx => "fx",
+            Api::Telegram => "telegram",
+            Api::Twitter => "twitter",
+        }
+    }
+}
+
+/// Overrides by API, set once at startup
+static OVERRIDES: OnceLock<HashMap<&'static str, ApiConfig>> = OnceLock::new();
+
+/// Install the `apis` section of the config. Only the first call takes
+/// effect. Returns the keys that don't name a known API.
+pub fn configure(apis: &HashMap<String, ApiConfig>) -> Vec<String> {
+    let mut overrides = HashMap::new();
+    let mut unknown = Vec::new();
+    for (name, api) in apis {
+        let Some(known) = Api::ALL.iter().find(|a| a.key().eq_ignore_ascii_case(name.trim())) else {
+            unknown.push(name.clone());
+            continue;
+        };
+        let mut api = api.clone();
+        api.base_url = api
+            .base_url
+            .map(|u| u.trim().trim_end_matches('/').to_string())
+            .filter(|u| !u.is_empty());
+        overrides.insert(known.key(), api);
+    }
+    let _ = OVERRIDES.set(overrides);
+    unknown.sort();
+    unknown
+}
+
+fn get_override(api: Api) -> Option<&'static ApiConfig> {
+    OVERRIDES.get()?.get(api.key())
+}
+
+/// Base URL for `api`: the configured one, else `default`
+pub fn base_url(api: Api, default: &'static str) -> &'static str {
+    get_override(api)
+        .and_then(|a| a.base_url.as_deref())
+        .unwrap_or(default)
+}
+
+/// A GET request to `url` carrying `api`'s configured headers (API keys).
+/// Headers naming a keyring secret that isn't stored are left out.
+pub fn get(client: &reqwest::Client, api: Api, url: &str) -> reqwest::RequestBuilder {
+    let mut request = client.get(url);
+    if let Some(config) = get_override(api) {
+        for (name, value) in &config.headers {
+            if let Some(value) = secrets::resolve(value) {
+                request = request.header(name.as_str(), value);
+            }
+        }
+    }
+    request
+}
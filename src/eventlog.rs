@@ -0,0 +1,177 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use chrono::Local;
+use serde::Serialize;
+
+use crate::alerts::{Target, TargetKind};
+use crate::api::PairData;
+use crate::notify::Severity;
+use crate::storage;
+
+/// One line of the event log
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    /// A TUI or daemon process started writing to the log
+    Started {
+        at: String,
+        mode: &'static str,
+        host: String,
+        pid: u32,
+    },
+    /// The monitored pair, target or interval was set
+    Config {
+        at: String,
+        chain: String,
+        pair: String,
+        target: f64,
+        target_kind: TargetKind,
+        interval: u64,
+    },
+    Fetch {
+        at: String,
+        chain: String,
+        pair: String,
+        symbol: String,
+        price_usd: f64,
+        market_cap: f64,
+        liquidity_usd: f64,
+        volume_24h: f64,
+    },
+    FetchFailed {
+        at: String,
+        chain: String,
+        pair: String,
+        error: String,
+    },
+    Alert {
+        at: String,
+        chain: String,
+        pair: String,
+        symbol: String,
+        severity: Severity,
+        message: String,
+    },
+    AlarmStopped {
+        at: String,
+        chain: String,
+        pair: String,
+        reason: String,
+    },
+}
+
+impl Event {
+    pub fn started(mode: &'static str) -> Self {
+        Event::Started {
+            at: now(),
+            mode,
+            host: storage::hostname(),
+            pid: std::process::id(),
+        }
+    }
+
+    pub fn config(chain: &str, pair: &str, target: Target, interval: u64) -> Self {
+        Event::Config {
+            at: now(),
+            chain: chain.to_string(),
+            pair: pair.to_string(),
+            target: target.value,
+            target_kind: target.kind,
+            interval,
+        }
+    }
+
+    pub fn fetch(chain: &str, pair: &str, data: &PairData) -> Self {
+        Event::Fetch {
+            at: now(),
+            chain: chain.to_string(),
+            pair: pair.to_string(),
+            symbol: data.base_symbol().to_string(),
+            price_usd: data.price_usd_f64(),
+            market_cap: data.market_cap_usd(),
+            liquidity_usd: data.liquidity_usd(),
+            volume_24h: data.volume_24h_usd(),
+        }
+    }
+
+    pub fn fetch_failed(chain: &str, pair: &str, error: &str) -> Self {
+        Event::FetchFailed {
+            at: now(),
+            chain: chain.to_string(),
+            pair: pair.to_string(),
+            error: error.to_string(),
+        }
+    }
+
+    pub fn alert(chain: &str, pair: &str, symbol: &str, severity: Severity, message: &str) -> Self {
+        Event::Alert {
+            at: now(),
+            chain: chain.to_string(),
+            pair: pair.to_string(),
+            symbol: symbol.to_string(),
+            severity,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn alarm_stopped(chain: &str, pair: &str, reason: &str) -> Self {
+        Event::AlarmStopped {
+            at: now(),
+            chain: chain.to_string(),
+            pair: pair.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+fn now() -> String {
+    Local::now().to_rfc3339()
+}
+
+/// Append-only JSONL file of fetches, alerts and configuration changes, for
+/// post-mortems. Each line goes straight to the OS in a single append, so
+/// killing the process loses nothing already recorded and several processes
+/// can share one file; alerts are also fsynced to survive a crash or power cut.
+pub struct EventLog {
+    file: File,
+    path: String,
+}
+
+impl EventLog {
+    pub fn open(path: &str) -> Result<Self, String> {
+        if let Some(dir) = Path::new(path).parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Can't create {}: {}", dir.display(), e))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Can't open {}: {}", path, e))?;
+        Ok(Self {
+            file,
+            path: path.to_string(),
+        })
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn append(&mut self, event: &Event) -> Result<(), String> {
+        let mut line = serde_json::to_string(event)
+            .map_err(|e| format!("Failed to serialize event: {}", e))?;
+        line.push('\n');
+        self.file
+            .write_all(line.as_bytes())
+            .map_err(|e| format!("Write to {} failed: {}", self.path, e))?;
+        if matches!(event, Event::Alert { .. }) {
+            self.file
+                .sync_data()
+                .map_err(|e| format!("fsync of {} failed: {}", self.path, e))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::api;
+use crate::endpoints::{self, Api};
+
+/// ECB reference rates via Frankfurter, updated on working days
+const BASE_URL: &str = "https://api.frankfurter.app";
+
+/// Symbols for common currencies; others are shown by code, e.g. "CHF 1.2M"
+const SYMBOLS: &[(&str, &str)] = &[
+    ("USD", "$"),
+    ("EUR", "€"),
+    ("GBP", "£"),
+    ("JPY", "¥"),
+    ("CNY", "¥"),
+    ("KRW", "₩"),
+    ("INR", "₹"),
+    ("TRY", "₺"),
+    ("BRL", "R$"),
+    ("AUD", "A$"),
+    ("CAD", "C$"),
+    ("HKD", "HK$"),
+    ("SGD", "S$"),
+];
+
+#[derive(Debug, Deserialize)]
+struct RatesResponse {
+    rates: HashMap<String, f64>,
+}
+
+/// Currency values are displayed in. Everything is fetched in USD and
+/// converted at display time; until a rate is known values stay in USD.
+#[derive(Debug, Clone)]
+pub struct Currency {
+    /// ISO 4217 code, uppercase
+    pub code: String,
+    /// Units of the currency per US dollar
+    pub rate: Option<f64>,
+}
+
+impl Currency {
+    pub fn usd() -> Self {
+        Self {
+            code: "USD".to_string(),
+            rate: Some(1.0),
+        }
+    }
+
+    /// Parse a currency code such as "eur"
+    pub fn parse(code: &str) -> Result<Self, String> {
+        let code = code.trim().to_uppercase();
+        if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("'{}' is not a currency code (e.g. EUR, GBP, JPY)", code));
+        }
+        if code == "USD" {
+            return Ok(Self::usd());
+        }
+        Ok(Self { code, rate: None })
+    }
+
+    pub fn is_usd(&self) -> bool {
+        self.code == "USD"
+    }
+
+    /// Prefix for displayed amounts: the currency's symbol, or its code and a
+    /// space. "$" while the rate is still unknown, since values are then USD.
+    pub fn symbol(&self) -> String {
+        if self.rate.is_none() {
+            return "$".to_string();
+        }
+        SYMBOLS
+            .iter()
+            .find(|(code, _)| *code == self.code)
+            .map(|(_, symbol)| symbol.to_string())
+            .unwrap_or_else(|| format!("{} ", self.code))
+    }
+
+    /// A USD amount in this currency
+    pub fn convert(&self, usd: f64) -> f64 {
+        usd * self.rate.unwrap_or(1.0)
+    }
+}
+
+/// Units of `code` per US dollar
+pub async fn fetch_rate(client: &reqwest::Client, code: &str) -> Result<f64, String> {
+    let url = format!(
+        "{}/latest?from=USD&to={}",
+        endpoints::base_url(Api::Fx, BASE_URL),
+        code
+    );
+    let response = endpoints::get(client, Api::Fx, &url)
+        .header("Accept", "application/json")
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(api::describe_http_error(status, &body));
+    }
+
+    let parsed: RatesResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("JSON parse error: {}", e))?;
+    parsed
+        .rates
+        .get(code)
+        .copied()
+        .filter(|r| *r > 0.0)
+        .ok_or_else(|| format!("No USD/{} rate", code))
+}
@@ -0,0 +1,126 @@
+use serde::Deserialize;
+
+use crate::api;
+use crate::endpoints::{self, Api};
+
+const BASE_URL: &str = "https://api.geckoterminal.com/api/v2";
+
+/// Most candles GeckoTerminal returns for one request
+pub const MAX_CANDLES: usize = 1000;
+
+/// One OHLCV candle, prices in USD
+#[derive(Debug, Clone, Copy)]
+pub struct Candle {
+    /// Unix seconds at the candle's open
+    pub timestamp: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OhlcvResponse {
+    data: OhlcvData,
+}
+
+#[derive(Debug, Deserialize)]
+struct OhlcvData {
+    attributes: OhlcvAttributes,
+}
+
+#[derive(Debug, Deserialize)]
+struct OhlcvAttributes {
+    ohlcv_list: Vec<[f64; 6]>,
+}
+
+/// Map a DexScreener chain id to GeckoTerminal's network id
+pub fn network_id(chain: &str) -> String {
+    match chain.to_lowercase().as_str() {
+        "ethereum" => "eth".to_string(),
+        "polygon" => "polygon_pos".to_string(),
+        "avalanche" => "avax".to_string(),
+        "fantom" => "ftm".to_string(),
+        "pulsechain" => "pulsechain".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Pick a candle timeframe (`minute`/`hour`/`day`, aggregate) close to the
+/// fetch interval so backfilled points line up with live ones
+pub fn timeframe_for_interval(interval_secs: u64) -> (&'static str, u32) {
+    match interval_secs {
+        0..=90 => ("minute", 1),
+        91..=450 => ("minute", 5),
+        451..=1800 => ("minute", 15),
+        1801..=7200 => ("hour", 1),
+        7201..=28800 => ("hour", 4),
+        _ => ("day", 1),
+    }
+}
+
+/// Length in seconds of the candles `timeframe_for_interval` picks
+pub fn candle_secs(interval_secs: u64) -> u64 {
+    let (timeframe, aggregate) = timeframe_for_interval(interval_secs);
+    let unit = match timeframe {
+        "minute" => 60,
+        "hour" => 3600,
+        _ => 86400,
+    };
+    unit * aggregate as u64
+}
+
+/// Fetch up to `limit` candles for a pool, oldest first
+pub async fn fetch_ohlcv(
+    client: &reqwest::Client,
+    chain: &str,
+    pool_address: &str,
+    interval_secs: u64,
+    limit: usize,
+) -> Result<Vec<Candle>, String> {
+    let (timeframe, aggregate) = timeframe_for_interval(interval_secs);
+    let url = format!(
+        "{}/networks/{}/pools/{}/ohlcv/{}?aggregate={}&limit={}&currency=usd",
+        endpoints::base_url(Api::GeckoTerminal, BASE_URL),
+        network_id(chain),
+        pool_address,
+        timeframe,
+        aggregate,
+        limit
+    );
+
+    let response = endpoints::get(client, Api::GeckoTerminal, &url)
+        .header("Accept", "application/json")
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(api::describe_http_error(status, &body));
+    }
+
+    let parsed: OhlcvResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("JSON parse error: {}", e))?;
+
+    // GeckoTerminal returns newest first
+    let mut candles: Vec<Candle> = parsed
+        .data
+        .attributes
+        .ohlcv_list
+        .iter()
+        .map(|c| Candle {
+            timestamp: c[0] as i64,
+            open: c[1],
+            high: c[2],
+            low: c[3],
+            close: c[4],
+        })
+        .collect();
+    candles.sort_by_key(|c| c.timestamp);
+    Ok(candles)
+}
@@ -0,0 +1,150 @@
+//! Response time and error rate of each market data provider, so the failover
+//! chain can lead with whichever is answering fastest and most reliably.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::api::Provider;
+
+/// Requests remembered per provider
+const SAMPLES: usize = 20;
+
+/// Requests older than this no longer count
+const WINDOW: Duration = Duration::from_secs(30 * 60);
+
+/// How much better another provider's score must be to take the lead, so two
+/// similar providers don't swap places on every fetch
+const SWITCH_MARGIN: f64 = 1.25;
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    at: Instant,
+    latency: Duration,
+    ok: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Record {
+    provider: Provider,
+    samples: VecDeque<Sample>,
+    last_error: Option<String>,
+}
+
+/// Recent requests to one provider, summed up
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub provider: Provider,
+    /// Median response time, failures included (a timeout is slow too)
+    pub latency: Duration,
+    /// Share of requests that failed, 0 to 1
+    pub error_rate: f64,
+    pub requests: usize,
+    pub last_error: Option<String>,
+}
+
+impl Summary {
+    /// Expected wait for a good answer: latency over success rate. Lower is better.
+    pub fn score(&self) -> f64 {
+        self.latency.as_secs_f64() * 1000.0 / (1.0 - self.error_rate).max(0.05)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProviderHealth {
+    records: Vec<Record>,
+    leader: Option<Provider>,
+}
+
+impl ProviderHealth {
+    /// Note how one request to `provider` went
+    pub fn record(&mut self, provider: Provider, latency: Duration, error: Option<&str>) {
+        let index = match self.records.iter().position(|r| r.provider == provider) {
+            Some(index) => index,
+            None => {
+                self.records.push(Record {
+                    provider,
+                    samples: VecDeque::new(),
+                    last_error: None,
+                });
+                self.records.len() - 1
+            }
+        };
+        let record = &mut self.records[index];
+        record.samples.push_back(Sample {
+            at: Instant::now(),
+            latency,
+            ok: error.is_none(),
+        });
+        while record.samples.len() > SAMPLES {
+            record.samples.pop_front();
+        }
+        if let Some(error) = error {
+            record.last_error = Some(error.to_string());
+        }
+    }
+
+    /// Recent requests to `provider`, or None if it hasn't been asked lately
+    pub fn summary(&self, provider: Provider) -> Option<Summary> {
+        let record = self.records.iter().find(|r| r.provider == provider)?;
+        let mut latencies: Vec<Duration> = record
+            .samples
+            .iter()
+            .filter(|s| s.at.elapsed() <= WINDOW)
+            .map(|s| s.latency)
+            .collect();
+        if latencies.is_empty() {
+            return None;
+        }
+        let failures = record
+            .samples
+            .iter()
+            .filter(|s| s.at.elapsed() <= WINDOW && !s.ok)
+            .count();
+        latencies.sort();
+        Some(Summary {
+            provider,
+            latency: latencies[latencies.len() / 2],
+            error_rate: failures as f64 / latencies.len() as f64,
+            requests: latencies.len(),
+            last_error: record.last_error.clone(),
+        })
+    }
+
+    /// Every provider asked so far, in the order they were first used
+    pub fn providers(&self) -> Vec<Provider> {
+        self.records.iter().map(|r| r.provider).collect()
+    }
+
+    /// The provider currently leading the failover chain, once one is measured
+    pub fn leader(&self) -> Option<Provider> {
+        self.leader
+    }
+
+    /// `providers` reordered healthiest first. The leader keeps its place until
+    /// another provider scores clearly better; providers without recent
+    /// requests follow the measured ones in their configured order.
+    pub fn rank(&mut self, providers: &[Provider]) -> Vec<Provider> {
+        let mut measured: Vec<(Provider, f64)> = providers
+            .iter()
+            .filter_map(|p| self.summary(*p).map(|s| (*p, s.score())))
+            .collect();
+        measured.sort_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some(&(best, best_score)) = measured.first() {
+            let keep = self
+                .leader
+                .and_then(|leader| measured.iter().find(|(p, _)| *p == leader))
+                .filter(|(_, score)| *score <= best_score * SWITCH_MARGIN)
+                .map(|(p, _)| *p);
+            self.leader = Some(keep.unwrap_or(best));
+        }
+
+        let leader = self.leader.filter(|l| providers.contains(l));
+        let mut order: Vec<Provider> = leader.into_iter().collect();
+        for provider in measured.iter().map(|(p, _)| p).chain(providers) {
+            if !order.contains(provider) {
+                order.push(*provider);
+            }
+        }
+        order
+    }
+}
@@ -0,0 +1,102 @@
+//! The `on_alert` hook: a shell command run on every alert, with the alert
+//! described in environment variables, for integrations mooncap doesn't have
+//! (a sell script, a home automation trigger, ...).
+
+use std::process::{Child, Command, Stdio};
+
+use crate::notify::{AlertContext, Severity};
+
+/// Environment the command runs with
+fn vars(
+    severity: Severity,
+    message: &str,
+    rule: &str,
+    context: &AlertContext,
+) -> Vec<(&'static str, String)> {
+    vec![
+        ("SYMBOL", context.symbol.clone()),
+        ("MCAP", format!("{:.0}", context.market_cap)),
+        ("TARGET", context.target.to_string()),
+        ("RULE", rule.to_string()),
+        ("SEVERITY", severity.name().to_string()),
+        ("MESSAGE", message.to_string()),
+        ("NAME", context.name.clone()),
+        ("CHAIN", context.chain.clone()),
+        ("PAIR", context.pair.clone()),
+        ("PRICE", context.price.to_string()),
+    ]
+}
+
+/// A started command, until it exits
+#[derive(Debug)]
+struct Running {
+    rule: String,
+    child: Child,
+}
+
+/// Commands started by alerts. They run in the background; `reap` collects
+/// the ones that have exited.
+#[derive(Debug, Default)]
+pub struct Hooks {
+    running: Vec<Running>,
+}
+
+impl Hooks {
+    /// Start `command` through the shell for an alert. Returns its process id.
+    pub fn run(
+        &mut self,
+        command: &str,
+        severity: Severity,
+        message: &str,
+        rule: &str,
+        context: &AlertContext,
+    ) -> Result<u32, String> {
+        #[cfg(windows)]
+        let mut shell = {
+            let mut shell = Command::new("cmd");
+            shell.arg("/C");
+            shell
+        };
+        #[cfg(not(windows))]
+        let mut shell = {
+            let mut shell = Command::new("sh");
+            shell.arg("-c");
+            shell
+        };
+        // Output would draw over the TUI
+        let child = shell
+            .arg(command)
+            .envs(vars(severity, message, rule, context))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("could not run on_alert command: {}", e))?;
+        let pid = child.id();
+        self.running.push(Running {
+            rule: rule.to_string(),
+            child,
+        });
+        Ok(pid)
+    }
+
+    /// Collect commands that have exited, describing the ones that failed
+    pub fn reap(&mut self) -> Vec<String> {
+        let mut failures = Vec::new();
+        self.running.retain_mut(|running| match running.child.try_wait() {
+            Ok(None) => true,
+            Ok(Some(status)) => {
+                if !status.success() {
+                    let rule = if running.rule.is_empty() { "alert" } else { &running.rule };
+                    failures.push(format!("on_alert command for {} failed ({})", rule, status));
+                }
+                false
+            }
+            Err(e) => {
+                failures.push(format!("on_alert command lost: {}", e));
+                false
+            }
+        });
+        failures
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// xorshift state, seeded from the clock and pid on first use so instances
+/// started together still pick different delays
+static STATE: AtomicU64 = AtomicU64::new(0);
+
+/// A random delay between zero and `max_secs`, added to each scheduled fetch
+/// so several mooncaps polling the same API don't all hit it at once
+pub fn delay(max_secs: u64) -> Duration {
+    if max_secs == 0 {
+        return Duration::ZERO;
+    }
+    let millis = next() % (max_secs * 1000 + 1);
+    Duration::from_millis(millis)
+}
+
+/// Time from `from` to the next multiple of `interval_secs` since the Unix
+/// epoch, so aligned fetches land on the same wall-clock seconds on every
+/// machine (every minute at :00, every 5 minutes at :00, :05, ...)
+pub fn until_boundary(interval_secs: u64, from: SystemTime) -> Duration {
+    let interval = interval_secs.max(1) as u128 * 1000;
+    let millis = from
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    Duration::from_millis((interval - millis % interval) as u64)
+}
+
+fn next() -> u64 {
+    let mut x = STATE.load(Ordering::Relaxed);
+    if x == 0 {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        x = (nanos ^ ((std::process::id() as u64) << 32)).max(1);
+    }
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    STATE.store(x, Ordering::Relaxed);
+    // xorshift64*: the multiply mixes the low bits used by the modulo
+    x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+}
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::api::{
+    self, DataSource, Liquidity, PairData, PriceChange, Provider, Target, Token, TxnCount, Txns,
+    Volume,
+};
+use crate::endpoints::{self, Api};
+
+const BASE_URL: &str = "https://lite-api.jup.ag/tokens/v2";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JupiterToken {
+    id: String,
+    name: Option<String>,
+    symbol: Option<String>,
+    usd_price: Option<f64>,
+    mcap: Option<f64>,
+    fdv: Option<f64>,
+    liquidity: Option<f64>,
+    stats5m: Option<Stats>,
+    stats1h: Option<Stats>,
+    stats24h: Option<Stats>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Stats {
+    price_change: Option<f64>,
+    buy_volume: Option<f64>,
+    sell_volume: Option<f64>,
+    num_buys: Option<u64>,
+    num_sells: Option<u64>,
+}
+
+/// Jupiter token API (Solana only); prefers the known mint over a pool address
+pub struct Jupiter {
+    client: reqwest::Client,
+}
+
+impl Jupiter {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl DataSource for Jupiter {
+    fn provider(&self) -> Provider {
+        Provider::Jupiter
+    }
+
+    async fn fetch(&self, target: &Target<'_>) -> Result<PairData, String> {
+        fetch_mint(&self.client, target.token.unwrap_or(target.address)).await
+    }
+}
+
+/// Look up a Solana mint on Jupiter and map it into `PairData`. Jupiter
+/// aggregates across pools, so there is no single pool (`dex_id` is None).
+async fn fetch_mint(client: &reqwest::Client, mint: &str) -> Result<PairData, String> {
+    let url = format!(
+        "{}/search?query={}",
+        endpoints::base_url(Api::Jupiter, BASE_URL),
+        mint.trim()
+    );
+
+    let response = endpoints::get(client, Api::Jupiter, &url)
+        .header("Accept", "application/json")
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(api::describe_http_error(status, &body));
+    }
+
+    let tokens: Vec<JupiterToken> = response
+        .json()
+        .await
+        .map_err(|e| format!("JSON parse error: {}", e))?;
+
+    let token = tokens
+        .into_iter()
+        .find(|t| t.id == mint.trim())
+        .ok_or_else(|| format!("Jupiter has no token with mint {}", mint))?;
+
+    let day = token.stats24h.as_ref();
+    let volume_24h = day.map(|s| s.buy_volume.unwrap_or(0.0) + s.sell_volume.unwrap_or(0.0));
+
+    Ok(PairData {
+        chain_id: Some("solana".to_string()),
+        dex_id: None,
+        pair_address: None,
+        base_token: Some(Token {
+            address: Some(token.id),
+            name: token.name,
+            symbol: token.symbol,
+        }),
+        quote_token: None,
+        price_native: None,
+        price_usd: token.usd_price.map(|p| p.to_string()),
+        fdv: token.fdv,
+        market_cap: token.mcap,
+        txns: day.map(|s| Txns {
+            m5: None,
+            h1: None,
+            h6: None,
+            h24: Some(TxnCount {
+                buys: s.num_buys,
+                sells: s.num_sells,
+            }),
+        }),
+        volume: Some(Volume {
+            h24: volume_24h,
+            h6: None,
+            h1: None,
+            m5: None,
+        }),
+        price_change: Some(PriceChange {
+            m5: token.stats5m.as_ref().and_then(|s| s.price_change),
+            h1: token.stats1h.as_ref().and_then(|s| s.price_change),
+            h6: None,
+            h24: day.and_then(|s| s.price_change),
+        }),
+        liquidity: Some(Liquidity {
+            usd: token.liquidity,
+            base: None,
+            quote: None,
+        }),
+        pair_created_at: None,
+        info: None,
+        bonding_curve: None,
+    })
+}
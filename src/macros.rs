@@ -0,0 +1,59 @@
+//! Recorded keystroke sequences, replayed from a digit key. Keys are stored by
+//! name (`x`, `enter`, `ctrl+p`) so macros in the config file stay readable
+//! and editable by hand.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Longest macro that can be recorded, so a forgotten recording doesn't grow forever
+pub const MAX_KEYS: usize = 64;
+
+/// Name of a key press, or None for keys macros can't hold (function keys, ...)
+pub fn key_name(code: KeyCode, modifiers: KeyModifiers) -> Option<String> {
+    let name = match code {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::BackTab => "backtab".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        _ => return None,
+    };
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        Some(format!("ctrl+{}", name))
+    } else {
+        Some(name)
+    }
+}
+
+/// The key press a stored name stands for
+pub fn parse_key(name: &str) -> Option<(KeyCode, KeyModifiers)> {
+    let (modifiers, name) = match name.strip_prefix("ctrl+") {
+        Some(rest) => (KeyModifiers::CONTROL, rest),
+        None => (KeyModifiers::NONE, name),
+    };
+    let code = match name {
+        "space" => KeyCode::Char(' '),
+        "enter" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        _ => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => return None,
+            }
+        }
+    };
+    Some((code, modifiers))
+}
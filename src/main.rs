@@ -1,10 +1,65 @@
 mod alarm;
+mod alerts;
 mod api;
 mod app;
+mod ath;
+mod birdeye;
+mod bus;
+mod cex;
+mod chaos;
+mod coingecko;
+mod compare;
+mod condition;
+mod config;
 mod daemon;
+mod discord;
+mod display;
+mod duck;
+mod endpoints;
+mod eventlog;
+mod fx;
+mod geckoterminal;
+mod health;
+mod hook;
+mod jitter;
+mod jupiter;
+mod macros;
+mod metadata;
+mod mqtt;
+mod names;
+mod notify;
+mod ohlc;
+mod onboarding;
+mod output;
+mod pacing;
+mod perps;
+mod portfolio;
+mod power;
+mod profile;
+mod pumpfun;
+mod quotes;
+mod rpc;
+mod safety;
+mod schedule;
+mod screencast;
+mod secrets;
+mod session;
+mod slack;
+mod social;
+mod speech;
 // remove this to avoid animation
 mod splash;
+mod storage;
+#[cfg(feature = "stream")]
+mod stream;
+mod telegram;
+#[cfg(test)]
+mod testing;
+mod twilio;
 mod ui;
+mod unlocks;
+mod wallet;
+mod webhook;
 
 use std::io;
 use std::sync::atomic::AtomicBool;
@@ -12,11 +67,17 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use chrono::Local;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
 use ratatui::DefaultTerminal;
 
-use app::App;
+use alerts::Targets;
+use app::{App, ModalField, SwitcherMode};
+use daemon::DaemonConfig;
+use portfolio::GroupMember;
+
+/// How often to re-check the battery state when auto low-power is enabled
+const POWER_CHECK_INTERVAL: Duration = Duration::from_secs(60);
 
 /// 🚀 MoonCap — Monitor any crypto token's market cap from DexScreener
 #[derive(Parser)]
@@ -30,9 +91,11 @@ struct Cli {
     #[arg(short, long, default_value = "solana")]
     chain: String,
 
-    /// Target market cap to trigger alarm
+    /// Target market cap to trigger alarm, or a token price prefixed with `p:` (e.g. p:0.0005).
+    /// A comma-separated list (e.g. 50000,100000,250000) alerts on each level; the highest
+    /// is the target
     #[arg(short, long, default_value = "100000")]
-    target: f64,
+    target: Targets,
 
     /// Interval between API checks in seconds
     #[arg(short, long, default_value = "180")]
@@ -46,11 +109,250 @@ struct Cli {
     #[arg(long, default_value = "300")]
     alarm_duration: u64,
 
+    /// Data providers to try in order, comma-separated (overrides per-chain config defaults).
+    /// With `coingecko`, --pair is a CoinGecko coin id such as `bitcoin`;
+    /// `pumpfun` tracks Solana tokens still on their pump.fun bonding curve.
+    #[arg(long, alias = "source", value_delimiter = ',')]
+    provider: Vec<String>,
+
+    /// RPC endpoint for on-chain queries (overrides per-chain config defaults)
+    #[arg(long)]
+    rpc_url: Option<String>,
+
+    /// Show a named portfolio from the config file in the portfolio panel
+    #[arg(long)]
+    portfolio: Option<String>,
+
+    /// Alert when this wallet's balance of the monitored token changes. On
+    /// Solana, also watch every token in it in the portfolio panel, re-scanned
+    /// every few minutes as tokens are bought or sold; without --pair, the
+    /// largest holding is monitored. Accepts ENS (.eth) and SNS (.sol) names.
+    #[arg(long, value_name = "ADDRESS")]
+    wallet: Option<String>,
+
+    /// Leave wallet tokens worth less than this many dollars out of the watchlist
+    #[arg(long, value_name = "USD", default_value = "10")]
+    wallet_min: f64,
+
+    /// A second pair address to show side-by-side with --pair
+    #[arg(long)]
+    compare: Option<String>,
+
+    /// Chain of the --compare pair (defaults to --chain)
+    #[arg(long)]
+    compare_chain: Option<String>,
+
+    /// Visual-only alerts: banner, terminal title, OSC notification and tmux
+    /// status (`#{@mooncap_status}`), never audio
+    #[arg(long)]
+    silent_alerts: bool,
+
+    /// Alert when the token's price differs by at least this percent between
+    /// its quote pools (e.g. SOL vs USDC)
+    #[arg(long, value_name = "PERCENT")]
+    gap_alert: Option<f64>,
+
+    /// Re-arm the target alert once the market cap (or price) falls this percent
+    /// below it, so the alarm fires again on the next breakout
+    #[arg(long, value_name = "PERCENT")]
+    rearm: Option<f64>,
+
+    /// Separate fully diluted valuation target ($) with its own gauge and alert,
+    /// for tokens with a large locked supply
+    #[arg(long, value_name = "USD")]
+    fdv_target: Option<f64>,
+
+    /// Alert when the market cap falls this percent from its session peak, a
+    /// trailing stop for when momentum has died
+    #[arg(long, value_name = "PERCENT")]
+    trailing_stop: Option<f64>,
+
+    /// Alert when the market cap beats the pair's all-time high from earlier
+    /// sessions (highs are always recorded in the config file)
+    #[arg(long)]
+    ath_alert: bool,
+
+    /// Alert when no fetch has succeeded for this many intervals in a row, so a
+    /// silent API outage doesn't go unnoticed
+    #[arg(long, value_name = "INTERVALS")]
+    stale_after: Option<u32>,
+
+    /// Raise the --stale-after alert as critical, setting off the alarm
+    #[arg(long)]
+    stale_alarm: bool,
+
+    /// Desktop notification when the target (or a milestone) is hit and when
+    /// fetches keep failing, with the token, its market cap and progress
+    #[arg(long)]
+    desktop_notify: bool,
+
+    /// Write `SYMBOL $mcap (x% to target)` to this file after every fetch, for
+    /// a tmux status line [default: status_file from the config file]
+    #[arg(long, value_name = "PATH")]
+    status_file: Option<String>,
+
+    /// Alert when the market cap changes faster than this many dollars per
+    /// minute, either way, over --velocity-window
+    #[arg(long, value_name = "USD_PER_MIN")]
+    velocity: Option<f64>,
+
+    /// Sliding window for --velocity, in minutes
+    #[arg(long, value_name = "MINUTES", default_value_t = 5)]
+    velocity_window: u64,
+
+    /// Alert when the buys and sells between two fetches reach this many times
+    /// the pair's average rate over the last 24h, a sign of whale or bot activity
+    #[arg(long, value_name = "MULTIPLE")]
+    whale_alert: Option<f64>,
+
+    /// Seconds an alert rule stays quiet after firing, for rules without their
+    /// own entry in the config's alert_cooldowns (default 300)
+    #[arg(long, value_name = "SECS")]
+    cooldown: Option<u64>,
+
+    /// Fetch on wall-clock multiples of the interval (every minute at :00)
+    /// instead of counting from launch, so exports from several machines and
+    /// sessions line up
+    #[arg(long)]
+    align: bool,
+
+    /// Critical alert when the pool's liquidity falls by at least this percent
+    /// from one fetch to the next, the classic sign of a rug pull
+    #[arg(long, value_name = "PERCENT")]
+    rug_alert: Option<f64>,
+
+    /// Alert on relative price moves over DexScreener's 5m/1h/6h/24h windows,
+    /// e.g. `+30%/1h` or `-20%/5m` (repeatable or comma-separated)
+    #[arg(long, value_name = "MOVE", value_delimiter = ',', allow_hyphen_values = true)]
+    move_alert: Vec<String>,
+
+    /// Info alert at each of these percentages of the way to the target, e.g.
+    /// `25,50,75,90`, as a heads-up before the alarm (repeatable or comma-separated)
+    #[arg(long, value_name = "PERCENT", value_delimiter = ',')]
+    progress_alert: Vec<f64>,
+
+    /// Alert when an expression over the pair data becomes true, e.g.
+    /// `mcap > 1_000_000 && liquidity < 50_000` or `change_1h > 25` (repeatable)
+    #[arg(long, value_name = "EXPR")]
+    when: Vec<condition::Condition>,
+
+    /// Attach a saved rule from the config file's `rules` by name, for this
+    /// session (repeatable or comma-separated)
+    #[arg(long, value_name = "NAME", value_delimiter = ',')]
+    rule: Vec<String>,
+
+    /// Show liquidity and 24h volume summed across all of the token's pools
+    /// instead of the monitored pool's
+    #[arg(long)]
+    all_pools: bool,
+
+    /// Cross-check the DEX price against a CEX ticker (binance, coinbase)
+    #[arg(long, value_name = "EXCHANGE")]
+    cex: Option<String>,
+
+    /// CEX base symbol when it differs from the token's symbol
+    #[arg(long, value_name = "SYMBOL")]
+    cex_symbol: Option<String>,
+
+    /// Alert when the DEX and CEX prices diverge by at least this percent
+    #[arg(long, value_name = "PERCENT")]
+    cex_alert: Option<f64>,
+
+    /// Alert when the token is listed on a major centralized exchange
+    /// (checks CoinGecko every 10 minutes)
+    #[arg(long)]
+    listings: bool,
+
+    /// Show funding rate and open interest of the token's Binance perpetual
+    /// futures market, if it has one (every 5 minutes)
+    #[arg(long)]
+    perps: bool,
+
+    /// Show the token's Telegram member and Twitter follower counts (every
+    /// 15 minutes). Twitter needs TWITTER_BEARER_TOKEN or the
+    /// twitter-bearer-token secret.
+    #[arg(long)]
+    social: bool,
+
+    /// Alert when a Telegram or Twitter audience grows by at least this
+    /// percent between checks (implies --social)
+    #[arg(long, value_name = "PERCENT")]
+    social_alert: Option<f64>,
+
+    /// Compute market cap as on-chain supply x price, reading supply from the
+    /// chain RPC (--rpc-url or the chain's configured rpc_url)
+    #[arg(long)]
+    onchain_supply: bool,
+
+    /// Query every provider each cycle and use the median price/market cap,
+    /// flagging sources that stray from it
+    #[arg(long)]
+    aggregate: bool,
+
+    /// Stream live prices from Birdeye's websocket between fetches (Solana,
+    /// needs a Birdeye API key and the `stream` feature)
+    #[arg(long)]
+    stream: bool,
+
+    /// Roll fetched market caps into hourly or daily open/high/low/close rows
+    /// (daemons also append them to a CSV next to their log)
+    #[arg(long, value_name = "PERIOD")]
+    summary: Option<String>,
+
+    /// Low-power mode: slower redraws, no animations, stretched fetch interval
+    #[arg(long)]
+    low_power: bool,
+
+    /// Most redraws per second, 1 to 60; keys are still read between frames
+    /// [default: 10, or max_fps from the config file]
+    #[arg(long, value_name = "N")]
+    fps: Option<u32>,
+
+    /// Colors to draw with: auto (from NO_COLOR, TERM and COLORTERM), full,
+    /// 16 or mono
+    #[arg(long, value_name = "MODE", default_value = "auto")]
+    color: display::ColorMode,
+
+    /// Characters to draw with: auto (from TERM and the locale), unicode or
+    /// ascii (plain borders and bars, no emoji)
+    #[arg(long, value_name = "MODE", default_value = "auto")]
+    glyphs: display::GlyphMode,
+
+    /// Demo mode: play a scripted pump through the target, the alarm and its
+    /// stop at one fetch per second, with no network access. Notification
+    /// channels in the config file still receive the alert.
+    #[arg(long)]
+    screencast: bool,
+
+    /// Show prices, market caps, volume and liquidity in this currency
+    /// (e.g. EUR, GBP, JPY). Targets and alert thresholds stay in USD.
+    #[arg(long, value_name = "CODE", default_value = "USD")]
+    currency: String,
+
+    /// Instance name shown in the header, terminal title and notifications, to
+    /// tell several running mooncaps apart
+    #[arg(long, value_name = "NAME")]
+    name: Option<String>,
+
+    /// Read-only mode for shared screens: only refresh and portfolio cycling
+    /// work, and quitting takes a second press of q
+    #[arg(long)]
+    observer: bool,
+
+    /// Developer mode: inject faults into every data source, e.g.
+    /// `latency=200-2000,fail=0.2,malformed=0.1,seed=42` (same seed, same run)
+    #[arg(long, value_name = "SPEC")]
+    chaos: Option<String>,
+
     /// Run in background daemon mode (no TUI, survives terminal close).
     /// Sends a desktop notification when the target is hit.
     #[arg(short, long)]
     daemon: bool,
 
+    /// Line format of the daemon's log and output: human, logfmt, json or csv
+    #[arg(long, value_name = "FORMAT", default_value = "human")]
+    output_format: output::OutputFormat,
+
     /// Stop a running daemon for the given --pair address
     #[arg(long)]
     stop: bool,
@@ -58,12 +360,56 @@ struct Cli {
     /// Internal flag: marks this process as the daemon worker (hidden)
     #[arg(long, hide = true)]
     daemon_worker: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Manage API keys and tokens stored in the OS keyring (needs the `keyring` feature)
+    Secrets {
+        #[command(subcommand)]
+        action: SecretsAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum SecretsAction {
+    /// Store a secret, e.g. `birdeye-api-key`; the value is prompted for
+    /// without echo, or read from stdin when piped
+    Set { name: String },
+    /// Remove a stored secret
+    Delete { name: String },
+    /// Show which known secrets are stored
+    List,
 }
 
 #[tokio::main]
 async fn main() -> io::Result<()> {
     let cli = Cli::parse();
 
+    if let Some(Command::Secrets { action }) = cli.command {
+        if let Err(e) = run_secrets(action) {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(ref spec) = cli.chaos {
+        match chaos::parse_spec(spec) {
+            Ok(config) => chaos::enable(config),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Base URL and API key overrides apply in every mode
+    let unknown_apis = endpoints::configure(&config::Config::load().apis);
+
     // --stop: kill a running daemon
     if cli.stop {
         let pair = cli.pair.as_deref().unwrap_or("");
@@ -85,14 +431,26 @@ async fn main() -> io::Result<()> {
             eprintln!("Error: --daemon-worker requires --pair");
             std::process::exit(1);
         }
-        daemon::run_daemon_worker(
+        daemon::run_daemon_worker(DaemonConfig {
             pair,
-            cli.chain,
-            cli.target,
-            cli.interval,
-            cli.alarm,
-            cli.alarm_duration,
-        )
+            chain: cli.chain,
+            target: cli.target.top().value,
+            target_kind: cli.target.kind,
+            milestones: cli.target.milestones().to_vec(),
+            interval: cli.interval,
+            alarm: cli.alarm,
+            alarm_duration: cli.alarm_duration,
+            providers: cli.provider,
+            rpc_url: cli.rpc_url,
+            silent: cli.silent_alerts,
+            summary: cli.summary,
+            name: cli.name,
+            align: cli.align,
+            output_format: cli.output_format,
+            stale_after: cli.stale_after,
+            stale_alarm: cli.stale_alarm,
+            conditions: cli.when.iter().map(|c| c.to_string()).collect(),
+        })
         .await;
         return Ok(());
     }
@@ -104,19 +462,39 @@ async fn main() -> io::Result<()> {
             eprintln!("Error: --daemon requires --pair <ADDRESS>");
             std::process::exit(1);
         }
-        match daemon::spawn_daemon(
-            pair,
-            &cli.chain,
-            cli.target,
-            cli.interval,
-            cli.alarm.as_deref(),
-            cli.alarm_duration,
-        ) {
+        let unsupported = tui_only_alerts(&cli);
+        if !unsupported.is_empty() {
+            eprintln!(
+                "Error: the daemon doesn't check {}; run without --daemon to use them",
+                unsupported.join(", ")
+            );
+            std::process::exit(1);
+        }
+        match daemon::spawn_daemon(&DaemonConfig {
+            pair: pair.to_string(),
+            chain: cli.chain.clone(),
+            target: cli.target.top().value,
+            target_kind: cli.target.kind,
+            milestones: cli.target.milestones().to_vec(),
+            interval: cli.interval,
+            alarm: cli.alarm.clone(),
+            alarm_duration: cli.alarm_duration,
+            providers: cli.provider.clone(),
+            rpc_url: cli.rpc_url.clone(),
+            silent: cli.silent_alerts,
+            summary: cli.summary.clone(),
+            name: cli.name.clone(),
+            align: cli.align,
+            output_format: cli.output_format,
+            stale_after: cli.stale_after,
+            stale_alarm: cli.stale_alarm,
+            conditions: cli.when.iter().map(|c| c.to_string()).collect(),
+        }) {
             Ok(pid) => {
                 let log_path = daemon::log_file(pair);
                 println!("🌙 MoonCap daemon started in background");
                 println!("   PID:    {}", pid);
-                println!("   Target: ${:.0}", cli.target);
+                println!("   Target: {}", cli.target.kind.format(cli.target.top().value));
                 println!("   Log:    {}", log_path.display());
                 println!();
                 println!("   Stop with: mooncap --stop --pair {}", pair);
@@ -130,58 +508,412 @@ async fn main() -> io::Result<()> {
     }
 
     // Normal TUI mode
-    let mut app = if let Some(ref pair) = cli.pair {
-        App::new_with_config(
+    let currency = match fx::Currency::parse(&cli.currency) {
+        Ok(currency) => currency,
+        Err(e) => {
+            eprintln!("Error: --currency {}", e);
+            std::process::exit(1);
+        }
+    };
+    let move_alerts = match cli
+        .move_alert
+        .iter()
+        .map(|spec| alerts::MoveRule::parse(spec))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(rules) => rules,
+        Err(e) => {
+            eprintln!("Error: --move-alert {}", e);
+            std::process::exit(1);
+        }
+    };
+    if cli.observer && cli.pair.is_none() && cli.wallet.is_none() && !cli.screencast {
+        eprintln!("Error: --observer requires --pair (there's no config modal to pick one)");
+        std::process::exit(1);
+    }
+    // --wallet takes an address or an ENS/SNS name, resolved once up front
+    let mut wallet_address: Option<(String, Option<String>)> = None;
+    if let Some(ref input) = cli.wallet {
+        let input = input.trim();
+        if input.is_empty() {
+            eprintln!("Error: --wallet requires an address");
+            std::process::exit(1);
+        }
+        if cli.pair.is_none() && !wallet::supports_chain(&cli.chain) {
+            eprintln!("Error: --wallet without --pair is only supported on Solana");
+            std::process::exit(1);
+        }
+        if names::is_name(input) {
+            if !names::fits_chain(input, &cli.chain) {
+                eprintln!(
+                    "Error: {} can't be used on {} (.sol names are Solana wallets, .eth names EVM)",
+                    input, cli.chain
+                );
+                std::process::exit(1);
+            }
+            println!("🔎 Resolving {}...", input);
+            match names::resolve(&reqwest::Client::new(), input).await {
+                Ok(address) => wallet_address = Some((address, Some(input.to_lowercase()))),
+                Err(e) => {
+                    eprintln!("Error: couldn't resolve {}: {}", input, e);
+                    std::process::exit(1);
+                }
+            }
+        } else {
+            wallet_address = Some((input.to_string(), None));
+        }
+    }
+    // A wallet without --pair monitors its largest holding
+    let mut wallet_scan = None;
+    if let (Some((ref address, ref name)), None) = (&wallet_address, &cli.pair) {
+        let config = config::Config::load();
+        let rpc_url = cli
+            .rpc_url
+            .clone()
+            .or_else(|| config.chain_defaults(&cli.chain).and_then(|d| d.rpc_url.clone()))
+            .or_else(|| rpc::public_rpc(&cli.chain).map(str::to_string))
+            .unwrap_or_default();
+        let label = name.clone().unwrap_or_else(|| wallet::short_address(address));
+        println!("👛 Scanning wallet {}...", label);
+        let client = reqwest::Client::new();
+        match wallet::scan(&client, &rpc_url, &cli.chain, address, cli.wallet_min).await {
+            Ok(tokens) if tokens.is_empty() => {
+                eprintln!("Error: no tokens worth ${:.0}+ in wallet {}", cli.wallet_min, label);
+                std::process::exit(1);
+            }
+            Ok(tokens) => wallet_scan = Some(tokens),
+            Err(e) => {
+                eprintln!("Error: couldn't scan wallet {}: {}", label, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let tui_only = tui_only_alerts(&cli);
+    let mut app = if cli.screencast {
+        let mut app = App::new_with_config(
+            screencast::PAIR.to_string(),
+            screencast::CHAIN.to_string(),
+            screencast::TARGET,
+            screencast::INTERVAL,
+            cli.alarm.clone(),
+            cli.alarm_duration,
+        );
+        app.screencast = Some(screencast::Script::new());
+        app.metadata = metadata::MetadataCache::default();
+        app.add_log(format!(
+            "[{}] 🎬 Screencast: scripted pump → target hit → alarm → stop",
+            Local::now().format("%H:%M:%S")
+        ));
+        app
+    } else if let Some(ref tokens) = wallet_scan {
+        let mut app = App::new_with_config(
+            tokens[0].mint.clone(),
+            cli.chain.clone(),
+            cli.target.top().value,
+            cli.interval,
+            cli.alarm.clone(),
+            cli.alarm_duration,
+        );
+        app.set_target(cli.target);
+        app
+    } else if let Some(ref pair) = cli.pair {
+        let mut app = App::new_with_config(
             pair.clone(),
             cli.chain.clone(),
-            cli.target,
+            cli.target.top().value,
             cli.interval,
             cli.alarm.clone(),
             cli.alarm_duration,
-        )
+        );
+        app.set_target(cli.target);
+        app
     } else {
         // Check for a running daemon to resume from
         let daemons = daemon::find_running_daemons();
         if let Some(cfg) = daemons.into_iter().next() {
             // Kill the daemon and take over in TUI mode
             daemon::kill_daemon_quiet(&cfg.pair);
-            App::new_with_config(
+            let target = cfg.target_spec();
+            let mut app = App::new_with_config(
                 cfg.pair,
                 cfg.chain,
                 cfg.target,
                 cfg.interval,
                 cfg.alarm.or(cli.alarm.clone()),
                 cfg.alarm_duration,
-            )
+            );
+            if !cfg.providers.is_empty() {
+                let (providers, unknown) = api::parse_providers(&cfg.providers);
+                app.set_providers(providers, &unknown);
+            }
+            if cfg.rpc_url.is_some() {
+                app.rpc_url = cfg.rpc_url;
+            }
+            app.conditions = cfg.conditions.iter().filter_map(|c| c.parse().ok()).collect();
+            app.set_target(target);
+            app.instance = cfg.name;
+            app
+        } else if let Some(pin) = config::Config::load().pinned.first().cloned() {
+            // Start watching the pinned favourites without any flags
+            let mut app = App::new_with_config(
+                pin.pair,
+                pin.chain,
+                pin.target.unwrap_or(cli.target.top().value),
+                cli.interval,
+                cli.alarm.clone(),
+                cli.alarm_duration,
+            );
+            if pin.target.is_none() {
+                app.set_target(cli.target);
+            }
+            app
         } else {
             App::new_interactive(cli.alarm.clone(), cli.alarm_duration)
         }
     };
 
+    app.restore_metadata();
+
+    // Explicit CLI provider flags override the per-chain defaults
+    if !cli.provider.is_empty() {
+        let (providers, unknown) = api::parse_providers(&cli.provider);
+        app.set_providers(providers, &unknown);
+    }
+    if cli.rpc_url.is_some() {
+        app.rpc_url = cli.rpc_url.clone();
+    }
+    app.silent_alerts = cli.silent_alerts;
+    if let Some(name) = cli.name.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+        app.instance = Some(name.to_string());
+    }
+    app.gap_alert = cli.gap_alert;
+    app.rug_alert = cli.rug_alert.filter(|p| *p > 0.0);
+    app.rearm_percent = cli.rearm.filter(|p| *p >= 0.0);
+    let progress_alerts = if cli.progress_alert.is_empty() {
+        app.config.progress_alerts.clone()
+    } else {
+        cli.progress_alert.clone()
+    };
+    app.set_progress_alerts(progress_alerts);
+    app.fdv_target = cli.fdv_target.filter(|t| *t > 0.0);
+    app.cooldown_override = cli.cooldown.map(Duration::from_secs);
+    app.align_fetches = cli.align || app.config.align_fetches;
+    app.trailing_stop = cli.trailing_stop.filter(|p| *p > 0.0 && *p < 100.0);
+    app.ath_alert = cli.ath_alert;
+    app.stale_after = cli.stale_after.filter(|n| *n > 0);
+    app.stale_alarm = cli.stale_alarm;
+    app.desktop_notify = cli.desktop_notify;
+    app.status_file = cli.status_file.clone().or(app.config.status_file.clone());
+    app.velocity = cli
+        .velocity
+        .filter(|v| *v > 0.0)
+        .map(|v| alerts::Velocity::new(v, cli.velocity_window));
+    app.whale_watch = cli.whale_alert.filter(|m| *m > 0.0).map(alerts::WhaleWatch::new);
+    if app.align_fetches {
+        let msg = format!(
+            "[{}] 🕐 Fetches aligned to every {}s on the clock",
+            Local::now().format("%H:%M:%S"),
+            app.effective_interval()
+        );
+        app.add_log(msg);
+    }
+    if !cli.when.is_empty() {
+        let rules: Vec<String> = cli.when.iter().map(|c| c.to_string()).collect();
+        app.add_log(format!(
+            "[{}] 📐 Conditions: {}",
+            Local::now().format("%H:%M:%S"),
+            rules.join("; ")
+        ));
+        app.conditions = cli.when.clone();
+    }
+    app.rule_names = cli.rule.clone();
+    app.tui_only_alerts = tui_only;
+    app.attach_saved_rules();
+    if !move_alerts.is_empty() {
+        app.move_alerts = move_alerts;
+        app.modal_fields[ModalField::MoveAlerts] = app.move_alerts_text();
+        app.log_move_alerts();
+    }
+    app.all_pools = cli.all_pools;
+    let invalid: Vec<String> = app
+        .config
+        .schedules
+        .iter()
+        .filter_map(|s| s.validate().err().map(|e| format!("{}: {}", s.pair, e)))
+        .collect();
+    for e in invalid {
+        app.add_log(format!(
+            "[{}] ⚠ Schedule ignored for {}",
+            Local::now().format("%H:%M:%S"),
+            e
+        ));
+    }
+    if let Some(quiet) = app.config.quiet_hours.clone() {
+        let msg = match quiet.validate() {
+            Ok(()) => format!("🌙 Quiet hours: {}", quiet.describe()),
+            Err(e) => format!("⚠ Quiet hours ignored: {}", e),
+        };
+        app.add_log(format!("[{}] {}", Local::now().format("%H:%M:%S"), msg));
+    }
+    if let Some(ref name) = cli.summary {
+        match ohlc::Period::parse(name) {
+            Some(period) => app.ohlc = Some(ohlc::OhlcTable::new(period)),
+            None => app.add_error(format!("Unknown summary period '{}' (use hourly or daily)", name)),
+        }
+    }
+    if let Some(ref spec) = cli.chaos {
+        app.add_log(format!(
+            "[{}] 🧪 Chaos mode: {}",
+            Local::now().format("%H:%M:%S"),
+            spec
+        ));
+    }
+    if let Some(ref name) = cli.cex {
+        match cex::Exchange::parse(name) {
+            Some(exchange) => {
+                app.cex = Some(cex::CexCheck::new(exchange, cli.cex_symbol.clone(), cli.cex_alert));
+            }
+            None => app.add_error(format!("Unknown exchange '{}' (use binance or coinbase)", name)),
+        }
+    }
+    app.watch_listings = cli.listings;
+    app.watch_perps = cli.perps;
+    app.social_alert = cli.social_alert.filter(|p| *p > 0.0);
+    app.watch_social = cli.social || app.social_alert.is_some();
+    app.aggregate |= cli.aggregate;
+    if app.aggregate && app.providers.len() < 2 {
+        app.add_log(format!(
+            "[{}] ⚠ --aggregate needs two or more providers (e.g. --provider dexscreener,jupiter)",
+            Local::now().format("%H:%M:%S")
+        ));
+    }
+    app.onchain_mcap = cli.onchain_supply;
+    if cli.onchain_supply && app.rpc_url.is_none() {
+        app.add_log(format!(
+            "[{}] ⚠ --onchain-supply needs --rpc-url or an rpc_url for the chain in config",
+            Local::now().format("%H:%M:%S")
+        ));
+    }
+    if cli.stream {
+        let now = Local::now().format("%H:%M:%S");
+        if !cfg!(feature = "stream") {
+            app.add_log(format!(
+                "[{}] ⚠ --stream needs the 'stream' feature (cargo install --features stream)",
+                now
+            ));
+        } else if birdeye::api_key(app.config.birdeye_api_key.as_deref()).is_none() {
+            app.add_log(format!(
+                "[{}] ⚠ --stream needs a Birdeye API key ({} or birdeye_api_key in config)",
+                now,
+                birdeye::API_KEY_ENV
+            ));
+        } else {
+            app.streaming = true;
+        }
+    }
+    if cli.low_power {
+        app.low_power_auto = false;
+        app.set_low_power(true, "--low-power");
+    } else if app.low_power_auto && power::on_battery() {
+        app.set_low_power(true, "on battery");
+    }
+    app.currency = currency;
+    app.display = display::Display::detect(cli.color, cli.glyphs);
+    app.frames.set_fps(cli.fps.or(app.config.max_fps).unwrap_or(pacing::DEFAULT_FPS));
+    if app.display.is_degraded() {
+        app.add_log(format!(
+            "[{}] 🖥  Basic terminal: drawing in {}",
+            Local::now().format("%H:%M:%S"),
+            app.display.describe()
+        ));
+    }
+    if cli.observer {
+        app.observer = true;
+        app.add_log(format!(
+            "[{}] 👁  Observer mode: read-only, press q twice to quit",
+            Local::now().format("%H:%M:%S")
+        ));
+    }
+    if !unknown_apis.is_empty() {
+        app.add_log(format!(
+            "[{}] ⚠ Unknown API(s) in config ignored: {}",
+            Local::now().format("%H:%M:%S"),
+            unknown_apis.join(", ")
+        ));
+    }
+    if let Some(ref pair) = cli.compare {
+        let chain = cli.compare_chain.clone().unwrap_or_else(|| app.chain.clone());
+        app.set_compare(pair.clone(), chain);
+    }
+    if let Some((address, name)) = wallet_address {
+        if let Some(ref name) = name {
+            app.add_log(format!(
+                "[{}] 🔎 {} → {}",
+                Local::now().format("%H:%M:%S"),
+                name,
+                address
+            ));
+        }
+        app.wallet = Some(address);
+        app.wallet_name = name;
+        app.wallet_min_usd = cli.wallet_min;
+        if let Some(tokens) = wallet_scan {
+            app.set_wallet_tokens(Ok(tokens));
+        }
+    }
+    if let Some(ref name) = cli.portfolio {
+        if !app.set_portfolio(name) {
+            app.add_error(format!("Unknown portfolio '{}'", name));
+        }
+    } else if !app.config.pinned.is_empty()
+        && !cli.screencast
+        && app.wallet_group_name().is_none()
+    {
+        app.set_portfolio(config::PINNED_PORTFOLIO);
+    }
+
     let mut terminal = ratatui::init();
+    alarm::set_title(app.instance.as_deref(), app.status_file.as_deref());
 
     // Play startup animation
     // remove this to avoid animation
-    splash::run_splash(&mut terminal);
+    if !app.low_power && !app.display.is_degraded() {
+        splash::run_splash(&mut terminal);
+    }
 
     let result = run_app(&mut terminal, &mut app).await;
     ratatui::restore();
+    alarm::clear_status();
+    app.save_ath();
 
     // If the user chose to go idle from the TUI, spawn a daemon
     if app.go_idle {
-        match daemon::spawn_daemon(
-            &app.pair_address,
-            &app.chain,
-            app.target_market_cap,
-            app.check_interval,
-            app.alarm_file.as_deref(),
-            app.alarm_duration,
-        ) {
+        match daemon::spawn_daemon(&DaemonConfig {
+            pair: app.pair_address.clone(),
+            chain: app.chain.clone(),
+            target: app.target_market_cap,
+            target_kind: app.target_kind,
+            milestones: app.milestones.clone(),
+            interval: app.check_interval,
+            alarm: app.alarm_file.clone(),
+            alarm_duration: app.alarm_duration,
+            providers: app.providers.iter().map(|p| p.name().to_string()).collect(),
+            rpc_url: app.rpc_url.clone(),
+            silent: app.silent_alerts,
+            summary: app.ohlc.as_ref().map(|t| t.period.name().to_string()),
+            name: app.instance.clone(),
+            align: app.align_fetches,
+            output_format: cli.output_format,
+            stale_after: app.stale_after,
+            stale_alarm: app.stale_alarm,
+            conditions: app.conditions.iter().map(|c| c.to_string()).collect(),
+        }) {
             Ok(pid) => {
                 let log_path = daemon::log_file(&app.pair_address);
                 println!("🌙 MoonCap now running in background (idle mode)");
                 println!("   PID:    {}", pid);
-                println!("   Target: ${:.0}", app.target_market_cap);
+                println!("   Target: {}", app.target_kind.format(app.target_market_cap));
                 println!("   Log:    {}", log_path.display());
                 println!();
                 println!(
@@ -202,6 +934,64 @@ async fn main() -> io::Result<()> {
     Ok(())
 }
 
+/// Alert flags given that only the TUI checks; the daemon runs the target,
+/// milestones, --stale-after and --when
+fn tui_only_alerts(cli: &Cli) -> Vec<&'static str> {
+    [
+        ("--gap-alert", cli.gap_alert.is_some()),
+        ("--rearm", cli.rearm.is_some()),
+        ("--fdv-target", cli.fdv_target.is_some()),
+        ("--trailing-stop", cli.trailing_stop.is_some()),
+        ("--ath-alert", cli.ath_alert),
+        ("--velocity", cli.velocity.is_some()),
+        ("--whale-alert", cli.whale_alert.is_some()),
+        ("--rug-alert", cli.rug_alert.is_some()),
+        ("--move-alert", !cli.move_alert.is_empty()),
+        ("--progress-alert", !cli.progress_alert.is_empty()),
+        ("--rule", !cli.rule.is_empty()),
+        ("--cex-alert", cli.cex_alert.is_some()),
+        ("--social-alert", cli.social_alert.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(flag, given)| given.then_some(flag))
+    .collect()
+}
+
+/// `mooncap secrets ...`
+fn run_secrets(action: SecretsAction) -> Result<(), String> {
+    match action {
+        SecretsAction::Set { name } => {
+            if !secrets::valid_name(&name) {
+                return Err(format!(
+                    "'{}' isn't a valid name (lowercase letters, digits and dashes)",
+                    name
+                ));
+            }
+            let value = secrets::prompt(&format!("Value for {}: ", name))
+                .map_err(|e| format!("couldn't read the value: {}", e))?;
+            if value.is_empty() {
+                return Err("empty value, nothing stored".to_string());
+            }
+            secrets::set(&name, &value)?;
+            println!("🔑 Stored {} in the keyring", name);
+        }
+        SecretsAction::Delete { name } => {
+            if secrets::delete(&name)? {
+                println!("🔑 Removed {} from the keyring", name);
+            } else {
+                println!("No {} in the keyring", name);
+            }
+        }
+        SecretsAction::List => {
+            for (name, what) in secrets::KNOWN {
+                let stored = if secrets::read(name)?.is_some() { "stored" } else { "-" };
+                println!("{:<20} {:<8} {}", name, stored, what);
+            }
+        }
+    }
+    Ok(())
+}
+
 async fn run_app(
     terminal: &mut DefaultTerminal,
     app: &mut App,
@@ -210,62 +1000,806 @@ async fn run_app(
     let mut last_fetch = Instant::now();
     let mut needs_immediate_fetch = app.configured; // fetch immediately if pre-configured
     let mut alarm_handle: Option<Arc<AtomicBool>> = None;
-    let tick_rate = Duration::from_millis(200);
+    let mut alarm_playing = notify::Severity::Info;
+    let mut last_power_check = Instant::now();
+    #[cfg(feature = "stream")]
+    let mut price_stream: Option<stream::PriceStream> = None;
+    let (bus, bus_errors) = bus::EventBus::connect(&app.config.redis, &app.config.mqtt).await;
+    for e in bus_errors {
+        app.add_log(format!("[{}] ⚠ {}", Local::now().format("%H:%M:%S"), e));
+    }
+    let store = match storage::open(&app.config.storage).await {
+        Ok(store) => store,
+        Err(e) => {
+            app.add_log(format!(
+                "[{}] ⚠ History storage disabled: {}",
+                Local::now().format("%H:%M:%S"),
+                e
+            ));
+            None
+        }
+    };
+    if let Some(path) = app.config.event_log.clone() {
+        match eventlog::EventLog::open(&path) {
+            Ok(log) => {
+                app.event_log = Some(log);
+                app.add_log(format!(
+                    "[{}] 📝 Recording events to {}",
+                    Local::now().format("%H:%M:%S"),
+                    path
+                ));
+                app.record_event(eventlog::Event::started("tui"));
+                if app.configured {
+                    app.record_event(eventlog::Event::config(
+                        &app.chain,
+                        &app.pair_address,
+                        app.target(),
+                        app.check_interval,
+                    ));
+                }
+            }
+            Err(e) => app.add_log(format!(
+                "[{}] ⚠ Event log disabled: {}",
+                Local::now().format("%H:%M:%S"),
+                e
+            )),
+        }
+    }
 
     while app.running {
-        // Draw
-        terminal.draw(|frame| ui::draw(frame, app))?;
+        // Follow the battery state if auto low-power is enabled
+        if app.low_power_auto && last_power_check.elapsed() >= POWER_CHECK_INTERVAL {
+            last_power_check = Instant::now();
+            if power::on_battery() {
+                app.set_low_power(true, "on battery");
+            } else {
+                app.set_low_power(false, "on AC power");
+            }
+        }
+
+        if app.configured {
+            app.check_unlocks();
+            app.check_stale();
+        }
+        #[cfg(feature = "stream")]
+        sync_price_stream(app, &mut price_stream);
+        app.deliver_notifications(&client);
+        app.reap_hooks();
+
+        // Draw, at most at the frame cap
+        if app.frames.due(app.tick_rate()) {
+            let started = Instant::now();
+            terminal.draw(|frame| {
+                ui::draw(frame, app);
+                app.display.apply(frame.buffer_mut());
+            })?;
+            app.frames.record(started.elapsed());
+        }
+
+        needs_immediate_fetch |= app.update_schedule();
+
+        // The onboarding search runs after the frame showing "Searching…"
+        if let Some(query) = app.pending_onboarding_search() {
+            let results = api::search_pairs(&client, &query).await;
+            if let Some(onboarding) = app.onboarding.as_mut() {
+                onboarding.set_results(results);
+            }
+            app.frames.mark_dirty();
+        }
 
         // Only fetch data when configured and not in modal
         if app.configured
             && !app.modal_open
             && (needs_immediate_fetch
-                || last_fetch.elapsed() >= Duration::from_secs(app.check_interval))
+                || last_fetch.elapsed() >= app.fetch_delay())
         {
             needs_immediate_fetch = false;
             last_fetch = Instant::now();
+            app.start_fetch();
+            if app.screencast.is_some() {
+                play_screencast(app, &mut alarm_handle);
+            } else {
+                poll_once(&client, app, bus.as_ref(), store.as_deref()).await;
+            }
+            app.frames.mark_dirty();
+        }
 
-            match api::fetch_pair_data(&client, &app.chain, &app.pair_address).await {
-                Ok(data) => {
-                    app.update_from_pair_data(&data);
-
-                    // Trigger alarm if target hit and no alarm running
-                    if app.alarm_active && alarm_handle.is_none() {
-                        let handle = alarm::start_alarm(
-                            app.alarm_file.as_deref(),
-                            app.alarm_duration,
-                        );
-                        alarm_handle = Some(handle);
-                    }
-                }
-                Err(e) => {
-                    app.add_error(e);
+        // Publish alerts raised since the last pass
+        for event in std::mem::take(&mut app.bus_queue) {
+            if let Some(ref bus) = bus {
+                if let Err(e) = bus.publish(&event).await {
+                    app.add_log(format!(
+                        "[{}] ⚠ {}",
+                        Local::now().format("%H:%M:%S"),
+                        api::truncate(&e, 100)
+                    ));
                 }
             }
         }
 
-        // Handle input (non-blocking with timeout)
-        if event::poll(tick_rate)? {
-            if let Event::Key(key) = event::read()? {
+        // A re-armed target silences its alarm, so the next breakout starts a new one
+        if !app.alarm_active {
+            if let Some(handle) = alarm_handle.take() {
+                alarm::stop_alarm(&handle);
+            }
+        }
+
+        // A more severe alert takes over from the sound of a milder one
+        if app.alarm_active && app.alarm_severity > alarm_playing {
+            if let Some(handle) = alarm_handle.take() {
+                alarm::stop_alarm(&handle);
+            }
+        }
+
+        // Trigger alarm if an alert fired and no alarm running
+        if app.alarm_active && alarm_handle.is_none() {
+            alarm_playing = app.alarm_severity;
+            let handle = if app.silent_alerts {
+                let summary = app.labelled(app.last_alert.as_deref().unwrap_or("alert"));
+                alarm::start_visual_alert(&summary)
+            } else {
+                let (file, duration) = app.alarm_sound();
+                alarm::start_alarm(
+                    file.as_deref(),
+                    duration,
+                    app.config.duck_volume,
+                    app.alarm_severity,
+                )
+            };
+            alarm_handle = Some(handle);
+        }
+
+        // A playing macro feeds its keys one per frame, so each one is drawn
+        if !app.macro_queue.is_empty() {
+            if app.frames.is_dirty() {
+                tokio::time::sleep(app.frames.wait(app.tick_rate())).await;
+            } else if let Some((code, modifiers)) = app.macro_queue.pop_front() {
+                handle_key(
+                    app,
+                    code,
+                    modifiers,
+                    true,
+                    &mut needs_immediate_fetch,
+                    &mut alarm_handle,
+                );
+                app.frames.mark_dirty();
+            }
+            continue;
+        }
+
+        // Handle input until the next frame is due; keys are read as they come,
+        // only their redraw waits for the frame cap
+        if event::poll(app.frames.wait(app.tick_rate()))? {
+            let event = event::read()?;
+            if let Event::Key(key) = event {
                 if key.kind == KeyEventKind::Press {
-                    if app.modal_open {
-                        handle_modal_input(app, key.code, key.modifiers, &mut needs_immediate_fetch);
-                    } else {
-                        handle_normal_input(
-                            app,
-                            key.code,
-                            &mut needs_immediate_fetch,
-                            &mut alarm_handle,
-                        );
-                    }
+                    handle_key(
+                        app,
+                        key.code,
+                        key.modifiers,
+                        false,
+                        &mut needs_immediate_fetch,
+                        &mut alarm_handle,
+                    );
                 }
             }
+            app.frames.mark_dirty();
         }
     }
 
     Ok(())
 }
 
+/// Route a key press to whatever has focus, recording it first if a macro is
+/// being recorded. `replayed` keys come from a playing macro.
+fn handle_key(
+    app: &mut App,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+    replayed: bool,
+    needs_immediate_fetch: &mut bool,
+    alarm_handle: &mut Option<Arc<AtomicBool>>,
+) {
+    if app.macro_unbound.is_some() {
+        app.bind_macro(code);
+        return;
+    }
+    let focused = app.modal_open
+        || app.pool_selector_open
+        || app.switcher_open
+        || app.onboarding.is_some();
+    if !focused && !app.observer && modifiers.is_empty() {
+        match code {
+            KeyCode::Char('m') if !replayed => {
+                app.toggle_macro_recording();
+                return;
+            }
+            // Macros don't start other macros, so one can't replay itself forever
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                if !replayed && app.macro_recording.is_none() {
+                    app.play_macro(c);
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    if !replayed {
+        app.record_macro_key(code, modifiers);
+    }
+
+    if app.modal_open {
+        handle_modal_input(app, code, modifiers, needs_immediate_fetch);
+    } else if app.pool_selector_open {
+        handle_pool_selector_input(app, code, needs_immediate_fetch);
+    } else if app.switcher_open {
+        handle_switcher_input(app, code, needs_immediate_fetch);
+    } else if app.onboarding.is_some() {
+        handle_onboarding_input(app, code, needs_immediate_fetch);
+    } else {
+        handle_normal_input(app, code, modifiers, needs_immediate_fetch, alarm_handle);
+    }
+}
+
+/// One polling pass: fetch the monitored pair, update state and alerts, then
+/// refresh the side panels (compare, Birdeye, portfolio, ticker, ...)
+async fn poll_once(
+    client: &reqwest::Client,
+    app: &mut App,
+    bus: Option<&bus::EventBus>,
+    store: Option<&dyn storage::Storage>,
+) {
+    // A paused pair skips its own fetch; the portfolio keeps its own schedules
+    let result = if app.polling_paused() {
+        None
+    } else if app.aggregate {
+        match api::fetch_aggregated(
+            client,
+            &app.providers,
+            &app.chain,
+            &app.pair_address,
+            app.token_address.as_deref(),
+            &mut app.provider_health,
+        )
+        .await
+        {
+            Ok(agg) => {
+                let source = agg.sources[0];
+                app.set_aggregate_sources(agg.sources, agg.outliers);
+                Some(Ok((agg.data, source)))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    } else {
+        let providers = app.provider_order();
+        Some(
+            api::fetch_with_source(
+                client,
+                &providers,
+                &app.chain,
+                &app.pair_address,
+                app.token_address.as_deref(),
+                Some(&mut app.provider_health),
+            )
+            .await,
+        )
+    };
+    match result {
+        None => {}
+        Some(Ok((data, source))) => {
+            app.set_active_source(source);
+            app.close_onboarding();
+            if app.needs_supply_refresh() {
+                if let (Some(rpc_url), Some(token)) = (
+                    app.rpc_url.clone(),
+                    data.base_token.as_ref().and_then(|t| t.address.clone()),
+                ) {
+                    let chain = data.chain_id.clone().unwrap_or_else(|| app.chain.clone());
+                    let decimals = app.token_decimals;
+                    let supply =
+                        rpc::fetch_supply(client, &rpc_url, &chain, &token, decimals).await;
+                    app.set_onchain_supply(supply);
+                }
+            }
+            app.record_event(eventlog::Event::fetch(&app.chain, &app.pair_address, &data));
+            app.update_from_pair_data(&data);
+            if let Err(e) = alarm::set_status(&app.status_line()) {
+                app.add_log(format!("[{}] ⚠ {}", Local::now().format("%H:%M:%S"), e));
+            }
+            if let Some(bus) = bus {
+                let event = bus::BusEvent::update(&app.chain, &app.pair_address, &data);
+                if let Err(e) = bus.publish(&event).await {
+                    app.add_log(format!(
+                        "[{}] ⚠ {}",
+                        Local::now().format("%H:%M:%S"),
+                        api::truncate(&e, 100)
+                    ));
+                }
+            }
+            if let Some(store) = store {
+                let sample = storage::Sample::new(&app.chain, &app.pair_address, &data);
+                if let Err(e) = store.record(&sample).await {
+                    app.add_log(format!(
+                        "[{}] ⚠ {} write failed: {}",
+                        Local::now().format("%H:%M:%S"),
+                        store.name(),
+                        api::truncate(&e, 80)
+                    ));
+                }
+            }
+
+            // First fetch after a pause: report what happened in the gap
+            if let Some(since) = app.catch_up_since.take() {
+                catch_up(client, app, &data, since).await;
+            }
+
+            // Look up the token's other pools and backfill the chart and
+            // summary table once per configuration
+            if app.fetch_count == 1 && data.dex_id.is_some() {
+                if let Some(token) = data.base_token.as_ref().and_then(|t| t.address.clone()) {
+                    if let Ok(pools) = api::fetch_token_pools(client, &app.chain, &token).await {
+                        app.set_pools(pools);
+                    }
+                }
+                if let Some(ref pool) = data.pair_address {
+                    let chain = data.chain_id.clone().unwrap_or_else(|| app.chain.clone());
+                    match geckoterminal::fetch_ohlcv(
+                        client,
+                        &chain,
+                        pool,
+                        app.check_interval,
+                        geckoterminal::MAX_CANDLES,
+                    )
+                    .await
+                    {
+                        Ok(candles) => app.backfill_history(&candles, "GeckoTerminal"),
+                        Err(e) => app.add_log(format!(
+                            "[{}] ⚠ History backfill unavailable: {}",
+                            Local::now().format("%H:%M:%S"),
+                            api::truncate(&e, 80)
+                        )),
+                    }
+                }
+            } else if app.quote_gap.is_some() || app.all_pools {
+                // Keep multi-quote prices current for the arb gap and pool totals
+                if let Some(token) = app.token_address.clone() {
+                    if let Ok(pools) = api::fetch_token_pools(client, &app.chain, &token).await {
+                        app.refresh_pools(pools);
+                    }
+                }
+            }
+        }
+        Some(Err(e)) => {
+            app.record_event(eventlog::Event::fetch_failed(&app.chain, &app.pair_address, &e));
+            // A pair that has never loaded gets diagnostics instead of "Loading…"
+            if app.fetch_count == 0 && !app.onboarding_offered {
+                let other_chains = if e.starts_with("HTTP request failed") {
+                    Vec::new()
+                } else {
+                    api::listed_chains(client, &app.pair_address)
+                        .await
+                        .into_iter()
+                        .filter(|c| !c.eq_ignore_ascii_case(&app.chain))
+                        .collect()
+                };
+                app.open_onboarding(&e, other_chains);
+            }
+            // After repeated failures, check whether it's us or the API
+            app.consecutive_failures += 1;
+            if app.consecutive_failures == app::OFFLINE_PROBE_AFTER {
+                let message = format!(
+                    "{} fetch failed {} times in a row: {}",
+                    app.token_symbol,
+                    app::OFFLINE_PROBE_AFTER,
+                    e
+                );
+                app.notify_failure(&message);
+            }
+            if app.consecutive_failures < app::OFFLINE_PROBE_AFTER {
+                app.add_error(e);
+            } else if api::network_reachable(client).await {
+                // Network is fine, so the API itself is failing
+                app.offline = false;
+                app.add_error(e);
+            } else {
+                app.mark_offline(&e);
+            }
+        }
+    }
+
+    // The refreshes are independent of each other, so they wait on the network
+    // together and are applied once all are back
+    let shown = &*app;
+    let pair_refreshes = async {
+        if shown.polling_paused() {
+            return Vec::new();
+        }
+        let (compare, birdeye, profile, security, cex, listings, perps, social, probe) =
+            tokio::join!(
+                refresh_compare(client, shown),
+                refresh_birdeye(client, shown),
+                refresh_profile(client, shown),
+                refresh_security(client, shown),
+                refresh_cex(client, shown),
+                refresh_listings(client, shown),
+                refresh_perps(client, shown),
+                refresh_social(client, shown),
+                probe_providers(client, shown),
+            );
+        vec![compare, birdeye, profile, security, cex, listings, perps, social, probe]
+    };
+    let (mut updates, wallet, balance, portfolio, ticker, fx, fee) = tokio::join!(
+        pair_refreshes,
+        refresh_wallet(client, shown),
+        refresh_balance(client, shown),
+        refresh_portfolio(client, shown),
+        refresh_ticker(client, shown),
+        refresh_fx(client, shown),
+        refresh_fee(client, shown),
+    );
+    updates.extend([wallet, balance, portfolio, ticker, fx, fee]);
+    for update in updates.into_iter().flatten() {
+        update(app);
+    }
+    app.evaluate_watchlist_alerts();
+}
+
+/// What a background refresh found, applied to the app once it's back
+type Update = Box<dyn FnOnce(&mut App)>;
+
+/// Advance the --screencast script by one fetch
+fn play_screencast(app: &mut App, alarm_handle: &mut Option<Arc<AtomicBool>>) {
+    let Some(mut script) = app.screencast.take() else {
+        return;
+    };
+    let now = Local::now().format("%H:%M:%S");
+    match script.next_step() {
+        screencast::Step::Fetch(data) => {
+            app.set_active_source(api::Provider::DexScreener);
+            app.update_from_pair_data(&data);
+            app.evaluate_watchlist_alerts();
+        }
+        screencast::Step::StopAlarm => {
+            if let Some(ref handle) = alarm_handle {
+                alarm::stop_alarm(handle);
+            }
+            *alarm_handle = None;
+            app.alarm_active = false;
+            app.add_log(format!("[{}] 🔇 Alarm stopped by the screencast", now));
+            app.record_event(eventlog::Event::alarm_stopped(
+                &app.chain,
+                &app.pair_address,
+                "screencast",
+            ));
+        }
+        screencast::Step::Finish => {
+            app.add_log(format!("[{}] 🎬 Screencast finished", now));
+            app.running = false;
+        }
+    }
+    app.screencast = Some(script);
+}
+
+/// Fetch GeckoTerminal candles covering a pause and report the range they
+/// show, so targets crossed while nobody was polling aren't missed
+async fn catch_up(
+    client: &reqwest::Client,
+    app: &mut App,
+    data: &api::PairData,
+    since: chrono::DateTime<Local>,
+) {
+    let Some(ref pool) = data.pair_address else {
+        return;
+    };
+    let chain = data.chain_id.clone().unwrap_or_else(|| app.chain.clone());
+    // Aim for a few hundred candles across the gap
+    let gap = (Local::now() - since).num_seconds().max(0) as u64;
+    let interval = (gap / 300).max(60);
+    let candle = geckoterminal::candle_secs(interval);
+    let limit = ((gap / candle) as usize + 2).min(geckoterminal::MAX_CANDLES);
+    match geckoterminal::fetch_ohlcv(client, &chain, pool, interval, limit).await {
+        Ok(candles) => app.report_catch_up(since, &candles, candle as i64),
+        Err(e) => app.add_log(format!(
+            "[{}] ⚠ Catch-up report unavailable: {}",
+            Local::now().format("%H:%M:%S"),
+            api::truncate(&e, 80)
+        )),
+    }
+}
+
+/// Fetch fresh data for the compare-mode pair
+async fn refresh_compare(client: &reqwest::Client, app: &App) -> Option<Update> {
+    let compare = app.compare.as_ref()?;
+    let providers = app.providers_for_chain(&compare.chain);
+    let result = api::fetch_from_providers(client, &providers, &compare.chain, &compare.pair).await;
+    Some(Box::new(move |app| {
+        if let Some(ref mut compare) = app.compare {
+            match result {
+                Ok(data) => compare.update(data),
+                Err(e) => compare.error = Some(e),
+            }
+        }
+    }))
+}
+
+/// Keep the price stream pointed at the monitored token and apply its updates
+#[cfg(feature = "stream")]
+fn sync_price_stream(app: &mut App, price_stream: &mut Option<stream::PriceStream>) {
+    let wanted = if app.streaming && app.configured {
+        app.birdeye_key().zip(app.token_address.clone())
+    } else {
+        None
+    };
+    let current = price_stream.as_ref().map(|s| s.token.as_str());
+    if wanted.as_ref().map(|(_, token)| token.as_str()) != current {
+        *price_stream = wanted.map(|(key, token)| stream::PriceStream::start(key, token));
+        app.stream_live = false;
+    }
+
+    let Some(s) = price_stream.as_mut() else {
+        return;
+    };
+    while let Some(event) = s.try_next() {
+        let now = Local::now().format("%H:%M:%S");
+        match event {
+            stream::StreamEvent::Price(price) => app.apply_stream_price(price),
+            stream::StreamEvent::Connected => {
+                app.stream_live = true;
+                app.add_log(format!("[{}] ⚡ Streaming live prices from Birdeye", now));
+            }
+            stream::StreamEvent::Disconnected(reason) => {
+                app.stream_live = false;
+                app.add_log(format!(
+                    "[{}] ⚠ Price stream down: {} — reconnecting",
+                    now,
+                    api::truncate(&reason, 80)
+                ));
+            }
+        }
+    }
+}
+
+/// Fetch the CEX ticker for the monitored token once its symbol is known
+async fn refresh_cex(client: &reqwest::Client, app: &App) -> Option<Update> {
+    let check = app.cex.as_ref()?;
+    if app.fetch_count == 0 {
+        return None;
+    }
+    let exchange = check.exchange;
+    let symbol = check.symbol.clone().unwrap_or_else(|| app.token_symbol.clone());
+    let result = cex::fetch_price(client, exchange, &symbol).await;
+    Some(Box::new(move |app| app.set_cex_price(result)))
+}
+
+/// Look for new centralized exchange listings (--listings)
+async fn refresh_listings(client: &reqwest::Client, app: &App) -> Option<Update> {
+    if !app.needs_listings_refresh() {
+        return None;
+    }
+    let token = app.token_address.as_deref()?;
+    let result = coingecko::fetch_listings(client, &app.chain, token).await;
+    Some(Box::new(move |app| app.set_listings(result)))
+}
+
+/// Funding and open interest of the token's perp market (--perps)
+async fn refresh_perps(client: &reqwest::Client, app: &App) -> Option<Update> {
+    if !app.needs_perps_refresh() {
+        return None;
+    }
+    let symbol = match app.cex.as_ref().and_then(|c| c.symbol.clone()) {
+        Some(symbol) => symbol,
+        None => app.token_symbol.clone(),
+    };
+    let result = perps::fetch_market(client, &symbol).await;
+    Some(Box::new(move |app| app.set_perp(result)))
+}
+
+/// Re-read the token's Telegram and Twitter audience (--social)
+async fn refresh_social(client: &reqwest::Client, app: &App) -> Option<Update> {
+    if !app.needs_social_refresh() {
+        return None;
+    }
+    let mut results = Vec::new();
+    for (network, handle) in app.social_accounts() {
+        let result = social::fetch_count(client, network, &handle).await;
+        results.push((network, handle, result));
+    }
+    Some(Box::new(move |app| app.set_social(results)))
+}
+
+/// Ask the providers behind the leader for the pair too, so the diagnostics
+/// panel and the failover order reflect how they're doing now
+async fn probe_providers(client: &reqwest::Client, app: &App) -> Option<Update> {
+    if !app.needs_health_probe() {
+        return None;
+    }
+    let checked = Instant::now();
+    let target = api::Target {
+        chain: &app.chain,
+        address: &app.pair_address,
+        token: app.token_address.as_deref(),
+    };
+    let mut probes = Vec::new();
+    for provider in &app.providers {
+        if Some(*provider) == app.active_source {
+            continue;
+        }
+        let started = Instant::now();
+        let result = provider.source(client).fetch(&target).await;
+        probes.push((*provider, started.elapsed(), result.err()));
+    }
+    Some(Box::new(move |app| {
+        app.health_checked = Some(checked);
+        for (provider, latency, error) in probes {
+            app.provider_health.record(provider, latency, error.as_deref());
+        }
+    }))
+}
+
+/// Update the Birdeye holder panel, keeping the last good numbers on failure
+async fn refresh_birdeye(client: &reqwest::Client, app: &App) -> Option<Update> {
+    let key = app.birdeye_key()?;
+    let token = app.token_address.as_deref()?;
+    let result = birdeye::fetch_view(client, &key, token).await;
+    Some(Box::new(move |app| match result {
+        Ok(view) => app.birdeye = Some(view),
+        Err(e) => {
+            let mut view = app.birdeye.take().unwrap_or_default();
+            view.error = Some(e);
+            app.birdeye = Some(view);
+        }
+    }))
+}
+
+/// Refresh the exchange rate for --currency
+async fn refresh_fx(client: &reqwest::Client, app: &App) -> Option<Update> {
+    if !app.needs_fx_refresh() {
+        return None;
+    }
+    let result = fx::fetch_rate(client, &app.currency.code).await;
+    Some(Box::new(move |app| app.set_fx_rate(result)))
+}
+
+/// Update the gas / priority fee shown next to the chain badge
+async fn refresh_fee(client: &reqwest::Client, app: &App) -> Option<Update> {
+    if !app.needs_fee_refresh() {
+        return None;
+    }
+    let checked = Instant::now();
+    let fee = match app.read_rpc() {
+        Some(rpc_url) => Some(rpc::fetch_fee(client, &rpc_url, &app.chain).await.ok()),
+        None => None,
+    };
+    Some(Box::new(move |app| {
+        app.fee_checked = Some(checked);
+        if let Some(fee) = fee {
+            app.fee = fee;
+        }
+    }))
+}
+
+/// Refresh the majors in the header ticker strip, keeping the last prices on failure
+async fn refresh_ticker(client: &reqwest::Client, app: &App) -> Option<Update> {
+    if !app.needs_ticker_refresh() {
+        return None;
+    }
+    let checked = Instant::now();
+    let quotes = coingecko::fetch_ticker(client, &app.config.ticker_coins()).await;
+    Some(Box::new(move |app| {
+        app.ticker_checked = Some(checked);
+        if let Ok(quotes) = quotes {
+            app.ticker = quotes;
+        }
+    }))
+}
+
+/// Check DexScreener's profile and boost feeds for the monitored token
+async fn refresh_profile(client: &reqwest::Client, app: &App) -> Option<Update> {
+    if !app.needs_profile_refresh() {
+        return None;
+    }
+    let token = app.token_address.as_deref()?;
+    let result = profile::fetch_profile(client, &app.chain, token).await;
+    Some(Box::new(move |app| app.set_profile(result)))
+}
+
+/// Run the rug/honeypot check once for a newly configured pair
+async fn refresh_security(client: &reqwest::Client, app: &App) -> Option<Update> {
+    if !app.needs_security_check() {
+        return None;
+    }
+    let token = app.token_address.as_deref()?;
+    let result = safety::fetch_report(client, &app.chain, token).await;
+    Some(Box::new(move |app| app.set_security(result)))
+}
+
+/// Re-scan the --wallet for tokens bought or sold
+async fn refresh_wallet(client: &reqwest::Client, app: &App) -> Option<Update> {
+    if !app.needs_wallet_refresh() {
+        return None;
+    }
+    let address = app.wallet.as_deref()?;
+    let rpc_url = app.read_rpc()?;
+    let result = wallet::scan(client, &rpc_url, &app.chain, address, app.wallet_min_usd).await;
+    Some(Box::new(move |app| app.set_wallet_tokens(result)))
+}
+
+/// Re-read how much of the monitored token the --wallet holds
+async fn refresh_balance(client: &reqwest::Client, app: &App) -> Option<Update> {
+    if !app.needs_balance_refresh() {
+        return None;
+    }
+    let (Some(address), Some(token), Some(rpc_url)) =
+        (app.wallet.as_deref(), app.token_address.as_deref(), app.read_rpc())
+    else {
+        return None;
+    };
+    let decimals = app.token_decimals;
+    let result =
+        rpc::fetch_token_balance(client, &rpc_url, &app.chain, address, token, decimals).await;
+    Some(Box::new(move |app| app.set_wallet_balance(result)))
+}
+
+/// Fetch fresh data for every member of the active portfolio
+async fn refresh_portfolio(client: &reqwest::Client, app: &App) -> Option<Update> {
+    let mut group = app.portfolio.clone()?;
+
+    let mut chains: Vec<String> = group.members.iter().map(|m| m.chain.clone()).collect();
+    chains.sort();
+    chains.dedup();
+
+    // Members outside their monitoring schedule keep their last data
+    for member in group.members.iter_mut() {
+        member.paused = !schedule::is_active(&app.config.schedules, &member.pair);
+    }
+
+    for chain in chains {
+        let providers = app.providers_for_chain(&chain);
+        let polled = |m: &&mut GroupMember| m.chain == chain && !m.paused;
+
+        // DexScreener-first chains are fetched in one batched request
+        if providers.first() == Some(&api::Provider::DexScreener) {
+            let addresses: Vec<String> = group
+                .members
+                .iter()
+                .filter(|m| m.chain == chain && !m.paused)
+                .map(|m| m.pair.clone())
+                .collect();
+            if addresses.is_empty() {
+                continue;
+            }
+            let result = api::fetch_pairs_batch(client, &chain, &addresses).await;
+            for member in group.members.iter_mut().filter(polled) {
+                match result {
+                    Ok(ref found) => match found.get(&member.pair) {
+                        Some(data) => {
+                            member.data = Some(data.clone());
+                            member.error = None;
+                        }
+                        None => member.error = Some("Not found in batch response".into()),
+                    },
+                    Err(ref e) => member.error = Some(e.clone()),
+                }
+            }
+            continue;
+        }
+
+        for member in group.members.iter_mut().filter(polled) {
+            match api::fetch_from_providers(client, &providers, &member.chain, &member.pair).await {
+                Ok(data) => {
+                    member.data = Some(data);
+                    member.error = None;
+                }
+                Err(e) => member.error = Some(e),
+            }
+        }
+    }
+    Some(Box::new(move |app| {
+        app.remember_members(&group);
+        app.portfolio = Some(group);
+    }))
+}
+
 fn handle_modal_input(
     app: &mut App,
     key: KeyCode,
@@ -273,12 +1807,10 @@ fn handle_modal_input(
     needs_immediate_fetch: &mut bool,
 ) {
     match key {
-        KeyCode::Enter => {
-            // Only submit if pair address is not empty
-            if !app.modal_fields[0].trim().is_empty() {
-                app.apply_modal_config();
-                *needs_immediate_fetch = true;
-            }
+        // Only submit if pair address is not empty
+        KeyCode::Enter if !app.modal_fields[ModalField::Pair].trim().is_empty() => {
+            app.apply_modal_config();
+            *needs_immediate_fetch = true;
         }
         KeyCode::Esc => {
             if app.configured {
@@ -312,13 +1844,109 @@ fn handle_modal_input(
     }
 }
 
+fn handle_switcher_input(app: &mut App, key: KeyCode, needs_immediate_fetch: &mut bool) {
+    match key {
+        KeyCode::Enter => {
+            *needs_immediate_fetch |= app.switch_to_selected();
+        }
+        KeyCode::Esc => {
+            app.close_switcher();
+        }
+        KeyCode::Down | KeyCode::Tab => {
+            app.switcher_move(1);
+        }
+        KeyCode::Up | KeyCode::BackTab => {
+            app.switcher_move(-1);
+        }
+        KeyCode::Backspace => {
+            app.switcher_backspace();
+        }
+        KeyCode::Char(c) => {
+            app.switcher_type_char(c);
+        }
+        _ => {}
+    }
+}
+
+fn handle_onboarding_input(app: &mut App, key: KeyCode, needs_immediate_fetch: &mut bool) {
+    let Some(onboarding) = app.onboarding.as_mut() else {
+        return;
+    };
+    if onboarding.searching {
+        match key {
+            // Enter searches for a new query, or monitors the selected result
+            KeyCode::Enter => {
+                let searching = onboarding.request_search();
+                if !searching {
+                    *needs_immediate_fetch |= app.onboarding_select();
+                }
+            }
+            KeyCode::Esc => onboarding.searching = false,
+            KeyCode::Down | KeyCode::Tab => onboarding.move_selection(1),
+            KeyCode::Up | KeyCode::BackTab => onboarding.move_selection(-1),
+            KeyCode::Backspace => onboarding.backspace(),
+            KeyCode::Char(c) => onboarding.type_char(c),
+            _ => {}
+        }
+        return;
+    }
+    match key {
+        KeyCode::Char('c') => app.onboarding_edit_config(ModalField::Pair),
+        KeyCode::Char('n') => {
+            *needs_immediate_fetch |= app.onboarding_switch_chain();
+        }
+        KeyCode::Char('s') => onboarding.searching = true,
+        KeyCode::Esc => app.close_onboarding(),
+        _ => {}
+    }
+}
+
+fn handle_pool_selector_input(app: &mut App, key: KeyCode, needs_immediate_fetch: &mut bool) {
+    match key {
+        KeyCode::Enter => {
+            *needs_immediate_fetch |= app.select_pool();
+        }
+        KeyCode::Esc | KeyCode::Char('o') => {
+            app.pool_selector_open = false;
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.pool_move(1);
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.pool_move(-1);
+        }
+        _ => {}
+    }
+}
+
 fn handle_normal_input(
     app: &mut App,
     key: KeyCode,
+    modifiers: KeyModifiers,
     needs_immediate_fetch: &mut bool,
     alarm_handle: &mut Option<Arc<AtomicBool>>,
 ) {
+    // Observers can look but not touch; quitting needs a second press
+    if app.observer {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc if !app.confirm_quit() => return,
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Char('r') | KeyCode::Char('g') => {}
+            _ => return,
+        }
+    }
     match key {
+        // Ctrl+P — quick-switch between recently monitored pairs
+        KeyCode::Char('p') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.open_switcher(SwitcherMode::Switch);
+        }
+        // Toggle compare mode — pick the second pair from the recent list
+        KeyCode::Char('x') => {
+            if app.compare.is_some() {
+                app.clear_compare();
+            } else {
+                app.open_switcher(SwitcherMode::Compare);
+            }
+        }
         KeyCode::Char('q') | KeyCode::Esc => {
             app.running = false;
             if let Some(ref handle) = alarm_handle {
@@ -335,14 +1963,60 @@ fn handle_normal_input(
         KeyCode::Char('c') => {
             app.open_modal();
         }
-        KeyCode::Char('d') => {
-            // Go idle — spawn daemon and exit TUI
-            if app.configured && !app.pair_address.is_empty() {
-                app.go_idle = true;
-                app.running = false;
-                if let Some(ref handle) = alarm_handle {
-                    alarm::stop_alarm(handle);
-                }
+        KeyCode::Char('o') => {
+            app.open_pool_selector();
+        }
+        KeyCode::Char('p') => {
+            *needs_immediate_fetch |= app.toggle_pin();
+        }
+        KeyCode::Char('l') => {
+            app.toggle_low_power();
+        }
+        KeyCode::Char('i') => {
+            app.show_session = !app.show_session;
+        }
+        KeyCode::Char('t') => {
+            app.show_gauge_values = !app.show_gauge_values;
+        }
+        KeyCode::Char('w') => {
+            app.dismiss_warning();
+        }
+        KeyCode::Char('u') => {
+            app.refresh_metadata();
+            *needs_immediate_fetch = true;
+        }
+        KeyCode::Char('h') => {
+            app.show_health = !app.show_health;
+        }
+        KeyCode::Char('f') => {
+            app.cycle_provider_pin();
+            *needs_immediate_fetch = true;
+        }
+        KeyCode::Char('z') => {
+            app.cycle_snooze();
+        }
+        KeyCode::Char(' ') => {
+            *needs_immediate_fetch |= app.toggle_pause();
+        }
+        KeyCode::Char('g') => {
+            app.cycle_portfolio();
+            *needs_immediate_fetch |= app.portfolio.is_some();
+        }
+        // Go idle — spawn daemon and exit TUI
+        KeyCode::Char('d') if app.configured && !app.pair_address.is_empty() => {
+            if !app.tui_only_alerts.is_empty() {
+                let msg = format!(
+                    "[{}] ⚠ Can't go idle: the daemon doesn't check {}",
+                    Local::now().format("%H:%M:%S"),
+                    app.tui_only_alerts.join(", ")
+                );
+                app.add_log(msg);
+                return;
+            }
+            app.go_idle = true;
+            app.running = false;
+            if let Some(ref handle) = alarm_handle {
+                alarm::stop_alarm(handle);
             }
         }
         KeyCode::Char('s') => {
@@ -353,6 +2027,11 @@ fn handle_normal_input(
                     "[{}] 🔇 Alarm stopped manually",
                     Local::now().format("%H:%M:%S")
                 ));
+                app.record_event(eventlog::Event::alarm_stopped(
+                    &app.chain,
+                    &app.pair_address,
+                    "manual",
+                ));
             }
             *alarm_handle = None;
         }
@@ -0,0 +1,136 @@
+//! Token metadata kept on disk by address (name, symbol, decimals, profile
+//! links, the pool picked with `o`), so a restart or a portfolio load shows
+//! each token at once instead of resolving it all over again.
+
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::api::PairData;
+use crate::profile::Link;
+
+/// Hours an entry is trusted when `metadata_ttl_hours` isn't set
+pub const DEFAULT_TTL_HOURS: u64 = 24;
+
+/// Entries kept, least recently resolved dropped first
+const MAX_ENTRIES: usize = 500;
+
+/// What was resolved about the token behind one pair
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct TokenMetadata {
+    pub chain: String,
+    pub pair: String,
+    pub name: String,
+    pub symbol: String,
+    /// Address of the token itself, also accepted as the key
+    pub token_address: Option<String>,
+    pub decimals: Option<u32>,
+    pub description: Option<String>,
+    pub links: Vec<Link>,
+    /// Pool picked among the token's pools with `o`, monitored instead of
+    /// `pair` on the next start
+    pub chosen_pair: Option<String>,
+    /// Unix time the entry was resolved
+    pub resolved_at: i64,
+}
+
+impl TokenMetadata {
+    /// Name, symbol and token address of a fetched pair
+    pub fn from_pair(chain: &str, pair: &str, data: &PairData) -> Self {
+        let base = data.base_token.as_ref();
+        Self {
+            chain: chain.to_string(),
+            pair: pair.to_string(),
+            name: base.and_then(|t| t.name.clone()).unwrap_or_default(),
+            symbol: data.base_symbol().to_string(),
+            token_address: base.and_then(|t| t.address.clone()),
+            resolved_at: Utc::now().timestamp(),
+            ..Default::default()
+        }
+    }
+
+    fn matches(&self, chain: &str, address: &str) -> bool {
+        self.chain.eq_ignore_ascii_case(chain)
+            && (self.pair == address || self.token_address.as_deref() == Some(address))
+    }
+
+    /// Seconds since the entry was resolved
+    pub fn age_secs(&self) -> u64 {
+        (Utc::now().timestamp() - self.resolved_at).max(0) as u64
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct MetadataCache {
+    entries: Vec<TokenMetadata>,
+    /// File the cache is saved to; None keeps it in memory only
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+/// Returns the cache file path (`$XDG_CACHE_HOME/mooncap/metadata.json`,
+/// falling back to `~/.cache/mooncap/metadata.json`)
+pub fn cache_path() -> PathBuf {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".cache")))
+        .unwrap_or_else(|| PathBuf::from("/tmp"));
+    base.join("mooncap").join("metadata.json")
+}
+
+impl MetadataCache {
+    /// Load the cache from disk. A missing or unreadable file starts it empty.
+    pub fn load() -> Self {
+        let path = cache_path();
+        let mut cache: Self = fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        cache.path = Some(path);
+        cache
+    }
+
+    /// Write the cache back to disk, creating the directory if needed
+    pub fn save(&self) -> Result<(), String> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Failed to create cache dir: {}", e))?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize metadata cache: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to write metadata cache: {}", e))
+    }
+
+    /// The entry for a pair or token address, however old
+    pub fn find(&self, chain: &str, address: &str) -> Option<&TokenMetadata> {
+        self.entries.iter().find(|e| e.matches(chain, address))
+    }
+
+    /// The entry for a pair or token address, if resolved within `ttl_hours`
+    pub fn get(&self, chain: &str, address: &str, ttl_hours: u64) -> Option<&TokenMetadata> {
+        self.find(chain, address)
+            .filter(|e| e.age_secs() < ttl_hours * 3600)
+    }
+
+    /// Add or replace the entry for `entry.pair`
+    pub fn put(&mut self, entry: TokenMetadata) {
+        self.entries
+            .retain(|e| !(e.pair == entry.pair && e.chain.eq_ignore_ascii_case(&entry.chain)));
+        self.entries.insert(0, entry);
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    /// Drop the entry for a pair, so it's resolved again
+    pub fn remove(&mut self, chain: &str, pair: &str) -> bool {
+        let before = self.entries.len();
+        self.entries
+            .retain(|e| !(e.pair == pair && e.chain.eq_ignore_ascii_case(chain)));
+        self.entries.len() != before
+    }
+}
@@ -0,0 +1,171 @@
+#![cfg_attr(not(feature = "mqtt"), allow(dead_code))]
+
+//! Fetch snapshots and alerts published to an MQTT broker, for home-automation
+//! setups that flash lights or sound a siren when the target hits.
+
+use serde::{Deserialize, Serialize};
+
+use crate::bus::BusEvent;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct MqttConfig {
+    /// Broker host name or address; unset disables publishing
+    pub host: Option<String>,
+    pub port: u16,
+    pub username: Option<String>,
+    /// `keyring:<name>` is read from the OS keyring instead
+    pub password: Option<String>,
+    /// Topic of each fetch snapshot; `{chain}`, `{pair}` and `{symbol}` are
+    /// filled in
+    pub updates_topic: String,
+    /// Topic of each alert, with the same placeholders
+    pub alerts_topic: String,
+    /// Keep the latest snapshot on the broker for clients that subscribe later
+    pub retain: bool,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            host: None,
+            port: 1883,
+            username: None,
+            password: None,
+            updates_topic: "mooncap/{symbol}/update".to_string(),
+            alerts_topic: "mooncap/{symbol}/alert".to_string(),
+            retain: false,
+        }
+    }
+}
+
+impl MqttConfig {
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref().map(str::trim).filter(|h| !h.is_empty())
+    }
+
+    /// The topic `event` goes to
+    fn topic(&self, event: &BusEvent) -> String {
+        let (template, chain, pair, symbol) = match event {
+            BusEvent::Update {
+                chain,
+                pair,
+                symbol,
+                ..
+            } => (&self.updates_topic, chain, pair, symbol),
+            BusEvent::Alert {
+                chain,
+                pair,
+                symbol,
+                ..
+            } => (&self.alerts_topic, chain, pair, symbol),
+        };
+        // `+`, `#` and `/` are topic syntax
+        let clean = |value: &str| value.replace(['+', '#', '/'], "_");
+        template
+            .replace("{chain}", &clean(chain))
+            .replace("{pair}", &clean(pair))
+            .replace("{symbol}", &clean(symbol))
+    }
+}
+
+/// A connection to the broker; a background task keeps it alive and
+/// reconnects after a drop
+pub struct MqttBus {
+    #[cfg(feature = "mqtt")]
+    client: rumqttc::AsyncClient,
+    config: MqttConfig,
+}
+
+impl MqttBus {
+    /// Connect to the configured broker, if any, waiting for it to accept
+    pub async fn connect(config: &MqttConfig) -> Result<Option<Self>, String> {
+        let Some(host) = config.host() else {
+            return Ok(None);
+        };
+
+        #[cfg(feature = "mqtt")]
+        {
+            use rumqttc::{AsyncClient, Event, MqttOptions, Packet};
+            use std::time::Duration;
+
+            use crate::secrets;
+
+            let id = format!("mooncap-{}", std::process::id());
+            let mut options = MqttOptions::new(id, host, config.port);
+            options.set_keep_alive(Duration::from_secs(30));
+            if let Some(ref username) = config.username {
+                let password = config
+                    .password
+                    .as_deref()
+                    .and_then(secrets::resolve)
+                    .unwrap_or_default();
+                options.set_credentials(username.as_str(), password);
+            }
+            let (client, mut eventloop) = AsyncClient::new(options, 64);
+
+            let connected = tokio::time::timeout(Duration::from_secs(10), async {
+                loop {
+                    match eventloop.poll().await {
+                        Ok(Event::Incoming(Packet::ConnAck(_))) => return Ok(()),
+                        Ok(_) => {}
+                        Err(e) => return Err(e.to_string()),
+                    }
+                }
+            })
+            .await
+            .map_err(|_| "no answer from the broker".to_string())
+            .and_then(|result| result);
+            if let Err(e) = connected {
+                return Err(format!("MQTT connect to {}:{} failed: {}", host, config.port, e));
+            }
+
+            // The event loop sends what `publish` queues; after an error it
+            // reconnects on the next poll
+            tokio::spawn(async move {
+                loop {
+                    if eventloop.poll().await.is_err() {
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                    }
+                }
+            });
+            Ok(Some(Self {
+                client,
+                config: config.clone(),
+            }))
+        }
+
+        #[cfg(not(feature = "mqtt"))]
+        {
+            let _ = host;
+            Err("mqtt.host is set but mooncap was built without the 'mqtt' feature".to_string())
+        }
+    }
+
+    pub async fn publish(&self, event: &BusEvent) -> Result<(), String> {
+        let topic = self.config.topic(event);
+        let payload = serde_json::to_string(event)
+            .map_err(|e| format!("Failed to serialize event: {}", e))?;
+
+        #[cfg(feature = "mqtt")]
+        {
+            use rumqttc::QoS;
+
+            // Alerts must arrive; a missed snapshot is replaced by the next one
+            let (qos, retain) = match event {
+                BusEvent::Update { .. } => (QoS::AtMostOnce, self.config.retain),
+                BusEvent::Alert { .. } => (QoS::AtLeastOnce, false),
+            };
+            self.client
+                .publish(topic.as_str(), qos, retain, payload)
+                .await
+                .map_err(|e| format!("MQTT publish to {} failed: {}", topic, e))
+        }
+
+        #[cfg(not(feature = "mqtt"))]
+        {
+            let _ = (topic, payload);
+            Ok(())
+        }
+    }
+}
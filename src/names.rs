@@ -0,0 +1,81 @@
+use serde::Deserialize;
+
+use crate::api;
+use crate::endpoints::{self, Api};
+
+/// Bonfida's SNS proxy (`.sol`) and ENS Ideas (`.eth`)
+const SNS_URL: &str = "https://sns-sdk-proxy.bonfida.workers.dev/resolve";
+const ENS_URL: &str = "https://api.ensideas.com/ens/resolve";
+
+#[derive(Debug, Deserialize)]
+struct SnsResponse {
+    /// "ok" or "error"
+    s: String,
+    /// The owner's address, or the error message
+    result: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct EnsResponse {
+    address: Option<String>,
+}
+
+/// Whether `input` is an ENS (`vitalik.eth`) or SNS (`toly.sol`) name rather than an address
+pub fn is_name(input: &str) -> bool {
+    let input = input.trim().to_lowercase();
+    [".eth", ".sol"]
+        .iter()
+        .any(|tld| input.len() > tld.len() && input.ends_with(tld))
+}
+
+/// Whether the name's service resolves addresses usable on `chain`: SNS
+/// names are Solana wallets, ENS names are EVM addresses on any EVM chain
+pub fn fits_chain(name: &str, chain: &str) -> bool {
+    let sol_name = name.trim().to_lowercase().ends_with(".sol");
+    sol_name == chain.eq_ignore_ascii_case("solana")
+}
+
+/// Look up the address an ENS or SNS name points to
+pub async fn resolve(client: &reqwest::Client, name: &str) -> Result<String, String> {
+    let name = name.trim().to_lowercase();
+    let address = if name.ends_with(".sol") {
+        let url = format!("{}/{}", endpoints::base_url(Api::Sns, SNS_URL), name);
+        let reply: SnsResponse = get(client, Api::Sns, &url).await?;
+        if reply.s != "ok" {
+            return Err(format!("SNS: {}", reply.result));
+        }
+        reply.result
+    } else {
+        let url = format!("{}/{}", endpoints::base_url(Api::Ens, ENS_URL), name);
+        let reply: EnsResponse = get(client, Api::Ens, &url).await?;
+        reply.address.unwrap_or_default()
+    };
+    if address.trim().is_empty() {
+        return Err(format!("{} doesn't resolve to an address", name));
+    }
+    Ok(address)
+}
+
+async fn get<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    api: Api,
+    url: &str,
+) -> Result<T, String> {
+    let response = endpoints::get(client, api, url)
+        .header("Accept", "application/json")
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(api::describe_http_error(status, &body));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("JSON parse error: {}", e))
+}
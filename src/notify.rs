@@ -0,0 +1,633 @@
+use std::collections::HashMap;
+#[cfg(not(feature = "desktop"))]
+use std::process::Command;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::config::Config;
+use crate::discord;
+use crate::slack;
+use crate::speech;
+use crate::telegram;
+use crate::twilio;
+use crate::webhook;
+
+/// Delivery attempts per alert and channel before giving up
+const MAX_ATTEMPTS: u32 = 5;
+
+/// Delay before the first retry; doubles after each further failure
+const RETRY_BASE_SECS: u64 = 15;
+
+/// How urgent an alert is; decides which channels it is routed to, and how the
+/// alarm sounds. Ordered from least to most urgent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+/// Where an alert is delivered, on top of the log line every alert gets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Channel {
+    /// Terminal bell / audio file, or the visual alert with --silent-alerts
+    Alarm,
+    /// Native desktop notification with the `desktop` feature, otherwise
+    /// notify-send on Linux and osascript on macOS
+    Desktop,
+    /// Message from the configured Telegram bot
+    Telegram,
+    /// Embed posted to the pair's Discord webhook
+    Discord,
+    /// Block Kit message posted to the Slack webhook
+    Slack,
+    /// JSON rendered from the `webhook` template, sent to its URL
+    Webhook,
+    /// Text message, and optionally a phone call, through Twilio
+    Twilio,
+    /// The alert read out by the speech synthesizer
+    Speech,
+}
+
+impl Channel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Channel::Alarm => "alarm",
+            Channel::Desktop => "desktop",
+            Channel::Telegram => "telegram",
+            Channel::Discord => "discord",
+            Channel::Slack => "slack",
+            Channel::Webhook => "webhook",
+            Channel::Twilio => "twilio",
+            Channel::Speech => "speech",
+        }
+    }
+
+    /// Parse a channel name (case-insensitive)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "alarm" | "audio" => Some(Channel::Alarm),
+            "desktop" => Some(Channel::Desktop),
+            "telegram" => Some(Channel::Telegram),
+            "discord" => Some(Channel::Discord),
+            "slack" => Some(Channel::Slack),
+            "webhook" => Some(Channel::Webhook),
+            "twilio" | "sms" => Some(Channel::Twilio),
+            "speech" | "tts" | "say" => Some(Channel::Speech),
+            _ => None,
+        }
+    }
+
+    /// Whether the channel is a service reached over the network, which
+    /// daemons deliver to as well
+    pub fn is_remote(&self) -> bool {
+        !matches!(self, Channel::Alarm | Channel::Desktop | Channel::Speech)
+    }
+}
+
+/// `names` as channels, without repeats, plus the names that aren't channels
+fn parse_channels<'a>(
+    names: impl IntoIterator<Item = &'a String>,
+) -> (Vec<Channel>, Vec<String>) {
+    let mut known = Vec::new();
+    let mut unknown = Vec::new();
+    for name in names {
+        match Channel::parse(name) {
+            Some(c) if !known.contains(&c) => known.push(c),
+            Some(_) => {}
+            None => unknown.push(name.trim().to_string()),
+        }
+    }
+    (known, unknown)
+}
+
+/// Channels each severity is routed to, by name. Every alert is logged;
+/// an empty list means log only.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertRouting {
+    pub info: Vec<String>,
+    pub warning: Vec<String>,
+    pub critical: Vec<String>,
+    /// Channels by type of alert, used instead of its severity's list and the
+    /// remote defaults. Keyed by rule ("rule:breakout") or kind ("target",
+    /// "milestone", "rug", "whale", "move", "stale", ...); "all" stands for
+    /// the alarm, desktop and every remote channel set up.
+    pub types: HashMap<String, Vec<String>>,
+}
+
+impl Default for AlertRouting {
+    fn default() -> Self {
+        Self {
+            info: Vec::new(),
+            warning: vec!["alarm".to_string()],
+            critical: vec!["alarm".to_string()],
+            types: HashMap::new(),
+        }
+    }
+}
+
+impl AlertRouting {
+    /// Channels for a severity, plus any configured names that aren't available
+    pub fn channels(&self, severity: Severity) -> (Vec<Channel>, Vec<String>) {
+        let names = match severity {
+            Severity::Info => &self.info,
+            Severity::Warning => &self.warning,
+            Severity::Critical => &self.critical,
+        };
+        parse_channels(names)
+    }
+
+    /// The `types` entry for an alert raised by `rule`: the rule's own, else
+    /// its kind's (the part before ':')
+    fn for_type(&self, rule: &str) -> Option<&Vec<String>> {
+        if rule.is_empty() {
+            return None;
+        }
+        let kind = rule.split(':').next().unwrap_or_default();
+        self.types.get(rule).or_else(|| self.types.get(kind))
+    }
+
+    /// Whether any severity names `channel`
+    pub fn mentions(&self, channel: Channel) -> bool {
+        [&self.info, &self.warning, &self.critical]
+            .iter()
+            .flat_map(|names| names.iter())
+            .any(|name| Channel::parse(name) == Some(channel))
+    }
+}
+
+/// The pair an alert is about, for channels that show more than its text
+#[derive(Debug, Clone, Default)]
+pub struct AlertContext {
+    pub chain: String,
+    pub pair: String,
+    pub name: String,
+    pub symbol: String,
+    /// Zero until the pair has been fetched
+    pub market_cap: f64,
+    pub price: f64,
+    /// Highest target, in the target's own unit (market cap or price)
+    pub target: f64,
+    /// How far along the target is, e.g. "60% of $100000"
+    pub progress: Option<String>,
+    /// Recent market caps, oldest first
+    pub history: Vec<u64>,
+}
+
+/// One alert waiting to go out on one channel
+#[derive(Debug, Clone)]
+pub struct Delivery {
+    pub channel: Channel,
+    pub severity: Severity,
+    pub message: String,
+    pub context: AlertContext,
+    attempts: u32,
+    next_attempt: Instant,
+}
+
+impl Delivery {
+    fn new(channel: Channel, severity: Severity, message: String, context: AlertContext) -> Self {
+        Self {
+            channel,
+            severity,
+            message,
+            context,
+            attempts: 0,
+            next_attempt: Instant::now(),
+        }
+    }
+}
+
+/// Outcome of a channel's most recent delivery attempt, for the status bar
+#[derive(Debug, Clone)]
+pub struct DeliveryStatus {
+    pub channel: Channel,
+    pub ok: bool,
+    /// Local time of the attempt
+    pub at: String,
+    /// Whether a failed delivery will be retried
+    pub retrying: bool,
+    recorded: Instant,
+}
+
+/// A finished attempt, sent back from its task
+type Attempt = (Delivery, Result<(), String>);
+
+/// Alerts queued for delivery, retried with exponential backoff on failure.
+/// Attempts run as tasks of their own so a slow channel never holds up the UI
+/// or the daemon's polling.
+#[derive(Debug)]
+pub struct Outbox {
+    pending: Vec<Delivery>,
+    /// Attempts started and not yet handed back by `finished`
+    in_flight: usize,
+    finished_tx: mpsc::UnboundedSender<Attempt>,
+    finished_rx: mpsc::UnboundedReceiver<Attempt>,
+    /// Latest attempt on each channel, so a success on one channel never
+    /// hides a failure on another
+    statuses: HashMap<Channel, DeliveryStatus>,
+}
+
+impl Default for Outbox {
+    fn default() -> Self {
+        let (finished_tx, finished_rx) = mpsc::unbounded_channel();
+        Self {
+            pending: Vec::new(),
+            in_flight: 0,
+            finished_tx,
+            finished_rx,
+            statuses: HashMap::new(),
+        }
+    }
+}
+
+impl Outbox {
+    pub fn queue(
+        &mut self,
+        channel: Channel,
+        severity: Severity,
+        message: String,
+        context: AlertContext,
+    ) {
+        self.pending.push(Delivery::new(channel, severity, message, context));
+    }
+
+    /// Queue an alert on the remote channels among those `route` gave it
+    pub fn queue_remote(
+        &mut self,
+        routed: &[Channel],
+        severity: Severity,
+        message: &str,
+        context: &AlertContext,
+    ) {
+        for &channel in routed.iter().filter(|c| c.is_remote()) {
+            self.queue(channel, severity, message.to_string(), context.clone());
+        }
+    }
+
+    /// Whether every queued delivery has been sent or given up on
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty() && self.in_flight == 0
+    }
+
+    /// Remove and return the deliveries whose (re)try time has come
+    pub fn take_due(&mut self) -> Vec<Delivery> {
+        let now = Instant::now();
+        let (due, waiting) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|d| d.next_attempt <= now);
+        self.pending = waiting;
+        due
+    }
+
+    /// Start every due delivery in the background; `finished` hands back the
+    /// results. Needs a Tokio runtime.
+    pub fn dispatch(&mut self, client: &reqwest::Client, config: &Config) {
+        let due = self.take_due();
+        if due.is_empty() {
+            return;
+        }
+        let config = Arc::new(config.clone());
+        self.in_flight += due.len();
+        for delivery in due {
+            let client = client.clone();
+            let config = Arc::clone(&config);
+            let finished = self.finished_tx.clone();
+            tokio::spawn(async move {
+                let result = deliver(&client, &config, &delivery).await;
+                let _ = finished.send((delivery, result));
+            });
+        }
+    }
+
+    /// Attempts that have completed since the last call
+    pub fn finished(&mut self) -> Vec<Attempt> {
+        let mut done = Vec::new();
+        while let Ok(attempt) = self.finished_rx.try_recv() {
+            self.in_flight -= 1;
+            done.push(attempt);
+        }
+        done
+    }
+
+    /// Record the result of an attempt, scheduling a retry on failure.
+    /// Returns what happened, as an error if the delivery failed.
+    pub fn record(
+        &mut self,
+        mut delivery: Delivery,
+        result: Result<(), String>,
+    ) -> Result<String, String> {
+        delivery.attempts += 1;
+        let channel = delivery.channel;
+        let ok = result.is_ok();
+        let at = Local::now().format("%H:%M:%S").to_string();
+
+        let (outcome, retrying) = match result {
+            Ok(()) => (Ok(format!("Alert sent via {}", channel.name())), false),
+            Err(e) if delivery.attempts < MAX_ATTEMPTS => {
+                let delay = RETRY_BASE_SECS * 2u64.pow(delivery.attempts - 1);
+                delivery.next_attempt = Instant::now() + Duration::from_secs(delay);
+                self.pending.push(delivery);
+                let line =
+                    format!("{} delivery failed: {} — retrying in {}s", channel.name(), e, delay);
+                (Err(line), true)
+            }
+            Err(e) => (
+                Err(format!(
+                    "{} delivery failed after {} attempts, giving up: {}",
+                    channel.name(),
+                    MAX_ATTEMPTS,
+                    e
+                )),
+                false,
+            ),
+        };
+
+        let status = DeliveryStatus {
+            channel,
+            ok,
+            at,
+            retrying,
+            recorded: Instant::now(),
+        };
+        self.statuses.insert(channel, status);
+        outcome
+    }
+
+    /// Channels whose latest attempt failed, oldest first
+    pub fn failing(&self) -> Vec<&DeliveryStatus> {
+        let mut failing: Vec<_> = self.statuses.values().filter(|s| !s.ok).collect();
+        failing.sort_by_key(|s| s.recorded);
+        failing
+    }
+
+    /// The most recent attempt on any channel
+    pub fn latest(&self) -> Option<&DeliveryStatus> {
+        self.statuses.values().max_by_key(|s| s.recorded)
+    }
+}
+
+/// Deliver one alert on its channel
+pub async fn deliver(
+    client: &reqwest::Client,
+    config: &Config,
+    delivery: &Delivery,
+) -> Result<(), String> {
+    match delivery.channel {
+        // The alarm is started by the main loop, never queued
+        Channel::Alarm => Ok(()),
+        Channel::Desktop => {
+            let summary = format!("MoonCap {}", delivery.severity.name());
+            send_desktop(summary, delivery.message.clone()).await
+        }
+        Channel::Telegram => {
+            telegram::send(client, &config.telegram, delivery.severity, &delivery.message).await
+        }
+        Channel::Discord => {
+            let Some(url) = config.discord.webhook(&delivery.context.pair) else {
+                return Err("no Discord webhook configured for the pair".to_string());
+            };
+            let context = &delivery.context;
+            discord::send(client, &url, delivery.severity, &delivery.message, context).await
+        }
+        Channel::Slack => {
+            let Some(url) = config.slack.webhook() else {
+                return Err("no Slack webhook configured".to_string());
+            };
+            let context = &delivery.context;
+            slack::send(client, &url, delivery.severity, &delivery.message, context).await
+        }
+        Channel::Webhook => {
+            let context = &delivery.context;
+            webhook::send(client, &config.webhook, delivery.severity, &delivery.message, context)
+                .await
+        }
+        Channel::Twilio => {
+            twilio::send(client, &config.twilio, delivery.severity, &delivery.message).await
+        }
+        Channel::Speech => speech::speak(&config.speech, &delivery.message),
+    }
+}
+
+/// Remote channels set up in the config for alerts about `pair`
+fn configured_channels(config: &Config, pair: &str) -> Vec<Channel> {
+    let mut channels = Vec::new();
+    if config.telegram.is_configured() {
+        channels.push(Channel::Telegram);
+    }
+    if config.discord.webhook(pair).is_some() {
+        channels.push(Channel::Discord);
+    }
+    if config.slack.webhook().is_some() {
+        channels.push(Channel::Slack);
+    }
+    if config.webhook.url().is_some() {
+        channels.push(Channel::Webhook);
+    }
+    if config.twilio.is_configured() {
+        channels.push(Channel::Twilio);
+    }
+    channels
+}
+
+/// Remote channels set up in the config for alerts about `pair` of
+/// `severity`, unless `alert_routing` names them. Info alerts stay in the log;
+/// Telegram, Discord and the webhook get warnings and critical alerts, Slack and
+/// Twilio only the critical ones (target hits, liquidity drops, rugs).
+fn remote_channels(config: &Config, pair: &str, severity: Severity) -> Vec<Channel> {
+    let mut channels = configured_channels(config, pair);
+    channels.retain(|c| match c {
+        Channel::Slack | Channel::Twilio => severity == Severity::Critical,
+        _ => severity >= Severity::Warning,
+    });
+    channels.retain(|c| !config.alert_routing.mentions(*c));
+    channels
+}
+
+/// Channels an alert raised by `rule` goes to: its `alert_routing.types`
+/// entry if it has one, otherwise its severity's list plus the remote channels
+/// that take it by default. Also returns configured names that aren't channels.
+pub fn route(
+    config: &Config,
+    pair: &str,
+    severity: Severity,
+    rule: &str,
+) -> (Vec<Channel>, Vec<String>) {
+    let Some(names) = config.alert_routing.for_type(rule) else {
+        let (mut channels, unknown) = config.alert_routing.channels(severity);
+        // Configured remote channels get alerts unless the routing says otherwise
+        for channel in remote_channels(config, pair, severity) {
+            if !channels.contains(&channel) {
+                channels.push(channel);
+            }
+        }
+        return (channels, unknown);
+    };
+    let all = |name: &String| matches!(name.trim().to_lowercase().as_str(), "all" | "everything");
+    let (mut channels, unknown) = parse_channels(names.iter().filter(|n| !all(n)));
+    if names.iter().any(all) {
+        // Speech counts once it is set up or routed to, as it has no credentials
+        let speech =
+            config.speech.is_configured() || config.alert_routing.mentions(Channel::Speech);
+        let everything = [Channel::Alarm, Channel::Desktop]
+            .into_iter()
+            .chain(speech.then_some(Channel::Speech))
+            .chain(configured_channels(config, pair));
+        for channel in everything {
+            if !channels.contains(&channel) {
+                channels.push(channel);
+            }
+        }
+    }
+    (channels, unknown)
+}
+
+/// Channels set up to hear about failing fetches, which aren't routed alerts
+pub fn error_channels(config: &Config) -> Vec<Channel> {
+    let mut channels = Vec::new();
+    if config.slack.webhook().is_some() {
+        channels.push(Channel::Slack);
+    }
+    channels
+}
+
+/// Prefix `text` with the instance name (`--name`), if any, so alerts from
+/// several running mooncaps can be told apart
+pub fn label(instance: Option<&str>, text: &str) -> String {
+    match instance {
+        Some(name) => format!("[{}] {}", name, text),
+        None => text.to_string(),
+    }
+}
+
+/// Show a desktop notification. The notifier blocks until the notification
+/// service answers, so it runs on a blocking thread, off the async workers.
+pub async fn send_desktop(summary: String, body: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || show_desktop(&summary, &body))
+        .await
+        .map_err(|e| format!("notification task failed: {}", e))?
+}
+
+/// Show a desktop notification through the OS notification service
+#[cfg(feature = "desktop")]
+fn show_desktop(summary: &str, body: &str) -> Result<(), String> {
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .appname("mooncap")
+        .summary(summary)
+        .body(body)
+        .timeout(notify_rust::Timeout::Never);
+    #[cfg(all(unix, not(target_os = "macos")))]
+    notification.urgency(notify_rust::Urgency::Critical);
+    notification
+        .show()
+        .map(|_| ())
+        .map_err(|e| format!("notification failed: {}", e))
+}
+
+/// Show a desktop notification, reporting whether the notifier succeeded
+#[cfg(not(feature = "desktop"))]
+fn show_desktop(summary: &str, body: &str) -> Result<(), String> {
+    #[cfg(target_os = "macos")]
+    let output = Command::new("osascript")
+        .args([
+            "-e",
+            &format!("display notification {:?} with title {:?}", body, summary),
+        ])
+        .output();
+    #[cfg(not(target_os = "macos"))]
+    let output = Command::new("notify-send")
+        .args(["--urgency=critical", "--expire-time=0", summary, body])
+        .output();
+
+    match output {
+        Ok(out) if out.status.success() => Ok(()),
+        Ok(out) => Err(format!(
+            "notifier exited with {}: {}",
+            out.status,
+            String::from_utf8_lossy(&out.stderr).trim()
+        )),
+        Err(e) => Err(format!("could not run notifier: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A config with Telegram, Discord, Slack, the webhook and Twilio set up
+    fn remote_config() -> Config {
+        let mut config = Config::default();
+        config.telegram.bot_token = Some("123:token".to_string());
+        config.telegram.chat_id = Some("42".to_string());
+        config.discord.webhook_url = Some("https://discord.test/hook".to_string());
+        config.slack.webhook_url = Some("https://slack.test/hook".to_string());
+        config.webhook.url = Some("https://example.test/alerts".to_string());
+        config.twilio.account_sid = Some("AC123".to_string());
+        config.twilio.auth_token = Some("token".to_string());
+        config.twilio.from = Some("+15005550006".to_string());
+        config.twilio.to = vec!["+15005550001".to_string()];
+        config
+    }
+
+    #[test]
+    fn info_alerts_stay_off_remote_channels() {
+        let (channels, _) = route(&remote_config(), "pair", Severity::Info, "progress");
+        assert!(channels.iter().all(|c| !c.is_remote()), "{:?}", channels);
+    }
+
+    #[test]
+    fn warnings_skip_slack_and_twilio() {
+        let (channels, _) = route(&remote_config(), "pair", Severity::Warning, "move");
+        assert_eq!(
+            channels,
+            [Channel::Alarm, Channel::Telegram, Channel::Discord, Channel::Webhook]
+        );
+    }
+
+    #[test]
+    fn critical_alerts_reach_every_remote_channel() {
+        let (channels, _) = route(&remote_config(), "pair", Severity::Critical, "rug");
+        for channel in [Channel::Telegram, Channel::Slack, Channel::Twilio] {
+            assert!(channels.contains(&channel), "{:?} missing", channel);
+        }
+    }
+
+    #[test]
+    fn failure_outlasts_success_on_another_channel() {
+        let mut outbox = Outbox::default();
+        let delivery = |channel| {
+            Delivery::new(channel, Severity::Critical, "MOON hit".to_string(), Default::default())
+        };
+        let failed = outbox.record(delivery(Channel::Telegram), Err("token expired".to_string()));
+        assert_eq!(
+            failed,
+            Err("telegram delivery failed: token expired — retrying in 15s".to_string())
+        );
+        let sent = outbox.record(delivery(Channel::Desktop), Ok(()));
+        assert_eq!(sent.as_deref(), Ok("Alert sent via desktop"));
+
+        let failing: Vec<_> = outbox.failing().iter().map(|s| s.channel).collect();
+        assert_eq!(failing, [Channel::Telegram]);
+        assert_eq!(outbox.latest().map(|s| s.channel), Some(Channel::Desktop));
+
+        assert!(outbox.record(delivery(Channel::Telegram), Ok(())).is_ok());
+        assert!(outbox.failing().is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+
+/// Closed rows kept in memory for the summary table
+const MAX_ROWS: usize = 48;
+
+/// Length of one summary row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Hourly,
+    Daily,
+}
+
+impl Period {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "hourly" | "hour" | "1h" => Some(Self::Hourly),
+            "daily" | "day" | "1d" => Some(Self::Daily),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Hourly => "hourly",
+            Self::Daily => "daily",
+        }
+    }
+
+    /// Label of the period containing `at`; samples with the same label share a row
+    fn label(&self, at: DateTime<Local>) -> String {
+        match self {
+            Self::Hourly => at.format("%Y-%m-%d %H:00").to_string(),
+            Self::Daily => at.format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+/// Open/high/low/close of the market cap over one period
+#[derive(Debug, Clone)]
+pub struct OhlcRow {
+    pub period: String,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub samples: usize,
+}
+
+impl OhlcRow {
+    fn new(period: String, value: f64) -> Self {
+        Self {
+            period,
+            open: value,
+            high: value,
+            low: value,
+            close: value,
+            samples: 1,
+        }
+    }
+
+    /// Close vs open, in percent
+    pub fn change(&self) -> f64 {
+        if self.open > 0.0 {
+            (self.close - self.open) / self.open * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    pub fn csv_line(&self) -> String {
+        format!(
+            "{},{:.0},{:.0},{:.0},{:.0},{}",
+            self.period, self.open, self.high, self.low, self.close, self.samples
+        )
+    }
+}
+
+pub const CSV_HEADER: &str = "period,open,high,low,close,samples";
+
+/// Rolls fetched market caps up into one row per hour or day
+#[derive(Debug, Clone)]
+pub struct OhlcTable {
+    pub period: Period,
+    /// Oldest first; the last row is the period in progress
+    pub rows: VecDeque<OhlcRow>,
+}
+
+impl OhlcTable {
+    pub fn new(period: Period) -> Self {
+        Self {
+            period,
+            rows: VecDeque::new(),
+        }
+    }
+
+    /// Add a sample. Returns the previous row when this sample starts a new period.
+    pub fn record(&mut self, at: DateTime<Local>, value: f64) -> Option<OhlcRow> {
+        if value <= 0.0 {
+            return None;
+        }
+        let label = self.period.label(at);
+        if let Some(row) = self.rows.back_mut().filter(|r| r.period == label) {
+            row.high = row.high.max(value);
+            row.low = row.low.min(value);
+            row.close = value;
+            row.samples += 1;
+            return None;
+        }
+
+        let closed = self.rows.back().cloned();
+        self.rows.push_back(OhlcRow::new(label, value));
+        if self.rows.len() > MAX_ROWS {
+            self.rows.pop_front();
+        }
+        closed
+    }
+
+    /// Seed the table with history from before the first live sample. `candles`
+    /// are (open time, [open, high, low, close]) oldest first; each is merged
+    /// into the row of its period, and rows recorded so far are kept on top.
+    pub fn backfill(&mut self, candles: impl IntoIterator<Item = (DateTime<Local>, [f64; 4])>) {
+        let live = std::mem::take(&mut self.rows);
+        for (at, [open, high, low, close]) in candles {
+            if close <= 0.0 {
+                continue;
+            }
+            let row = OhlcRow {
+                period: self.period.label(at),
+                open,
+                high,
+                low,
+                close,
+                samples: 1,
+            };
+            self.merge(row);
+        }
+        for row in live {
+            self.merge(row);
+        }
+        while self.rows.len() > MAX_ROWS {
+            self.rows.pop_front();
+        }
+    }
+
+    /// Append `row`, folding it into the last row if both cover the same period
+    fn merge(&mut self, row: OhlcRow) {
+        match self.rows.back_mut().filter(|r| r.period == row.period) {
+            Some(last) => {
+                last.high = last.high.max(row.high);
+                last.low = last.low.min(row.low);
+                last.close = row.close;
+                last.samples += row.samples;
+            }
+            None => self.rows.push_back(row),
+        }
+    }
+}
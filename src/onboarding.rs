@@ -0,0 +1,120 @@
+//! Help for a pair whose very first fetch fails: the likely causes, and
+//! one-key ways out (edit the config, switch chain, search by symbol) instead
+//! of a "Loading…" screen that never changes.
+
+use crate::api::PairData;
+
+#[derive(Debug, Clone)]
+pub struct Onboarding {
+    /// The error the first fetch failed with
+    pub error: String,
+    /// Other chains DexScreener lists the address on, most liquid first
+    pub other_chains: Vec<String>,
+    /// Whether the symbol search is shown instead of the causes
+    pub searching: bool,
+    pub query: String,
+    /// Query the results belong to, so Enter after editing it searches again
+    pub searched: Option<String>,
+    /// Set until the search has run; the main loop runs it between frames
+    pub search_pending: bool,
+    pub results: Result<Vec<PairData>, String>,
+    pub selected: usize,
+}
+
+impl Onboarding {
+    pub fn new(error: String, other_chains: Vec<String>) -> Self {
+        Self {
+            error,
+            other_chains,
+            searching: false,
+            query: String::new(),
+            searched: None,
+            search_pending: false,
+            results: Ok(Vec::new()),
+            selected: 0,
+        }
+    }
+
+    /// The chain `n` switches to, if the address is listed on another one
+    pub fn suggested_chain(&self) -> Option<&str> {
+        self.other_chains.first().map(String::as_str)
+    }
+
+    /// Likely explanations for the failure, most likely first
+    pub fn causes(&self, chain: &str) -> Vec<String> {
+        let mut causes = Vec::new();
+        if !self.other_chains.is_empty() {
+            causes.push(format!(
+                "The address trades on {}, not {}",
+                self.other_chains.join(", "),
+                chain
+            ));
+        }
+        let error = self.error.as_str();
+        if error.contains("may not be supported") {
+            causes.push(format!(
+                "DexScreener doesn't index a chain called '{}' — check the spelling \
+                 (solana, ethereum, bsc, base, ...)",
+                chain
+            ));
+        }
+        if error.starts_with("No pairs found") || error.contains("HTTP 404") {
+            if self.other_chains.is_empty() {
+                causes.push("A typo in the address, or a truncated paste".to_string());
+            }
+            causes.push("The token is too new to have a DEX pool yet".to_string());
+        } else if error.starts_with("HTTP request failed") {
+            causes.push("No answer from the API — check your connection or proxy".to_string());
+        } else if error.contains("HTTP 429") {
+            causes.push("The API is rate limiting requests — it clears by itself".to_string());
+        } else if error.contains("HTTP 5") {
+            causes.push("The API is having an outage".to_string());
+        } else if causes.is_empty() {
+            causes.push("The provider rejected the request (see the error below)".to_string());
+        }
+        causes
+    }
+
+    pub fn type_char(&mut self, c: char) {
+        self.query.push(c);
+    }
+
+    pub fn backspace(&mut self) {
+        self.query.pop();
+    }
+
+    /// Results of the current search, empty until one has run
+    pub fn matches(&self) -> &[PairData] {
+        match &self.results {
+            Ok(pairs) if self.searched.is_some() => pairs,
+            _ => &[],
+        }
+    }
+
+    /// Move the result selection up (-1) or down (+1), wrapping around
+    pub fn move_selection(&mut self, delta: i32) {
+        let len = self.matches().len();
+        if len == 0 {
+            return;
+        }
+        self.selected = (self.selected as i32 + delta).rem_euclid(len as i32) as usize;
+    }
+
+    /// Queue a search for the typed query. Returns false if there's nothing new
+    /// to search for.
+    pub fn request_search(&mut self) -> bool {
+        let query = self.query.trim();
+        if query.is_empty() || self.searched.as_deref() == Some(query) {
+            return false;
+        }
+        self.search_pending = true;
+        true
+    }
+
+    pub fn set_results(&mut self, results: Result<Vec<PairData>, String>) {
+        self.searched = Some(self.query.trim().to_string());
+        self.search_pending = false;
+        self.results = results;
+        self.selected = 0;
+    }
+}
@@ -0,0 +1,282 @@
+//! Line formats for the headless daemon's output (`--output-format`), so its
+//! log can go straight into a log pipeline: human-readable lines, logfmt, JSON
+//! lines or a CSV stream.
+
+use chrono::{DateTime, Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::Target;
+use crate::notify::Severity;
+
+/// Something the daemon reports
+pub enum Record<'a> {
+    /// Startup, configuration and schedule messages
+    Info(&'a str),
+    /// A feature that was turned off or a write that failed
+    Warning(&'a str),
+    Fetch {
+        name: &'a str,
+        symbol: &'a str,
+        market_cap: f64,
+        price: f64,
+        target: Target,
+    },
+    FetchFailed(&'a str),
+    Alert {
+        severity: Severity,
+        symbol: &'a str,
+        message: &'a str,
+    },
+}
+
+impl Record<'_> {
+    fn level(&self) -> &'static str {
+        match self {
+            Record::Info(_) | Record::Fetch { .. } => "info",
+            Record::Warning(_) => "warning",
+            Record::FetchFailed(_) => "error",
+            Record::Alert { severity, .. } => severity.name(),
+        }
+    }
+
+    fn event(&self) -> &'static str {
+        match self {
+            Record::Info(_) | Record::Warning(_) => "log",
+            Record::Fetch { .. } => "fetch",
+            Record::FetchFailed(_) => "fetch_failed",
+            Record::Alert { .. } => "alert",
+        }
+    }
+
+    /// The message without the emoji the human format leads with
+    fn message(&self) -> String {
+        match self {
+            Record::Info(text) | Record::Warning(text) => {
+                text.trim_start_matches(|c: char| !c.is_alphanumeric()).to_string()
+            }
+            Record::Fetch { name, symbol, .. } => format!("{} ({})", name, symbol),
+            Record::FetchFailed(error) => error.to_string(),
+            Record::Alert { message, .. } => message.to_string(),
+        }
+    }
+}
+
+/// Turns records into output lines
+pub trait Formatter: Send + Sync {
+    /// Line written before the first record, e.g. a CSV header
+    fn header(&self) -> Option<String> {
+        None
+    }
+
+    fn format(&self, at: DateTime<Local>, record: &Record) -> String;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Logfmt,
+    Json,
+    Csv,
+}
+
+impl OutputFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OutputFormat::Human => "human",
+            OutputFormat::Logfmt => "logfmt",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+        }
+    }
+
+    pub fn formatter(&self) -> Box<dyn Formatter> {
+        match self {
+            OutputFormat::Human => Box::new(Human),
+            OutputFormat::Logfmt => Box::new(Logfmt),
+            OutputFormat::Json => Box::new(Json),
+            OutputFormat::Csv => Box::new(Csv),
+        }
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "human" | "text" => Ok(OutputFormat::Human),
+            "logfmt" => Ok(OutputFormat::Logfmt),
+            "json" | "jsonl" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err("use human, logfmt, json or csv".to_string()),
+        }
+    }
+}
+
+/// `[2026-10-16 14:32:07] ✓ Pepe (PEPE) | MCap: ...`, as the daemon always wrote
+struct Human;
+
+impl Formatter for Human {
+    fn format(&self, at: DateTime<Local>, record: &Record) -> String {
+        let text = match record {
+            Record::Info(text) => text.to_string(),
+            Record::Warning(text) => format!("⚠ {}", text),
+            Record::Fetch {
+                name,
+                symbol,
+                market_cap,
+                price,
+                target,
+            } => format!(
+                "✓ {} ({}) | MCap: ${:.0} | Price: ${:.8} | Target: {}",
+                name,
+                symbol,
+                market_cap,
+                price,
+                target.kind.format(target.value)
+            ),
+            Record::FetchFailed(error) => format!("❌ Fetch error: {}", error),
+            Record::Alert { message, .. } => format!("🚨 {}", message),
+        };
+        format!("[{}] {}", at.format("%Y-%m-%d %H:%M:%S"), text)
+    }
+}
+
+/// `time=... level=info event=fetch symbol=PEPE market_cap=812345 ...`
+struct Logfmt;
+
+impl Logfmt {
+    fn value(value: &str) -> String {
+        if !value.is_empty() && !value.contains([' ', '"', '=', '\\']) {
+            return value.to_string();
+        }
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+impl Formatter for Logfmt {
+    fn format(&self, at: DateTime<Local>, record: &Record) -> String {
+        let mut fields = vec![
+            ("time", at.to_rfc3339_opts(SecondsFormat::Secs, false)),
+            ("level", record.level().to_string()),
+            ("event", record.event().to_string()),
+        ];
+        match record {
+            Record::Fetch {
+                name,
+                symbol,
+                market_cap,
+                price,
+                target,
+            } => {
+                fields.push(("symbol", symbol.to_string()));
+                fields.push(("name", name.to_string()));
+                fields.push(("market_cap", format!("{:.0}", market_cap)));
+                fields.push(("price", price.to_string()));
+                fields.push(("target", target.value.to_string()));
+                fields.push(("target_kind", target.kind.name().replace(' ', "_")));
+            }
+            Record::Alert { symbol, .. } => {
+                fields.push(("symbol", symbol.to_string()));
+                fields.push(("msg", record.message()));
+            }
+            _ => fields.push(("msg", record.message())),
+        }
+        fields
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, Self::value(value)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// One JSON object per line
+struct Json;
+
+impl Formatter for Json {
+    fn format(&self, at: DateTime<Local>, record: &Record) -> String {
+        let mut line = serde_json::json!({
+            "time": at.to_rfc3339_opts(SecondsFormat::Secs, false),
+            "level": record.level(),
+            "event": record.event(),
+        });
+        match record {
+            Record::Fetch {
+                name,
+                symbol,
+                market_cap,
+                price,
+                target,
+            } => {
+                line["symbol"] = (*symbol).into();
+                line["name"] = (*name).into();
+                line["market_cap"] = (*market_cap).into();
+                line["price"] = (*price).into();
+                line["target"] = target.value.into();
+                line["target_kind"] = target.kind.name().replace(' ', "_").into();
+            }
+            Record::Alert { symbol, .. } => {
+                line["symbol"] = (*symbol).into();
+                line["message"] = record.message().into();
+            }
+            _ => line["message"] = record.message().into(),
+        }
+        line.to_string()
+    }
+}
+
+/// Fixed columns, blank where a record has no value
+struct Csv;
+
+impl Csv {
+    fn field(value: &str) -> String {
+        if value.contains([',', '"', '\n']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+impl Formatter for Csv {
+    fn header(&self) -> Option<String> {
+        Some("time,level,event,symbol,market_cap,price,target,message".to_string())
+    }
+
+    fn format(&self, at: DateTime<Local>, record: &Record) -> String {
+        let (symbol, market_cap, price, target) = match record {
+            Record::Fetch {
+                symbol,
+                market_cap,
+                price,
+                target,
+                ..
+            } => (
+                symbol.to_string(),
+                format!("{:.0}", market_cap),
+                price.to_string(),
+                target.value.to_string(),
+            ),
+            Record::Alert { symbol, .. } => {
+                (symbol.to_string(), String::new(), String::new(), String::new())
+            }
+            _ => Default::default(),
+        };
+        [
+            at.to_rfc3339_opts(SecondsFormat::Secs, false),
+            record.level().to_string(),
+            record.event().to_string(),
+            symbol,
+            market_cap,
+            price,
+            target,
+            record.message(),
+        ]
+        .iter()
+        .map(|value| Self::field(value))
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
@@ -0,0 +1,114 @@
+//! Frame pacing for the TUI: redraws are capped at a frame rate however fast
+//! keys and updates arrive, while input keeps being read between frames, and
+//! the time each frame takes to render is measured for the diagnostics panel.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Frame cap when neither `--fps` nor `max_fps` is set
+pub const DEFAULT_FPS: u32 = 10;
+
+/// Highest cap accepted; terminals gain nothing beyond it
+const MAX_FPS: u32 = 60;
+
+/// Frames the render time and rate are measured over
+const SAMPLES: usize = 50;
+
+/// Render time and frame rate over the recent frames
+#[derive(Debug, Clone, Copy)]
+pub struct FrameStats {
+    pub average: Duration,
+    pub max: Duration,
+    /// Frames actually drawn per second
+    pub fps: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FramePacer {
+    /// Shortest time between two frames
+    min_interval: Duration,
+    last_frame: Option<Instant>,
+    /// Whether something changed since the last frame
+    dirty: bool,
+    /// When each recent frame was drawn and how long it took to render
+    frames: VecDeque<(Instant, Duration)>,
+}
+
+impl Default for FramePacer {
+    fn default() -> Self {
+        let mut pacer = Self {
+            min_interval: Duration::ZERO,
+            last_frame: None,
+            dirty: true,
+            frames: VecDeque::new(),
+        };
+        pacer.set_fps(DEFAULT_FPS);
+        pacer
+    }
+}
+
+impl FramePacer {
+    /// Cap redraws at `fps` frames per second (1 to 60)
+    pub fn set_fps(&mut self, fps: u32) {
+        let fps = fps.clamp(1, MAX_FPS);
+        self.min_interval = Duration::from_secs(1) / fps;
+    }
+
+    /// The frame cap, in frames per second
+    pub fn fps(&self) -> u32 {
+        (Duration::from_secs(1).as_nanos() / self.min_interval.as_nanos().max(1)) as u32
+    }
+
+    /// Note that the screen is out of date, so the next frame is drawn as
+    /// soon as the cap allows
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Time left until a frame should be drawn: the cap's interval once
+    /// something changed, otherwise `idle`, so clocks and blinking keep moving
+    pub fn wait(&self, idle: Duration) -> Duration {
+        let Some(last) = self.last_frame else {
+            return Duration::ZERO;
+        };
+        let interval = if self.dirty { self.min_interval } else { idle.max(self.min_interval) };
+        interval.saturating_sub(last.elapsed())
+    }
+
+    /// Whether a frame should be drawn now
+    pub fn due(&self, idle: Duration) -> bool {
+        self.wait(idle).is_zero()
+    }
+
+    /// Record a drawn frame and the time it took to render
+    pub fn record(&mut self, render: Duration) {
+        let now = Instant::now();
+        self.last_frame = Some(now);
+        self.dirty = false;
+        self.frames.push_back((now, render));
+        while self.frames.len() > SAMPLES {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Render time and frame rate over the recent frames, once there are two
+    pub fn stats(&self) -> Option<FrameStats> {
+        let (first, _) = *self.frames.front()?;
+        let (last, _) = *self.frames.back()?;
+        if self.frames.len() < 2 {
+            return None;
+        }
+        let total: Duration = self.frames.iter().map(|(_, render)| *render).sum();
+        let max = self.frames.iter().map(|(_, render)| *render).max().unwrap_or_default();
+        let span = last.duration_since(first).as_secs_f64();
+        Some(FrameStats {
+            average: total / self.frames.len() as u32,
+            max,
+            fps: if span > 0.0 { (self.frames.len() - 1) as f64 / span } else { 0.0 },
+        })
+    }
+}
@@ -0,0 +1,98 @@
+use chrono::{DateTime, Local, TimeZone};
+use serde::Deserialize;
+
+use crate::api;
+
+const BINANCE_FUTURES_URL: &str = "https://fapi.binance.com/fapi/v1";
+
+/// Multipliers Binance lists low-priced tokens under, e.g. 1000BONKUSDT
+const CONTRACT_PREFIXES: [&str; 4] = ["", "1000", "1000000", "1M"];
+
+/// Funding per interval (in percent) that counts as elevated / extreme. The
+/// baseline rate is 0.01% every 8 hours.
+pub const FUNDING_ELEVATED: f64 = 0.05;
+pub const FUNDING_EXTREME: f64 = 0.1;
+
+/// Funding and open interest of the token's perpetual futures market
+#[derive(Debug, Clone)]
+pub struct PerpMarket {
+    /// Contract symbol, e.g. 1000PEPEUSDT
+    pub symbol: String,
+    /// Last funding rate in percent; positive means longs pay shorts
+    pub funding_rate: f64,
+    pub next_funding: Option<DateTime<Local>>,
+    /// Open interest in USD at the mark price
+    pub open_interest_usd: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PremiumIndex {
+    symbol: String,
+    mark_price: String,
+    last_funding_rate: String,
+    next_funding_time: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OpenInterest {
+    open_interest: String,
+}
+
+/// The Binance USDT-margined perp for `symbol`, or None if it has none
+pub async fn fetch_market(
+    client: &reqwest::Client,
+    symbol: &str,
+) -> Result<Option<PerpMarket>, String> {
+    let symbol = symbol.trim().to_uppercase();
+    let indexes: Vec<PremiumIndex> = get(client, "premiumIndex").await?;
+    let Some(index) = CONTRACT_PREFIXES.iter().find_map(|prefix| {
+        let contract = format!("{}{}USDT", prefix, symbol);
+        indexes.iter().find(|i| i.symbol == contract)
+    }) else {
+        return Ok(None);
+    };
+
+    let interest: OpenInterest =
+        get(client, &format!("openInterest?symbol={}", index.symbol)).await?;
+    let mark_price: f64 = index.mark_price.parse().unwrap_or(0.0);
+    let contracts: f64 = interest.open_interest.parse().unwrap_or(0.0);
+    let funding: f64 = index
+        .last_funding_rate
+        .parse()
+        .map_err(|_| format!("Binance returned an invalid funding rate for {}", index.symbol))?;
+    Ok(Some(PerpMarket {
+        symbol: index.symbol.clone(),
+        funding_rate: funding * 100.0,
+        next_funding: Local.timestamp_millis_opt(index.next_funding_time).single(),
+        open_interest_usd: contracts * mark_price,
+    }))
+}
+
+async fn get<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    path: &str,
+) -> Result<T, String> {
+    let response = client
+        .get(format!("{}/{}", BINANCE_FUTURES_URL, path))
+        .header("Accept", "application/json")
+        .header("User-Agent", "mooncap")
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        if status.as_u16() == 451 {
+            return Err("Binance futures is unavailable from this location".to_string());
+        }
+        return Err(api::describe_http_error(status, &body));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("JSON parse error: {}", e))
+}
@@ -0,0 +1,113 @@
+use crate::api::PairData;
+use crate::config::Portfolio;
+
+/// Live state of one pair inside a portfolio
+#[derive(Debug, Clone)]
+pub struct GroupMember {
+    pub pair: String,
+    pub chain: String,
+    pub target: Option<f64>,
+    pub data: Option<PairData>,
+    pub error: Option<String>,
+    /// Outside its monitoring schedule, so not being polled
+    pub paused: bool,
+    /// Symbol from the metadata cache, shown until the first fetch
+    pub cached_symbol: Option<String>,
+}
+
+impl GroupMember {
+    pub fn market_cap(&self) -> f64 {
+        self.data.as_ref().map(|d| d.market_cap_usd()).unwrap_or(0.0)
+    }
+
+    pub fn symbol(&self) -> String {
+        match self.data {
+            Some(ref d) => d.base_symbol().to_string(),
+            None => match self.cached_symbol {
+                Some(ref symbol) => symbol.clone(),
+                None => self.pair.chars().take(8).collect(),
+            },
+        }
+    }
+}
+
+/// A portfolio being monitored, with aggregated stats across its members
+#[derive(Debug, Clone)]
+pub struct GroupView {
+    pub name: String,
+    pub members: Vec<GroupMember>,
+}
+
+impl GroupView {
+    pub fn from_config(portfolio: &Portfolio) -> Self {
+        Self {
+            name: portfolio.name.clone(),
+            members: portfolio
+                .members
+                .iter()
+                .map(|m| GroupMember {
+                    pair: m.pair.clone(),
+                    chain: m.chain.clone(),
+                    target: m.target,
+                    data: None,
+                    error: None,
+                    paused: false,
+                    cached_symbol: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// A group of untargeted tokens or pairs on one chain, e.g. a wallet's holdings
+    pub fn from_addresses(name: &str, chain: &str, addresses: &[String]) -> Self {
+        Self {
+            name: name.to_string(),
+            members: addresses
+                .iter()
+                .map(|address| GroupMember {
+                    pair: address.clone(),
+                    chain: chain.to_string(),
+                    target: None,
+                    data: None,
+                    error: None,
+                    paused: false,
+                    cached_symbol: None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn total_market_cap(&self) -> f64 {
+        self.members.iter().map(|m| m.market_cap()).sum()
+    }
+
+    pub fn total_volume_24h(&self) -> f64 {
+        self.members
+            .iter()
+            .filter_map(|m| m.data.as_ref())
+            .map(|d| d.volume_24h_usd())
+            .sum()
+    }
+
+    pub fn total_liquidity(&self) -> f64 {
+        self.members
+            .iter()
+            .filter_map(|m| m.data.as_ref())
+            .map(|d| d.liquidity_usd())
+            .sum()
+    }
+
+    /// Blended progress (0–100) across members that have a target. Each member
+    /// contributes at most its own target, so one moonshot can't fill the bar alone.
+    pub fn blended_progress(&self) -> f64 {
+        let (reached, total) = self
+            .members
+            .iter()
+            .filter_map(|m| m.target.filter(|t| *t > 0.0).map(|t| (m.market_cap().min(t), t)))
+            .fold((0.0, 0.0), |(r, t), (mr, mt)| (r + mr, t + mt));
+        if total <= 0.0 {
+            return 0.0;
+        }
+        (reached / total * 100.0).min(100.0)
+    }
+}
@@ -0,0 +1,39 @@
+use std::fs;
+use std::process;
+
+/// Returns true if the machine is running on battery power.
+/// Linux reads `/sys/class/power_supply`; macOS asks `pmset`. Unknown platforms
+/// (or desktops without a battery) report false.
+pub fn on_battery() -> bool {
+    if cfg!(target_os = "macos") {
+        return process::Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).contains("Battery Power"))
+            .unwrap_or(false);
+    }
+
+    let Ok(entries) = fs::read_dir("/sys/class/power_supply") else {
+        return false;
+    };
+
+    let mut has_battery = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let kind = fs::read_to_string(path.join("type")).unwrap_or_default();
+        match kind.trim() {
+            // Any AC adapter reporting online means we're plugged in
+            "Mains"
+                if fs::read_to_string(path.join("online")).is_ok_and(|v| v.trim() == "1") =>
+            {
+                return false;
+            }
+            "Battery" => {
+                let status = fs::read_to_string(path.join("status")).unwrap_or_default();
+                has_battery |= status.trim() == "Discharging";
+            }
+            _ => {}
+        }
+    }
+    has_battery
+}
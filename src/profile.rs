@@ -0,0 +1,128 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::api;
+use crate::endpoints::{self, Api};
+
+/// Latest token profiles, latest boosts and the most boosted tokens
+const PROFILES_PATH: &str = "/token-profiles/latest/v1";
+const BOOSTS_PATHS: [&str; 2] = ["/token-boosts/latest/v1", "/token-boosts/top/v1"];
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileEntry {
+    chain_id: Option<String>,
+    token_address: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    links: Vec<Link>,
+    /// Present on boost entries only
+    amount: Option<f64>,
+    total_amount: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Link {
+    /// "twitter", "telegram", ... (websites have a label instead)
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub label: Option<String>,
+    pub url: String,
+}
+
+impl Link {
+    pub fn name(&self) -> &str {
+        self.label
+            .as_deref()
+            .or(self.kind.as_deref())
+            .unwrap_or("link")
+    }
+}
+
+/// DexScreener profile and boost status for the profile panel
+#[derive(Debug, Clone, Default)]
+pub struct TokenProfile {
+    /// Active boost amount, if the token is currently boosted
+    pub boost: Option<f64>,
+    /// Boosts bought over the token's lifetime
+    pub boost_total: Option<f64>,
+    pub description: Option<String>,
+    pub links: Vec<Link>,
+    pub error: Option<String>,
+}
+
+impl TokenProfile {
+    /// Whether there is anything worth a panel
+    pub fn is_empty(&self) -> bool {
+        self.boost.is_none()
+            && self.description.is_none()
+            && self.links.is_empty()
+            && self.error.is_none()
+    }
+}
+
+async fn get<T: DeserializeOwned>(client: &reqwest::Client, path: &str) -> Result<T, String> {
+    let url = format!("{}{}", api::dexscreener_root(), path);
+    let response = endpoints::get(client, Api::DexScreener, &url)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(api::describe_http_error(status, &body));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("JSON parse error: {}", e))
+}
+
+/// Look the token up in DexScreener's latest profiles and boost lists. These
+/// are global feeds, so a token that isn't in them simply has no profile/boost.
+pub async fn fetch_profile(
+    client: &reqwest::Client,
+    chain: &str,
+    token: &str,
+) -> Result<TokenProfile, String> {
+    let matches = |e: &ProfileEntry| {
+        e.chain_id
+            .as_deref()
+            .is_some_and(|c| c.eq_ignore_ascii_case(chain))
+            && e.token_address
+                .as_deref()
+                .is_some_and(|a| a.eq_ignore_ascii_case(token))
+    };
+
+    let mut profile = TokenProfile::default();
+    let profiles: Vec<ProfileEntry> = get(client, PROFILES_PATH).await?;
+    if let Some(entry) = profiles.into_iter().find(|e| matches(e)) {
+        profile.description = entry.description;
+        profile.links = entry.links;
+    }
+
+    for path in BOOSTS_PATHS {
+        let boosts: Vec<ProfileEntry> = get(client, path).await?;
+        let Some(entry) = boosts.into_iter().find(|e| matches(e)) else {
+            continue;
+        };
+        profile.boost = entry.amount.filter(|a| *a > 0.0).or(profile.boost);
+        profile.boost_total = entry.total_amount.or(profile.boost_total);
+        if profile.description.is_none() {
+            profile.description = entry.description;
+        }
+        if profile.links.is_empty() {
+            profile.links = entry.links;
+        }
+    }
+
+    profile.description = profile
+        .description
+        .map(|d| d.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|d| !d.is_empty());
+    Ok(profile)
+}
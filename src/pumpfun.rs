@@ -0,0 +1,152 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::api::{self, BondingCurve, DataSource, Liquidity, PairData, Provider, Target, Token};
+use crate::endpoints::{self, Api};
+
+const BASE_URL: &str = "https://frontend-api-v3.pump.fun/coins";
+
+/// Tokens sold on the curve before it completes and the token migrates, in
+/// whole tokens (pump.fun tokens have 6 decimals and a 1B supply)
+const CURVE_TOKENS: f64 = 793_100_000.0;
+/// Virtual token reserves minus real ones, constant for every pump.fun curve
+const VIRTUAL_TOKEN_OFFSET: f64 = 279_900_000.0;
+const TOKEN_DECIMALS: i32 = 6;
+const LAMPORTS_PER_SOL: f64 = 1e9;
+
+#[derive(Debug, Deserialize)]
+struct Coin {
+    mint: String,
+    name: Option<String>,
+    symbol: Option<String>,
+    /// Set once the curve is full and liquidity has migrated
+    #[serde(default)]
+    complete: bool,
+    /// Market cap in SOL and USD
+    market_cap: Option<f64>,
+    usd_market_cap: Option<f64>,
+    total_supply: Option<f64>,
+    virtual_token_reserves: Option<f64>,
+    real_token_reserves: Option<f64>,
+    real_sol_reserves: Option<f64>,
+    created_timestamp: Option<i64>,
+    raydium_pool: Option<String>,
+    pump_swap_pool: Option<String>,
+}
+
+/// pump.fun coin API (Solana only), for tokens still on the bonding curve
+/// and so without a DexScreener pair
+pub struct PumpFun {
+    client: reqwest::Client,
+}
+
+impl PumpFun {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl DataSource for PumpFun {
+    fn provider(&self) -> Provider {
+        Provider::PumpFun
+    }
+
+    async fn fetch(&self, target: &Target<'_>) -> Result<PairData, String> {
+        if !target.chain.eq_ignore_ascii_case("solana") {
+            return Err("pump.fun only lists Solana tokens".to_string());
+        }
+        fetch_coin(&self.client, target.token.unwrap_or(target.address)).await
+    }
+}
+
+/// Whether `address` looks like a pump.fun mint (they are ground to end in "pump")
+pub fn is_pump_mint(address: &str) -> bool {
+    address.trim().ends_with("pump")
+}
+
+async fn fetch_coin(client: &reqwest::Client, mint: &str) -> Result<PairData, String> {
+    let url = format!("{}/{}", endpoints::base_url(Api::PumpFun, BASE_URL), mint.trim());
+    let response = endpoints::get(client, Api::PumpFun, &url)
+        .header("Accept", "application/json")
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(api::describe_http_error(status, &body));
+    }
+
+    // Unknown mints come back as an empty body rather than a 404
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("HTTP read failed: {}", e))?;
+    if body.trim().is_empty() || body.trim() == "null" {
+        return Err(format!("pump.fun has no coin with mint {}", mint));
+    }
+    let coin: Coin =
+        serde_json::from_str(&body).map_err(|e| format!("JSON parse error: {}", e))?;
+
+    let usd_cap = coin.usd_market_cap.unwrap_or(0.0);
+    let supply = coin.total_supply.unwrap_or(1e15) / 10f64.powi(TOKEN_DECIMALS);
+    // SOL/USD implied by the two market caps, to value the SOL in the curve
+    let sol_price = match coin.market_cap {
+        Some(sol_cap) if sol_cap > 0.0 => usd_cap / sol_cap,
+        _ => 0.0,
+    };
+
+    let tokens_left = coin
+        .real_token_reserves
+        .map(|r| r / 10f64.powi(TOKEN_DECIMALS))
+        .or_else(|| {
+            coin.virtual_token_reserves
+                .map(|v| v / 10f64.powi(TOKEN_DECIMALS) - VIRTUAL_TOKEN_OFFSET)
+        });
+    let progress = if coin.complete {
+        100.0
+    } else {
+        tokens_left
+            .map(|left| (1.0 - left / CURVE_TOKENS) * 100.0)
+            .unwrap_or(0.0)
+            .clamp(0.0, 100.0)
+    };
+    let sol_in_curve = coin.real_sol_reserves.map(|l| l / LAMPORTS_PER_SOL);
+
+    Ok(PairData {
+        chain_id: Some("solana".to_string()),
+        dex_id: None,
+        pair_address: coin.pump_swap_pool.clone().or(coin.raydium_pool.clone()),
+        base_token: Some(Token {
+            address: Some(coin.mint),
+            name: coin.name,
+            symbol: coin.symbol,
+        }),
+        quote_token: Some(Token {
+            address: None,
+            name: Some("Wrapped SOL".to_string()),
+            symbol: Some("SOL".to_string()),
+        }),
+        price_native: None,
+        price_usd: (supply > 0.0).then(|| (usd_cap / supply).to_string()),
+        fdv: Some(usd_cap),
+        market_cap: Some(usd_cap),
+        txns: None,
+        volume: None,
+        price_change: None,
+        liquidity: Some(Liquidity {
+            usd: sol_in_curve.map(|sol| sol * sol_price),
+            base: tokens_left,
+            quote: sol_in_curve,
+        }),
+        pair_created_at: coin.created_timestamp,
+        info: None,
+        bonding_curve: Some(BondingCurve {
+            progress,
+            complete: coin.complete,
+        }),
+    })
+}
@@ -0,0 +1,97 @@
+use crate::api::PairData;
+
+/// Pools thinner than this are ignored when comparing quotes, since their
+/// prices are too easily skewed to mean anything
+const MIN_POOL_LIQUIDITY: f64 = 1_000.0;
+
+/// USD price of the token in its most liquid pool for one quote asset
+#[derive(Debug, Clone)]
+pub struct QuotePrice {
+    pub quote: String,
+    pub price: f64,
+}
+
+/// Implied price difference between the cheapest and richest quote pools
+#[derive(Debug, Clone)]
+pub struct QuoteGap {
+    pub cheap: QuotePrice,
+    pub rich: QuotePrice,
+    /// (rich - cheap) / cheap, in percent
+    pub percent: f64,
+}
+
+/// Liquidity and volume summed over every pool of the token on one chain
+#[derive(Debug, Clone, Copy)]
+pub struct PoolTotals {
+    pub pools: usize,
+    pub liquidity_usd: f64,
+    pub volume_24h: f64,
+}
+
+/// Sum liquidity and 24h volume across the token's pools on `chain`
+pub fn pool_totals(pools: &[PairData], chain: &str) -> Option<PoolTotals> {
+    let on_chain: Vec<&PairData> = pools
+        .iter()
+        .filter(|p| {
+            p.chain_id
+                .as_deref()
+                .is_some_and(|c| c.eq_ignore_ascii_case(chain))
+        })
+        .collect();
+    if on_chain.is_empty() {
+        return None;
+    }
+    Some(PoolTotals {
+        pools: on_chain.len(),
+        liquidity_usd: on_chain.iter().map(|p| p.liquidity_usd()).sum(),
+        volume_24h: on_chain.iter().map(|p| p.volume_24h_usd()).sum(),
+    })
+}
+
+/// Compare the token's USD price across quote assets (e.g. SOL vs USDC pools)
+/// on `chain`. Returns None unless at least two quote assets have a usable pool.
+pub fn quote_gap(pools: &[PairData], chain: &str) -> Option<QuoteGap> {
+    let mut by_quote: Vec<(QuotePrice, f64)> = Vec::new();
+    for pool in pools {
+        let on_chain = pool
+            .chain_id
+            .as_deref()
+            .is_some_and(|c| c.eq_ignore_ascii_case(chain));
+        let price = pool.price_usd_f64();
+        let liquidity = pool.liquidity_usd();
+        if !on_chain || price <= 0.0 || liquidity < MIN_POOL_LIQUIDITY {
+            continue;
+        }
+
+        let quote = pool.quote_symbol().to_uppercase();
+        let candidate = QuotePrice {
+            quote: quote.clone(),
+            price,
+        };
+        match by_quote.iter_mut().find(|(q, _)| q.quote == quote) {
+            Some(entry) if entry.1 < liquidity => *entry = (candidate, liquidity),
+            Some(_) => {}
+            None => by_quote.push((candidate, liquidity)),
+        }
+    }
+
+    if by_quote.len() < 2 {
+        return None;
+    }
+    let cheap = by_quote
+        .iter()
+        .min_by(|a, b| a.0.price.total_cmp(&b.0.price))?
+        .0
+        .clone();
+    let rich = by_quote
+        .iter()
+        .max_by(|a, b| a.0.price.total_cmp(&b.0.price))?
+        .0
+        .clone();
+    let percent = (rich.price - cheap.price) / cheap.price * 100.0;
+    Some(QuoteGap {
+        cheap,
+        rich,
+        percent,
+    })
+}
@@ -0,0 +1,252 @@
+use serde_json::{json, Value};
+
+/// `totalSupply()`, `decimals()` and `balanceOf(address)` ERC-20 selectors
+const TOTAL_SUPPLY: &str = "0x18160ddd";
+const DECIMALS: &str = "0x313ce567";
+const BALANCE_OF: &str = "0x70a08231";
+
+/// Public endpoints used for the fee indicator when no RPC is configured
+const PUBLIC_RPC: &[(&str, &str)] = &[
+    ("solana", "https://api.mainnet-beta.solana.com"),
+    ("ethereum", "https://ethereum-rpc.publicnode.com"),
+    ("base", "https://mainnet.base.org"),
+    ("bsc", "https://bsc-dataseed.bnbchain.org"),
+    ("arbitrum", "https://arb1.arbitrum.io/rpc"),
+    ("optimism", "https://mainnet.optimism.io"),
+    ("polygon", "https://polygon-rpc.com"),
+];
+
+/// Public RPC endpoint for a chain, if one is known
+pub fn public_rpc(chain: &str) -> Option<&'static str> {
+    PUBLIC_RPC
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(chain))
+        .map(|(_, url)| *url)
+}
+
+/// What it currently costs to get a transaction in
+#[derive(Debug, Clone, Copy)]
+pub enum Fee {
+    /// EVM gas price
+    Gwei(f64),
+    /// Solana median recent priority fee, in micro-lamports per compute unit
+    MicroLamports(f64),
+}
+
+impl Fee {
+    pub fn describe(&self) -> String {
+        match *self {
+            Fee::Gwei(g) if g < 1.0 => format!("{:.3} gwei", g),
+            Fee::Gwei(g) => format!("{:.1} gwei", g),
+            Fee::MicroLamports(m) if m >= 1_000.0 => format!("{:.1}K µL/CU", m / 1_000.0),
+            Fee::MicroLamports(m) => format!("{:.0} µL/CU", m),
+        }
+    }
+}
+
+/// Gas price (EVM) or recent priority fee (Solana) from the chain's RPC
+pub async fn fetch_fee(
+    client: &reqwest::Client,
+    rpc_url: &str,
+    chain: &str,
+) -> Result<Fee, String> {
+    if chain.eq_ignore_ascii_case("solana") {
+        let result = call(client, rpc_url, "getRecentPrioritizationFees", json!([])).await?;
+        let mut fees: Vec<f64> = result
+            .as_array()
+            .ok_or("getRecentPrioritizationFees returned no fees")?
+            .iter()
+            .filter_map(|f| f["prioritizationFee"].as_f64())
+            .filter(|f| *f > 0.0)
+            .collect();
+        if fees.is_empty() {
+            return Ok(Fee::MicroLamports(0.0));
+        }
+        fees.sort_by(|a, b| a.total_cmp(b));
+        return Ok(Fee::MicroLamports(fees[fees.len() / 2]));
+    }
+
+    let result = call(client, rpc_url, "eth_gasPrice", json!([])).await?;
+    let wei = hex_to_f64(
+        result
+            .as_str()
+            .ok_or("eth_gasPrice returned no data")?
+            .trim_start_matches("0x"),
+    );
+    Ok(Fee::Gwei(wei / 1e9))
+}
+
+/// Total token supply in whole tokens, and the token's decimals, read from
+/// the chain's RPC. Solana uses `getTokenSupply`; every other chain is treated
+/// as EVM (`totalSupply()`), skipping `decimals()` when they're already known.
+pub async fn fetch_supply(
+    client: &reqwest::Client,
+    rpc_url: &str,
+    chain: &str,
+    token: &str,
+    decimals: Option<u32>,
+) -> Result<(f64, u32), String> {
+    if chain.eq_ignore_ascii_case("solana") {
+        let result = call(client, rpc_url, "getTokenSupply", json!([token])).await?;
+        let value = &result["value"];
+        let amount: f64 = value["amount"]
+            .as_str()
+            .and_then(|a| a.parse().ok())
+            .ok_or("getTokenSupply returned no amount")?;
+        let decimals = value["decimals"].as_u64().unwrap_or(0) as u32;
+        return Ok((amount / 10f64.powi(decimals as i32), decimals));
+    }
+
+    let supply = eth_call(client, rpc_url, token, TOTAL_SUPPLY).await?;
+    let decimals = match decimals {
+        Some(decimals) => decimals,
+        None => eth_call(client, rpc_url, token, DECIMALS).await? as u32,
+    };
+    Ok((supply / 10f64.powi(decimals as i32), decimals))
+}
+
+/// SPL Token and Token-2022 program ids
+const TOKEN_PROGRAMS: [&str; 2] = [
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PQnBxvhTPbkm1jq",
+];
+
+/// Non-zero token balances held by a Solana wallet, as (mint, whole tokens).
+/// A mint held in several token accounts appears once, summed.
+pub async fn fetch_token_balances(
+    client: &reqwest::Client,
+    rpc_url: &str,
+    owner: &str,
+) -> Result<Vec<(String, f64)>, String> {
+    let mut balances: Vec<(String, f64)> = Vec::new();
+    for program in TOKEN_PROGRAMS {
+        let filter = json!({ "programId": program });
+        let accounts = token_accounts(client, rpc_url, owner, filter).await?;
+        for (mint, amount) in accounts {
+            if amount <= 0.0 {
+                continue;
+            }
+            match balances.iter_mut().find(|(m, _)| *m == mint) {
+                Some((_, total)) => *total += amount,
+                None => balances.push((mint, amount)),
+            }
+        }
+    }
+    Ok(balances)
+}
+
+/// How much of `token` a wallet holds, in whole tokens. Solana sums the
+/// owner's token accounts for the mint (zero when there are none); every
+/// other chain is treated as EVM (`balanceOf`), with `decimals()` read unless
+/// they're already known.
+pub async fn fetch_token_balance(
+    client: &reqwest::Client,
+    rpc_url: &str,
+    chain: &str,
+    owner: &str,
+    token: &str,
+    decimals: Option<u32>,
+) -> Result<f64, String> {
+    if chain.eq_ignore_ascii_case("solana") {
+        let accounts = token_accounts(client, rpc_url, owner, json!({ "mint": token })).await?;
+        return Ok(accounts.iter().map(|(_, amount)| amount).sum());
+    }
+
+    let hex = owner.trim().trim_start_matches("0x");
+    if hex.len() != 40 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("{} is not an EVM address", owner));
+    }
+    let data = format!("{}{:0>64}", BALANCE_OF, hex.to_lowercase());
+    let balance = eth_call(client, rpc_url, token, &data).await?;
+    let decimals = match decimals {
+        Some(decimals) => decimals as f64,
+        None => eth_call(client, rpc_url, token, DECIMALS).await?,
+    };
+    Ok(balance / 10f64.powi(decimals as i32))
+}
+
+/// (mint, whole tokens) of each token account owned by `owner` that matches
+/// `filter` (`{"mint": ..}` or `{"programId": ..}`)
+async fn token_accounts(
+    client: &reqwest::Client,
+    rpc_url: &str,
+    owner: &str,
+    filter: Value,
+) -> Result<Vec<(String, f64)>, String> {
+    let params = json!([owner, filter, { "encoding": "jsonParsed" }]);
+    let result = call(client, rpc_url, "getTokenAccountsByOwner", params).await?;
+    let accounts = result["value"]
+        .as_array()
+        .ok_or("getTokenAccountsByOwner returned no accounts")?;
+    Ok(accounts
+        .iter()
+        .filter_map(|account| {
+            let info = &account["account"]["data"]["parsed"]["info"];
+            Some((
+                info["mint"].as_str()?.to_string(),
+                info["tokenAmount"]["uiAmount"].as_f64()?,
+            ))
+        })
+        .collect())
+}
+
+async fn eth_call(
+    client: &reqwest::Client,
+    rpc_url: &str,
+    contract: &str,
+    data: &str,
+) -> Result<f64, String> {
+    let result = call(
+        client,
+        rpc_url,
+        "eth_call",
+        json!([{ "to": contract, "data": data }, "latest"]),
+    )
+    .await?;
+    let hex = result
+        .as_str()
+        .ok_or("eth_call returned no data")?
+        .trim_start_matches("0x");
+    if hex.is_empty() {
+        return Err(format!("{} is not an ERC-20 contract", contract));
+    }
+    Ok(hex_to_f64(hex))
+}
+
+/// Parse a big-endian hex word into f64 (precision loss is fine for display)
+fn hex_to_f64(hex: &str) -> f64 {
+    hex.chars()
+        .filter_map(|c| c.to_digit(16))
+        .fold(0.0, |acc, d| acc * 16.0 + d as f64)
+}
+
+async fn call(
+    client: &reqwest::Client,
+    rpc_url: &str,
+    method: &str,
+    params: Value,
+) -> Result<Value, String> {
+    let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+    let response = client
+        .post(rpc_url)
+        .json(&body)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("RPC request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("RPC returned HTTP {}", status.as_u16()));
+    }
+
+    let mut reply: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("RPC JSON parse error: {}", e))?;
+    if let Some(err) = reply.get("error") {
+        let message = err["message"].as_str().unwrap_or("unknown error");
+        return Err(format!("RPC {} error: {}", method, message));
+    }
+    Ok(reply["result"].take())
+}
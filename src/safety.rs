@@ -0,0 +1,330 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::api;
+use crate::endpoints::{self, Api};
+
+/// GoPlus token security (EVM chains) and rugcheck.xyz report summaries (Solana)
+const GOPLUS_URL: &str = "https://api.gopluslabs.io/api/v1/token_security";
+const RUGCHECK_URL: &str = "https://api.rugcheck.xyz/v1/tokens";
+
+/// DexScreener chain ids and their GoPlus chain ids
+const GOPLUS_CHAINS: &[(&str, &str)] = &[
+    ("ethereum", "1"),
+    ("bsc", "56"),
+    ("polygon", "137"),
+    ("arbitrum", "42161"),
+    ("base", "8453"),
+    ("optimism", "10"),
+    ("avalanche", "43114"),
+    ("fantom", "250"),
+];
+
+/// Sell tax at or above this share of the trade makes a token dangerous
+const DANGER_SELL_TAX: f64 = 50.0;
+/// Buy or sell tax at or above this is worth a warning
+const CAUTION_TAX: f64 = 10.0;
+/// Less of the LP than this locked or burned is worth a warning
+const CAUTION_LP_LOCKED: f64 = 50.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Safe,
+    Caution,
+    Danger,
+}
+
+#[derive(Debug, Clone)]
+pub struct Flag {
+    pub level: Level,
+    pub text: String,
+}
+
+/// Result of the one-off safety check shown in the security panel
+#[derive(Debug, Clone, Default)]
+pub struct SecurityReport {
+    /// "GoPlus" or "RugCheck"
+    pub source: &'static str,
+    /// Whether new tokens can still be minted
+    pub mint_authority: Option<bool>,
+    /// Share of the liquidity that is locked or burned, in percent
+    pub lp_locked: Option<f64>,
+    /// Whether holders may be unable to sell. On Solana this is an enabled
+    /// freeze authority, which lets the creator freeze holders' accounts.
+    pub honeypot: Option<bool>,
+    pub buy_tax: Option<f64>,
+    pub sell_tax: Option<f64>,
+    /// Everything the check found, worst first
+    pub flags: Vec<Flag>,
+    pub error: Option<String>,
+}
+
+impl SecurityReport {
+    /// The worst level among the flags
+    pub fn level(&self) -> Level {
+        self.flags
+            .iter()
+            .map(|f| f.level)
+            .max()
+            .unwrap_or(Level::Safe)
+    }
+
+    pub fn is_dangerous(&self) -> bool {
+        self.level() == Level::Danger
+    }
+
+    /// The dangerous findings, for the banner and alert text
+    pub fn dangers(&self) -> Vec<&str> {
+        self.flags
+            .iter()
+            .filter(|f| f.level == Level::Danger)
+            .map(|f| f.text.as_str())
+            .collect()
+    }
+
+    fn flag(&mut self, level: Level, text: impl Into<String>) {
+        self.flags.push(Flag {
+            level,
+            text: text.into(),
+        });
+    }
+}
+
+/// Whether a safety API covers the chain
+pub fn supports_chain(chain: &str) -> bool {
+    chain.eq_ignore_ascii_case("solana") || goplus_chain(chain).is_some()
+}
+
+fn goplus_chain(chain: &str) -> Option<&'static str> {
+    GOPLUS_CHAINS
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(chain))
+        .map(|(_, id)| *id)
+}
+
+async fn get<T: DeserializeOwned>(
+    client: &reqwest::Client,
+    api: Api,
+    url: &str,
+) -> Result<T, String> {
+    let response = endpoints::get(client, api, url)
+        .header("Accept", "application/json")
+        .timeout(std::time::Duration::from_secs(15))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(api::describe_http_error(status, &body));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("JSON parse error: {}", e))
+}
+
+/// Run the safety check for `token` on `chain`
+pub async fn fetch_report(
+    client: &reqwest::Client,
+    chain: &str,
+    token: &str,
+) -> Result<SecurityReport, String> {
+    let mut report = if chain.eq_ignore_ascii_case("solana") {
+        fetch_rugcheck(client, token).await?
+    } else {
+        let id = goplus_chain(chain).ok_or_else(|| format!("No safety API for {}", chain))?;
+        fetch_goplus(client, id, token).await?
+    };
+    report.flags.sort_by_key(|f| Reverse(f.level));
+    Ok(report)
+}
+
+#[derive(Debug, Deserialize)]
+struct GoPlusEnvelope {
+    code: i64,
+    message: Option<String>,
+    #[serde(default)]
+    result: HashMap<String, GoPlusToken>,
+}
+
+/// GoPlus reports booleans and numbers as strings ("0"/"1", "0.05")
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct GoPlusToken {
+    is_honeypot: Option<String>,
+    cannot_sell_all: Option<String>,
+    is_mintable: Option<String>,
+    owner_change_balance: Option<String>,
+    hidden_owner: Option<String>,
+    selfdestruct: Option<String>,
+    transfer_pausable: Option<String>,
+    is_blacklisted: Option<String>,
+    is_proxy: Option<String>,
+    is_open_source: Option<String>,
+    buy_tax: Option<String>,
+    sell_tax: Option<String>,
+    lp_holders: Vec<LpHolder>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LpHolder {
+    percent: Option<String>,
+    #[serde(default)]
+    is_locked: u8,
+    address: Option<String>,
+}
+
+fn flag_set(value: &Option<String>) -> Option<bool> {
+    value.as_deref().map(|v| v == "1")
+}
+
+/// A fraction such as "0.05", as a percentage
+fn percent(value: &Option<String>) -> Option<f64> {
+    value
+        .as_deref()
+        .and_then(|v| v.parse::<f64>().ok())
+        .map(|f| f * 100.0)
+}
+
+async fn fetch_goplus(
+    client: &reqwest::Client,
+    chain_id: &str,
+    token: &str,
+) -> Result<SecurityReport, String> {
+    let url = format!(
+        "{}/{}?contract_addresses={}",
+        endpoints::base_url(Api::GoPlus, GOPLUS_URL),
+        chain_id,
+        token
+    );
+    let envelope: GoPlusEnvelope = get(client, Api::GoPlus, &url).await?;
+    if envelope.code != 1 {
+        return Err(format!(
+            "GoPlus error: {}",
+            envelope.message.unwrap_or_else(|| format!("code {}", envelope.code))
+        ));
+    }
+    let info = envelope
+        .result
+        .into_iter()
+        .find(|(address, _)| address.eq_ignore_ascii_case(token))
+        .map(|(_, info)| info)
+        .ok_or("GoPlus has no data for this token yet")?;
+
+    let mut report = SecurityReport {
+        source: "GoPlus",
+        mint_authority: flag_set(&info.is_mintable),
+        honeypot: flag_set(&info.is_honeypot),
+        buy_tax: percent(&info.buy_tax),
+        sell_tax: percent(&info.sell_tax),
+        ..SecurityReport::default()
+    };
+
+    let checks = [
+        (&info.is_honeypot, Level::Danger, "Honeypot: sells fail"),
+        (&info.cannot_sell_all, Level::Danger, "Holders can't sell their full balance"),
+        (&info.owner_change_balance, Level::Danger, "Owner can change balances"),
+        (&info.hidden_owner, Level::Danger, "Hidden owner"),
+        (&info.selfdestruct, Level::Danger, "Contract can self-destruct"),
+        (&info.is_mintable, Level::Caution, "Mintable"),
+        (&info.transfer_pausable, Level::Caution, "Transfers can be paused"),
+        (&info.is_blacklisted, Level::Caution, "Has a blacklist"),
+        (&info.is_proxy, Level::Caution, "Upgradeable proxy"),
+    ];
+    for (value, level, text) in checks {
+        if flag_set(value) == Some(true) {
+            report.flag(level, text);
+        }
+    }
+    if flag_set(&info.is_open_source) == Some(false) {
+        report.flag(Level::Caution, "Source not verified");
+    }
+
+    match report.sell_tax {
+        Some(tax) if tax >= DANGER_SELL_TAX => {
+            report.flag(Level::Danger, format!("Sell tax {:.0}%", tax))
+        }
+        Some(tax) if tax >= CAUTION_TAX => {
+            report.flag(Level::Caution, format!("Sell tax {:.0}%", tax))
+        }
+        _ => {}
+    }
+    if let Some(tax) = report.buy_tax.filter(|t| *t >= CAUTION_TAX) {
+        report.flag(Level::Caution, format!("Buy tax {:.0}%", tax));
+    }
+
+    // LP sent to the dead address counts as locked
+    if !info.lp_holders.is_empty() {
+        let locked: f64 = info
+            .lp_holders
+            .iter()
+            .filter(|h| {
+                h.is_locked == 1
+                    || h.address.as_deref().is_some_and(|a| {
+                        a.eq_ignore_ascii_case("0x000000000000000000000000000000000000dead")
+                            || a.trim_start_matches("0x").chars().all(|c| c == '0')
+                    })
+            })
+            .filter_map(|h| percent(&h.percent))
+            .sum();
+        let locked = locked.min(100.0);
+        report.lp_locked = Some(locked);
+        if locked < CAUTION_LP_LOCKED {
+            report.flag(Level::Caution, format!("Only {:.0}% of LP locked", locked));
+        }
+    }
+    Ok(report)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RugCheckSummary {
+    #[serde(default)]
+    risks: Vec<RugCheckRisk>,
+    lp_locked_pct: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RugCheckRisk {
+    name: String,
+    #[serde(default)]
+    level: String,
+}
+
+async fn fetch_rugcheck(client: &reqwest::Client, mint: &str) -> Result<SecurityReport, String> {
+    let url = format!(
+        "{}/{}/report/summary",
+        endpoints::base_url(Api::RugCheck, RUGCHECK_URL),
+        mint
+    );
+    let summary: RugCheckSummary = get(client, Api::RugCheck, &url).await?;
+
+    let has_risk = |needle: &str| {
+        summary
+            .risks
+            .iter()
+            .any(|r| r.name.to_lowercase().contains(needle))
+    };
+    let mut report = SecurityReport {
+        source: "RugCheck",
+        mint_authority: Some(has_risk("mint authority")),
+        honeypot: Some(has_risk("freeze authority")),
+        lp_locked: summary.lp_locked_pct,
+        ..SecurityReport::default()
+    };
+    for risk in &summary.risks {
+        let level = match risk.level.as_str() {
+            "danger" => Level::Danger,
+            "warn" => Level::Caution,
+            _ => continue,
+        };
+        report.flag(level, risk.name.clone());
+    }
+    Ok(report)
+}
@@ -0,0 +1,150 @@
+use chrono::{DateTime, Datelike, Local, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::notify::Severity;
+
+/// When a token is worth polling. Outside its schedule a token isn't fetched at all.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Schedule {
+    /// Pair or token address the schedule applies to
+    pub pair: String,
+    /// Local time window such as "08:00-23:00"; wraps past midnight when the end
+    /// is earlier than the start (e.g. "22:00-06:00")
+    pub hours: Option<String>,
+    /// Days to poll on: "mon".."sun", "weekdays" or "weekends". Empty means every day.
+    pub days: Vec<String>,
+}
+
+impl Schedule {
+    pub fn applies_to(&self, address: &str) -> bool {
+        self.pair.eq_ignore_ascii_case(address)
+    }
+
+    /// Check the hours and day names, so typos are reported instead of ignored
+    pub fn validate(&self) -> Result<(), String> {
+        validate(self.hours.as_deref(), &self.days)
+    }
+
+    /// Whether polling is allowed at `at`. Invalid fields don't restrict anything.
+    pub fn active_at(&self, at: DateTime<Local>) -> bool {
+        within(self.hours.as_deref(), &self.days, at)
+    }
+
+    /// Short description for the header and log, e.g. "08:00-23:00 weekdays"
+    pub fn describe(&self) -> String {
+        describe(self.hours.as_deref(), &self.days)
+    }
+}
+
+/// When the alarm stays silent. Alerts are still logged and notified, just
+/// without sound.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct QuietHours {
+    /// Local time window such as "01:00-07:00", wrapping past midnight like a
+    /// schedule's
+    pub hours: Option<String>,
+    /// Days quiet hours apply on, as for a schedule. Empty means every day.
+    pub days: Vec<String>,
+    /// Let critical alerts sound the alarm anyway
+    pub allow_critical: bool,
+}
+
+impl QuietHours {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.hours.is_none() {
+            return Err("no hours set (use HH:MM-HH:MM)".to_string());
+        }
+        validate(self.hours.as_deref(), &self.days)
+    }
+
+    /// Whether an alert of `severity` at `at` should stay silent. Invalid
+    /// quiet hours never silence anything.
+    pub fn silences(&self, severity: Severity, at: DateTime<Local>) -> bool {
+        if self.validate().is_err() || (self.allow_critical && severity == Severity::Critical) {
+            return false;
+        }
+        within(self.hours.as_deref(), &self.days, at)
+    }
+
+    /// e.g. "01:00-07:00 weekdays, critical alerts still sound"
+    pub fn describe(&self) -> String {
+        let window = describe(self.hours.as_deref(), &self.days);
+        if self.allow_critical {
+            format!("{}, critical alerts still sound", window)
+        } else {
+            window
+        }
+    }
+}
+
+/// The schedule for `address`, if one is configured
+pub fn find<'a>(schedules: &'a [Schedule], address: &str) -> Option<&'a Schedule> {
+    schedules.iter().find(|s| s.applies_to(address))
+}
+
+/// Whether `address` should be polled now; unscheduled addresses always are
+pub fn is_active(schedules: &[Schedule], address: &str) -> bool {
+    match find(schedules, address) {
+        Some(schedule) => schedule.active_at(Local::now()),
+        None => true,
+    }
+}
+
+fn validate(hours: Option<&str>, days: &[String]) -> Result<(), String> {
+    if let Some(hours) = hours {
+        if parse_hours(hours).is_none() {
+            return Err(format!("invalid hours '{}' (use HH:MM-HH:MM)", hours));
+        }
+    }
+    match days.iter().find(|d| parse_days(d).is_empty()) {
+        Some(day) => Err(format!("invalid day '{}'", day)),
+        None => Ok(()),
+    }
+}
+
+/// Whether `at` falls on one of `days` and within `hours`; unset or invalid
+/// fields don't restrict anything
+fn within(hours: Option<&str>, days: &[String], at: DateTime<Local>) -> bool {
+    let days: Vec<Weekday> = days.iter().flat_map(|d| parse_days(d)).collect();
+    if !days.is_empty() && !days.contains(&at.weekday()) {
+        return false;
+    }
+    let Some((start, end)) = hours.and_then(parse_hours) else {
+        return true;
+    };
+    let now = at.time();
+    if start <= end {
+        now >= start && now < end
+    } else {
+        now >= start || now < end
+    }
+}
+
+fn describe(hours: Option<&str>, days: &[String]) -> String {
+    let mut parts = Vec::new();
+    if let Some(hours) = hours {
+        parts.push(hours.to_string());
+    }
+    if !days.is_empty() {
+        parts.push(days.join(","));
+    }
+    parts.join(" ")
+}
+
+fn parse_hours(hours: &str) -> Option<(NaiveTime, NaiveTime)> {
+    let (start, end) = hours.split_once('-')?;
+    let start = NaiveTime::parse_from_str(start.trim(), "%H:%M").ok()?;
+    let end = NaiveTime::parse_from_str(end.trim(), "%H:%M").ok()?;
+    Some((start, end))
+}
+
+fn parse_days(day: &str) -> Vec<Weekday> {
+    use Weekday::*;
+    match day.trim().to_lowercase().as_str() {
+        "weekdays" => vec![Mon, Tue, Wed, Thu, Fri],
+        "weekends" => vec![Sat, Sun],
+        other => other.parse::<Weekday>().map(|d| vec![d]).unwrap_or_default(),
+    }
+}
@@ -0,0 +1,124 @@
+use crate::api::{Liquidity, PairData, PriceChange, Token, TxnCount, Txns, Volume};
+
+/// Address shown for the scripted token; nothing is fetched for it
+pub const PAIR: &str = "screencast";
+pub const CHAIN: &str = "solana";
+/// Target market cap the script pumps through
+pub const TARGET: f64 = 100_000.0;
+/// Seconds between scripted fetches
+pub const INTERVAL: u64 = 1;
+
+/// Market cap of the first fetch and the growth per fetch while pumping
+const START_CAP: f64 = 24_000.0;
+const GROWTH: f64 = 1.07;
+/// Fetches the alarm rings for before the script stops it
+const RING_STEPS: usize = 5;
+/// Fetches after the alarm is stopped before the screencast ends
+const COOLDOWN_STEPS: usize = 4;
+
+/// Fixed supply, so price and market cap move together
+const SUPPLY: f64 = 1_000_000_000.0;
+
+/// What the screencast does on its next fetch
+pub enum Step {
+    Fetch(Box<PairData>),
+    /// Stop the alarm, as pressing 's' does
+    StopAlarm,
+    Finish,
+}
+
+/// Scripted run for `--screencast`: a synthetic pump through the target, the
+/// alarm ringing, the alarm being stopped, then a short cool-off
+#[derive(Debug, Default)]
+pub struct Script {
+    step: usize,
+    /// Step at which the target was crossed
+    hit_at: Option<usize>,
+}
+
+impl Script {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn next_step(&mut self) -> Step {
+        let i = self.step;
+        self.step += 1;
+        let Some(hit) = self.hit_at else {
+            let cap = pump(i);
+            if cap >= TARGET {
+                self.hit_at = Some(i);
+            }
+            return Step::Fetch(Box::new(pair(i, cap, pump(i.saturating_sub(3)))));
+        };
+
+        let since = i - hit;
+        if since == RING_STEPS + 1 {
+            return Step::StopAlarm;
+        }
+        if since > RING_STEPS + 1 + COOLDOWN_STEPS {
+            return Step::Finish;
+        }
+        // Hover just above the target, drifting back a little after the peak
+        let peak = pump(hit);
+        let cap = peak * (1.0 + 0.03 * (i as f64 * 1.3).sin() - 0.01 * since as f64);
+        Step::Fetch(Box::new(pair(i, cap, peak)))
+    }
+}
+
+/// Market cap on step `i` of the pump, with some wobble so the chart looks real
+fn pump(i: usize) -> f64 {
+    START_CAP * GROWTH.powi(i as i32) * (1.0 + 0.04 * (i as f64 * 1.7).sin())
+}
+
+fn pair(i: usize, cap: f64, earlier: f64) -> PairData {
+    let trades = 40 + 9 * i as u64;
+    PairData {
+        chain_id: Some(CHAIN.to_string()),
+        dex_id: None,
+        pair_address: Some(PAIR.to_string()),
+        base_token: Some(Token {
+            address: Some(format!("{}-token", PAIR)),
+            name: Some("Screencast Demo".to_string()),
+            symbol: Some("DEMO".to_string()),
+        }),
+        quote_token: Some(Token {
+            address: None,
+            name: Some("Wrapped SOL".to_string()),
+            symbol: Some("SOL".to_string()),
+        }),
+        price_native: None,
+        price_usd: Some(format!("{:.10}", cap / SUPPLY)),
+        fdv: Some(cap),
+        market_cap: Some(cap),
+        txns: Some(Txns {
+            m5: None,
+            h1: None,
+            h6: None,
+            h24: Some(TxnCount {
+                buys: Some(trades * 3 / 2),
+                sells: Some(trades),
+            }),
+        }),
+        volume: Some(Volume {
+            h24: Some(cap * 1.6),
+            h6: None,
+            h1: None,
+            m5: None,
+        }),
+        price_change: Some(PriceChange {
+            m5: None,
+            h1: Some((cap / earlier - 1.0) * 100.0),
+            h6: None,
+            h24: Some((cap / START_CAP - 1.0) * 100.0),
+        }),
+        liquidity: Some(Liquidity {
+            usd: Some(cap * 0.18),
+            base: None,
+            quote: None,
+        }),
+        pair_created_at: None,
+        info: None,
+        bonding_curve: None,
+    }
+}
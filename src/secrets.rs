@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Write};
+use std::sync::{Mutex, OnceLock};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+
+use crate::birdeye;
+use crate::social;
+use crate::telegram;
+use crate::twilio;
+
+/// Prefix for config values that name a keyring secret instead of holding it,
+/// e.g. `"x-cg-pro-api-key": "keyring:coingecko-key"`
+pub const REFERENCE_PREFIX: &str = "keyring:";
+
+/// Secrets mooncap looks up by name, with what each one is for
+pub const KNOWN: &[(&str, &str)] = &[
+    (birdeye::API_KEY_SECRET, "Birdeye API key"),
+    (social::TWITTER_TOKEN_SECRET, "Twitter API bearer token (social panel)"),
+    (telegram::BOT_TOKEN_SECRET, "Telegram bot token (alerts)"),
+    (twilio::AUTH_TOKEN_SECRET, "Twilio auth token (SMS alerts)"),
+];
+
+/// Looked-up values by name, so the keyring is asked at most once per secret
+static CACHE: OnceLock<Mutex<HashMap<String, Option<String>>>> = OnceLock::new();
+
+/// Whether `name` is usable as a secret name: lowercase letters, digits and dashes
+pub fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// The secret stored under `name`, if the keyring has one
+pub fn get(name: &str) -> Option<String> {
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+    cache
+        .entry(name.to_string())
+        .or_insert_with(|| read(name).ok().flatten())
+        .clone()
+}
+
+/// A config value with `keyring:<name>` references replaced by the secret.
+/// None when the referenced secret isn't stored.
+pub fn resolve(value: &str) -> Option<String> {
+    match value.strip_prefix(REFERENCE_PREFIX) {
+        Some(name) => get(name.trim()),
+        None => Some(value.to_string()),
+    }
+}
+
+/// Store `value` under `name`, replacing any previous value
+pub fn set(name: &str, value: &str) -> Result<(), String> {
+    backend::set(name, value)
+}
+
+/// Remove the secret stored under `name`. Returns false if there was none.
+pub fn delete(name: &str) -> Result<bool, String> {
+    backend::delete(name)
+}
+
+/// Read `name` straight from the keyring, bypassing the cache
+pub fn read(name: &str) -> Result<Option<String>, String> {
+    backend::read(name)
+}
+
+/// Read a secret from the terminal without echoing it, or a line from stdin
+/// when it's piped (`pass show birdeye | mooncap secrets set birdeye-api-key`)
+pub fn prompt(prompt: &str) -> io::Result<String> {
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        let mut line = String::new();
+        stdin.read_line(&mut line)?;
+        return Ok(line.trim().to_string());
+    }
+
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    terminal::enable_raw_mode()?;
+    let mut value = String::new();
+    let read = loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Enter => break Ok(()),
+                KeyCode::Esc => {
+                    value.clear();
+                    break Ok(());
+                }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    value.clear();
+                    break Ok(());
+                }
+                KeyCode::Backspace => {
+                    value.pop();
+                }
+                KeyCode::Char(c) => value.push(c),
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(e) => break Err(e),
+        }
+    };
+    terminal::disable_raw_mode()?;
+    println!();
+    read.map(|_| value.trim().to_string())
+}
+
+#[cfg(feature = "keyring")]
+mod backend {
+    use keyring::{Entry, Error};
+
+    /// Service the entries are stored under in the OS keyring
+    const SERVICE: &str = "mooncap";
+
+    pub fn set(name: &str, value: &str) -> Result<(), String> {
+        let value = value.to_string();
+        with_entry(name, move |entry| entry.set_password(&value)).map_err(|e| e.to_string())
+    }
+
+    pub fn delete(name: &str) -> Result<bool, String> {
+        match with_entry(name, |entry| entry.delete_credential()) {
+            Ok(()) => Ok(true),
+            Err(Error::NoEntry) => Ok(false),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn read(name: &str) -> Result<Option<String>, String> {
+        match with_entry(name, |entry| entry.get_password()) {
+            Ok(value) => Ok(Some(value)),
+            Err(Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// The Secret Service backend blocks on its own async runtime, which
+    /// panics inside tokio's, so keyring calls run on a plain thread
+    fn with_entry<T: Send + 'static>(
+        name: &str,
+        f: impl FnOnce(Entry) -> Result<T, Error> + Send + 'static,
+    ) -> Result<T, Error> {
+        let name = name.to_string();
+        std::thread::spawn(move || f(Entry::new(SERVICE, &name)?))
+            .join()
+            .unwrap_or_else(|_| Err(Error::NoStorageAccess("keyring access panicked".into())))
+    }
+}
+
+#[cfg(not(feature = "keyring"))]
+mod backend {
+    const UNSUPPORTED: &str = "built without keyring support (rebuild with --features keyring)";
+
+    pub fn set(_: &str, _: &str) -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub fn delete(_: &str) -> Result<bool, String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub fn read(_: &str) -> Result<Option<String>, String> {
+        Err(UNSUPPORTED.to_string())
+    }
+}
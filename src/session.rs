@@ -0,0 +1,89 @@
+use chrono::{DateTime, Duration, Local};
+
+/// Running statistics over this session's fetches, for the session panel
+#[derive(Debug, Clone)]
+pub struct SessionStats {
+    pub started: DateTime<Local>,
+    pub fetches: u64,
+    sum: f64,
+    pub low: f64,
+    pub high: f64,
+    /// Time, market cap and side of the target of the previous fetch
+    last: Option<(DateTime<Local>, f64, bool)>,
+    /// Time spent at or above / below the target, judged at each fetch
+    pub above: Duration,
+    pub below: Duration,
+    /// Times the market cap moved from one side of the target to the other
+    pub crossings: u32,
+    /// Current run of rising (positive) or falling (negative) fetches
+    pub streak: i32,
+    pub longest_green: u32,
+    pub longest_red: u32,
+}
+
+impl SessionStats {
+    pub fn new(started: DateTime<Local>) -> Self {
+        Self {
+            started,
+            fetches: 0,
+            sum: 0.0,
+            low: 0.0,
+            high: 0.0,
+            last: None,
+            above: Duration::zero(),
+            below: Duration::zero(),
+            crossings: 0,
+            streak: 0,
+            longest_green: 0,
+            longest_red: 0,
+        }
+    }
+
+    /// Record a fetch; `at_target` is whether the market cap or price was at
+    /// or above the target
+    pub fn record(&mut self, at: DateTime<Local>, market_cap: f64, at_target: bool) {
+        if market_cap <= 0.0 {
+            return;
+        }
+        if self.fetches == 0 {
+            self.low = market_cap;
+            self.high = market_cap;
+        }
+        self.fetches += 1;
+        self.sum += market_cap;
+        self.low = self.low.min(market_cap);
+        self.high = self.high.max(market_cap);
+
+        if let Some((then, previous, was_at_target)) = self.last {
+            let elapsed = (at - then).max(Duration::zero());
+            if was_at_target {
+                self.above += elapsed;
+            } else {
+                self.below += elapsed;
+            }
+            if was_at_target != at_target {
+                self.crossings += 1;
+            }
+
+            // Unchanged fetches neither extend nor break a streak
+            if market_cap > previous {
+                self.streak = self.streak.max(0) + 1;
+                self.longest_green = self.longest_green.max(self.streak as u32);
+            } else if market_cap < previous {
+                self.streak = self.streak.min(0) - 1;
+                self.longest_red = self.longest_red.max(self.streak.unsigned_abs());
+            }
+        }
+        self.last = Some((at, market_cap, at_target));
+    }
+
+    pub fn average(&self) -> Option<f64> {
+        (self.fetches > 0).then(|| self.sum / self.fetches as f64)
+    }
+
+    /// Share of the tracked time spent at or above the target, in percent
+    pub fn above_percent(&self) -> Option<f64> {
+        let total = (self.above + self.below).num_seconds();
+        (total > 0).then(|| self.above.num_seconds() as f64 / total as f64 * 100.0)
+    }
+}
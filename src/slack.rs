@@ -0,0 +1,130 @@
+//! Alerts posted to a Slack channel through an incoming webhook, laid out with
+//! Block Kit, for teams running mooncap on a shared monitoring box. Target hits
+//! and fetch errors go there unless `alert_routing` says otherwise.
+
+use serde::{Deserialize, Serialize};
+
+use crate::notify::{AlertContext, Severity};
+use crate::secrets;
+use crate::storage;
+use crate::ui;
+
+/// Incoming webhook alerts are posted to
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SlackConfig {
+    /// e.g. `https://hooks.slack.com/services/T000/B000/XXXX`; `keyring:<name>`
+    /// is read from the OS keyring instead
+    pub webhook_url: Option<String>,
+}
+
+impl SlackConfig {
+    pub fn webhook(&self) -> Option<String> {
+        self.webhook_url
+            .as_deref()
+            .and_then(secrets::resolve)
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+    }
+}
+
+fn heading(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => ":information_source: MoonCap info",
+        Severity::Warning => ":warning: MoonCap warning",
+        Severity::Critical => ":rotating_light: MoonCap critical alert",
+    }
+}
+
+/// Slack's mrkdwn treats these three as markup
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn blocks(severity: Severity, message: &str, context: &AlertContext) -> serde_json::Value {
+    let mut fields = Vec::new();
+    let mut field = |name: &str, value: &str| {
+        fields.push(serde_json::json!({
+            "type": "mrkdwn",
+            "text": format!("*{}*\n{}", name, escape(value)),
+        }));
+    };
+    if !context.symbol.is_empty() {
+        let token = if context.name.is_empty() || context.name == context.symbol {
+            context.symbol.clone()
+        } else {
+            format!("{} ({})", context.name, context.symbol)
+        };
+        field("Token", &token);
+    }
+    if context.market_cap > 0.0 {
+        field("Market cap", &format!("${}", ui::format_amount(context.market_cap)));
+    }
+    if let Some(ref progress) = context.progress {
+        field("Progress", progress);
+    }
+
+    let mut blocks = vec![
+        serde_json::json!({
+            "type": "header",
+            "text": { "type": "plain_text", "text": heading(severity), "emoji": true },
+        }),
+        serde_json::json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": escape(message) },
+        }),
+    ];
+    if !fields.is_empty() {
+        blocks.push(serde_json::json!({ "type": "section", "fields": fields }));
+    }
+    // Which box and pair it came from, when several post to one channel
+    blocks.push(serde_json::json!({
+        "type": "context",
+        "elements": [{
+            "type": "mrkdwn",
+            "text": escape(&format!(
+                "{} · {} · {}",
+                context.chain,
+                context.pair,
+                storage::hostname()
+            )),
+        }],
+    }));
+    serde_json::Value::Array(blocks)
+}
+
+/// Post an alert to a webhook
+pub async fn send(
+    client: &reqwest::Client,
+    webhook_url: &str,
+    severity: Severity,
+    message: &str,
+    context: &AlertContext,
+) -> Result<(), String> {
+    let body = serde_json::json!({
+        // Shown in the notification itself, where blocks aren't
+        "text": format!("{}: {}", heading(severity), message),
+        "blocks": blocks(severity, message, context),
+    });
+    // Request errors carry the URL, and with it the webhook secret
+    let response = client
+        .post(webhook_url)
+        .json(&body)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e.without_url()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    // Slack answers with a plain-text reason, e.g. "invalid_payload"
+    let reason = response.text().await.unwrap_or_default();
+    let reason = reason.trim();
+    if reason.is_empty() {
+        Err(format!("Slack answered HTTP {}", status.as_u16()))
+    } else {
+        Err(format!("Slack refused the message: {}", reason))
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Duration, Local};
+use serde::Deserialize;
+
+use crate::api;
+use crate::endpoints::{self, Api};
+use crate::profile::Link;
+use crate::secrets;
+
+const TELEGRAM_URL: &str = "https://t.me";
+const TWITTER_URL: &str = "https://api.twitter.com/2";
+
+/// Keyring secret (or `TWITTER_BEARER_TOKEN`) for follower counts; X has no
+/// keyless API, so Twitter is skipped without it
+pub const TWITTER_TOKEN_SECRET: &str = "twitter-bearer-token";
+
+/// How far back the trend shown in the panel reaches
+const TREND_WINDOW: i64 = 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Telegram,
+    Twitter,
+}
+
+impl Network {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Network::Telegram => "Telegram",
+            Network::Twitter => "Twitter",
+        }
+    }
+
+    /// What the count is called on this network
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Network::Telegram => "members",
+            Network::Twitter => "followers",
+        }
+    }
+}
+
+/// One of the token's social accounts and its size over time
+#[derive(Debug, Clone)]
+pub struct Audience {
+    pub network: Network,
+    pub handle: String,
+    /// Counts by time, oldest first, covering the last day
+    pub samples: VecDeque<(DateTime<Local>, u64)>,
+    pub error: Option<String>,
+}
+
+impl Audience {
+    pub fn new(network: Network, handle: String) -> Self {
+        Self {
+            network,
+            handle,
+            samples: VecDeque::new(),
+            error: None,
+        }
+    }
+
+    pub fn latest(&self) -> Option<u64> {
+        self.samples.back().map(|(_, count)| *count)
+    }
+
+    /// Record a count. Returns the growth over the previous one, in percent.
+    pub fn record(&mut self, at: DateTime<Local>, count: u64) -> Option<f64> {
+        let previous = self.latest();
+        self.samples.push_back((at, count));
+        while let Some(&(first, _)) = self.samples.front() {
+            if at - first <= Duration::hours(TREND_WINDOW) {
+                break;
+            }
+            self.samples.pop_front();
+        }
+        self.error = None;
+        previous
+            .filter(|p| *p > 0)
+            .map(|p| (count as f64 - p as f64) / p as f64 * 100.0)
+    }
+
+    /// Change since the oldest sample, and how long ago that was
+    pub fn trend(&self) -> Option<(i64, Duration)> {
+        let (first_at, first) = self.samples.front()?;
+        let (last_at, last) = self.samples.back()?;
+        (last_at > first_at).then(|| (*last as i64 - *first as i64, *last_at - *first_at))
+    }
+}
+
+/// Telegram and Twitter accounts among the token's links, as (network, handle)
+pub fn accounts<'a>(links: impl IntoIterator<Item = &'a Link>) -> Vec<(Network, String)> {
+    let mut found: Vec<(Network, String)> = Vec::new();
+    for link in links {
+        let url = link.url.trim().trim_end_matches('/');
+        let path = url
+            .split_once("://")
+            .map(|(_, rest)| rest)
+            .unwrap_or(url)
+            .trim_start_matches("www.");
+        let Some((host, handle)) = path.split_once('/') else {
+            continue;
+        };
+        let network = match host.to_lowercase().as_str() {
+            "t.me" | "telegram.me" => Network::Telegram,
+            "x.com" | "twitter.com" => Network::Twitter,
+            _ => continue,
+        };
+        // Invite links (t.me/+abc, t.me/joinchat/...) and tweets don't name an account
+        let handle = handle.split(['?', '#']).next().unwrap_or("");
+        if handle.is_empty() || handle.contains('/') || handle.starts_with('+') {
+            continue;
+        }
+        if !found.iter().any(|(n, h)| *n == network && h.eq_ignore_ascii_case(handle)) {
+            found.push((network, handle.to_string()));
+        }
+    }
+    found
+}
+
+/// Current member or follower count of one account
+pub async fn fetch_count(
+    client: &reqwest::Client,
+    network: Network,
+    handle: &str,
+) -> Result<u64, String> {
+    match network {
+        Network::Telegram => fetch_telegram(client, handle).await,
+        Network::Twitter => fetch_twitter(client, handle).await,
+    }
+}
+
+/// Whether follower counts can be fetched at all
+pub fn twitter_enabled() -> bool {
+    twitter_token().is_some()
+}
+
+fn twitter_token() -> Option<String> {
+    std::env::var("TWITTER_BEARER_TOKEN")
+        .ok()
+        .filter(|t| !t.trim().is_empty())
+        .or_else(|| secrets::get(TWITTER_TOKEN_SECRET))
+}
+
+/// Telegram's public preview page, which states "12 345 members" for groups
+/// and "12 345 subscribers" for channels
+async fn fetch_telegram(client: &reqwest::Client, handle: &str) -> Result<u64, String> {
+    let url = format!("{}/{}", endpoints::base_url(Api::Telegram, TELEGRAM_URL), handle);
+    let response = endpoints::get(client, Api::Telegram, &url)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(api::describe_http_error(status, &body));
+    }
+    let page = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read page: {}", e))?;
+    parse_telegram_members(&page).ok_or_else(|| format!("No member count on t.me/{}", handle))
+}
+
+fn parse_telegram_members(page: &str) -> Option<u64> {
+    let start = page.find("tgme_page_extra")?;
+    let text = &page[start..];
+    let text = &text[text.find('>')? + 1..];
+    let text = &text[..text.find('<')?];
+    let (number, _) = text
+        .split_once(" members")
+        .or_else(|| text.split_once(" subscribers"))?;
+    let digits: String = number.chars().filter(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+#[derive(Debug, Deserialize)]
+struct TwitterUserResponse {
+    data: TwitterUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct TwitterUser {
+    public_metrics: TwitterMetrics,
+}
+
+#[derive(Debug, Deserialize)]
+struct TwitterMetrics {
+    followers_count: u64,
+}
+
+async fn fetch_twitter(client: &reqwest::Client, handle: &str) -> Result<u64, String> {
+    let token = twitter_token().ok_or("no Twitter bearer token")?;
+    let url = format!(
+        "{}/users/by/username/{}?user.fields=public_metrics",
+        endpoints::base_url(Api::Twitter, TWITTER_URL),
+        handle
+    );
+    let response = endpoints::get(client, Api::Twitter, &url)
+        .bearer_auth(token)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(api::describe_http_error(status, &body));
+    }
+    let user: TwitterUserResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("JSON parse error: {}", e))?;
+    Ok(user.data.public_metrics.followers_count)
+}
@@ -0,0 +1,240 @@
+//! Alerts read out loud — "PEPE hit five hundred thousand market cap" — through
+//! the system's speech synthesizer, alongside or instead of the alarm.
+
+use std::process::{Command, Stdio};
+
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::TargetKind;
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [(u64, &str); 4] = [
+    (1_000_000_000_000, "trillion"),
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+
+/// Synthesizer settings; by default `say` on macOS, PowerShell's speech
+/// synthesizer on Windows, and espeak-ng, espeak or spd-say elsewhere
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SpeechConfig {
+    /// Program and arguments run instead, with the text appended as the last
+    /// argument, e.g. `piper-say --speaker 3`
+    pub command: Option<String>,
+    /// Voice name passed to the default synthesizer (`say -v`, `espeak -v`)
+    pub voice: Option<String>,
+}
+
+impl SpeechConfig {
+    /// Whether a synthesizer command or voice is set up
+    pub fn is_configured(&self) -> bool {
+        [&self.command, &self.voice]
+            .iter()
+            .any(|v| v.as_deref().is_some_and(|v| !v.trim().is_empty()))
+    }
+}
+
+fn below_thousand(n: u64) -> String {
+    let mut parts = Vec::new();
+    if n >= 100 {
+        parts.push(format!("{} hundred", ONES[(n / 100) as usize]));
+    }
+    let rest = n % 100;
+    if rest >= 20 {
+        match rest % 10 {
+            0 => parts.push(TENS[(rest / 10) as usize].to_string()),
+            ones => parts.push(format!("{} {}", TENS[(rest / 10) as usize], ONES[ones as usize])),
+        }
+    } else if rest > 0 || parts.is_empty() {
+        parts.push(ONES[rest as usize].to_string());
+    }
+    parts.join(" ")
+}
+
+/// `n` in words, e.g. 500000 → "five hundred thousand"
+pub fn words(n: u64) -> String {
+    let mut parts = Vec::new();
+    let mut rest = n;
+    for (scale, name) in SCALES {
+        if rest >= scale {
+            parts.push(format!("{} {}", below_thousand(rest / scale), name));
+            rest %= scale;
+        }
+    }
+    if rest > 0 || parts.is_empty() {
+        parts.push(below_thousand(rest));
+    }
+    parts.join(" ")
+}
+
+/// A number in words; fractions are read digit by digit ("zero point zero
+/// zero five"), and only below 1000, where they still matter
+fn number(value: f64) -> String {
+    let value = value.abs();
+    if value >= 1000.0 || value.fract() == 0.0 {
+        return words(value.round() as u64);
+    }
+    let text = format!("{:.8}", value);
+    let (whole, fraction) = text.split_once('.').unwrap_or((&text, ""));
+    let fraction = fraction.trim_end_matches('0');
+    let mut spoken = words(whole.parse().unwrap_or(0));
+    if !fraction.is_empty() {
+        spoken.push_str(" point");
+        for digit in fraction.chars().filter_map(|c| c.to_digit(10)) {
+            spoken.push(' ');
+            spoken.push_str(ONES[digit as usize]);
+        }
+    }
+    spoken
+}
+
+/// What to say when the tracked value reaches a target or milestone `level`
+pub fn target_phrase(symbol: &str, kind: TargetKind, level: f64) -> String {
+    match kind {
+        TargetKind::MarketCap => format!("{} hit {} market cap", symbol, number(level)),
+        TargetKind::Price => format!("{} hit a price of {} dollars", symbol, number(level)),
+    }
+}
+
+/// An alert summary made fit to be read out: details in parentheses are
+/// dropped and dollar amounts (`$60.0K`) and percentages are spelled out
+pub fn phrase(summary: &str) -> String {
+    let mut out = String::new();
+    let mut chars = summary.chars().peekable();
+    let mut depth = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth > 0 => depth -= 1,
+            _ if depth > 0 => {}
+            '$' if chars.peek().is_some_and(char::is_ascii_digit) => {
+                let mut digits = String::new();
+                while let Some(&d) = chars.peek() {
+                    if d.is_ascii_digit() || d == '.' {
+                        digits.push(d);
+                    } else if d != ',' {
+                        break;
+                    }
+                    chars.next();
+                }
+                let scale = match chars.peek() {
+                    Some('K') => 1e3,
+                    Some('M') => 1e6,
+                    Some('B') => 1e9,
+                    _ => 1.0,
+                };
+                if scale > 1.0 {
+                    chars.next();
+                }
+                let value = digits.trim_end_matches('.').parse::<f64>().unwrap_or(0.0) * scale;
+                out.push_str(&number(value));
+                out.push_str(" dollars");
+            }
+            '%' => out.push_str(" percent"),
+            '→' => out.push_str(" to "),
+            _ => out.push(c),
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Candidate synthesizers, tried in order until one starts
+fn commands(config: &SpeechConfig, text: &str) -> Vec<Command> {
+    if let Some(ref line) = config.command {
+        let mut parts = line.split_whitespace();
+        let Some(program) = parts.next() else {
+            return Vec::new();
+        };
+        let mut command = Command::new(program);
+        command.args(parts).arg(text);
+        return vec![command];
+    }
+    let voice = config.voice.as_deref().map(str::trim).filter(|v| !v.is_empty());
+
+    #[cfg(target_os = "macos")]
+    let programs = ["say"];
+    #[cfg(windows)]
+    let programs = ["powershell"];
+    #[cfg(not(any(target_os = "macos", windows)))]
+    let programs = ["espeak-ng", "espeak", "spd-say"];
+
+    programs
+        .iter()
+        .map(|&program| {
+            let mut command = Command::new(program);
+            if program == "powershell" {
+                let mut script = String::from("Add-Type -AssemblyName System.Speech; ");
+                script.push_str("$s = New-Object System.Speech.Synthesis.SpeechSynthesizer; ");
+                if let Some(voice) = voice {
+                    script.push_str(&format!("$s.SelectVoice('{}'); ", voice.replace('\'', "''")));
+                }
+                script.push_str(&format!("$s.Speak('{}')", text.replace('\'', "''")));
+                command.args(["-NoProfile", "-Command", &script]);
+            } else {
+                if let Some(voice) = voice {
+                    // spd-say takes a synthesis voice with -y
+                    let flag = if program == "spd-say" { "-y" } else { "-v" };
+                    command.args([flag, voice]);
+                }
+                command.arg(text);
+            }
+            command
+        })
+        .collect()
+}
+
+/// Start reading `text` out. Returns once the synthesizer has started; it is
+/// waited for on a thread of its own.
+pub fn speak(config: &SpeechConfig, text: &str) -> Result<(), String> {
+    let mut tried = Vec::new();
+    for mut command in commands(config, text) {
+        let program = command.get_program().to_string_lossy().into_owned();
+        // Output would draw over the TUI
+        let started = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match started {
+            Ok(mut child) => {
+                std::thread::spawn(move || child.wait());
+                return Ok(());
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => tried.push(program),
+            Err(e) => return Err(format!("could not run {}: {}", program, e)),
+        }
+    }
+    if tried.is_empty() {
+        Err("no speech command configured".to_string())
+    } else {
+        Err(format!("no speech synthesizer found (tried {})", tried.join(", ")))
+    }
+}
@@ -0,0 +1,185 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::api::PairData;
+use crate::config::StorageConfig;
+
+/// One fetched data point for a monitored pair
+#[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+pub struct Sample {
+    pub recorded_at: DateTime<Utc>,
+    /// Machine that recorded the sample, so several daemons can share a table
+    pub host: String,
+    pub chain: String,
+    pub pair: String,
+    pub symbol: String,
+    pub price_usd: f64,
+    pub market_cap: f64,
+    pub liquidity_usd: f64,
+    pub volume_24h: f64,
+}
+
+impl Sample {
+    pub fn new(chain: &str, pair: &str, data: &PairData) -> Self {
+        Self {
+            recorded_at: Utc::now(),
+            host: hostname(),
+            chain: chain.to_string(),
+            pair: pair.to_string(),
+            symbol: data.base_symbol().to_string(),
+            price_usd: data.price_usd_f64(),
+            market_cap: data.market_cap_usd(),
+            liquidity_usd: data.liquidity_usd(),
+            volume_24h: data.volume_24h_usd(),
+        }
+    }
+}
+
+/// Somewhere token history is persisted
+#[async_trait]
+pub trait Storage: Send + Sync {
+    /// Short backend name for log lines
+    fn name(&self) -> &'static str;
+
+    async fn record(&self, sample: &Sample) -> Result<(), String>;
+}
+
+/// Open the configured storage backend, if any
+pub async fn open(config: &StorageConfig) -> Result<Option<Box<dyn Storage>>, String> {
+    let Some(ref url) = config.postgres_url else {
+        return Ok(None);
+    };
+
+    #[cfg(feature = "postgres")]
+    {
+        let store = postgres::PostgresStorage::connect(url, &config.table).await?;
+        Ok(Some(Box::new(store)))
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    {
+        let _ = url;
+        Err("storage.postgres_url is set but mooncap was built without the 'postgres' feature"
+            .to_string())
+    }
+}
+
+pub fn hostname() -> String {
+    let mut buf = [0u8; 256];
+    // SAFETY: buf is valid for buf.len() bytes; gethostname NUL-terminates on success
+    let rc = unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len()) };
+    if rc != 0 {
+        return "unknown".to_string();
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+}
+
+#[cfg(feature = "postgres")]
+mod postgres {
+    use async_trait::async_trait;
+    use tokio::sync::Mutex;
+
+    use super::{Sample, Storage};
+
+    /// Shared Postgres table, reconnecting lazily after the connection drops
+    pub struct PostgresStorage {
+        url: String,
+        insert: String,
+        client: Mutex<Option<tokio_postgres::Client>>,
+    }
+
+    impl PostgresStorage {
+        pub async fn connect(url: &str, table: &str) -> Result<Self, String> {
+            if table.is_empty()
+                || !table
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+            {
+                return Err(format!("Invalid storage table name '{}'", table));
+            }
+
+            let client = open_client(url).await?;
+            client
+                .batch_execute(&format!(
+                    "CREATE TABLE IF NOT EXISTS {} (
+                        id BIGSERIAL PRIMARY KEY,
+                        recorded_at TIMESTAMPTZ NOT NULL,
+                        host TEXT NOT NULL,
+                        chain TEXT NOT NULL,
+                        pair TEXT NOT NULL,
+                        symbol TEXT NOT NULL,
+                        price_usd DOUBLE PRECISION NOT NULL,
+                        market_cap DOUBLE PRECISION NOT NULL,
+                        liquidity_usd DOUBLE PRECISION NOT NULL,
+                        volume_24h DOUBLE PRECISION NOT NULL
+                    )",
+                    table
+                ))
+                .await
+                .map_err(|e| format!("Failed to create table {}: {}", table, e))?;
+
+            Ok(Self {
+                url: url.to_string(),
+                insert: format!(
+                    "INSERT INTO {} (recorded_at, host, chain, pair, symbol, price_usd, \
+                     market_cap, liquidity_usd, volume_24h) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+                    table
+                ),
+                client: Mutex::new(Some(client)),
+            })
+        }
+    }
+
+    async fn open_client(url: &str) -> Result<tokio_postgres::Client, String> {
+        let tls = native_tls::TlsConnector::new().map_err(|e| format!("TLS setup failed: {}", e))?;
+        let tls = postgres_native_tls::MakeTlsConnector::new(tls);
+        let (client, connection) = tokio_postgres::connect(url, tls)
+            .await
+            .map_err(|e| format!("Postgres connect failed: {}", e))?;
+        tokio::spawn(async move {
+            let _ = connection.await;
+        });
+        Ok(client)
+    }
+
+    #[async_trait]
+    impl Storage for PostgresStorage {
+        fn name(&self) -> &'static str {
+            "postgres"
+        }
+
+        async fn record(&self, sample: &Sample) -> Result<(), String> {
+            let mut guard = self.client.lock().await;
+            let stale = match guard.as_ref() {
+                Some(client) => client.is_closed(),
+                None => true,
+            };
+            if stale {
+                *guard = Some(open_client(&self.url).await?);
+            }
+            let Some(client) = guard.as_ref() else {
+                return Err("Postgres client unavailable".to_string());
+            };
+            client
+                .execute(
+                    &self.insert,
+                    &[
+                        &sample.recorded_at,
+                        &sample.host,
+                        &sample.chain,
+                        &sample.pair,
+                        &sample.symbol,
+                        &sample.price_usd,
+                        &sample.market_cap,
+                        &sample.liquidity_usd,
+                        &sample.volume_24h,
+                    ],
+                )
+                .await
+                .map(|_| ())
+                .map_err(|e| format!("Postgres insert failed: {}", e))
+        }
+    }
+}
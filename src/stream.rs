@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::Message;
+
+const WS_URL: &str = "wss://public-api.birdeye.so/socket/solana";
+
+/// Reconnect delay cap; the delay doubles from 1s after each failed attempt
+const MAX_RECONNECT_SECS: u64 = 60;
+
+pub enum StreamEvent {
+    Price(f64),
+    Connected,
+    Disconnected(String),
+}
+
+/// Live price feed for one Solana token from Birdeye's websocket. The
+/// connection runs on its own task and reconnects until the stream is dropped.
+pub struct PriceStream {
+    pub token: String,
+    rx: mpsc::UnboundedReceiver<StreamEvent>,
+    task: JoinHandle<()>,
+}
+
+impl PriceStream {
+    pub fn start(api_key: String, token: String) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let task = tokio::spawn(run(api_key, token.clone(), tx));
+        Self { token, rx, task }
+    }
+
+    /// Next pending event, without waiting
+    pub fn try_next(&mut self) -> Option<StreamEvent> {
+        self.rx.try_recv().ok()
+    }
+}
+
+impl Drop for PriceStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn run(api_key: String, token: String, tx: mpsc::UnboundedSender<StreamEvent>) {
+    let mut backoff = 1;
+    loop {
+        let reason = match session(&api_key, &token, &tx, &mut backoff).await {
+            Ok(()) => "connection closed".to_string(),
+            Err(e) => e,
+        };
+        if tx.send(StreamEvent::Disconnected(reason)).is_err() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_secs(backoff)).await;
+        backoff = (backoff * 2).min(MAX_RECONNECT_SECS);
+    }
+}
+
+/// One websocket connection: subscribe, then forward prices until it drops
+async fn session(
+    api_key: &str,
+    token: &str,
+    tx: &mpsc::UnboundedSender<StreamEvent>,
+    backoff: &mut u64,
+) -> Result<(), String> {
+    let mut request = format!("{}?x-api-key={}", WS_URL, api_key)
+        .into_client_request()
+        .map_err(|e| format!("Invalid stream URL: {}", e))?;
+    let headers = request.headers_mut();
+    headers.insert("Origin", HeaderValue::from_static("ws://public-api.birdeye.so"));
+    headers.insert("Sec-WebSocket-Protocol", HeaderValue::from_static("echo-protocol"));
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(request)
+        .await
+        .map_err(|e| format!("Connect failed: {}", e))?;
+
+    let subscribe = serde_json::json!({
+        "type": "SUBSCRIBE_PRICE",
+        "data": {
+            "queryType": "simple",
+            "chartType": "1m",
+            "address": token,
+            "currency": "usd",
+        }
+    });
+    ws.send(Message::Text(subscribe.to_string()))
+        .await
+        .map_err(|e| format!("Subscribe failed: {}", e))?;
+
+    *backoff = 1;
+    if tx.send(StreamEvent::Connected).is_err() {
+        return Ok(());
+    }
+
+    while let Some(msg) = ws.next().await {
+        match msg.map_err(|e| format!("Stream error: {}", e))? {
+            Message::Text(text) => {
+                if let Some(price) = parse_price(&text) {
+                    if tx.send(StreamEvent::Price(price)).is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+            Message::Close(_) => return Err("closed by server".to_string()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Extract the latest close from a `PRICE_DATA` message
+fn parse_price(text: &str) -> Option<f64> {
+    #[derive(Deserialize)]
+    struct Envelope {
+        #[serde(rename = "type")]
+        kind: String,
+        data: Option<Candle>,
+    }
+    #[derive(Deserialize)]
+    struct Candle {
+        c: Option<f64>,
+    }
+
+    let msg: Envelope = serde_json::from_str(text).ok()?;
+    if msg.kind != "PRICE_DATA" {
+        return None;
+    }
+    msg.data?.c.filter(|p| *p > 0.0)
+}
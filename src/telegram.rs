@@ -0,0 +1,109 @@
+//! Alerts posted to a Telegram chat by a bot, so an alarm reaches a phone
+//! when nobody is at the machine.
+
+use serde::{Deserialize, Serialize};
+
+use crate::notify::Severity;
+use crate::secrets;
+
+const BOT_API_URL: &str = "https://api.telegram.org";
+
+/// Environment variable overriding the configured bot token
+const BOT_TOKEN_ENV: &str = "TELEGRAM_BOT_TOKEN";
+
+/// Keyring entry holding the bot token (`mooncap secrets set telegram-bot-token`)
+pub const BOT_TOKEN_SECRET: &str = "telegram-bot-token";
+
+/// Bot and chat alerts are sent to; both must be set
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TelegramConfig {
+    /// Token @BotFather gave the bot; `keyring:<name>` is read from the OS
+    /// keyring instead (`TELEGRAM_BOT_TOKEN` overrides)
+    pub bot_token: Option<String>,
+    /// Chat the bot posts to: a user or group id, or `@channelname`
+    pub chat_id: Option<String>,
+}
+
+impl TelegramConfig {
+    /// Resolve the bot token: `TELEGRAM_BOT_TOKEN` first, then the keyring, then
+    /// the config file
+    pub fn bot_token(&self) -> Option<String> {
+        std::env::var(BOT_TOKEN_ENV)
+            .ok()
+            .filter(|t| !t.trim().is_empty())
+            .or_else(|| secrets::get(BOT_TOKEN_SECRET))
+            .or_else(|| self.bot_token.as_deref().and_then(secrets::resolve))
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+    }
+
+    fn chat_id(&self) -> Option<&str> {
+        self.chat_id.as_deref().map(str::trim).filter(|c| !c.is_empty())
+    }
+
+    /// Whether a chat is set and a bot token can be found for it
+    pub fn is_configured(&self) -> bool {
+        self.chat_id().is_some() && self.bot_token().is_some()
+    }
+}
+
+/// The message text: a severity line, then the alert
+fn format_message(severity: Severity, message: &str) -> String {
+    let icon = match severity {
+        Severity::Info => "ℹ️",
+        Severity::Warning => "⚠️",
+        Severity::Critical => "🚨",
+    };
+    format!("{} MoonCap {} alert\n{}", icon, severity.name(), message)
+}
+
+#[derive(Debug, Deserialize)]
+struct Reply {
+    ok: bool,
+    description: Option<String>,
+}
+
+/// Post an alert to the configured chat
+pub async fn send(
+    client: &reqwest::Client,
+    config: &TelegramConfig,
+    severity: Severity,
+    message: &str,
+) -> Result<(), String> {
+    let token = config
+        .bot_token()
+        .ok_or_else(|| "no Telegram bot token configured".to_string())?;
+    let chat_id = config
+        .chat_id()
+        .ok_or_else(|| "no Telegram chat_id configured".to_string())?;
+
+    let url = format!("{}/bot{}/sendMessage", BOT_API_URL, token);
+    let body = serde_json::json!({
+        "chat_id": chat_id,
+        "text": format_message(severity, message),
+        "disable_web_page_preview": true,
+    });
+    // Request errors carry the URL, and with it the bot token
+    let response = client
+        .post(&url)
+        .json(&body)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e.without_url()))?;
+
+    let status = response.status();
+    let reply: Reply = response
+        .json()
+        .await
+        .map_err(|_| format!("Telegram answered HTTP {}", status.as_u16()))?;
+    if reply.ok {
+        Ok(())
+    } else {
+        Err(format!(
+            "Telegram refused the message: {}",
+            reply.description.unwrap_or_else(|| format!("HTTP {}", status.as_u16()))
+        ))
+    }
+}
@@ -0,0 +1,338 @@
+//! Test harness, compiled for `cargo test` only: a shared mock DexScreener
+//! server plus helpers to build an `App` and drive it through `poll_once`, the
+//! same fetch → state → alert pass the TUI loop runs, and to render it to text
+//! for snapshot tests.
+
+use chrono::{DateTime, Local, TimeZone};
+use ratatui::{backend::TestBackend, Terminal};
+use serde_json::{json, Value};
+use tokio::sync::OnceCell;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::api::{self, PairData};
+use crate::app::App;
+use crate::config::Config;
+
+/// Chain used by test apps. Not a real chain, so nothing (fee RPCs,
+/// GeckoTerminal) is looked up for it outside the mock server.
+pub const CHAIN: &str = "mockchain";
+
+static SERVER: OnceCell<MockServer> = OnceCell::const_new();
+
+/// The mock server every DexScreener request goes to. It is shared by all tests
+/// (the API root can only be set once), so each test should use its own addresses.
+pub async fn server() -> &'static MockServer {
+    SERVER
+        .get_or_init(|| async {
+            let server = MockServer::start().await;
+            api::set_dexscreener_root(&server.uri());
+            server
+        })
+        .await
+}
+
+/// Answer GETs for `route` with `response`, for the next `times` requests if
+/// given and for good otherwise
+pub async fn mount(route: &str, response: ResponseTemplate, times: Option<u64>) {
+    let mut mock = Mock::given(method("GET")).and(path(route)).respond_with(response);
+    if let Some(times) = times {
+        mock = mock.up_to_n_times(times);
+    }
+    mock.mount(server().await).await;
+}
+
+/// A canned DexScreener pair
+#[derive(Debug, Clone)]
+pub struct PairFixture {
+    pub address: String,
+    pub symbol: String,
+    pub price: f64,
+    pub market_cap: f64,
+    pub liquidity: f64,
+    pub volume_24h: f64,
+}
+
+impl PairFixture {
+    pub fn new(address: &str, symbol: &str, market_cap: f64) -> Self {
+        Self {
+            address: address.to_string(),
+            symbol: symbol.to_string(),
+            price: market_cap / 1_000_000_000.0,
+            market_cap,
+            liquidity: 50_000.0,
+            volume_24h: 120_000.0,
+        }
+    }
+
+    pub fn market_cap(mut self, market_cap: f64) -> Self {
+        self.price = market_cap / 1_000_000_000.0;
+        self.market_cap = market_cap;
+        self
+    }
+
+    pub fn liquidity(mut self, liquidity: f64) -> Self {
+        self.liquidity = liquidity;
+        self
+    }
+
+    /// The pair as DexScreener returns it. There's no `dexId`, so the app
+    /// skips the pool lookup and GeckoTerminal backfill on first fetch.
+    pub fn to_json(&self) -> Value {
+        let created = Local.with_ymd_and_hms(2026, 1, 10, 9, 30, 0).unwrap();
+        json!({
+            "chainId": CHAIN,
+            "pairAddress": self.address,
+            "baseToken": {
+                "address": format!("{}-token", self.address),
+                "name": self.symbol,
+                "symbol": self.symbol
+            },
+            "quoteToken": { "address": "quote", "name": "USD Coin", "symbol": "USDC" },
+            "priceUsd": self.price.to_string(),
+            "marketCap": self.market_cap,
+            "fdv": self.market_cap,
+            "liquidity": { "usd": self.liquidity },
+            "volume": { "h24": self.volume_24h },
+            "priceChange": { "h1": 1.5, "h24": -3.0 },
+            "txns": { "h24": { "buys": 120, "sells": 80 } },
+            "pairCreatedAt": created.timestamp_millis(),
+            "info": {
+                "imageUrl": "https://example.com/token.png",
+                "websites": [{ "label": "Website", "url": "https://example.com" }],
+                "socials": [{ "type": "twitter", "url": "https://x.com/example" }]
+            }
+        })
+    }
+}
+
+/// Serve `fixture` for its address on the next `times` requests. Mounting
+/// several in a row plays them back in order, e.g. a rising market cap.
+pub async fn serve_pair(fixture: &PairFixture, times: u64) {
+    let route = format!("/token-pairs/v1/{}/{}", CHAIN, fixture.address);
+    let response = ResponseTemplate::new(200).set_body_json(json!([fixture.to_json()]));
+    mount(&route, response, Some(times)).await;
+}
+
+/// Answer requests for `address` with an HTTP error
+pub async fn serve_error(address: &str, status: u16) {
+    for route in [
+        format!("/token-pairs/v1/{}/{}", CHAIN, address),
+        format!("/latest/dex/tokens/{}", address),
+        format!("/latest/dex/pairs/{}/{}", CHAIN, address),
+    ] {
+        let response = ResponseTemplate::new(status).set_body_string("upstream down");
+        mount(&route, response, None).await;
+    }
+}
+
+/// An app monitoring `address` with a clean config, metadata cache and
+/// all-time highs (nothing read from or written to disk) and the ticker strip disabled, so
+/// only the mock server is contacted
+pub fn app(address: &str, target: f64) -> App {
+    app_with_config(address, target, Config::default())
+}
+
+pub fn app_with_config(address: &str, target: f64, mut config: Config) -> App {
+    config.ticker.get_or_insert_with(Vec::new);
+    let mut app =
+        App::new_with_config(address.to_string(), CHAIN.to_string(), target, 60, None, 10);
+    app.watchlist_alerts = crate::alerts::WatchlistAlerts::new(config.watchlist_alerts.clone());
+    app.config = config;
+    app.metadata = crate::metadata::MetadataCache::default();
+    app.highs = crate::ath::HighStore::default();
+    app.apply_chain_defaults();
+    app
+}
+
+/// Run one polling pass, as the TUI loop does every interval
+pub async fn poll(app: &mut App) {
+    server().await;
+    crate::poll_once(&reqwest::Client::new(), app, None, None).await;
+}
+
+/// The fixture as the app sees it after a fetch, without going through HTTP
+pub fn pair_data(fixture: &PairFixture) -> PairData {
+    serde_json::from_value(fixture.to_json()).expect("fixture is valid pair JSON")
+}
+
+/// Clock used for rendering snapshots
+pub fn snapshot_time() -> DateTime<Local> {
+    Local.with_ymd_and_hms(2026, 1, 15, 12, 0, 0).unwrap()
+}
+
+/// Pin every log line's time and date to the snapshot clock
+pub fn freeze_log(app: &mut App) {
+    for line in app.log.iter_mut() {
+        line.time = snapshot_time().format("%H:%M:%S").to_string();
+        line.date = snapshot_time().date_naive();
+    }
+}
+
+/// Whether any log line mentions `text`
+pub fn logged(app: &App, text: &str) -> bool {
+    app.log.iter().any(|l| l.text.contains(text))
+}
+
+/// Draw one frame of `width` x `height` at the snapshot clock, as plain text
+pub fn render(app: &App, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal
+        .draw(|frame| crate::ui::draw_at(frame, app, snapshot_time()))
+        .unwrap();
+    let buffer = terminal.backend().buffer();
+    let mut out = String::new();
+    for y in 0..height {
+        let row: String = (0..width).map(|x| buffer[(x, y)].symbol()).collect();
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+mod flows {
+    use super::*;
+    use crate::alerts::WatchlistRule;
+
+    #[tokio::test]
+    async fn fetch_updates_state() {
+        let pair = PairFixture::new("flow-state", "FROG", 42_000.0);
+        serve_pair(&pair, 1).await;
+        let mut app = app("flow-state", 100_000.0);
+
+        poll(&mut app).await;
+
+        assert_eq!(app.token_symbol, "FROG");
+        assert_eq!(app.market_cap, 42_000.0);
+        assert_eq!(app.liquidity_usd, 50_000.0);
+        assert_eq!(app.market_cap_history, vec![42_000]);
+        assert_eq!(app.token_address.as_deref(), Some("flow-state-token"));
+        assert!(!app.target_hit);
+        assert_eq!(app.error_count, 0);
+    }
+
+    #[tokio::test]
+    async fn target_hit_raises_critical_alert() {
+        let pair = PairFixture::new("flow-target", "MOON", 80_000.0);
+        serve_pair(&pair, 1).await;
+        serve_pair(&pair.clone().market_cap(120_000.0), 1).await;
+        let mut app = app("flow-target", 100_000.0);
+
+        poll(&mut app).await;
+        assert!(!app.target_hit);
+        assert!(!app.alarm_active);
+
+        poll(&mut app).await;
+        assert!(app.target_hit);
+        assert!(app.alarm_active, "critical alerts route to the alarm by default");
+        assert_eq!(app.last_alert.as_deref(), Some("MOON hit $120000 (target $100000)"));
+        assert!(logged(&app, "TARGET HIT"));
+    }
+
+    #[tokio::test]
+    async fn api_errors_are_counted_and_logged() {
+        serve_error("flow-error", 503).await;
+        let mut app = app("flow-error", 100_000.0);
+
+        poll(&mut app).await;
+
+        assert_eq!(app.error_count, 1);
+        assert_eq!(app.consecutive_failures, 1);
+        assert_eq!(app.fetch_count, 0);
+        assert!(logged(&app, "HTTP 503"));
+    }
+
+    #[tokio::test]
+    async fn token_on_another_chain_is_not_shown() {
+        // Only the chain-agnostic endpoint knows the address, on another chain
+        let mut other = PairFixture::new("flow-wrong-chain", "SOLTOK", 90_000.0).to_json();
+        other["chainId"] = json!("solana");
+        let response = ResponseTemplate::new(200).set_body_json(json!({ "pairs": [other] }));
+        mount("/latest/dex/tokens/flow-wrong-chain", response, None).await;
+        let mut app = app("flow-wrong-chain", 100_000.0);
+
+        poll(&mut app).await;
+
+        assert_eq!(app.fetch_count, 0);
+        assert_eq!(app.market_cap, 0.0);
+        assert!(logged(&app, "listed on solana, not mockchain"));
+    }
+
+    #[tokio::test]
+    async fn liquidity_pull_fires_watchlist_alert() {
+        let mut config = Config::default();
+        config.watchlist_alerts = vec![WatchlistRule::LiquidityDrop {
+            percent: 50.0,
+            window_mins: 60,
+        }];
+        let pair = PairFixture::new("flow-rug", "RUG", 60_000.0);
+        serve_pair(&pair, 1).await;
+        serve_pair(&pair.clone().liquidity(5_000.0), 1).await;
+        let mut app = app_with_config("flow-rug", 1_000_000.0, config);
+
+        poll(&mut app).await;
+        assert!(!app.alarm_active);
+
+        poll(&mut app).await;
+        assert!(app.alarm_active, "liquidity drops are critical");
+        assert_eq!(
+            app.last_alert.as_deref(),
+            Some("RUG liquidity down 90.0% in 60m (threshold 50%)")
+        );
+    }
+}
+
+mod snapshots {
+    use insta::assert_snapshot;
+
+    use super::*;
+
+    /// A configured app that has fetched `caps` in order
+    fn monitoring(address: &str, target: f64, caps: &[f64]) -> App {
+        let mut app = app(address, target);
+        for cap in caps {
+            app.update_from_pair_data(&pair_data(&PairFixture::new(address, "FROG", *cap)));
+        }
+        freeze_log(&mut app);
+        app
+    }
+
+    #[test]
+    fn loading() {
+        let mut app = app("snap-loading", 100_000.0);
+        freeze_log(&mut app);
+        assert_snapshot!(render(&app, 100, 30));
+    }
+
+    #[test]
+    fn normal() {
+        let app = monitoring("snap-normal", 100_000.0, &[42_000.0, 45_500.0, 51_200.0, 48_900.0]);
+        assert_snapshot!(render(&app, 100, 30));
+    }
+
+    #[test]
+    fn target_hit() {
+        let app = monitoring("snap-target", 100_000.0, &[80_000.0, 120_000.0]);
+        assert!(app.alarm_active);
+        assert_snapshot!(render(&app, 100, 30));
+    }
+
+    #[test]
+    fn modal_open() {
+        let mut app = monitoring("snap-modal", 100_000.0, &[42_000.0]);
+        app.open_modal();
+        assert_snapshot!(render(&app, 100, 30));
+    }
+
+    #[test]
+    fn tiny_terminal() {
+        let app = monitoring("snap-tiny", 100_000.0, &[42_000.0, 45_500.0]);
+        assert_snapshot!(render(&app, 40, 12));
+    }
+
+    #[test]
+    fn short_terminal() {
+        let app = monitoring("snap-short", 100_000.0, &[42_000.0, 45_500.0]);
+        assert_snapshot!(render(&app, 80, 16));
+    }
+}
@@ -0,0 +1,159 @@
+//! Critical alerts sent through Twilio as a text message, and optionally a
+//! phone call reading the alert out, for people who sleep through push
+//! notifications.
+
+use serde::{Deserialize, Serialize};
+
+use crate::api;
+use crate::notify::Severity;
+use crate::secrets;
+
+const API_URL: &str = "https://api.twilio.com/2010-04-01";
+
+/// Environment variable overriding the configured auth token
+const AUTH_TOKEN_ENV: &str = "TWILIO_AUTH_TOKEN";
+
+/// Keyring entry holding the auth token (`mooncap secrets set twilio-auth-token`)
+pub const AUTH_TOKEN_SECRET: &str = "twilio-auth-token";
+
+/// Longest text sent; Twilio splits anything over 160 characters into
+/// segments that are each billed
+const MAX_SMS_CHARS: usize = 320;
+
+/// Account and numbers alerts are sent with; the account, token, sender and at
+/// least one recipient must be set
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TwilioConfig {
+    /// Account SID, `AC...`
+    pub account_sid: Option<String>,
+    /// `keyring:<name>` is read from the OS keyring instead
+    /// (`TWILIO_AUTH_TOKEN` overrides)
+    pub auth_token: Option<String>,
+    /// Twilio number the messages come from, e.g. `+15005550006`
+    pub from: Option<String>,
+    /// Numbers alerted, in E.164 form
+    pub to: Vec<String>,
+    /// Also call each number and read the alert out
+    pub call: bool,
+}
+
+impl TwilioConfig {
+    /// Resolve the auth token: `TWILIO_AUTH_TOKEN` first, then the keyring, then
+    /// the config file
+    fn auth_token(&self) -> Option<String> {
+        std::env::var(AUTH_TOKEN_ENV)
+            .ok()
+            .filter(|t| !t.trim().is_empty())
+            .or_else(|| secrets::get(AUTH_TOKEN_SECRET))
+            .or_else(|| self.auth_token.as_deref().and_then(secrets::resolve))
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+    }
+
+    fn account_sid(&self) -> Option<&str> {
+        self.account_sid.as_deref().map(str::trim).filter(|s| !s.is_empty())
+    }
+
+    fn from(&self) -> Option<&str> {
+        self.from.as_deref().map(str::trim).filter(|f| !f.is_empty())
+    }
+
+    fn recipients(&self) -> impl Iterator<Item = &str> {
+        self.to.iter().map(|n| n.trim()).filter(|n| !n.is_empty())
+    }
+
+    /// Whether everything needed to send is set
+    pub fn is_configured(&self) -> bool {
+        self.account_sid().is_some()
+            && self.from().is_some()
+            && self.recipients().next().is_some()
+            && self.auth_token().is_some()
+    }
+}
+
+/// The text message: a severity line, then the alert
+fn sms_body(severity: Severity, message: &str) -> String {
+    api::truncate(&format!("MoonCap {}: {}", severity.name(), message), MAX_SMS_CHARS)
+}
+
+/// TwiML for the call: the alert's first line, said three times
+fn call_twiml(severity: Severity, message: &str) -> String {
+    let line = message.lines().next().unwrap_or_default();
+    let text = format!("MoonCap {} alert. {}", severity.name(), line)
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    format!("<Response><Say loop=\"3\">{}</Say></Response>", text)
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorReply {
+    message: Option<String>,
+}
+
+/// POST `form` to one of the account's resources (`Messages`, `Calls`)
+async fn post(
+    client: &reqwest::Client,
+    sid: &str,
+    token: &str,
+    resource: &str,
+    form: &[(&str, &str)],
+) -> Result<(), String> {
+    let url = format!("{}/Accounts/{}/{}.json", API_URL, sid, resource);
+    let response = client
+        .post(&url)
+        .basic_auth(sid, Some(token))
+        .form(form)
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e.without_url()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let reply: Option<ErrorReply> = response.json().await.ok();
+    match reply.and_then(|r| r.message) {
+        Some(message) => Err(format!("Twilio refused the request: {}", message)),
+        None => Err(format!("Twilio answered HTTP {}", status.as_u16())),
+    }
+}
+
+/// Text every configured number, and call them if `call` is set. Every number
+/// is tried; the first failure is returned.
+pub async fn send(
+    client: &reqwest::Client,
+    config: &TwilioConfig,
+    severity: Severity,
+    message: &str,
+) -> Result<(), String> {
+    let sid = config
+        .account_sid()
+        .ok_or_else(|| "no Twilio account_sid configured".to_string())?;
+    let token = config
+        .auth_token()
+        .ok_or_else(|| "no Twilio auth token configured".to_string())?;
+    let from = config
+        .from()
+        .ok_or_else(|| "no Twilio from number configured".to_string())?;
+
+    let body = sms_body(severity, message);
+    let twiml = call_twiml(severity, message);
+    let mut result = Ok(());
+    for to in config.recipients() {
+        let form = [("From", from), ("To", to), ("Body", body.as_str())];
+        let mut sent = post(client, sid, &token, "Messages", &form).await;
+        if config.call && sent.is_ok() {
+            let form = [("From", from), ("To", to), ("Twiml", twiml.as_str())];
+            sent = post(client, sid, &token, "Calls", &form).await;
+        }
+        if let Err(e) = sent {
+            if result.is_ok() {
+                result = Err(format!("{}: {}", to, e));
+            }
+        }
+    }
+    result
+}
@@ -1,49 +1,201 @@
+use chrono::{DateTime, Local, NaiveDate};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Gauge, List, ListItem, Paragraph, Sparkline},
+    widgets::{Block, Borders, Clear, Gauge, List, ListItem, Paragraph, Sparkline, Wrap},
     Frame,
 };
 
-use crate::app::{App, MODAL_FIELD_LABELS};
+use crate::alerts::TargetKind;
+use crate::api::{self, PairInfo, Provider};
+use crate::app::{App, ModalField, SwitcherMode, TokenWarning};
+use crate::birdeye::{self, BirdeyeView};
+use crate::compare;
+use crate::config::PriceFormat;
+use crate::fx::Currency;
+use crate::notify::Severity;
+use crate::ohlc::{OhlcTable, Period};
+use crate::onboarding::Onboarding;
+use crate::pacing::FramePacer;
+use crate::perps;
+use crate::portfolio::GroupView;
+use crate::profile::TokenProfile;
+use crate::safety::{Level, SecurityReport};
+use crate::session::SessionStats;
+use crate::social::Audience;
+use crate::unlocks;
+
+/// Smallest terminal the dashboard is laid out in; below it a compact view
+/// of the essentials is shown instead
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 13;
+
+/// Height of the log panel, which is dropped before the body is squeezed
+const LOG_HEIGHT: u16 = 8;
 
 /// Main rendering function
 pub fn draw(frame: &mut Frame, app: &App) {
+    draw_at(frame, app, Local::now());
+}
+
+/// Draw the dashboard as of `now`. Everything shown comes from `app` and `now`,
+/// so a frame can be reproduced exactly (see the snapshot tests).
+pub fn draw_at(frame: &mut Frame, app: &App, now: DateTime<Local>) {
     let area = frame.area();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        draw_compact(frame, app, area);
+    } else {
+        draw_dashboard(frame, app, now, area);
+    }
+
+    // Draw modal overlay on top if open
+    if app.modal_open {
+        draw_modal(frame, app, area);
+    } else if app.pool_selector_open {
+        draw_pool_selector(frame, app, area);
+    } else if app.switcher_open {
+        draw_switcher(frame, app, area);
+    } else if let Some(onboarding) = &app.onboarding {
+        draw_onboarding(frame, app, onboarding, area);
+    }
+}
+
+fn draw_dashboard(frame: &mut Frame, app: &App, now: DateTime<Local>, area: Rect) {
+    // Vertical sections: header, body, optional portfolio panel, footer. On a
+    // short terminal the portfolio goes first, then the log, so the body
+    // keeps its minimum height.
+    let spare = area.height - MIN_HEIGHT;
+    let log_height = if spare >= 3 { spare.min(LOG_HEIGHT) } else { 0 };
+    let portfolio_height = app
+        .portfolio
+        .as_ref()
+        .map(|g| (g.members.len() as u16 + 3).max(6))
+        .filter(|h| *h <= spare - log_height)
+        .unwrap_or(0);
 
-    // 3 vertical sections: header, body, footer
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),  // header
-            Constraint::Min(10),   // body
-            Constraint::Length(8), // log
+            Constraint::Length(3),                // header
+            Constraint::Min(MIN_HEIGHT - 3),      // body
+            Constraint::Length(portfolio_height), // portfolio
+            Constraint::Length(log_height),       // log
         ])
         .split(area);
 
     draw_header(frame, app, main_chunks[0]);
-    draw_body(frame, app, main_chunks[1]);
-    draw_log(frame, app, main_chunks[2]);
+    // A dangerous token, thin liquidity or a silent alarm keeps a warning across
+    // the top of the body until it's dismissed
+    let body_area = match app.active_warning() {
+        Some(warning) if main_chunks[1].height > 13 => {
+            let parts = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Min(10)])
+                .split(main_chunks[1]);
+            draw_warning_banner(frame, app, warning, parts[0]);
+            parts[1]
+        }
+        _ => main_chunks[1],
+    };
+    draw_body(frame, app, now, body_area);
+    if let Some(group) = app.portfolio.as_ref().filter(|_| portfolio_height > 0) {
+        draw_portfolio(frame, group, &app.currency, main_chunks[2]);
+    }
+    if log_height > 0 {
+        draw_log(frame, app, main_chunks[3]);
+    }
 
-    // Draw modal overlay on top if open
-    if app.modal_open {
-        draw_modal(frame, app, area);
+    if app.alarm_active {
+        draw_alert_banner(frame, app, main_chunks[1]);
     }
 }
 
+/// The token, its market cap and the progress to the target, for a terminal
+/// too small for the dashboard
+fn draw_compact(frame: &mut Frame, app: &App, area: Rect) {
+    let border = if app.alarm_active {
+        severity_color(app.alarm_severity)
+    } else {
+        Color::Magenta
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border))
+        .title(format!(" 🚀 {} (${}) ", app.token_name, app.token_symbol))
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+
+    let label =
+        |text: &str| Span::styled(format!(" {:<12}", text), Style::default().fg(Color::DarkGray));
+    let price_format = &app.config.price_format;
+    let target = match app.target_kind {
+        TargetKind::MarketCap => format_money(app.next_target(), &app.currency),
+        TargetKind::Price => format_price(app.next_target(), price_format, &app.currency),
+    };
+    let progress = if app.target_hit {
+        Span::styled("🔥 TARGET HIT!", Style::default().fg(Color::Yellow).bold())
+    } else {
+        Span::styled(
+            format!("{:.1}% of {}", app.progress(), target),
+            Style::default().fg(Color::Cyan),
+        )
+    };
+    let lines = vec![
+        Line::from(vec![
+            label("Market Cap"),
+            Span::styled(
+                format_money(app.market_cap, &app.currency),
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![
+            label("Price"),
+            Span::styled(
+                format_price(app.current_price, price_format, &app.currency),
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(vec![label("Target"), progress]),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(" Enlarge to {}×{} for the dashboard", MIN_WIDTH, MIN_HEIGHT),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+}
+
 fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
     let title = format!(
-        " 🚀 MOONCAP — {} (${}) ",
-        app.token_name, app.token_symbol
+        " 🚀 MOONCAP{} — {} (${}){} ",
+        app.instance
+            .as_deref()
+            .map(|name| format!(" [{}]", name))
+            .unwrap_or_default(),
+        app.token_name,
+        app.token_symbol,
+        if app.is_pinned() { " 📌" } else { "" }
     );
 
-    let status = if app.target_hit {
+    let status = if app.offline {
+        Span::styled(
+            " 📡 OFFLINE — retrying ",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )
+    } else if app.manual_paused {
+        Span::styled(" ⏸ paused (space resumes) ", Style::default().fg(Color::DarkGray))
+    } else if app.schedule_paused {
+        Span::styled(" 💤 paused by schedule ", Style::default().fg(Color::DarkGray))
+    } else if app.target_hit {
+        // No blinking in low-power mode
+        let modifier = if app.low_power {
+            Modifier::BOLD
+        } else {
+            Modifier::BOLD | Modifier::SLOW_BLINK
+        };
         Span::styled(
             " 🔥 TARGET HIT! ",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK),
+            Style::default().fg(Color::Yellow).add_modifier(modifier),
         )
     } else {
         let progress = app.progress();
@@ -61,38 +213,1001 @@ fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
             .add_modifier(Modifier::BOLD),
     );
 
-    let header_line = Line::from(vec![
-        chain_info,
-        Span::raw(" "),
-        status,
-    ]);
+    let mut header_line = Line::from(vec![chain_info]);
+    if let Some(fee) = app.fee {
+        header_line.push_span(Span::styled(
+            format!(" ⛽ {}", fee.describe()),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    header_line.push_span(Span::raw(" "));
+    header_line.push_span(status);
+    if let Some(next) = app.next_fetch().filter(|_| !app.offline && !app.polling_paused()) {
+        header_line.push_span(Span::styled(
+            format!(" ⏱ next {} ", next.format("%H:%M:%S")),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if let Some(until) = app.snoozed_until.filter(|_| app.snoozed()) {
+        header_line.push_span(Span::styled(
+            format!(" 😴 snoozed until {} ", until.format("%H:%M")),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if app.quiet_hours(Severity::Info) {
+        header_line.push_span(Span::styled(" 🌙 quiet ", Style::default().fg(Color::DarkGray)));
+    }
+    if let Some(ref keys) = app.macro_recording {
+        header_line.push_span(Span::styled(
+            format!(" ⏺ recording macro ({} keys, m stops) ", keys.len()),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    } else if app.macro_unbound.is_some() {
+        header_line.push_span(Span::styled(
+            " ⏺ press 0-9 to save the macro, Esc to discard ",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if app.low_power {
+        header_line.push_span(Span::styled(" 🔋 low power ", Style::default().fg(Color::DarkGray)));
+    }
+    if app.streaming {
+        let (text, color) = if app.stream_live {
+            (" ⚡ live ", Color::Green)
+        } else {
+            (" ⚡ connecting ", Color::DarkGray)
+        };
+        header_line.push_span(Span::styled(text, Style::default().fg(color)));
+    }
+    // Failing channels stay up until their next attempt succeeds
+    let failing = app.outbox.failing();
+    if let Some(last) = failing.last() {
+        let names: Vec<_> = failing.iter().map(|s| s.channel.name()).collect();
+        let names = names.join(", ");
+        let (text, color) = if failing.iter().all(|s| s.retrying) {
+            (format!(" 📨 {} failed {} — retrying ", names, last.at), Color::Yellow)
+        } else {
+            (format!(" 📨 {} FAILED {} ", names, last.at), Color::Red)
+        };
+        header_line.push_span(Span::styled(text, Style::default().fg(color)));
+    } else if let Some(last) = app.outbox.latest() {
+        let text = format!(" 📨 {} ok {} ", last.channel.name(), last.at);
+        header_line.push_span(Span::styled(text, Style::default().fg(Color::Green)));
+    }
 
-    let block = Block::default()
+    // The border takes the color of a sounding alarm
+    let border = if app.alarm_active {
+        severity_color(app.alarm_severity)
+    } else {
+        Color::Magenta
+    };
+    let mut block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Magenta))
-        .title(title)
+        .border_style(Style::default().fg(border))
+        .title(title.as_str())
         .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
 
+    // Majors ticker in the top-right corner of the border, as many as fit
+    let room = (area.width as usize).saturating_sub(Line::from(title.as_str()).width() + 4);
+    let mut ticker = vec![Span::raw(" ")];
+    for quote in &app.ticker {
+        let change = quote.change_24h.unwrap_or(0.0);
+        let entry = [
+            Span::styled(format!("{} ", quote.symbol), Style::default().fg(Color::DarkGray)),
+            Span::styled(format_ticker_price(quote.price, &app.currency), Style::default().fg(Color::White)),
+            Span::styled(
+                format!(" {} ", format_change(change)),
+                Style::default().fg(if change >= 0.0 { Color::Green } else { Color::Red }),
+            ),
+        ];
+        let width: usize = ticker.iter().chain(entry.iter()).map(|s| s.width()).sum();
+        if width > room {
+            break;
+        }
+        ticker.extend(entry);
+    }
+    if ticker.len() > 1 {
+        block = block.title(Line::from(ticker).right_aligned());
+    }
+
     let paragraph = Paragraph::new(header_line).block(block);
     frame.render_widget(paragraph, area);
 }
 
-fn draw_body(frame: &mut Frame, app: &App, area: Rect) {
+/// Full-width banner across the top of the body while an alert is active
+fn draw_alert_banner(frame: &mut Frame, app: &App, area: Rect) {
+    let banner_area = Rect::new(area.x, area.y, area.width, 3.min(area.height));
+    frame.render_widget(Clear, banner_area);
+
+    // Only critical alerts blink, and nothing does in low-power mode
+    let modifier = if app.low_power || app.alarm_severity < Severity::Critical {
+        Modifier::BOLD
+    } else {
+        Modifier::BOLD | Modifier::SLOW_BLINK
+    };
+    let (icon, fg, bg, border) = match app.alarm_severity {
+        Severity::Info => ("🔔", Color::Black, Color::Cyan, Color::White),
+        Severity::Warning => ("⚠", Color::Black, Color::Yellow, Color::Red),
+        Severity::Critical => ("🔥", Color::White, Color::Red, Color::Yellow),
+    };
+    let text = format!(
+        "{} {} — press 's' to dismiss",
+        icon,
+        app.last_alert.as_deref().unwrap_or("ALERT")
+    );
+    let banner = Paragraph::new(Line::from(Span::styled(
+        text,
+        Style::default().fg(fg).add_modifier(modifier),
+    )))
+    .alignment(ratatui::layout::Alignment::Center)
+    .style(Style::default().bg(bg))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border).bg(bg)),
+    );
+    frame.render_widget(banner, banner_area);
+}
+
+/// Color of alerts of `severity` in the header, banner and log
+fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Info => Color::Cyan,
+        Severity::Warning => Color::Yellow,
+        Severity::Critical => Color::Red,
+    }
+}
+
+/// Red warning for tokens the safety check found dangerous, yellow for the
+/// lesser warnings
+fn draw_warning_banner(frame: &mut Frame, app: &App, warning: TokenWarning, area: Rect) {
+    let (text, fg, bg) = match (warning, app.security.as_ref()) {
+        (TokenWarning::Security, Some(report)) => (
+            format!(
+                "⛔ DANGEROUS TOKEN ({}): {}",
+                report.source,
+                report.dangers().join(" · ")
+            ),
+            Color::White,
+            Color::Red,
+        ),
+        (TokenWarning::LowLiquidity, _) => (
+            format!(
+                "⚠ LOW LIQUIDITY: {} in the pool, so small trades move the price a lot",
+                format_money(app.liquidity_usd, &app.currency)
+            ),
+            Color::Black,
+            Color::Yellow,
+        ),
+        _ => (
+            "⚠ NO AUDIO: built without the audio feature, so the alarm rings the terminal bell"
+                .to_string(),
+            Color::Black,
+            Color::Yellow,
+        ),
+    };
+    let hint = " · w dismiss";
+    let width = area.width.saturating_sub(4) as usize;
+    let text = api::truncate(&text, width.saturating_sub(hint.chars().count())) + hint;
+    let banner = Paragraph::new(Line::from(Span::styled(
+        text,
+        Style::default().fg(fg).add_modifier(Modifier::BOLD),
+    )))
+    .alignment(ratatui::layout::Alignment::Center)
+    .style(Style::default().bg(bg))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(fg)),
+    );
+    frame.render_widget(banner, area);
+}
+
+fn draw_body(frame: &mut Frame, app: &App, now: DateTime<Local>, area: Rect) {
+    if app.compare.is_some() {
+        draw_compare(frame, app, area);
+        return;
+    }
+
     // Split body into chart (left) and stats (right)
     let body_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
         .split(area);
 
-    draw_chart(frame, app, body_chunks[0]);
-    draw_stats(frame, app, body_chunks[1]);
+    // Security, token info, Birdeye holder and DexScreener profile panels under
+    // the chart when available, in order, as many as fit below it
+    let mut room = body_chunks[0].height.saturating_sub(chart_height(app));
+    let mut constraints = vec![Constraint::Min(chart_height(app))];
+    let mut fits = |height: u16| {
+        if height > room {
+            return false;
+        }
+        room -= height;
+        constraints.push(Constraint::Length(height));
+        true
+    };
+    let security = app.security.as_ref().filter(|_| fits(5));
+    let info = app.info.as_ref().filter(|i| !i.is_empty());
+    let show_info = (info.is_some() || app.pair_created_at.is_some()) && fits(4);
+    let birdeye = app.birdeye.as_ref().filter(|_| fits(5));
+    let profile = app.profile.as_ref().filter(|p| !p.is_empty() && fits(5));
+    let show_session = app.show_session && fits(6);
+    let show_social =
+        !app.social.is_empty() && fits(app.social.len().min(SOCIAL_ROWS) as u16 + 2);
+    let health = if app.show_health { health_providers(app) } else { Vec::new() };
+    let show_health = app.show_health && fits(health.len().clamp(1, HEALTH_ROWS) as u16 + 3);
+    let summary = app
+        .ohlc
+        .as_ref()
+        .filter(|t| !t.rows.is_empty() && fits(t.rows.len().min(SUMMARY_ROWS) as u16 + 3));
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(body_chunks[0]);
+    draw_chart(frame, app, left[0]);
+    let mut next = 1;
+    if let Some(report) = security {
+        draw_security(frame, report, left[next]);
+        next += 1;
+    }
+    if show_info {
+        draw_info(frame, info, app.pair_created_at, now, left[next]);
+        next += 1;
+    }
+    if let Some(view) = birdeye {
+        draw_birdeye(frame, view, &app.config.price_format, &app.currency, left[next]);
+        next += 1;
+    }
+    if let Some(profile) = profile {
+        draw_profile(frame, profile, left[next]);
+        next += 1;
+    }
+    if show_session {
+        draw_session(frame, &app.session, &app.currency, left[next]);
+        next += 1;
+    }
+    if show_social {
+        draw_social(frame, &app.social, left[next]);
+        next += 1;
+    }
+    if show_health {
+        draw_health(frame, app, &health, left[next]);
+        next += 1;
+    }
+    if let Some(table) = summary {
+        draw_summary(frame, table, &app.currency, left[next]);
+    }
+    draw_stats(frame, app, now, body_chunks[1]);
+}
+
+/// Most recent OHLC rows shown under the chart
+const SUMMARY_ROWS: usize = 3;
+
+fn draw_summary(frame: &mut Frame, table: &OhlcTable, currency: &Currency, area: Rect) {
+    let title = match table.period {
+        Period::Hourly => " 📅 Hourly market cap ",
+        Period::Daily => " 📅 Daily market cap ",
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(title)
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+
+    // Hourly labels drop the year to fit; the daemon's CSV keeps it
+    let label_width = match table.period {
+        Period::Hourly => 11,
+        Period::Daily => 10,
+    };
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "  {:<w$} {:>7} {:>7} {:>7} {:>7} {:>7}",
+            "Period",
+            "Open",
+            "High",
+            "Low",
+            "Close",
+            "Δ",
+            w = label_width
+        ),
+        Style::default().fg(Color::DarkGray),
+    ))];
+    for row in table.rows.iter().rev().take(SUMMARY_ROWS) {
+        let label = &row.period[row.period.len() - label_width..];
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "  {:<w$} {:>7} {:>7} {:>7} {:>7} ",
+                    label,
+                    format_money(row.open, currency),
+                    format_money(row.high, currency),
+                    format_money(row.low, currency),
+                    format_money(row.close, currency),
+                    w = label_width
+                ),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(
+                format!("{:>7}", format_change(row.change())),
+                Style::default().fg(if row.change() >= 0.0 { Color::Green } else { Color::Red }),
+            ),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Session statistics: average and range, time either side of the target,
+/// crossings and the longest rising/falling runs of fetches
+fn draw_session(frame: &mut Frame, stats: &SessionStats, currency: &Currency, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(format!(
+            " 🧭 Session since {} · {} fetches ",
+            stats.started.format("%H:%M"),
+            stats.fetches
+        ))
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+
+    let Some(average) = stats.average() else {
+        let waiting = Line::from(Span::styled(
+            "  Waiting for the first fetch",
+            Style::default().fg(Color::DarkGray),
+        ));
+        frame.render_widget(Paragraph::new(waiting).block(block), area);
+        return;
+    };
+
+    let label =
+        |text: &str| Span::styled(format!("  {:<9}", text), Style::default().fg(Color::DarkGray));
+    let value = |text: String| Span::styled(text, Style::default().fg(Color::White));
+    let above = match stats.above_percent() {
+        Some(percent) => format!(" ({:.0}%)", percent),
+        None => String::new(),
+    };
+    let (now_text, now_color) = match stats.streak {
+        s if s > 0 => (format!("▲ {}", s), Color::Green),
+        s if s < 0 => (format!("▼ {}", -s), Color::Red),
+        _ => ("–".to_string(), Color::DarkGray),
+    };
+    let lines = vec![
+        Line::from(vec![
+            label("Average"),
+            value(format!("{:<9}", format_money(average, currency))),
+            label("Range"),
+            value(format!(
+                "{} – {}",
+                format_money(stats.low, currency),
+                format_money(stats.high, currency)
+            )),
+        ]),
+        Line::from(vec![
+            label("Above"),
+            value(format!("{}{}", format_span(stats.above), above)),
+            Span::styled("  below ", Style::default().fg(Color::DarkGray)),
+            value(format_span(stats.below)),
+        ]),
+        Line::from(vec![
+            label("Crossed"),
+            value(format!("{}× through the target", stats.crossings)),
+        ]),
+        Line::from(vec![
+            label("Streaks"),
+            Span::styled(format!("▲ {}", stats.longest_green), Style::default().fg(Color::Green)),
+            Span::raw("  "),
+            Span::styled(format!("▼ {}", stats.longest_red), Style::default().fg(Color::Red)),
+            Span::styled("  longest, now ", Style::default().fg(Color::DarkGray)),
+            Span::styled(now_text, Style::default().fg(now_color)),
+        ]),
+    ];
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Most social accounts listed in the social panel
+const SOCIAL_ROWS: usize = 3;
+
+/// Telegram members and Twitter followers, with the change over the last day
+fn draw_social(frame: &mut Frame, audiences: &[Audience], area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(" 👥 Social ")
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+
+    let lines: Vec<Line> = audiences
+        .iter()
+        .take(SOCIAL_ROWS)
+        .map(|audience| {
+            let mut spans = vec![
+                Span::styled(
+                    format!("  {:<9}", audience.network.name()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!("@{:<16}", audience.handle),
+                    Style::default().fg(Color::White),
+                ),
+            ];
+            let Some(count) = audience.latest() else {
+                let text = audience.error.as_deref().unwrap_or("checking…");
+                spans.push(Span::styled(
+                    api::truncate(text, 40),
+                    Style::default().fg(Color::DarkGray),
+                ));
+                return Line::from(spans);
+            };
+            spans.push(Span::styled(
+                format!("{} {}", format_count(count), audience.network.unit()),
+                Style::default().fg(Color::White),
+            ));
+            if let Some((change, span)) = audience.trend() {
+                let (text, color) = match change {
+                    c if c > 0 => (format!("  ▲ {}", format_count(c as u64)), Color::Green),
+                    c if c < 0 => (format!("  ▼ {}", format_count(c.unsigned_abs())), Color::Red),
+                    _ => ("  –".to_string(), Color::DarkGray),
+                };
+                spans.push(Span::styled(text, Style::default().fg(color)));
+                spans.push(Span::styled(
+                    format!(" in {}", format_span(span)),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Most providers listed in the diagnostics panel
+const HEALTH_ROWS: usize = 4;
+
+/// Configured providers, then any fallback that has been asked
+fn health_providers(app: &App) -> Vec<Provider> {
+    let mut providers = app.providers.clone();
+    for provider in app.provider_health.providers() {
+        if !providers.contains(&provider) {
+            providers.push(provider);
+        }
+    }
+    providers
+}
+
+/// How long frames take to draw against the frame cap
+fn render_line(pacer: &FramePacer) -> Line<'static> {
+    let label = Span::styled(format!("  {:<15}", "Render"), Style::default().fg(Color::White));
+    let cap = Span::styled(
+        format!("  cap {} fps", pacer.fps()),
+        Style::default().fg(Color::DarkGray),
+    );
+    let Some(stats) = pacer.stats() else {
+        let measuring = Span::styled("measuring…", Style::default().fg(Color::DarkGray));
+        return Line::from(vec![label, measuring, cap]);
+    };
+    // Frames rendering in under half their slot leave room for heavier widgets
+    let budget = std::time::Duration::from_secs(1) / pacer.fps().max(1);
+    let average_ms = stats.average.as_secs_f64() * 1000.0;
+    let color = if stats.average * 2 <= budget {
+        Color::Green
+    } else if stats.average <= budget {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+    Line::from(vec![
+        label,
+        Span::styled(format!("{:>6.1}ms", average_ms), Style::default().fg(color)),
+        Span::styled(
+            format!("  max {:.1}ms", stats.max.as_secs_f64() * 1000.0),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(format!("  {:.1} fps", stats.fps), Style::default().fg(Color::DarkGray)),
+        cap,
+    ])
+}
+
+/// Median response time and error rate of each provider over its recent
+/// requests, marking the one asked first, then how long frames take to draw
+fn draw_health(frame: &mut Frame, app: &App, providers: &[Provider], area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(" 🩺 Providers ")
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+
+    if providers.is_empty() {
+        let waiting = Line::from(Span::styled(
+            "  Waiting for the first fetch",
+            Style::default().fg(Color::DarkGray),
+        ));
+        let lines = vec![waiting, render_line(&app.frames)];
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+        return;
+    }
+
+    let preferred = app.preferred_provider();
+    let mut lines: Vec<Line> = providers
+        .iter()
+        .take(HEALTH_ROWS)
+        .map(|provider| {
+            let marker = match (Some(*provider) == preferred, app.pinned_provider) {
+                (true, Some(_)) => "📌",
+                (true, None) => "★ ",
+                _ => "  ",
+            };
+            let mut spans = vec![
+                Span::styled(format!("  {}", marker), Style::default().fg(Color::Yellow)),
+                Span::styled(format!("{:<13}", provider.name()), Style::default().fg(Color::White)),
+            ];
+            let Some(summary) = app.provider_health.summary(*provider) else {
+                spans.push(Span::styled("not asked lately", Style::default().fg(Color::DarkGray)));
+                return Line::from(spans);
+            };
+            let latency = summary.latency.as_millis();
+            let latency_color = match latency {
+                0..=799 => Color::Green,
+                800..=2499 => Color::Yellow,
+                _ => Color::Red,
+            };
+            let error_color = match summary.error_rate {
+                r if r <= 0.0 => Color::Green,
+                r if r < 0.25 => Color::Yellow,
+                _ => Color::Red,
+            };
+            spans.push(Span::styled(
+                format!("{:>6}ms", latency),
+                Style::default().fg(latency_color),
+            ));
+            spans.push(Span::styled(
+                format!("{:>5.0}% err", summary.error_rate * 100.0),
+                Style::default().fg(error_color),
+            ));
+            spans.push(Span::styled(
+                format!("  {} req", summary.requests),
+                Style::default().fg(Color::DarkGray),
+            ));
+            let failing = summary.error_rate > 0.0;
+            if let Some(error) = summary.last_error.as_deref().filter(|_| failing) {
+                spans.push(Span::styled(
+                    format!("  {}", api::truncate(error, 30)),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    lines.push(render_line(&app.frames));
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// A member or follower count, exact below 10K
+fn format_count(count: u64) -> String {
+    if count < 10_000 {
+        count.to_string()
+    } else {
+        format_amount(count as f64)
+    }
+}
+
+/// A length of time as "2h 05m", "14m" or "3d 4h"
+fn format_span(span: chrono::Duration) -> String {
+    if span.num_days() > 0 {
+        format!("{}d {}h", span.num_days(), span.num_hours() % 24)
+    } else if span.num_hours() > 0 {
+        format!("{}h {:02}m", span.num_hours(), span.num_minutes() % 60)
+    } else {
+        format!("{}m", span.num_minutes().max(0))
+    }
+}
+
+/// Pair creation date and the token's website and social links
+fn draw_info(
+    frame: &mut Frame,
+    info: Option<&PairInfo>,
+    created: Option<DateTime<Local>>,
+    now: DateTime<Local>,
+    area: Rect,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(" ℹ Token info ")
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    let width = area.width.saturating_sub(4) as usize;
+
+    let mut lines = Vec::new();
+    if let Some(created) = created {
+        lines.push(Line::from(vec![
+            Span::styled("  Created  ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                created.format("%Y-%m-%d %H:%M").to_string(),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(
+                format!("  ({} ago)", format_age(now - created)),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+    if let Some(info) = info {
+        let links: Vec<String> = info
+            .websites
+            .iter()
+            .chain(info.socials.iter())
+            .map(|l| {
+                let url = l.url.trim_start_matches("https://").trim_start_matches("http://");
+                format!("{} {}", l.name().to_lowercase(), url.trim_end_matches('/'))
+            })
+            .collect();
+        lines.push(Line::from(Span::styled(
+            format!("  🔗 {}", api::truncate(&links.join(" · "), width.saturating_sub(3))),
+            Style::default().fg(Color::Blue),
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Coarse age such as "412d", "5h" or "12m"
+fn format_age(age: chrono::Duration) -> String {
+    if age.num_days() > 0 {
+        format!("{}d", age.num_days())
+    } else if age.num_hours() > 0 {
+        format!("{}h", age.num_hours())
+    } else {
+        format!("{}m", age.num_minutes().max(0))
+    }
+}
+
+fn draw_security(frame: &mut Frame, report: &SecurityReport, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(" 🛡 Security ")
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    let width = area.width.saturating_sub(4) as usize;
+    let label =
+        |text: &str| Span::styled(format!("  {}  ", text), Style::default().fg(Color::DarkGray));
+
+    if let Some(ref e) = report.error {
+        let line = Line::from(Span::styled(
+            format!("  ⚠ {}", api::truncate(e, width.saturating_sub(2))),
+            Style::default().fg(Color::Red),
+        ));
+        frame.render_widget(Paragraph::new(line).block(block), area);
+        return;
+    }
+
+    let (verdict, color) = match report.level() {
+        Level::Danger => ("⛔ DANGER", Color::Red),
+        Level::Caution => ("⚠ CAUTION", Color::Yellow),
+        Level::Safe => ("✅ No issues found", Color::Green),
+    };
+    let verdict_line = Line::from(vec![
+        label("Verdict"),
+        Span::styled(verdict, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("  via {}", report.source), Style::default().fg(Color::DarkGray)),
+    ]);
+
+    // Red when the risky answer was found, green when it was ruled out
+    let check = |value: Option<bool>, bad: &str, good: &str| match value {
+        Some(true) => Span::styled(bad.to_string(), Style::default().fg(Color::Red)),
+        Some(false) => Span::styled(good.to_string(), Style::default().fg(Color::Green)),
+        None => Span::styled("?".to_string(), Style::default().fg(Color::DarkGray)),
+    };
+    let lp = match report.lp_locked {
+        Some(pct) => Span::styled(
+            format!("{:.0}% locked", pct),
+            Style::default().fg(if pct >= 90.0 {
+                Color::Green
+            } else if pct >= 50.0 {
+                Color::Yellow
+            } else {
+                Color::Red
+            }),
+        ),
+        None => Span::styled("?", Style::default().fg(Color::DarkGray)),
+    };
+    let checks_line = Line::from(vec![
+        label("Mint"),
+        check(report.mint_authority, "enabled", "renounced"),
+        label("LP"),
+        lp,
+        label("Honeypot"),
+        check(report.honeypot, "YES", "no"),
+    ]);
+
+    let mut lines = vec![verdict_line, checks_line];
+    let flags: Vec<&str> = report.flags.iter().map(|f| f.text.as_str()).collect();
+    if !flags.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", api::truncate(&flags.join(" · "), width)),
+            Style::default().fg(color),
+        )));
+    } else if let (Some(buy), Some(sell)) = (report.buy_tax, report.sell_tax) {
+        lines.push(Line::from(Span::styled(
+            format!("  Tax  buy {:.1}% / sell {:.1}%", buy, sell),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_profile(frame: &mut Frame, profile: &TokenProfile, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(" 📣 DexScreener ")
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    let width = area.width.saturating_sub(4) as usize;
+
+    let (boost, color) = match profile.boost {
+        Some(amount) => (format!("⚡ {:.0} active", amount), Color::Yellow),
+        None => ("none".to_string(), Color::DarkGray),
+    };
+    let mut boost_line = vec![
+        Span::styled("  Boost  ", Style::default().fg(Color::DarkGray)),
+        Span::styled(boost, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+    ];
+    if let Some(total) = profile.boost_total {
+        boost_line.push(Span::styled(
+            format!("  ({:.0} total)", total),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    let mut lines = vec![Line::from(boost_line)];
+    if let Some(ref e) = profile.error {
+        lines.push(Line::from(Span::styled(
+            format!("  ⚠ {}", api::truncate(e, width.saturating_sub(2))),
+            Style::default().fg(Color::Red),
+        )));
+    } else if let Some(ref description) = profile.description {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", api::truncate(description, width)),
+            Style::default().fg(Color::White),
+        )));
+    }
+    if !profile.links.is_empty() {
+        let links: Vec<String> = profile
+            .links
+            .iter()
+            .map(|l| format!("{} {}", l.name(), l.url))
+            .collect();
+        lines.push(Line::from(Span::styled(
+            format!("  🔗 {}", api::truncate(&links.join(" · "), width.saturating_sub(3))),
+            Style::default().fg(Color::Blue),
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn draw_birdeye(
+    frame: &mut Frame,
+    view: &BirdeyeView,
+    price_format: &PriceFormat,
+    currency: &Currency,
+    area: Rect,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(" 🐦 Birdeye ")
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+
+    let holders = view
+        .holders
+        .map(|h| h.to_string())
+        .unwrap_or_else(|| "—".to_string());
+    let (top, top_color) = match view.top_percent {
+        Some(p) if p >= 50.0 => (format!("{:.1}%", p), Color::Red),
+        Some(p) if p >= 25.0 => (format!("{:.1}%", p), Color::Yellow),
+        Some(p) => (format!("{:.1}%", p), Color::Green),
+        None => ("—".to_string(), Color::DarkGray),
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("  Live price  ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format_price(view.price, price_format, currency),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("   Holders  ", Style::default().fg(Color::DarkGray)),
+            Span::styled(holders, Style::default().fg(Color::Cyan)),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("  Top {} hold ", birdeye::TOP_HOLDERS),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(top, Style::default().fg(top_color).add_modifier(Modifier::BOLD)),
+        ]),
+    ];
+    if let Some(ref e) = view.error {
+        lines.push(Line::from(Span::styled(
+            format!("  ⚠ {}", e),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// One side of the compare view
+struct CompareColumn<'a> {
+    title: String,
+    price: String,
+    market_cap: f64,
+    change_1h: f64,
+    change_24h: f64,
+    volume_24h: f64,
+    liquidity: f64,
+    history: &'a [u64],
+    error: Option<&'a str>,
+}
+
+fn draw_compare(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(ref other) = app.compare else {
+        return;
+    };
+
+    let left = CompareColumn {
+        title: format!(" {} — {} ", app.token_symbol, app.chain),
+        price: format_price(app.current_price, &app.config.price_format, &app.currency),
+        market_cap: app.market_cap,
+        change_1h: app.price_change_1h,
+        change_24h: app.price_change_24h,
+        volume_24h: app.volume_24h,
+        liquidity: app.liquidity_usd,
+        history: &app.market_cap_history,
+        error: None,
+    };
+    let change = |f: fn(&crate::api::PriceChange) -> Option<f64>| {
+        other
+            .data
+            .as_ref()
+            .and_then(|d| d.price_change.as_ref())
+            .and_then(f)
+            .unwrap_or(0.0)
+    };
+    let right = CompareColumn {
+        title: format!(" {} — {} ", other.symbol(), other.chain),
+        price: format_price(
+            other.data.as_ref().map(|d| d.price_usd_f64()).unwrap_or(0.0),
+            &app.config.price_format,
+            &app.currency,
+        ),
+        market_cap: other.data.as_ref().map(|d| d.market_cap_usd()).unwrap_or(0.0),
+        change_1h: change(|pc| pc.h1),
+        change_24h: change(|pc| pc.h24),
+        volume_24h: other.data.as_ref().map(|d| d.volume_24h_usd()).unwrap_or(0.0),
+        liquidity: other.data.as_ref().map(|d| d.liquidity_usd()).unwrap_or(0.0),
+        history: &other.history,
+        error: other.error.as_deref(),
+    };
+
+    let (left_data, right_data, shared_max) = compare::normalize_pair(left.history, right.history);
+    let window = left_data.len();
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    draw_compare_column(frame, &left, &app.currency, &left_data, shared_max, window, columns[0]);
+    draw_compare_column(frame, &right, &app.currency, &right_data, shared_max, window, columns[1]);
+}
+
+fn draw_compare_column(
+    frame: &mut Frame,
+    col: &CompareColumn,
+    currency: &Currency,
+    data: &[u64],
+    shared_max: u64,
+    window: usize,
+    area: Rect,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(9)])
+        .split(area);
+
+    let window_change = compare::window_change(col.history, window);
+    let color = if window_change.unwrap_or(0.0) >= 0.0 {
+        Color::Green
+    } else {
+        Color::Red
+    };
+
+    let spark_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(col.title.clone())
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    let sparkline = Sparkline::default()
+        .block(spark_block)
+        .data(data)
+        .max(shared_max)
+        .style(Style::default().fg(color));
+    frame.render_widget(sparkline, chunks[0]);
+
+    let row = |label: &'static str, value: String, style: Style| {
+        Line::from(vec![
+            Span::styled(label, Style::default().fg(Color::DarkGray)),
+            Span::styled(value, style),
+        ])
+    };
+    let change_style = |v: f64| {
+        Style::default().fg(if v >= 0.0 { Color::Green } else { Color::Red })
+    };
+
+    let mut lines = vec![
+        row(
+            "  Window Δ    ",
+            window_change.map(format_change).unwrap_or_else(|| "—".into()),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        row("  Price       ", col.price.clone(), Style::default().fg(Color::White)),
+        row(
+            "  Market Cap  ",
+            format_money(col.market_cap, currency),
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        ),
+        row("  1h Change   ", format_change(col.change_1h), change_style(col.change_1h)),
+        row("  24h Change  ", format_change(col.change_24h), change_style(col.change_24h)),
+        row(
+            "  Volume 24h  ",
+            format_money(col.volume_24h, currency),
+            Style::default().fg(Color::Cyan),
+        ),
+        row(
+            "  Liquidity   ",
+            format_money(col.liquidity, currency),
+            Style::default().fg(Color::Cyan),
+        ),
+    ];
+    if let Some(err) = col.error {
+        lines.push(Line::from(Span::styled(
+            format!("  ❌ {}", err),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    let stats = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(stats, chunks[1]);
+}
+
+/// Rows the chart needs: the sparkline, the target gauge and the FDV gauge
+/// with --fdv-target
+fn chart_height(app: &App) -> u16 {
+    if app.fdv_progress().is_some() {
+        11
+    } else {
+        8
+    }
 }
 
 fn draw_chart(frame: &mut Frame, app: &App, area: Rect) {
-    // Split chart area: sparkline + gauge
+    // Split chart area: sparkline + gauge, and the FDV gauge with --fdv-target
+    let fdv_progress = app.fdv_progress();
+    let mut constraints = vec![Constraint::Min(5), Constraint::Length(3)];
+    if fdv_progress.is_some() {
+        constraints.push(Constraint::Length(3));
+    }
     let chart_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(5), Constraint::Length(3)])
+        .constraints(constraints)
         .split(area);
 
     // Sparkline
@@ -116,12 +1231,88 @@ fn draw_chart(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(sparkline, chart_chunks[0]);
 
     // Progress gauge toward target
-    let progress = app.progress();
-    let gauge_label = format!(
-        "${:.0} / ${:.0}",
-        app.market_cap, app.target_market_cap
+    let symbol = app.currency.symbol();
+    let gauge_label = match app.target_kind {
+        TargetKind::MarketCap => format!(
+            "{}{:.0} / {}{:.0}",
+            symbol,
+            app.currency.convert(app.market_cap),
+            symbol,
+            app.currency.convert(app.next_target())
+        ),
+        TargetKind::Price => format!(
+            "{} / {}",
+            format_price(app.current_price, &app.config.price_format, &app.currency),
+            format_price(app.next_target(), &app.config.price_format, &app.currency)
+        ),
+    };
+
+    // The gauge spans the whole run, up to the highest target. Ticks mark each
+    // quarter and each milestone (green once passed, yellow while ahead).
+    let top = app.top_target();
+    let value = app.tracked_value();
+    let progress = if top > 0.0 {
+        (value / top * 100.0).min(100.0)
+    } else {
+        0.0
+    };
+    let format_level = |level: f64| match app.target_kind {
+        TargetKind::MarketCap => format_money(level, &app.currency),
+        TargetKind::Price => format_price(level, &app.config.price_format, &app.currency),
+    };
+    let mut ticks: Vec<GaugeTick> = [0.25, 0.5, 0.75]
+        .iter()
+        .map(|ratio| GaugeTick {
+            ratio: *ratio,
+            color: if value >= ratio * top {
+                Color::White
+            } else {
+                Color::Gray
+            },
+            label: format_level(ratio * top),
+        })
+        .collect();
+    if top > 0.0 {
+        let milestones = &app.targets[..app.targets.len().saturating_sub(1)];
+        ticks.extend(milestones.iter().enumerate().map(|(i, level)| GaugeTick {
+            ratio: level / top,
+            color: if i < app.targets_hit {
+                Color::Green
+            } else {
+                Color::Yellow
+            },
+            label: format_level(*level),
+        }));
+    }
+    frame.render_widget(
+        target_gauge(" 🎯 Target Progress ", progress, gauge_label, app.show_gauge_values),
+        chart_chunks[1],
     );
+    draw_gauge_ticks(frame, chart_chunks[1], &mut ticks, app.show_gauge_values);
 
+    if let (Some(progress), Some(target)) = (fdv_progress, app.fdv_target) {
+        let label = format!(
+            "{}{:.0} / {}{:.0}",
+            symbol,
+            app.currency.convert(app.fdv),
+            symbol,
+            app.currency.convert(target)
+        );
+        frame.render_widget(
+            target_gauge(" 🎯 FDV Target ", progress, label, false),
+            chart_chunks[2],
+        );
+    }
+}
+
+/// A progress gauge toward a target, warming in color as it fills. A focused
+/// gauge has its border highlighted.
+fn target_gauge(
+    title: &'static str,
+    progress: f64,
+    label: String,
+    focused: bool,
+) -> Gauge<'static> {
     let gauge_color = if progress >= 100.0 {
         Color::Yellow
     } else if progress >= 75.0 {
@@ -132,22 +1323,72 @@ fn draw_chart(frame: &mut Frame, app: &App, area: Rect) {
         Color::Blue
     };
 
-    let gauge = Gauge::default()
+    let border_color = if focused { Color::Yellow } else { Color::DarkGray };
+    Gauge::default()
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
-                .title(" 🎯 Target Progress ")
+                .border_style(Style::default().fg(border_color))
+                .title(title)
                 .title_style(Style::default().fg(Color::Yellow)),
         )
         .gauge_style(Style::default().fg(gauge_color).bg(Color::DarkGray))
         .ratio(progress / 100.0)
-        .label(gauge_label);
+        .label(label)
+}
 
-    frame.render_widget(gauge, chart_chunks[1]);
+/// A point marked on a gauge's bottom border
+struct GaugeTick {
+    /// Position as a share of the gauge's width
+    ratio: f64,
+    color: Color,
+    /// The value at the tick, shown beside it when the gauge is focused
+    label: String,
 }
 
-fn draw_stats(frame: &mut Frame, app: &App, area: Rect) {
+/// Mark ticks along a gauge's bottom border, later ones drawn over earlier
+/// ones on the same cell. With `labels`, each value follows its tick where it
+/// fits before the next one.
+fn draw_gauge_ticks(frame: &mut Frame, area: Rect, ticks: &mut [GaugeTick], labels: bool) {
+    if area.width < 3 || area.height < 2 {
+        return;
+    }
+    let inner = f64::from(area.width - 2);
+    let y = area.bottom() - 1;
+    let column = |ratio: f64| {
+        let offset = (ratio.clamp(0.0, 1.0) * inner).round() as u16;
+        area.x + 1 + offset.min(area.width - 3)
+    };
+    for tick in ticks.iter() {
+        if let Some(cell) = frame.buffer_mut().cell_mut((column(tick.ratio), y)) {
+            cell.set_symbol("┴").set_fg(tick.color);
+        }
+    }
+    if !labels {
+        return;
+    }
+    ticks.sort_by(|a, b| a.ratio.total_cmp(&b.ratio));
+    let columns: Vec<u16> = ticks.iter().map(|tick| column(tick.ratio)).collect();
+    for (i, tick) in ticks.iter().enumerate() {
+        if columns.get(i + 1) == Some(&columns[i]) {
+            continue;
+        }
+        let start = columns[i] + 1;
+        let end = columns.get(i + 1).copied().unwrap_or(area.right() - 1);
+        let width = tick.label.chars().count() as u16;
+        if start + width > end {
+            continue;
+        }
+        frame.buffer_mut().set_string(
+            start,
+            y,
+            &tick.label,
+            Style::default().fg(tick.color),
+        );
+    }
+}
+
+fn draw_stats(frame: &mut Frame, app: &App, now: DateTime<Local>, area: Rect) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::DarkGray))
@@ -169,11 +1410,21 @@ fn draw_stats(frame: &mut Frame, app: &App, area: Rect) {
     let change_1h_str = format_change(app.price_change_1h);
     let change_24h_str = format_change(app.price_change_24h);
 
-    let lines = vec![
+    // Summed over every pool of the token with --all-pools
+    let (volume_24h, liquidity, pools_note) = match app.pool_totals {
+        Some(totals) if app.all_pools && totals.pools > 1 => (
+            totals.volume_24h,
+            totals.liquidity_usd,
+            format!("  Σ {} pools", totals.pools),
+        ),
+        _ => (app.volume_24h, app.liquidity_usd, String::new()),
+    };
+
+    let mut lines = vec![
         Line::from(vec![
             Span::styled("  Price       ", Style::default().fg(Color::DarkGray)),
             Span::styled(
-                format_price(app.current_price),
+                format_price(app.current_price, &app.config.price_format, &app.currency),
                 Style::default().fg(price_color).add_modifier(Modifier::BOLD),
             ),
         ]),
@@ -181,13 +1432,18 @@ fn draw_stats(frame: &mut Frame, app: &App, area: Rect) {
         Line::from(vec![
             Span::styled("  Market Cap  ", Style::default().fg(Color::DarkGray)),
             Span::styled(
-                format_dollar(app.market_cap),
+                format_money(app.market_cap, &app.currency),
                 Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
             ),
+            if app.onchain_supply.is_some() {
+                Span::styled(" ⛓ on-chain", Style::default().fg(Color::DarkGray))
+            } else {
+                Span::raw("")
+            },
         ]),
         Line::from(vec![
             Span::styled("  FDV         ", Style::default().fg(Color::DarkGray)),
-            Span::styled(format_dollar(app.fdv), Style::default().fg(Color::White)),
+            Span::styled(format_money(app.fdv, &app.currency), Style::default().fg(Color::White)),
         ]),
         Line::from(""),
         Line::from(vec![
@@ -201,15 +1457,76 @@ fn draw_stats(frame: &mut Frame, app: &App, area: Rect) {
         Line::from(""),
         Line::from(vec![
             Span::styled("  Volume 24h  ", Style::default().fg(Color::DarkGray)),
-            Span::styled(format_dollar(app.volume_24h), Style::default().fg(Color::Cyan)),
+            Span::styled(format_money(volume_24h, &app.currency), Style::default().fg(Color::Cyan)),
+            Span::styled(pools_note.clone(), Style::default().fg(Color::DarkGray)),
         ]),
         Line::from(vec![
             Span::styled("  Liquidity   ", Style::default().fg(Color::DarkGray)),
+            Span::styled(format_money(liquidity, &app.currency), Style::default().fg(Color::Cyan)),
+            Span::styled(pools_note, Style::default().fg(Color::DarkGray)),
+        ]),
+    ];
+
+    // pump.fun tokens still on their bonding curve
+    if let Some(curve) = app.bonding {
+        let filled = ((curve.progress / 10.0).round() as usize).min(10);
+        let (status, color) = if curve.complete {
+            ("  🎓 graduated".to_string(), Color::Green)
+        } else {
+            (format!(" {:.1}%", curve.progress), Color::Magenta)
+        };
+        lines.push(Line::from(vec![
+            Span::styled("  Curve       ", Style::default().fg(Color::DarkGray)),
             Span::styled(
-                format_dollar(app.liquidity_usd),
-                Style::default().fg(Color::Cyan),
+                format!("{}{}", "▰".repeat(filled), "▱".repeat(10 - filled)),
+                Style::default().fg(color),
             ),
-        ]),
+            Span::styled(status, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+        ]));
+    }
+
+    // The --wallet's own bag of the token, with its last change
+    if let Some(balance) = app.wallet_balance {
+        let mut spans = vec![
+            Span::styled("  Holding     ", Style::default().fg(Color::DarkGray)),
+            Span::styled(format_amount(balance), Style::default().fg(Color::White)),
+        ];
+        if app.current_price > 0.0 {
+            spans.push(Span::styled(
+                format!("  ≈ {}", format_money(balance * app.current_price, &app.currency)),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        if let Some(change) = app.balance_change {
+            let (sign, color) = if change > 0.0 {
+                ("+", Color::Green)
+            } else {
+                ("-", Color::Red)
+            };
+            spans.push(Span::styled(
+                format!("  {}{}", sign, format_amount(change.abs())),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ));
+        }
+        lines.push(Line::from(spans));
+
+        if let Some(ref address) = app.wallet {
+            let mut spans = vec![Span::styled(
+                "  Wallet      ",
+                Style::default().fg(Color::DarkGray),
+            )];
+            if let Some(ref name) = app.wallet_name {
+                spans.push(Span::styled(format!("{} ", name), Style::default().fg(Color::White)));
+            }
+            spans.push(Span::styled(
+                crate::wallet::short_address(address),
+                Style::default().fg(Color::DarkGray),
+            ));
+            lines.push(Line::from(spans));
+        }
+    }
+
+    lines.extend([
         Line::from(""),
         Line::from(vec![
             Span::styled("  Buys  24h   ", Style::default().fg(Color::DarkGray)),
@@ -229,12 +1546,25 @@ fn draw_stats(frame: &mut Frame, app: &App, area: Rect) {
         Line::from(vec![
             Span::styled("  Target      ", Style::default().fg(Color::DarkGray)),
             Span::styled(
-                format_dollar(app.target_market_cap),
+                match app.target_kind {
+                    TargetKind::MarketCap => format_money(app.next_target(), &app.currency),
+                    TargetKind::Price => {
+                        format_price(app.next_target(), &app.config.price_format, &app.currency)
+                    }
+                },
                 Style::default()
                     .fg(Color::Yellow)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" 🎯"),
+            Span::styled(
+                if app.targets.len() > 1 {
+                    format!(" {}/{}", app.targets_hit, app.targets.len())
+                } else {
+                    String::new()
+                },
+                Style::default().fg(Color::DarkGray),
+            ),
         ]),
         Line::from(vec![
             Span::styled("  Fetches     ", Style::default().fg(Color::DarkGray)),
@@ -251,50 +1581,371 @@ fn draw_stats(frame: &mut Frame, app: &App, area: Rect) {
                 Span::raw("")
             },
         ]),
-    ];
+    ]);
+
+    if let Some(ref velocity) = app.velocity {
+        let (text, color) = match velocity.current() {
+            Some(rate) => (
+                format!(
+                    "{}{}/min",
+                    if rate >= 0.0 { "+" } else { "-" },
+                    format_money(rate.abs(), &app.currency)
+                ),
+                if rate.abs() >= velocity.threshold {
+                    Color::Red
+                } else {
+                    Color::White
+                },
+            ),
+            None => ("—".to_string(), Color::DarkGray),
+        };
+        lines.push(Line::from(vec![
+            Span::styled("  Velocity    ", Style::default().fg(Color::DarkGray)),
+            Span::styled(text, Style::default().fg(color)),
+            Span::styled(
+                format!("  over {}m", velocity.window.as_secs() / 60),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+
+    // The gain over the previous high needs one from an earlier session
+    if let Some(high) = app.ath.as_ref().map(|h| h.market_cap) {
+        let (note, color) = match app.ath_previous {
+            Some(previous) if high > previous => {
+                let percent = (high - previous) / previous * 100.0;
+                let previous = format_money(previous, &app.currency);
+                (format!("  {:+.1}% over {}", percent, previous), Color::Green)
+            }
+            _ if app.market_cap >= high => ("  at the high".to_string(), Color::Green),
+            _ => {
+                let percent = (high - app.market_cap) / high * 100.0;
+                (format!("  {:.1}% below", percent), Color::DarkGray)
+            }
+        };
+        lines.push(Line::from(vec![
+            Span::styled("  ATH         ", Style::default().fg(Color::DarkGray)),
+            Span::styled(format_money(high, &app.currency), Style::default().fg(Color::White)),
+            Span::styled(note, Style::default().fg(color)),
+        ]));
+    }
+
+    if let Some(level) = app.trailing_stop_level() {
+        let fired = app.trailing_fired_at.is_some();
+        lines.push(Line::from(vec![
+            Span::styled("  Trail stop  ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format_money(level, &app.currency),
+                Style::default().fg(if fired { Color::Red } else { Color::White }),
+            ),
+            Span::styled(
+                format!(
+                    "  -{}% from peak {}",
+                    app.trailing_stop.unwrap_or(0.0),
+                    format_money(app.session.high, &app.currency)
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+
+    if let Some(ref gap) = app.quote_gap {
+        let firing = app.gap_alert.is_some_and(|t| gap.percent >= t);
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("  Quote gap   ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{:.2}%", gap.percent),
+                Style::default()
+                    .fg(if firing { Color::Red } else { Color::Yellow })
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "  {} {} / {} {}",
+                    gap.cheap.quote,
+                    format_price(gap.cheap.price, &app.config.price_format, &app.currency),
+                    gap.rich.quote,
+                    format_price(gap.rich.price, &app.config.price_format, &app.currency)
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+
+    if let Some(ref cex) = app.cex {
+        let detail = match (&cex.error, cex.price) {
+            (Some(e), _) => format!("  {}", e),
+            (None, Some(price)) => format!(
+                "  {} {}",
+                cex.exchange.name(),
+                format_price(price, &app.config.price_format, &app.currency)
+            ),
+            (None, None) => format!("  {} …", cex.exchange.name()),
+        };
+        let (spread, color) = match cex.spread(app.current_price) {
+            Some(s) if cex.alert_percent.is_some_and(|t| s.abs() >= t) => {
+                (format!("{:+.2}%", s), Color::Red)
+            }
+            Some(s) => (format!("{:+.2}%", s), Color::Yellow),
+            None => ("—".to_string(), Color::DarkGray),
+        };
+        lines.push(Line::from(vec![
+            Span::styled("  CEX spread  ", Style::default().fg(Color::DarkGray)),
+            Span::styled(spread, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::styled(detail, Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+
+    if let Some(ref market) = app.perp {
+        let rate = market.funding_rate;
+        let color = if rate.abs() >= perps::FUNDING_EXTREME {
+            Color::Red
+        } else if rate.abs() >= perps::FUNDING_ELEVATED {
+            Color::Yellow
+        } else {
+            Color::White
+        };
+        let next = match market.next_funding {
+            Some(at) => format!("  next {}", at.format("%H:%M")),
+            None => String::new(),
+        };
+        lines.push(Line::from(vec![
+            Span::styled("  Funding     ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{:+.4}%", rate),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{}  {}", next, market.symbol),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("  Open int.   ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format_money(market.open_interest_usd, &app.currency),
+                Style::default().fg(Color::White),
+            ),
+        ]));
+    } else if let Some(ref e) = app.perp_error {
+        lines.push(Line::from(vec![
+            Span::styled("  Funding     ", Style::default().fg(Color::DarkGray)),
+            Span::styled(api::truncate(e, 40), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+
+    if app.aggregate && !app.aggregate_sources.is_empty() {
+        let (status, color) = if app.outliers.is_empty() {
+            ("agree".to_string(), Color::Green)
+        } else {
+            let names: Vec<&str> = app.outliers.iter().map(|p| p.name()).collect();
+            (format!("outlier: {}", names.join(", ")), Color::Yellow)
+        };
+        lines.push(Line::from(vec![
+            Span::styled("  Median of   ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{} sources", app.aggregate_sources.len()),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("  {}", status), Style::default().fg(color)),
+        ]));
+    }
+
+    if let Some((unlock, at)) = app.next_unlock(now) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("  Unlock in   ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                unlocks::format_countdown(at, now),
+                Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  {}", unlock.describe()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
 
     let paragraph = Paragraph::new(lines).block(block);
     frame.render_widget(paragraph, area);
 }
 
-fn draw_log(frame: &mut Frame, app: &App, area: Rect) {
+fn draw_portfolio(frame: &mut Frame, group: &GroupView, currency: &Currency, area: Rect) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::DarkGray))
-        .title(" 📋 Log ")
-        .title_style(Style::default().fg(Color::White));
+        .title(format!(" 💼 Portfolio — {} ", group.name))
+        .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
 
-    let items: Vec<ListItem> = app
-        .log_messages
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(inner);
+
+    // Members: symbol, chain, market cap, own progress
+    let members: Vec<Line> = group
+        .members
         .iter()
-        .rev()
-        .take(area.height as usize - 2)
-        .map(|msg| {
-            let style = if msg.contains("🔥") {
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD)
-            } else if msg.contains("❌") {
-                Style::default().fg(Color::Red)
-            } else {
-                Style::default().fg(Color::DarkGray)
+        .map(|m| {
+            let status = match (&m.data, &m.error) {
+                _ if m.paused => Span::styled("  💤", Style::default().fg(Color::DarkGray)),
+                (_, Some(_)) => Span::styled("  ❌", Style::default().fg(Color::Red)),
+                (None, None) => Span::styled("  …", Style::default().fg(Color::DarkGray)),
+                (Some(_), None) => match m.target {
+                    Some(t) if t > 0.0 => Span::styled(
+                        format!("  {:.1}%", (m.market_cap() / t * 100.0).min(100.0)),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    _ => Span::raw(""),
+                },
             };
-            ListItem::new(Span::styled(msg.clone(), style))
+            Line::from(vec![
+                Span::styled(
+                    format!("  {:<10}", m.symbol()),
+                    Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("{:<10}", m.chain), Style::default().fg(Color::Magenta)),
+                Span::styled(
+                    format!("{:>10}", format_money(m.market_cap(), currency)),
+                    Style::default().fg(Color::White),
+                ),
+                status,
+            ])
         })
         .collect();
+    frame.render_widget(Paragraph::new(members), chunks[0]);
 
-    let help = Line::from(vec![
-        Span::styled(" q", Style::default().fg(Color::Yellow).bold()),
-        Span::styled(" quit  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("r", Style::default().fg(Color::Yellow).bold()),
-        Span::styled(" refresh  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("c", Style::default().fg(Color::Yellow).bold()),
-        Span::styled(" config  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("d", Style::default().fg(Color::Yellow).bold()),
-        Span::styled(" idle  ", Style::default().fg(Color::DarkGray)),
-        Span::styled("s", Style::default().fg(Color::Yellow).bold()),
-        Span::styled(" stop alarm", Style::default().fg(Color::DarkGray)),
-    ]);
+    // Aggregates + blended gauge
+    let agg_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(chunks[1]);
+
+    let totals = vec![
+        Line::from(vec![
+            Span::styled("  Total MCap  ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format_money(group.total_market_cap(), currency),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  Volume 24h  ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format_money(group.total_volume_24h(), currency),
+                Style::default().fg(Color::Cyan),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  Liquidity   ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format_money(group.total_liquidity(), currency),
+                Style::default().fg(Color::Cyan),
+            ),
+        ]),
+    ];
+    frame.render_widget(Paragraph::new(totals), agg_chunks[0]);
+
+    let progress = group.blended_progress();
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(Color::Green).bg(Color::DarkGray))
+        .ratio(progress / 100.0)
+        .label(format!("{:.1}% blended", progress));
+    frame.render_widget(gauge, agg_chunks[1]);
+}
+
+fn draw_log(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(" 📋 Log ")
+        .title_style(Style::default().fg(Color::White));
+
+    // Once the log spans midnight, lines from earlier days show their date and
+    // a separator marks where each day starts
+    let spans_days = app.log.first().map(|l| l.date) != app.log.last().map(|l| l.date);
+    let today = app.log.last().map(|l| l.date);
+    let mut items: Vec<ListItem> = Vec::new();
+    let lines = app.log.iter().rev().take(area.height.saturating_sub(2) as usize);
+    let mut newer: Option<NaiveDate> = None;
+    for line in lines {
+        if let Some(day) = newer.filter(|day| *day != line.date) {
+            items.push(ListItem::new(Span::styled(
+                format!("── ▲ {} ──", day.format("%a %d %b %Y")),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        newer = Some(line.date);
+        // Lines from an earlier day carry their date: `[10-16 23:58:02] ...`
+        let msg = if spans_days && Some(line.date) != today {
+            format!("[{} {}] {}", line.date.format("%m-%d"), line.time, line.text)
+        } else {
+            line.to_string()
+        };
+        let style = if let Some(severity) = line.level {
+            let style = Style::default().fg(severity_color(severity));
+            if severity > Severity::Info {
+                style.add_modifier(Modifier::BOLD)
+            } else {
+                style
+            }
+        } else if msg.contains("🔥") {
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else if msg.contains("🚨") {
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        } else if msg.contains("❌") {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        items.push(ListItem::new(Span::styled(msg, style)));
+    }
+
+    let help = if app.quit_pending() {
+        Line::from(Span::styled(
+            " Press q again to quit",
+            Style::default().fg(Color::Yellow).bold(),
+        ))
+    } else if app.observer {
+        Line::from(vec![
+            Span::styled(" 👁 observer  ", Style::default().fg(Color::Magenta).bold()),
+            Span::styled("q q", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" quit  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("r", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" refresh  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("g", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" group", Style::default().fg(Color::DarkGray)),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled(" q", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" quit  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("r", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" refresh  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("c", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" config  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("d", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" idle  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("s", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" stop alarm  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("o", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" pools  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("p", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" pin  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("x", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" compare  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("l", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" low power  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("g", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" group  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("^P", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" recent", Style::default().fg(Color::DarkGray)),
+        ])
+    };
 
     // We draw the list and the help line within the block
     let inner = block.inner(area);
@@ -335,7 +1986,14 @@ fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
 }
 
 fn draw_modal(frame: &mut Frame, app: &App, area: Rect) {
-    let modal_area = centered_rect(60, 50, area);
+    // Tall enough for every field: label and value, a blank line between
+    // fields, padding, footer and borders
+    let height = (ModalField::ALL.len() as u16 * 3 + 3).min(area.height);
+    let modal_area = Rect {
+        y: area.y + (area.height - height) / 2,
+        height,
+        ..centered_rect(60, 100, area)
+    };
 
     // Clear the area behind the modal
     frame.render_widget(Clear, modal_area);
@@ -353,27 +2011,24 @@ fn draw_modal(frame: &mut Frame, app: &App, area: Rect) {
     let inner = block.inner(modal_area);
     frame.render_widget(block, modal_area);
 
-    // Layout: fields + footer
+    // Layout: top padding, fields separated by a blank line, footer
+    let mut constraints = vec![Constraint::Length(1)];
+    for i in 0..ModalField::ALL.len() {
+        if i > 0 {
+            constraints.push(Constraint::Length(1));
+        }
+        constraints.push(Constraint::Length(2));
+    }
+    constraints.push(Constraint::Min(0)); // spacer
+    constraints.push(Constraint::Length(1)); // footer help
     let modal_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(1),  // top padding
-            Constraint::Length(2),  // field 0
-            Constraint::Length(1),  // spacing
-            Constraint::Length(2),  // field 1
-            Constraint::Length(1),  // spacing
-            Constraint::Length(2),  // field 2
-            Constraint::Length(1),  // spacing
-            Constraint::Length(2),  // field 3
-            Constraint::Min(1),    // spacer
-            Constraint::Length(1), // footer help
-        ])
+        .constraints(constraints)
         .split(inner);
 
-    let field_areas = [modal_chunks[1], modal_chunks[3], modal_chunks[5], modal_chunks[7]];
-
-    for (i, field_area) in field_areas.iter().enumerate() {
-        let is_active = i == app.modal_active_field;
+    for (i, field) in ModalField::ALL.iter().enumerate() {
+        let field_area = modal_chunks[1 + 2 * i];
+        let is_active = *field == app.modal_active_field;
 
         let label_style = if is_active {
             Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
@@ -392,19 +2047,19 @@ fn draw_modal(frame: &mut Frame, app: &App, area: Rect) {
 
         let label_line = Line::from(vec![
             Span::styled(indicator, label_style),
-            Span::styled(MODAL_FIELD_LABELS[i], label_style),
+            Span::styled(field.label(), label_style),
         ]);
 
         let value_line = Line::from(vec![
             Span::raw("   "),
-            Span::styled(&app.modal_fields[i], value_style),
+            Span::styled(&app.modal_fields[*field], value_style),
             Span::styled(cursor, Style::default().fg(Color::Cyan)),
         ]);
 
         let field_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Length(1), Constraint::Length(1)])
-            .split(*field_area);
+            .split(field_area);
 
         frame.render_widget(Paragraph::new(label_line), field_chunks[0]);
         frame.render_widget(Paragraph::new(value_line), field_chunks[1]);
@@ -422,28 +2077,405 @@ fn draw_modal(frame: &mut Frame, app: &App, area: Rect) {
         Span::styled(" cancel", Style::default().fg(Color::DarkGray)),
     ]);
 
-    frame.render_widget(Paragraph::new(footer), modal_chunks[9]);
+    frame.render_widget(Paragraph::new(footer), modal_chunks[modal_chunks.len() - 1]);
+}
+
+// ========== Pool Selector ==========
+
+fn draw_pool_selector(frame: &mut Frame, app: &App, area: Rect) {
+    let popup_area = centered_rect(70, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(format!(" 🏊 Pools for {} ", app.token_symbol))
+        .title_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let items: Vec<ListItem> = app
+        .pools
+        .iter()
+        .enumerate()
+        .map(|(i, pool)| {
+            let selected = i == app.pool_selected;
+            let indicator = if selected { " ▶ " } else { "   " };
+            let current = if app.is_current_pool(pool) { " ●" } else { "" };
+            let style = if selected {
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(indicator, Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    format!("{:<12}", pool.dex_id.as_deref().unwrap_or("?")),
+                    style,
+                ),
+                Span::styled(
+                    format!("{:<14}", format!("{}/{}", pool.base_symbol(), pool.quote_symbol())),
+                    style,
+                ),
+                Span::styled(
+                    format!("{:<10}", pool.chain_id.as_deref().unwrap_or("?")),
+                    Style::default().fg(Color::Magenta),
+                ),
+                Span::styled(
+                    format!("liq {:<10}", format_money(pool.liquidity_usd(), &app.currency)),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    format!("vol {:<10}", format_money(pool.volume_24h_usd(), &app.currency)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(current, Style::default().fg(Color::Green)),
+            ]))
+        })
+        .collect();
+    frame.render_widget(List::new(items), chunks[0]);
+
+    let footer = Line::from(vec![
+        Span::styled(" Enter", Style::default().fg(Color::Green).bold()),
+        Span::styled(" monitor pool  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("↑/↓", Style::default().fg(Color::Yellow).bold()),
+        Span::styled(" select  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Esc", Style::default().fg(Color::Red).bold()),
+        Span::styled(" close", Style::default().fg(Color::DarkGray)),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
+
+// ========== Recent Pairs Switcher ==========
+
+fn draw_switcher(frame: &mut Frame, app: &App, area: Rect) {
+    let popup_area = centered_rect(70, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(match app.switcher_mode {
+            SwitcherMode::Switch => " 🕘 Recent Pairs ",
+            SwitcherMode::Compare => " ⚖  Compare With ",
+        })
+        .title_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // query
+            Constraint::Length(1), // spacing
+            Constraint::Min(1),    // list
+            Constraint::Length(1), // footer help
+        ])
+        .split(inner);
+
+    let query_line = Line::from(vec![
+        Span::styled(" 🔎 ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            &app.switcher_query,
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("█", Style::default().fg(Color::Cyan)),
+    ]);
+    frame.render_widget(Paragraph::new(query_line), chunks[0]);
+
+    let matches = app.switcher_matches();
+    let items: Vec<ListItem> = if matches.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "   No recent pairs yet",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        matches
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                let selected = i == app.switcher_selected;
+                let indicator = if selected { " ▶ " } else { "   " };
+                let style = if selected {
+                    Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Gray)
+                };
+                let short_addr: String = r.pair.chars().take(12).collect();
+                ListItem::new(Line::from(vec![
+                    Span::styled(indicator, Style::default().fg(Color::Cyan)),
+                    Span::styled(format!("{:<10}", r.symbol), style),
+                    Span::styled(format!("{:<10}", r.chain), Style::default().fg(Color::Magenta)),
+                    Span::styled(format!("{}…  ", short_addr), Style::default().fg(Color::DarkGray)),
+                    Span::styled(&r.last_used, Style::default().fg(Color::DarkGray)),
+                ]))
+            })
+            .collect()
+    };
+    frame.render_widget(List::new(items), chunks[2]);
+
+    let footer = Line::from(vec![
+        Span::styled(" Enter", Style::default().fg(Color::Green).bold()),
+        Span::styled(
+            match app.switcher_mode {
+                SwitcherMode::Switch => " switch  ",
+                SwitcherMode::Compare => " compare  ",
+            },
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled("↑/↓", Style::default().fg(Color::Yellow).bold()),
+        Span::styled(" select  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("type", Style::default().fg(Color::Yellow).bold()),
+        Span::styled(" filter  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Esc", Style::default().fg(Color::Red).bold()),
+        Span::styled(" close", Style::default().fg(Color::DarkGray)),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[3]);
+}
+
+// ========== First Fetch Diagnostics ==========
+
+fn draw_onboarding(frame: &mut Frame, app: &App, onboarding: &Onboarding, area: Rect) {
+    let popup_area = centered_rect(70, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let short_addr: String = app.pair_address.chars().take(12).collect();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(format!(" ⚠ Couldn't load {}… on {} ", short_addr, app.chain))
+        .title_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    if onboarding.searching {
+        draw_onboarding_search(frame, app, onboarding, chunks[0]);
+        let footer = Line::from(vec![
+            Span::styled(" Enter", Style::default().fg(Color::Green).bold()),
+            Span::styled(" search / monitor  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("↑/↓", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" select  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("Esc", Style::default().fg(Color::Red).bold()),
+            Span::styled(" back", Style::default().fg(Color::DarkGray)),
+        ]);
+        frame.render_widget(Paragraph::new(footer), chunks[1]);
+        return;
+    }
+
+    let mut lines = vec![Line::from(Span::styled(
+        " Likely causes:",
+        Style::default().fg(Color::Yellow).bold(),
+    ))];
+    for cause in onboarding.causes(&app.chain) {
+        lines.push(Line::from(vec![
+            Span::styled("  • ", Style::default().fg(Color::Yellow)),
+            Span::styled(cause, Style::default().fg(Color::White)),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(" Error: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(&onboarding.error, Style::default().fg(Color::Red)),
+    ]));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(" Still retrying every {}s in the background.", app.check_interval),
+        Style::default().fg(Color::DarkGray),
+    )));
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+    let switch = match onboarding.suggested_chain() {
+        Some(chain) => format!(" switch to {}  ", chain),
+        None => " change chain  ".to_string(),
+    };
+    let footer = Line::from(vec![
+        Span::styled(" c", Style::default().fg(Color::Green).bold()),
+        Span::styled(" edit config  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("n", Style::default().fg(Color::Yellow).bold()),
+        Span::styled(switch, Style::default().fg(Color::DarkGray)),
+        Span::styled("s", Style::default().fg(Color::Yellow).bold()),
+        Span::styled(" search by symbol  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("Esc", Style::default().fg(Color::Red).bold()),
+        Span::styled(" keep waiting", Style::default().fg(Color::DarkGray)),
+    ]);
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
+
+fn draw_onboarding_search(frame: &mut Frame, app: &App, onboarding: &Onboarding, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // query
+            Constraint::Length(1), // spacing
+            Constraint::Min(1),    // results
+        ])
+        .split(area);
+
+    let query_line = Line::from(vec![
+        Span::styled(" 🔎 ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            &onboarding.query,
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("█", Style::default().fg(Color::Cyan)),
+    ]);
+    frame.render_widget(Paragraph::new(query_line), chunks[0]);
+
+    let note = |text: String| {
+        vec![ListItem::new(Span::styled(text, Style::default().fg(Color::DarkGray)))]
+    };
+    let items: Vec<ListItem> = if onboarding.search_pending {
+        note("   Searching…".to_string())
+    } else if onboarding.searched.is_none() {
+        note("   Type a symbol or token name, then Enter".to_string())
+    } else {
+        match &onboarding.results {
+            Err(e) => note(format!("   {}", e)),
+            Ok(_) => onboarding
+                .matches()
+                .iter()
+                .enumerate()
+                .map(|(i, pair)| {
+                    let selected = i == onboarding.selected;
+                    let indicator = if selected { " ▶ " } else { "   " };
+                    let style = if selected {
+                        Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(Color::Gray)
+                    };
+                    let symbols = format!("{}/{}", pair.base_symbol(), pair.quote_symbol());
+                    let liquidity = format_money(pair.liquidity_usd(), &app.currency);
+                    ListItem::new(Line::from(vec![
+                        Span::styled(indicator, Style::default().fg(Color::Cyan)),
+                        Span::styled(format!("{:<14}", symbols), style),
+                        Span::styled(
+                            format!("{:<10}", pair.chain_id.as_deref().unwrap_or("?")),
+                            Style::default().fg(Color::Magenta),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", pair.dex_id.as_deref().unwrap_or("?")),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                        Span::styled(
+                            format!("liq {:<10}", liquidity),
+                            Style::default().fg(Color::Cyan),
+                        ),
+                    ]))
+                })
+                .collect(),
+        }
+    };
+    frame.render_widget(List::new(items), chunks[2]);
 }
 
 // ========== Formatting Helpers ==========
 
-fn format_dollar(val: f64) -> String {
+/// A USD amount in the display currency, e.g. "$1.25M" or "€1.15M"
+fn format_money(val: f64, currency: &Currency) -> String {
+    let symbol = currency.symbol();
+    let val = currency.convert(val);
     if val >= 1_000_000.0 {
-        format!("${:.2}M", val / 1_000_000.0)
+        format!("{}{:.2}M", symbol, val / 1_000_000.0)
+    } else if val >= 1_000.0 {
+        format!("{}{:.1}K", symbol, val / 1_000.0)
+    } else {
+        format!("{}{:.2}", symbol, val)
+    }
+}
+
+/// A token amount in compact form, e.g. "1.25M", "12.3K", "42.50"
+pub fn format_amount(val: f64) -> String {
+    if val >= 1_000_000_000.0 {
+        format!("{:.2}B", val / 1_000_000_000.0)
+    } else if val >= 1_000_000.0 {
+        format!("{:.2}M", val / 1_000_000.0)
     } else if val >= 1_000.0 {
-        format!("${:.1}K", val / 1_000.0)
+        format!("{:.1}K", val / 1_000.0)
     } else {
-        format!("${:.2}", val)
+        format!("{:.2}", val)
     }
 }
 
-fn format_price(val: f64) -> String {
+/// Subscript notation kicks in at this many zeros after the decimal point
+const SUBSCRIPT_MIN_ZEROS: usize = 4;
+
+/// Significant figures shown in subscript notation when `sig_digits` is unset
+const SUBSCRIPT_SIG_DIGITS: usize = 4;
+
+/// A USD price in the display currency, rounded per `fmt`
+pub fn format_price(val: f64, fmt: &PriceFormat, currency: &Currency) -> String {
+    let symbol = currency.symbol();
+    let val = currency.convert(val);
     if val >= 1.0 {
-        format!("${:.4}", val)
-    } else if val >= 0.01 {
-        format!("${:.6}", val)
+        return format!("{}{:.*}", symbol, fmt.decimals, val);
+    }
+    let sig = match (fmt.sig_digits, fmt.subscript_zeros) {
+        (Some(sig), _) => sig.max(1),
+        (None, true) if val < 0.01 => SUBSCRIPT_SIG_DIGITS,
+        (None, _) if val >= 0.01 => return format!("{}{:.6}", symbol, val),
+        (None, _) => return format!("{}{:.10}", symbol, val),
+    };
+    if val <= 0.0 || !val.is_finite() {
+        return format!("{}0", symbol);
+    }
+
+    // Take the exponent after rounding, so 0.00009999 at 3 figures becomes 0.000100
+    let scientific = format!("{:.*e}", sig - 1, val);
+    let exponent: i32 = scientific
+        .split_once('e')
+        .and_then(|(_, e)| e.parse().ok())
+        .unwrap_or(-1);
+    let zeros = (-exponent - 1).max(0) as usize;
+    let text = format!("{:.*}", zeros + sig, val);
+    if !fmt.subscript_zeros || zeros < SUBSCRIPT_MIN_ZEROS {
+        return format!("{}{}", symbol, text);
+    }
+
+    let count: String = zeros
+        .to_string()
+        .chars()
+        .filter_map(|d| d.to_digit(10))
+        .filter_map(|d| char::from_u32(0x2080 + d))
+        .collect();
+    format!("{}0.0{}{}", symbol, count, &text[2 + zeros..])
+}
+
+/// Compact major-coin price: `$97.2K`, `$3,412`, `$182.40`
+fn format_ticker_price(val: f64, currency: &Currency) -> String {
+    let symbol = currency.symbol();
+    let val = currency.convert(val);
+    if val >= 10_000.0 {
+        format!("{}{:.1}K", symbol, val / 1_000.0)
+    } else if val >= 1_000.0 {
+        let whole = val.round() as u64;
+        format!("{}{},{:03}", symbol, whole / 1000, whole % 1000)
     } else {
-        format!("${:.10}", val)
+        format!("{}{:.2}", symbol, val)
     }
 }
 
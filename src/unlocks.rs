@@ -0,0 +1,83 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+
+/// A known token unlock / vesting event, recorded in the config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Unlock {
+    /// Token or pair address the unlock applies to
+    pub token: String,
+    /// When it happens: RFC 3339, or local `YYYY-MM-DD HH:MM` / `YYYY-MM-DD`
+    pub date: String,
+    /// Share of total supply being unlocked, in percent
+    #[serde(default)]
+    pub percent_of_supply: Option<f64>,
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Hours before the unlock at which to raise an alert
+    #[serde(default = "default_alert_hours")]
+    pub alert_hours_before: Vec<u64>,
+}
+
+fn default_alert_hours() -> Vec<u64> {
+    vec![24, 1]
+}
+
+impl Unlock {
+    pub fn at(&self) -> Option<DateTime<Local>> {
+        parse_date(&self.date)
+    }
+
+    /// Short description, e.g. "12.5% of supply (team cliff)"
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(pct) = self.percent_of_supply {
+            parts.push(format!("{:.1}% of supply", pct));
+        }
+        if let Some(ref note) = self.note {
+            parts.push(format!("({})", note));
+        }
+        parts.join(" ")
+    }
+}
+
+fn parse_date(s: &str) -> Option<DateTime<Local>> {
+    let s = s.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Local));
+    }
+    let naive = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })?;
+    Local.from_local_datetime(&naive).earliest()
+}
+
+/// The next unlock after `now` matching any of `addresses`
+pub fn next_unlock<'a>(
+    unlocks: &'a [Unlock],
+    addresses: &[&str],
+    now: DateTime<Local>,
+) -> Option<(&'a Unlock, DateTime<Local>)> {
+    unlocks
+        .iter()
+        .filter(|u| addresses.iter().any(|a| !a.is_empty() && u.token.eq_ignore_ascii_case(a)))
+        .filter_map(|u| u.at().map(|at| (u, at)))
+        .filter(|(_, at)| *at > now)
+        .min_by_key(|(_, at)| *at)
+}
+
+/// Compact countdown from `now`, such as "3d 04h 12m" or "45m"
+pub fn format_countdown(until: DateTime<Local>, now: DateTime<Local>) -> String {
+    let secs = (until - now).num_seconds().max(0);
+    let (days, hours, mins) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{}d {:02}h {:02}m", days, hours, mins)
+    } else if hours > 0 {
+        format!("{}h {:02}m", hours, mins)
+    } else {
+        format!("{}m", mins)
+    }
+}
@@ -0,0 +1,74 @@
+use crate::api::{self, PairData};
+use crate::rpc;
+
+/// Most tokens a wallet adds to the watchlist, largest holdings first
+pub const MAX_TOKENS: usize = 20;
+
+/// A token held by the wallet, valued at its most liquid pair
+#[derive(Debug, Clone)]
+pub struct WalletToken {
+    pub mint: String,
+    pub value_usd: f64,
+    pub data: PairData,
+}
+
+impl WalletToken {
+    pub fn symbol(&self) -> &str {
+        self.data.base_symbol()
+    }
+}
+
+/// Wallets are scanned through the Solana RPC; EVM chains have no RPC call
+/// that lists a wallet's ERC-20 balances
+pub fn supports_chain(chain: &str) -> bool {
+    chain.eq_ignore_ascii_case("solana")
+}
+
+/// Short form of an address for labels, e.g. "7xKX…gAsU"
+pub fn short_address(address: &str) -> String {
+    let chars: Vec<char> = address.chars().collect();
+    if chars.len() <= 10 {
+        return address.to_string();
+    }
+    format!(
+        "{}…{}",
+        chars[..4].iter().collect::<String>(),
+        chars[chars.len() - 4..].iter().collect::<String>()
+    )
+}
+
+/// List the wallet's tokens, resolve each to its most liquid DexScreener pair
+/// and keep those worth at least `min_usd`, largest first. Tokens without a
+/// pair can't be valued and are left out.
+pub async fn scan(
+    client: &reqwest::Client,
+    rpc_url: &str,
+    chain: &str,
+    owner: &str,
+    min_usd: f64,
+) -> Result<Vec<WalletToken>, String> {
+    if !supports_chain(chain) {
+        return Err(format!("Wallet scanning isn't supported on {} (Solana only)", chain));
+    }
+    let balances = rpc::fetch_token_balances(client, rpc_url, owner).await?;
+    if balances.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mints: Vec<String> = balances.iter().map(|(m, _)| m.clone()).collect();
+    let pairs = api::fetch_pairs_batch(client, chain, &mints).await?;
+    let mut tokens: Vec<WalletToken> = balances
+        .into_iter()
+        .filter_map(|(mint, amount)| {
+            let data = pairs.get(&mint)?.clone();
+            Some(WalletToken {
+                value_usd: amount * data.price_usd_f64(),
+                mint,
+                data,
+            })
+        })
+        .filter(|t| t.value_usd >= min_usd)
+        .collect();
+    tokens.sort_by(|a, b| b.value_usd.total_cmp(&a.value_usd));
+    Ok(tokens)
+}
@@ -0,0 +1,153 @@
+//! Alerts sent to any HTTP endpoint as JSON rendered from a user template, with
+//! `{{symbol}}`, `{{market_cap}}`, `{{target}}` and the like filled in.
+
+use std::collections::HashMap;
+
+use chrono::{Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::api;
+use crate::notify::{AlertContext, Severity};
+use crate::secrets;
+use crate::storage;
+
+/// Endpoint alerts are sent to and the body they're sent as
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct WebhookConfig {
+    /// Unset disables the webhook
+    pub url: Option<String>,
+    /// Extra request headers, e.g. `{"Authorization": "Bearer ..."}`; a value
+    /// of `keyring:<name>` is read from the OS keyring instead
+    pub headers: HashMap<String, String>,
+    /// JSON body with `{{placeholder}}`s in its strings; unset sends every
+    /// placeholder as a field of its own
+    pub template: Option<Value>,
+}
+
+impl WebhookConfig {
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref().map(str::trim).filter(|u| !u.is_empty())
+    }
+}
+
+/// What each placeholder stands for, in the order of the default body
+fn values(
+    severity: Severity,
+    message: &str,
+    context: &AlertContext,
+) -> Vec<(&'static str, Value)> {
+    vec![
+        ("severity", severity.name().into()),
+        ("message", message.into()),
+        ("symbol", context.symbol.as_str().into()),
+        ("name", context.name.as_str().into()),
+        ("chain", context.chain.as_str().into()),
+        ("pair", context.pair.as_str().into()),
+        ("market_cap", context.market_cap.into()),
+        ("price", context.price.into()),
+        ("target", context.target.into()),
+        ("progress", context.progress.as_deref().unwrap_or_default().into()),
+        ("host", storage::hostname().into()),
+        ("time", Local::now().to_rfc3339_opts(SecondsFormat::Secs, false).into()),
+    ]
+}
+
+/// `template` with its placeholders filled in. A string that is nothing but
+/// one placeholder takes the value's own type, so `"{{market_cap}}"` becomes a
+/// number; inside longer strings values are written out as text. Unknown
+/// placeholders are left as they are.
+fn render(template: &Value, values: &[(&str, Value)]) -> Value {
+    match template {
+        Value::String(text) => {
+            let whole = values
+                .iter()
+                .find(|(key, _)| text.trim() == format!("{{{{{}}}}}", key));
+            if let Some((_, value)) = whole {
+                return value.clone();
+            }
+            let mut text = text.clone();
+            for (key, value) in values {
+                let placeholder = format!("{{{{{}}}}}", key);
+                if text.contains(&placeholder) {
+                    let value = match value {
+                        Value::String(s) => s.clone(),
+                        // Whole dollars without a trailing ".0"
+                        Value::Number(n) => match n.as_f64() {
+                            Some(f) if f.fract() == 0.0 && f.abs() < 1e15 => format!("{:.0}", f),
+                            _ => n.to_string(),
+                        },
+                        other => other.to_string(),
+                    };
+                    text = text.replace(&placeholder, &value);
+                }
+            }
+            Value::String(text)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|v| render(v, values)).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), render(value, values)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// The request body for an alert
+fn body(
+    config: &WebhookConfig,
+    severity: Severity,
+    message: &str,
+    context: &AlertContext,
+) -> Value {
+    let values = values(severity, message, context);
+    match config.template {
+        Some(ref template) => render(template, &values),
+        None => Value::Object(
+            values
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        ),
+    }
+}
+
+/// Send an alert to the configured endpoint
+pub async fn send(
+    client: &reqwest::Client,
+    config: &WebhookConfig,
+    severity: Severity,
+    message: &str,
+    context: &AlertContext,
+) -> Result<(), String> {
+    let url = config
+        .url()
+        .ok_or_else(|| "no webhook url configured".to_string())?;
+    let mut request = client
+        .post(url)
+        .json(&body(config, severity, message, context))
+        .timeout(std::time::Duration::from_secs(10));
+    for (name, value) in &config.headers {
+        if let Some(value) = secrets::resolve(value) {
+            request = request.header(name.as_str(), value);
+        }
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e.without_url()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let text = response.text().await.unwrap_or_default();
+    Err(format!(
+        "webhook answered HTTP {}: {}",
+        status.as_u16(),
+        api::truncate(text.trim(), 80)
+    ))
+}